@@ -132,6 +132,70 @@ pub struct AssetHierarchy {
     pub updated_at: String,
 }
 
+/// Registry entry for an asset type and the behavior keyed off it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetTypeDefinition {
+    pub name: String,
+    pub can_have_children: bool,
+    pub can_have_vault: bool,
+    /// Lowercase extensions without the dot; empty allows any file
+    pub allowed_file_extensions: Vec<String>,
+}
+
+impl AssetTypeDefinition {
+    pub fn allows_file(&self, file_name: &str) -> bool {
+        if self.allowed_file_extensions.is_empty() {
+            return true;
+        }
+        std::path::Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| self.allowed_file_extensions.iter().any(|allowed| allowed.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HierarchyExportFormat {
+    /// Nested JSON document
+    Json,
+    /// Indented plain-text outline
+    Text,
+}
+
+impl std::fmt::Display for HierarchyExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchyExportFormat::Json => write!(f, "json"),
+            HierarchyExportFormat::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl std::str::FromStr for HierarchyExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(HierarchyExportFormat::Json),
+            "text" | "txt" => Ok(HierarchyExportFormat::Text),
+            _ => Err(anyhow::anyhow!("Invalid hierarchy export format: {}", s)),
+        }
+    }
+}
+
+/// One asset in an exported hierarchy document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetTreeNode {
+    pub id: i64,
+    pub name: String,
+    pub asset_type: AssetType,
+    pub description: String,
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty", default)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    pub children: Vec<AssetTreeNode>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MoveAssetRequest {
     pub asset_id: i64,
@@ -139,6 +203,33 @@ pub struct MoveAssetRequest {
     pub new_sort_order: Option<i64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AssetSearchField {
+    Name,
+    Description,
+    Metadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetSearchHit {
+    pub asset: Asset,
+    pub matched_fields: Vec<AssetSearchField>,
+    /// Metadata keys whose values contained the search term
+    pub matched_metadata_keys: Vec<String>,
+}
+
+/// An asset and when its configuration last changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetActivity {
+    pub asset_id: i64,
+    pub name: String,
+    pub asset_type: AssetType,
+    pub version_count: i64,
+    /// Creation time of the newest configuration version
+    pub last_activity_at: String,
+    pub days_inactive: i64,
+}
+
 // Thread-safe service wrapper
 pub struct AssetService {
     db_manager: std::sync::Arc<std::sync::Mutex<crate::database::DatabaseManager>>,
@@ -187,6 +278,32 @@ pub trait AssetRepository {
     fn validate_asset_move(&self, asset_id: i64, new_parent_id: Option<i64>) -> Result<bool>;
     fn get_asset_path(&self, asset_id: i64) -> Result<Vec<Asset>>;
     fn get_next_sort_order(&self, parent_id: Option<i64>) -> Result<i64>;
+    fn export_asset_hierarchy(&self, root_asset_id: Option<i64>, format: HierarchyExportFormat) -> Result<Vec<u8>>;
+
+    // Search methods
+    fn search_assets(&self, query: &str, include_metadata: bool, limit: usize) -> Result<Vec<AssetSearchHit>>;
+
+    // Completeness methods
+    fn get_assets_missing_vaults(&self, asset_type_filter: Option<AssetType>) -> Result<Vec<Asset>>;
+    fn get_dormant_assets(&self, inactive_days: u32) -> Result<Vec<AssetActivity>>;
+
+    // Asset type registry methods
+    fn get_asset_types(&self) -> Result<Vec<AssetTypeDefinition>>;
+    fn get_asset_type(&self, name: &str) -> Result<Option<AssetTypeDefinition>>;
+    fn register_asset_type(&self, definition: &AssetTypeDefinition) -> Result<()>;
+    fn unregister_asset_type(&self, name: &str) -> Result<()>;
+}
+
+/// Escape LIKE wildcards so a search for `%` or `_` matches them literally; pair with `ESCAPE '\'`
+fn escape_like_pattern(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 pub struct SqliteAssetRepository<'a> {
@@ -221,6 +338,27 @@ impl<'a> SqliteAssetRepository<'a> {
             CREATE INDEX IF NOT EXISTS idx_assets_parent_id ON assets(parent_id);
             CREATE INDEX IF NOT EXISTS idx_assets_type ON assets(asset_type);
             CREATE INDEX IF NOT EXISTS idx_assets_sort_order ON assets(parent_id, sort_order);
+
+            CREATE TABLE IF NOT EXISTS asset_types (
+                name TEXT PRIMARY KEY,
+                can_have_children BOOLEAN NOT NULL DEFAULT 0,
+                can_have_vault BOOLEAN NOT NULL DEFAULT 1,
+                allowed_file_extensions TEXT NOT NULL DEFAULT '[]',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
+            -- Seed the built-in types once so unregistered types stay unregistered
+            INSERT INTO asset_types (name, can_have_children, can_have_vault)
+                SELECT name, can_have_children, can_have_vault FROM (
+                    SELECT 'folder' AS name, 1 AS can_have_children, 0 AS can_have_vault
+                    UNION ALL SELECT 'device', 0, 1
+                    UNION ALL SELECT 'equipment', 0, 1
+                    UNION ALL SELECT 'system', 0, 1
+                    UNION ALL SELECT 'area', 0, 1
+                    UNION ALL SELECT 'line', 0, 1
+                )
+                WHERE NOT EXISTS (SELECT 1 FROM asset_types);
             "#,
         )?;
         Ok(())
@@ -244,6 +382,16 @@ impl<'a> SqliteAssetRepository<'a> {
         })
     }
 
+    fn row_to_asset_type(row: &Row) -> rusqlite::Result<AssetTypeDefinition> {
+        let extensions: String = row.get("allowed_file_extensions")?;
+        Ok(AssetTypeDefinition {
+            name: row.get("name")?,
+            can_have_children: row.get("can_have_children")?,
+            can_have_vault: row.get("can_have_vault")?,
+            allowed_file_extensions: serde_json::from_str(&extensions).unwrap_or_default(),
+        })
+    }
+
     fn row_to_asset_info(row: &Row) -> rusqlite::Result<AssetInfo> {
         let asset_type_str: String = row.get("asset_type")?;
         let asset_type = AssetType::from_str(&asset_type_str)
@@ -284,16 +432,24 @@ impl<'a> AssetRepository for SqliteAssetRepository<'a> {
             return Err(anyhow::anyhow!("Asset with this name already exists"));
         }
 
+        if self.get_asset_type(request.asset_type.as_str())?.is_none() {
+            return Err(anyhow::anyhow!("Asset type '{}' is not registered", request.asset_type));
+        }
+
         // Validate parent exists if provided
         if let Some(parent_id) = request.parent_id {
             match self.get_asset_by_id(parent_id)? {
-                Some(parent) if parent.asset_type != AssetType::Folder => {
-                    return Err(anyhow::anyhow!("Parent asset must be a folder"));
+                Some(parent) => {
+                    let parent_can_have_children = self.get_asset_type(parent.asset_type.as_str())?
+                        .map(|definition| definition.can_have_children)
+                        .unwrap_or(false);
+                    if !parent_can_have_children {
+                        return Err(anyhow::anyhow!("Parent asset must be a folder or another type that can have children"));
+                    }
                 }
                 None => {
                     return Err(anyhow::anyhow!("Parent asset not found"));
                 }
-                _ => {}
             }
         }
 
@@ -536,10 +692,16 @@ impl<'a> AssetRepository for SqliteAssetRepository<'a> {
             return Ok(false);
         }
         
-        // Check if new parent exists and is a folder
+        // Check if new parent exists and its type can have children
         match self.get_asset_by_id(new_parent_id)? {
-            Some(parent) if parent.asset_type == AssetType::Folder => {},
-            Some(_) => return Ok(false), // Parent is not a folder
+            Some(parent) => {
+                let can_have_children = self.get_asset_type(parent.asset_type.as_str())?
+                    .map(|definition| definition.can_have_children)
+                    .unwrap_or(false);
+                if !can_have_children {
+                    return Ok(false);
+                }
+            }
             None => return Ok(false), // Parent doesn't exist
         }
         
@@ -594,6 +756,348 @@ impl<'a> AssetRepository for SqliteAssetRepository<'a> {
         let next_order: i64 = stmt.query_row([parent_id], |row| row.get(0))?;
         Ok(next_order)
     }
+
+    fn export_asset_hierarchy(&self, root_asset_id: Option<i64>, format: HierarchyExportFormat) -> Result<Vec<u8>> {
+        fn find_subtree(nodes: Vec<AssetHierarchy>, asset_id: i64) -> Option<AssetHierarchy> {
+            for node in nodes {
+                if node.id == asset_id {
+                    return Some(node);
+                }
+                if let Some(found) = find_subtree(node.children, asset_id) {
+                    return Some(found);
+                }
+            }
+            None
+        }
+
+        fn to_tree_node(
+            asset: AssetHierarchy,
+            metadata: &mut std::collections::HashMap<i64, serde_json::Map<String, serde_json::Value>>,
+        ) -> AssetTreeNode {
+            AssetTreeNode {
+                id: asset.id,
+                metadata: metadata.remove(&asset.id).unwrap_or_default(),
+                name: asset.name,
+                asset_type: asset.asset_type,
+                description: asset.description,
+                children: asset.children.into_iter().map(|child| to_tree_node(child, metadata)).collect(),
+            }
+        }
+
+        fn write_outline(out: &mut String, node: &AssetTreeNode, depth: usize) {
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(&format!("- {} [{}]", node.name, node.asset_type));
+            if !node.metadata.is_empty() {
+                let pairs: Vec<String> = node.metadata.iter()
+                    .map(|(key, value)| match value {
+                        serde_json::Value::String(text) => format!("{}={}", key, text),
+                        other => format!("{}={}", key, other),
+                    })
+                    .collect();
+                out.push_str(&format!(" ({})", pairs.join(", ")));
+            }
+            out.push('\n');
+            for child in &node.children {
+                write_outline(out, child, depth + 1);
+            }
+        }
+
+        let hierarchy = self.get_asset_hierarchy()?;
+        let roots = match root_asset_id {
+            Some(asset_id) => vec![find_subtree(hierarchy, asset_id)
+                .ok_or_else(|| anyhow::anyhow!("Asset not found"))?],
+            None => hierarchy,
+        };
+
+        // Metadata lives in its own module's table, which may not exist in every database
+        let mut metadata: std::collections::HashMap<i64, serde_json::Map<String, serde_json::Value>> = std::collections::HashMap::new();
+        let has_metadata: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'asset_metadata'",
+            [],
+            |row| row.get(0),
+        )?;
+        if has_metadata {
+            let mut stmt = self.conn.prepare(
+                "SELECT asset_id, metadata_values_json FROM asset_metadata ORDER BY asset_id, schema_id"
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (asset_id, values_json) = row?;
+                if let Ok(serde_json::Value::Object(values)) = serde_json::from_str(&values_json) {
+                    metadata.entry(asset_id).or_default().extend(values);
+                }
+            }
+        }
+
+        let nodes: Vec<AssetTreeNode> = roots.into_iter()
+            .map(|root| to_tree_node(root, &mut metadata))
+            .collect();
+
+        match format {
+            HierarchyExportFormat::Json => {
+                let document = serde_json::json!({
+                    "exported_at": chrono::Utc::now().to_rfc3339(),
+                    "root_asset_id": root_asset_id,
+                    "assets": nodes,
+                });
+                Ok(serde_json::to_vec_pretty(&document)?)
+            }
+            HierarchyExportFormat::Text => {
+                let mut out = String::new();
+                for node in &nodes {
+                    write_outline(&mut out, node, 0);
+                }
+                Ok(out.into_bytes())
+            }
+        }
+    }
+
+    fn search_assets(&self, query: &str, include_metadata: bool, limit: usize) -> Result<Vec<AssetSearchHit>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(anyhow::anyhow!("Search query cannot be empty"));
+        }
+
+        let needle = query.to_lowercase();
+        // SQLite's LOWER() only folds ASCII, so a non-ASCII query skips the LIKE pre-filter
+        // and every row is matched with Rust's Unicode case folding below instead
+        let pattern = needle.is_ascii().then(|| format!("%{}%", escape_like_pattern(&needle)));
+        let mut hits: std::collections::BTreeMap<i64, AssetSearchHit> = std::collections::BTreeMap::new();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, description, asset_type, parent_id, sort_order, created_by, created_at, updated_at 
+             FROM assets 
+             WHERE ?1 IS NULL OR LOWER(name) LIKE ?1 ESCAPE '\\' OR LOWER(COALESCE(description, '')) LIKE ?1 ESCAPE '\\'"
+        )?;
+
+        let asset_iter = stmt.query_map([&pattern], Self::row_to_asset)?;
+        for asset in asset_iter {
+            let asset = asset?;
+            let mut matched_fields = Vec::new();
+            if asset.name.to_lowercase().contains(&needle) {
+                matched_fields.push(AssetSearchField::Name);
+            }
+            if asset.description.to_lowercase().contains(&needle) {
+                matched_fields.push(AssetSearchField::Description);
+            }
+            if matched_fields.is_empty() {
+                continue;
+            }
+            hits.insert(asset.id, AssetSearchHit {
+                asset,
+                matched_fields,
+                matched_metadata_keys: Vec::new(),
+            });
+        }
+
+        if include_metadata {
+            // The LIKE pre-filter also matches key names and JSON syntax, so the
+            // values are checked individually once parsed
+            let mut stmt = self.conn.prepare(
+                "SELECT asset_id, metadata_values_json FROM asset_metadata 
+                 WHERE ?1 IS NULL OR LOWER(metadata_values_json) LIKE ?1 ESCAPE '\\'"
+            )?;
+
+            let rows = stmt.query_map([&pattern], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+
+            for row in rows {
+                let (asset_id, values_json) = row?;
+                let values: serde_json::Value = match serde_json::from_str(&values_json) {
+                    Ok(values) => values,
+                    Err(_) => continue,
+                };
+
+                let matched_keys: Vec<String> = match values.as_object() {
+                    Some(map) => map
+                        .iter()
+                        .filter(|(_, value)| {
+                            let text = match value {
+                                serde_json::Value::String(s) => s.clone(),
+                                other => other.to_string(),
+                            };
+                            text.to_lowercase().contains(&needle)
+                        })
+                        .map(|(key, _)| key.clone())
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                if matched_keys.is_empty() {
+                    continue;
+                }
+
+                if !hits.contains_key(&asset_id) {
+                    let asset = match self.get_asset_by_id(asset_id)? {
+                        Some(asset) => asset,
+                        None => continue,
+                    };
+                    hits.insert(asset_id, AssetSearchHit {
+                        asset,
+                        matched_fields: Vec::new(),
+                        matched_metadata_keys: Vec::new(),
+                    });
+                }
+
+                if let Some(hit) = hits.get_mut(&asset_id) {
+                    if !hit.matched_fields.contains(&AssetSearchField::Metadata) {
+                        hit.matched_fields.push(AssetSearchField::Metadata);
+                    }
+                    for key in matched_keys {
+                        if !hit.matched_metadata_keys.contains(&key) {
+                            hit.matched_metadata_keys.push(key);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<AssetSearchHit> = hits.into_values().collect();
+        results.sort_by(|a, b| a.asset.name.to_lowercase().cmp(&b.asset.name.to_lowercase()));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
+    fn get_assets_missing_vaults(&self, asset_type_filter: Option<AssetType>) -> Result<Vec<Asset>> {
+        // Folders only group other assets, so without a filter every other type is expected to have a vault
+        let type_condition = match &asset_type_filter {
+            Some(_) => "a.asset_type = ?1",
+            None => "a.asset_type != 'folder' AND ?1 IS NULL",
+        };
+
+        // Vaults live in their own module's tables, which may not exist in every database
+        let has_vaults: bool = self.conn.query_row(
+            "SELECT COUNT(*) = 2 FROM sqlite_master WHERE type = 'table' AND name IN ('vault_entries', 'vault_secrets')",
+            [],
+            |row| row.get(0),
+        )?;
+        let vault_condition = if has_vaults {
+            "AND NOT EXISTS (
+                 SELECT 1 FROM vault_entries ve
+                 JOIN vault_secrets vs ON vs.vault_id = ve.id
+                 WHERE ve.asset_id = a.id
+             )"
+        } else {
+            ""
+        };
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.id, a.name, a.description, a.asset_type, a.parent_id, a.sort_order, a.created_by, a.created_at, a.updated_at
+             FROM assets a
+             WHERE {} {}
+             ORDER BY a.name",
+            type_condition, vault_condition
+        ))?;
+
+        let asset_type = asset_type_filter.as_ref().map(|t| t.as_str());
+        let assets = stmt.query_map([asset_type], Self::row_to_asset)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(assets)
+    }
+
+    fn get_dormant_assets(&self, inactive_days: u32) -> Result<Vec<AssetActivity>> {
+        if inactive_days == 0 {
+            return Err(anyhow::anyhow!("Inactive days must be at least 1"));
+        }
+
+        // Assets without configurations have no activity to measure, and assets whose
+        // versions are all Archived have already been retired
+        let mut stmt = self.conn.prepare(
+            "SELECT a.id, a.name, a.asset_type,
+                    COUNT(cv.id) AS version_count,
+                    MAX(cv.created_at) AS last_activity_at,
+                    CAST(julianday('now') - julianday(MAX(cv.created_at)) AS INTEGER) AS days_inactive
+             FROM assets a
+             JOIN configuration_versions cv ON cv.asset_id = a.id
+             GROUP BY a.id, a.name, a.asset_type
+             HAVING julianday(MAX(cv.created_at)) < julianday('now', ?1)
+                AND SUM(COALESCE(cv.status, 'Draft') != 'Archived') > 0
+             ORDER BY last_activity_at, a.name"
+        )?;
+
+        let assets = stmt.query_map([format!("-{} days", inactive_days)], |row| {
+            let asset_type: String = row.get("asset_type")?;
+            Ok(AssetActivity {
+                asset_id: row.get("id")?,
+                name: row.get("name")?,
+                asset_type: AssetType::from_str(&asset_type)
+                    .map_err(|_| rusqlite::Error::InvalidColumnType(0, "asset_type".to_string(), rusqlite::types::Type::Text))?,
+                version_count: row.get("version_count")?,
+                last_activity_at: row.get("last_activity_at")?,
+                days_inactive: row.get("days_inactive")?,
+            })
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(assets)
+    }
+
+    fn get_asset_types(&self) -> Result<Vec<AssetTypeDefinition>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, can_have_children, can_have_vault, allowed_file_extensions FROM asset_types ORDER BY name"
+        )?;
+        let types = stmt.query_map([], Self::row_to_asset_type)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(types)
+    }
+
+    fn get_asset_type(&self, name: &str) -> Result<Option<AssetTypeDefinition>> {
+        let result = self.conn.query_row(
+            "SELECT name, can_have_children, can_have_vault, allowed_file_extensions FROM asset_types WHERE name = ?1",
+            [name],
+            Self::row_to_asset_type,
+        );
+
+        match result {
+            Ok(definition) => Ok(Some(definition)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn register_asset_type(&self, definition: &AssetTypeDefinition) -> Result<()> {
+        // Stored assets are read back through AssetType, so only its variants can be registered
+        let asset_type = AssetType::from_str(&definition.name)?;
+
+        let mut extensions = Vec::new();
+        for extension in &definition.allowed_file_extensions {
+            let extension = extension.trim().trim_start_matches('.').to_lowercase();
+            if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(anyhow::anyhow!("Invalid file extension '{}' for asset type '{}'", extension, asset_type));
+            }
+            if !extensions.contains(&extension) {
+                extensions.push(extension);
+            }
+        }
+
+        self.conn.execute(
+            "INSERT INTO asset_types (name, can_have_children, can_have_vault, allowed_file_extensions) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET can_have_children = excluded.can_have_children,
+                 can_have_vault = excluded.can_have_vault, allowed_file_extensions = excluded.allowed_file_extensions,
+                 updated_at = CURRENT_TIMESTAMP",
+            (asset_type.as_str(), &definition.can_have_children, &definition.can_have_vault, &serde_json::to_string(&extensions)?),
+        )?;
+        Ok(())
+    }
+
+    fn unregister_asset_type(&self, name: &str) -> Result<()> {
+        let in_use: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM assets WHERE asset_type = ?1",
+            [name],
+            |row| row.get(0),
+        )?;
+        if in_use > 0 {
+            return Err(anyhow::anyhow!("Asset type '{}' is used by {} assets", name, in_use));
+        }
+
+        let removed = self.conn.execute("DELETE FROM asset_types WHERE name = ?1", [name])?;
+        if removed == 0 {
+            return Err(anyhow::anyhow!("Asset type '{}' is not registered", name));
+        }
+        Ok(())
+    }
 }
 
 impl<'a> SqliteAssetRepository<'a> {
@@ -639,6 +1143,37 @@ impl<'a> SqliteAssetRepository<'a> {
         let asset = asset.unwrap();
         Ok(asset.created_by == user_id || permission == "read")
     }
+
+    /// Creates an asset and, when `with_vault` is set, an empty identity vault named after it.
+    /// Both are written in one transaction so a failed vault leaves no asset behind.
+    pub fn create_asset_with_vault(&self, request: CreateAssetRequest, with_vault: bool) -> Result<(Asset, Option<crate::vault::IdentityVault>)> {
+        use crate::vault::{SqliteVaultRepository, VaultRepository, CreateVaultRequest};
+
+        if !with_vault {
+            return Ok((self.create_asset(request)?, None));
+        }
+
+        if let Some(definition) = self.get_asset_type(request.asset_type.as_str())? {
+            if !definition.can_have_vault {
+                return Err(anyhow::anyhow!("Asset type '{}' cannot have a vault", definition.name));
+            }
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        let asset = self.create_asset(request)?;
+
+        // Vault names share the 100 character limit with asset names, so leave room for the suffix
+        let base_name: String = asset.name.chars().take(94).collect();
+        let vault = SqliteVaultRepository::new(self.conn).create_vault(CreateVaultRequest {
+            asset_id: asset.id,
+            name: format!("{} Vault", base_name),
+            description: format!("Identity vault for {}", asset.name),
+            created_by: asset.created_by,
+        })?;
+
+        tx.commit()?;
+        Ok((asset, Some(vault)))
+    }
 }
 
 #[cfg(test)]
@@ -1229,4 +1764,372 @@ mod tests {
         assert_eq!(control_room.children.len(), 1); // HMI-001
         assert_eq!(control_room.children[0].name, "HMI-001");
     }
-}
\ No newline at end of file
+
+    fn create_metadata_table(conn: &Connection) {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE asset_metadata (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id INTEGER NOT NULL,
+                schema_id INTEGER NOT NULL,
+                metadata_values_json TEXT NOT NULL,
+                schema_version INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_search_assets_description_and_metadata() {
+        let (_temp_file, conn) = setup_test_db();
+        create_metadata_table(&conn);
+        let repo = SqliteAssetRepository::new(&conn);
+
+        let described = repo.create_asset(CreateAssetRequest {
+            name: "PLC-Line5".to_string(),
+            description: "Packaging conveyor controller".to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        }).unwrap();
+
+        let tagged = repo.create_asset(CreateAssetRequest {
+            name: "HMI-Line5".to_string(),
+            description: "Operator panel".to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        }).unwrap();
+
+        conn.execute(
+            "INSERT INTO asset_metadata (asset_id, schema_id, metadata_values_json, schema_version) VALUES (?1, 1, ?2, 1)",
+            (tagged.id, r#"{"location": "Conveyor hall B", "ip_address": "10.0.0.5"}"#),
+        ).unwrap();
+
+        let hits = repo.search_assets("conveyor", true, 10).unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let described_hit = hits.iter().find(|hit| hit.asset.id == described.id).unwrap();
+        assert_eq!(described_hit.matched_fields, vec![AssetSearchField::Description]);
+        assert!(described_hit.matched_metadata_keys.is_empty());
+
+        let tagged_hit = hits.iter().find(|hit| hit.asset.id == tagged.id).unwrap();
+        assert_eq!(tagged_hit.matched_fields, vec![AssetSearchField::Metadata]);
+        assert_eq!(tagged_hit.matched_metadata_keys, vec!["location".to_string()]);
+    }
+
+    #[test]
+    fn test_search_assets_without_metadata() {
+        let (_temp_file, conn) = setup_test_db();
+        create_metadata_table(&conn);
+        let repo = SqliteAssetRepository::new(&conn);
+
+        let asset = repo.create_asset(CreateAssetRequest {
+            name: "HMI-Line5".to_string(),
+            description: "Operator panel".to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        }).unwrap();
+
+        conn.execute(
+            "INSERT INTO asset_metadata (asset_id, schema_id, metadata_values_json, schema_version) VALUES (?1, 1, ?2, 1)",
+            (asset.id, r#"{"location": "Conveyor hall B"}"#),
+        ).unwrap();
+
+        // Key names are not searchable, only values
+        assert!(repo.search_assets("location", true, 10).unwrap().is_empty());
+        assert!(repo.search_assets("conveyor", false, 10).unwrap().is_empty());
+
+        let hits = repo.search_assets("line5", false, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].matched_fields, vec![AssetSearchField::Name]);
+
+        assert!(repo.search_assets("   ", true, 10).is_err());
+    }
+
+    #[test]
+    fn test_search_assets_literal_wildcards_and_unicode_case() {
+        let (_temp_file, conn) = setup_test_db();
+        create_metadata_table(&conn);
+        let repo = SqliteAssetRepository::new(&conn);
+        let create = |name: &str, description: &str| repo.create_asset(CreateAssetRequest {
+            name: name.to_string(),
+            description: description.to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        }).unwrap();
+
+        let underscored = create("PLC_Line5", "Runs at 100% load");
+        create("PLCXLine5", "Spare controller");
+        let accented = create("PRÜFSTAND-1", "Test bench");
+
+        let hits = repo.search_assets("plc_", false, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].asset.id, underscored.id);
+
+        let hits = repo.search_assets("100%", false, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].matched_fields, vec![AssetSearchField::Description]);
+
+        let hits = repo.search_assets("prüf", false, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].asset.id, accented.id);
+    }
+
+    #[test]
+    fn test_export_asset_hierarchy() {
+        let (_temp_file, conn) = setup_test_db();
+        create_metadata_table(&conn);
+        let repo = SqliteAssetRepository::new(&conn);
+        let create = |name: &str, asset_type: AssetType, parent_id: Option<i64>| {
+            repo.create_asset(CreateAssetRequest {
+                name: name.to_string(),
+                description: format!("{} description", name),
+                asset_type,
+                parent_id,
+                created_by: 1,
+            }).unwrap()
+        };
+
+        let line = create("Production Line 1", AssetType::Folder, None);
+        let plc = create("PLC-001", AssetType::Device, Some(line.id));
+        let room = create("Control Room", AssetType::Folder, Some(line.id));
+        let hmi = create("HMI-001", AssetType::Device, Some(room.id));
+        let spares = create("Spares", AssetType::Folder, None);
+        conn.execute(
+            "INSERT INTO asset_metadata (asset_id, schema_id, metadata_values_json, schema_version) VALUES (?1, 1, ?2, 1)",
+            (plc.id, r#"{"ip_address":"10.0.0.5","vendor":"Siemens"}"#),
+        ).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(
+            &repo.export_asset_hierarchy(None, HierarchyExportFormat::Json).unwrap()
+        ).unwrap();
+        let roots = json["assets"].as_array().unwrap();
+        let root_ids: Vec<i64> = roots.iter().map(|n| n["id"].as_i64().unwrap()).collect();
+        assert_eq!(root_ids, vec![line.id, spares.id]);
+
+        let children = roots[0]["children"].as_array().unwrap();
+        let child_ids: Vec<i64> = children.iter().map(|n| n["id"].as_i64().unwrap()).collect();
+        assert_eq!(child_ids, vec![plc.id, room.id]);
+        assert_eq!(children[0]["asset_type"], "Device");
+        assert_eq!(children[0]["metadata"]["vendor"], "Siemens");
+        assert!(children[1].get("metadata").is_none());
+        assert_eq!(children[1]["children"][0]["id"], hmi.id);
+
+        // A subtree export starts at the requested asset
+        let json: serde_json::Value = serde_json::from_slice(
+            &repo.export_asset_hierarchy(Some(room.id), HierarchyExportFormat::Json).unwrap()
+        ).unwrap();
+        assert_eq!(json["assets"].as_array().unwrap().len(), 1);
+        assert_eq!(json["assets"][0]["id"], room.id);
+        assert_eq!(json["assets"][0]["children"][0]["name"], "HMI-001");
+
+        let text = String::from_utf8(
+            repo.export_asset_hierarchy(None, HierarchyExportFormat::Text).unwrap()
+        ).unwrap();
+        assert_eq!(text, "\
+- Production Line 1 [folder]
+  - PLC-001 [device] (ip_address=10.0.0.5, vendor=Siemens)
+  - Control Room [folder]
+    - HMI-001 [device]
+- Spares [folder]
+");
+
+        assert!(repo.export_asset_hierarchy(Some(9999), HierarchyExportFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_get_assets_missing_vaults() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssetRepository::new(&conn);
+        let create = |name: &str, asset_type: AssetType| {
+            repo.create_asset(CreateAssetRequest {
+                name: name.to_string(),
+                description: format!("{} description", name),
+                asset_type,
+                parent_id: None,
+                created_by: 1,
+            }).unwrap()
+        };
+
+        let unvaulted = create("PLC-001", AssetType::Device);
+        let vaulted = create("PLC-002", AssetType::Device);
+        let empty_vault = create("HMI-001", AssetType::Device);
+        let drive = create("Drive-001", AssetType::Equipment);
+        create("Plant", AssetType::Folder);
+
+        // Without the vault tables every non-folder asset is a gap
+        let missing: Vec<i64> = repo.get_assets_missing_vaults(None).unwrap().iter().map(|a| a.id).collect();
+        assert_eq!(missing, vec![drive.id, empty_vault.id, unvaulted.id, vaulted.id]);
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE vault_entries (id INTEGER PRIMARY KEY, asset_id INTEGER NOT NULL, name TEXT NOT NULL);
+            CREATE TABLE vault_secrets (id INTEGER PRIMARY KEY, vault_id INTEGER NOT NULL, label TEXT NOT NULL);
+            "#,
+        ).unwrap();
+        conn.execute("INSERT INTO vault_entries (id, asset_id, name) VALUES (1, ?1, 'Populated')", [vaulted.id]).unwrap();
+        conn.execute("INSERT INTO vault_secrets (vault_id, label) VALUES (1, 'Admin Password')", []).unwrap();
+        conn.execute("INSERT INTO vault_entries (id, asset_id, name) VALUES (2, ?1, 'Empty')", [empty_vault.id]).unwrap();
+
+        let missing: Vec<i64> = repo.get_assets_missing_vaults(None).unwrap().iter().map(|a| a.id).collect();
+        assert_eq!(missing, vec![drive.id, empty_vault.id, unvaulted.id]);
+
+        let missing: Vec<i64> = repo.get_assets_missing_vaults(Some(AssetType::Device)).unwrap().iter().map(|a| a.id).collect();
+        assert_eq!(missing, vec![empty_vault.id, unvaulted.id]);
+    }
+
+    #[test]
+    fn test_get_dormant_assets() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssetRepository::new(&conn);
+        let create = |name: &str| {
+            repo.create_asset(CreateAssetRequest {
+                name: name.to_string(),
+                description: String::new(),
+                asset_type: AssetType::Device,
+                parent_id: None,
+                created_by: 1,
+            }).unwrap()
+        };
+        let recent = create("PLC-Recent");
+        let dormant = create("PLC-Dormant");
+        let retired = create("PLC-Retired");
+        create("PLC-Unconfigured");
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE configuration_versions (
+                id INTEGER PRIMARY KEY,
+                asset_id INTEGER NOT NULL,
+                status TEXT DEFAULT 'Draft',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+        ).unwrap();
+        let add_version = |asset_id: i64, status: &str, age: &str| {
+            conn.execute(
+                "INSERT INTO configuration_versions (asset_id, status, created_at) VALUES (?1, ?2, datetime('now', ?3))",
+                (asset_id, status, age),
+            ).unwrap();
+        };
+        // An old version does not make an asset dormant when a newer one exists
+        add_version(recent.id, "Golden", "-400 days");
+        add_version(recent.id, "Draft", "-5 days");
+        add_version(dormant.id, "Golden", "-200 days");
+        add_version(dormant.id, "Archived", "-300 days");
+        add_version(retired.id, "Archived", "-400 days");
+
+        let found = repo.get_dormant_assets(90).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].asset_id, dormant.id);
+        assert_eq!(found[0].version_count, 2);
+        assert_eq!(found[0].days_inactive, 200);
+
+        assert!(repo.get_dormant_assets(365).unwrap().is_empty());
+        assert!(repo.get_dormant_assets(0).is_err());
+    }
+
+    #[test]
+    fn test_create_device_asset_with_vault() {
+        use crate::vault::{SqliteVaultRepository, VaultRepository};
+
+        let (_temp_file, conn) = setup_test_db();
+        SqliteVaultRepository::new(&conn).initialize_schema().unwrap();
+        let repo = SqliteAssetRepository::new(&conn);
+        let vault_repo = SqliteVaultRepository::new(&conn);
+        let request = |name: &str| CreateAssetRequest {
+            name: name.to_string(),
+            description: "Line 5 controller".to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        };
+
+        let (with_vault, vault) = repo.create_asset_with_vault(request("PLC-Line5"), true).unwrap();
+        let vault = vault.unwrap();
+        assert_eq!(vault.asset_id, with_vault.id);
+        assert_eq!(vault.name, "PLC-Line5 Vault");
+        let stored = vault_repo.get_vault_by_asset_id(with_vault.id).unwrap().unwrap();
+        assert_eq!(stored.vault.id, vault.id);
+        assert_eq!(stored.secret_count, 0);
+
+        let (without_vault, vault) = repo.create_asset_with_vault(request("PLC-Line6"), false).unwrap();
+        assert!(vault.is_none());
+        assert!(vault_repo.get_vault_by_asset_id(without_vault.id).unwrap().is_none());
+
+        // A maximum length asset name still yields a valid vault name
+        let long_name = "P".repeat(100);
+        let (_, vault) = repo.create_asset_with_vault(request(&long_name), true).unwrap();
+        assert_eq!(vault.unwrap().name.len(), 100);
+    }
+
+    #[test]
+    fn test_create_asset_validates_registered_type() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssetRepository::new(&conn);
+        let request = |name: &str, asset_type: AssetType| CreateAssetRequest {
+            name: name.to_string(),
+            description: String::new(),
+            asset_type,
+            parent_id: None,
+            created_by: 1,
+        };
+
+        let device = repo.create_asset(request("PLC-Line8", AssetType::Device)).unwrap();
+        assert_eq!(device.asset_type, AssetType::Device);
+
+        repo.unregister_asset_type("line").unwrap();
+        let result = repo.create_asset(request("Line 8", AssetType::Line));
+        assert!(result.unwrap_err().to_string().contains("is not registered"));
+        assert!(repo.unregister_asset_type("device").is_err());
+
+        repo.register_asset_type(&AssetTypeDefinition {
+            name: "line".to_string(),
+            can_have_children: true,
+            can_have_vault: false,
+            allowed_file_extensions: vec![".L5X".to_string(), "acd".to_string()],
+        }).unwrap();
+        let line = repo.create_asset(request("Line 8", AssetType::Line)).unwrap();
+        let definition = repo.get_asset_type("line").unwrap().unwrap();
+        assert_eq!(definition.allowed_file_extensions, vec!["l5x", "acd"]);
+        assert!(definition.allows_file("Line8.L5X"));
+        assert!(!definition.allows_file("notes.txt"));
+
+        // Registered properties drive hierarchy and vault rules
+        let child = CreateAssetRequest { parent_id: Some(line.id), ..request("PLC-Line8b", AssetType::Device) };
+        assert!(repo.create_asset(child).is_ok());
+        let child = CreateAssetRequest { parent_id: Some(device.id), ..request("PLC-Line8c", AssetType::Device) };
+        assert!(repo.create_asset(child).is_err());
+        assert!(repo.create_asset_with_vault(request("Line 9", AssetType::Line), true).is_err());
+
+        assert!(repo.register_asset_type(&AssetTypeDefinition {
+            name: "robot".to_string(),
+            can_have_children: false,
+            can_have_vault: true,
+            allowed_file_extensions: Vec::new(),
+        }).is_err());
+    }
+
+    #[test]
+    fn test_create_asset_with_vault_rolls_back_on_vault_failure() {
+        let (_temp_file, conn) = setup_test_db();
+        // Without the vault schema the vault insert fails after the asset row is written
+        let repo = SqliteAssetRepository::new(&conn);
+
+        let result = repo.create_asset_with_vault(CreateAssetRequest {
+            name: "PLC-Line7".to_string(),
+            description: String::new(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: 1,
+        }, true);
+
+        assert!(result.is_err());
+        assert!(!repo.asset_exists_by_name("PLC-Line7").unwrap());
+    }
+}
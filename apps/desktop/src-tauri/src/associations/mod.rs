@@ -197,16 +197,53 @@ pub struct CreateAssociationRequest {
     pub created_by: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkAssociationOutcome {
+    /// Position of the request in the submitted batch
+    pub index: usize,
+    pub asset_id: i64,
+    pub file_id: i64,
+    pub file_type: AssociationType,
+    pub association: Option<AssetFileAssociation>,
+    pub error: Option<String>,
+}
+
+/// What `repair_association` did with a broken association
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepairOutcome {
+    /// Pointed at the one surviving file on the asset matching the original name or hash
+    Relinked,
+    /// Several files match, so the association was left for someone to choose
+    Ambiguous,
+    /// No file on the asset matches, and removal was not requested
+    NoCandidate,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkAssociationResult {
+    pub created: usize,
+    pub failed: usize,
+    pub outcomes: Vec<BulkAssociationOutcome>,
+}
+
 pub trait AssociationRepository {
     fn create_file_association(&self, request: CreateAssociationRequest) -> Result<AssetFileAssociation>;
+    fn create_file_associations_bulk(&self, requests: Vec<CreateAssociationRequest>) -> Result<BulkAssociationResult>;
     fn get_asset_associations(&self, asset_id: i64) -> Result<Vec<AssociationInfo>>;
     fn get_association_by_id(&self, association_id: i64) -> Result<Option<AssetFileAssociation>>;
     fn remove_association(&self, association_id: i64) -> Result<()>;
     fn reorder_associations(&self, asset_id: i64, association_order: Vec<(i64, i64)>) -> Result<()>;
     fn validate_file_association(&self, asset_id: i64, file_id: i64, file_type: &AssociationType) -> Result<Vec<AssociationValidation>>;
     fn get_association_health_status(&self, asset_id: i64) -> Result<HealthStatus>;
+    fn get_last_health_check(&self, asset_id: i64) -> Result<Option<String>>;
     fn get_broken_associations(&self) -> Result<Vec<AssociationInfo>>;
-    fn repair_association(&self, association_id: i64) -> Result<()>;
+    fn repair_association(&self, association_id: i64, remove_if_unresolved: bool) -> Result<RepairOutcome>;
+    
+    // Re-validation when an associated file changes
+    fn revalidate_file_associations(&self, file_id: i64, file_type: &AssociationType) -> Result<Vec<AssociationValidation>>;
+    fn get_auto_revalidate_enabled(&self) -> Result<bool>;
+    fn set_auto_revalidate_enabled(&self, enabled: bool) -> Result<()>;
     
     // File import session management
     fn create_import_session(&self, session_name: String, asset_id: i64, file_paths: Vec<String>, created_by: i64) -> Result<FileImportSession>;
@@ -237,6 +274,9 @@ impl<'a> SqliteAssociationRepository<'a> {
                 file_type TEXT NOT NULL CHECK(file_type IN ('configuration', 'firmware')),
                 association_order INTEGER NOT NULL DEFAULT 0,
                 metadata TEXT,
+                -- Name and hash of the file when linked, used to relink if the file is removed
+                original_file_name TEXT,
+                original_file_hash TEXT,
                 created_by INTEGER NOT NULL,
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
@@ -268,6 +308,25 @@ impl<'a> SqliteAssociationRepository<'a> {
                 FOREIGN KEY (association_id) REFERENCES asset_file_associations(id) ON DELETE CASCADE
             );
 
+            -- One row per health check run, so repeated checks stay auditable
+            CREATE TABLE IF NOT EXISTS association_health_checks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id INTEGER NOT NULL,
+                healthy BOOLEAN NOT NULL,
+                issue_count INTEGER NOT NULL,
+                warning_count INTEGER NOT NULL,
+                checked_at TEXT NOT NULL,
+                FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+            );
+
+            -- Re-validate a file's associations whenever its content changes; single row
+            CREATE TABLE IF NOT EXISTS association_revalidation_policy (
+                id INTEGER PRIMARY KEY CHECK(id = 1),
+                auto_revalidate BOOLEAN NOT NULL DEFAULT 1,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT OR IGNORE INTO association_revalidation_policy (id, auto_revalidate) VALUES (1, 1);
+
             CREATE INDEX IF NOT EXISTS idx_associations_asset_id ON asset_file_associations(asset_id);
             CREATE INDEX IF NOT EXISTS idx_associations_file_id ON asset_file_associations(file_id);
             CREATE INDEX IF NOT EXISTS idx_associations_file_type ON asset_file_associations(file_type);
@@ -282,6 +341,8 @@ impl<'a> SqliteAssociationRepository<'a> {
             CREATE INDEX IF NOT EXISTS idx_validations_association_id ON association_validations(association_id);
             CREATE INDEX IF NOT EXISTS idx_validations_result ON association_validations(validation_result);
             CREATE INDEX IF NOT EXISTS idx_validations_type ON association_validations(validation_type);
+            
+            CREATE INDEX IF NOT EXISTS idx_health_checks_asset_id ON association_health_checks(asset_id, checked_at);
             "#,
         )?;
         Ok(())
@@ -426,20 +487,54 @@ impl<'a> SqliteAssociationRepository<'a> {
 
         Ok(())
     }
-}
 
-impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
-    fn create_file_association(&self, request: CreateAssociationRequest) -> Result<AssetFileAssociation> {
-        // Validate constraints
-        self.validate_association_constraints(&request)?;
-        
+    /// Every file of `file_type` on the asset as `(id, file name, hash)`; firmware is named by
+    /// the last component of its stored path
+    fn asset_file_identities(&self, asset_id: i64, file_type: &AssociationType) -> Result<Vec<(i64, String, String)>> {
+        let sql = match file_type {
+            AssociationType::Configuration => "SELECT id, file_name, content_hash FROM configuration_versions WHERE asset_id = ?1",
+            AssociationType::Firmware => "SELECT id, file_path, file_hash FROM firmware_versions WHERE asset_id = ?1",
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+        let files = stmt.query_map([asset_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(files.into_iter()
+            .map(|(id, name, hash)| match file_type {
+                AssociationType::Configuration => (id, name, hash),
+                AssociationType::Firmware => {
+                    let name = std::path::Path::new(&name).file_name()
+                        .map(|file_name| file_name.to_string_lossy().into_owned())
+                        .unwrap_or(name);
+                    (id, name, hash)
+                }
+            })
+            .collect())
+    }
+
+    fn file_identity(&self, file_id: i64, file_type: &AssociationType) -> Result<Option<(String, String)>> {
+        let owner_sql = match file_type {
+            AssociationType::Configuration => "SELECT asset_id FROM configuration_versions WHERE id = ?1",
+            AssociationType::Firmware => "SELECT asset_id FROM firmware_versions WHERE id = ?1",
+        };
+        let Some(owner) = self.conn.query_row(owner_sql, [file_id], |row| row.get::<_, i64>(0)).optional()? else {
+            return Ok(None);
+        };
+        Ok(self.asset_file_identities(owner, file_type)?
+            .into_iter()
+            .find(|(id, _, _)| *id == file_id)
+            .map(|(_, name, hash)| (name, hash)))
+    }
+
+    fn insert_association(&self, request: &CreateAssociationRequest) -> Result<AssetFileAssociation> {
         // Get next association order
         let association_order = self.get_next_association_order(request.asset_id)?;
+        let (original_file_name, original_file_hash) = self.file_identity(request.file_id, &request.file_type)?.unzip();
         
         // Create the association
         let mut stmt = self.conn.prepare(
-            "INSERT INTO asset_file_associations (asset_id, file_id, file_type, association_order, metadata, created_by) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6) RETURNING *"
+            "INSERT INTO asset_file_associations (asset_id, file_id, file_type, association_order, metadata, original_file_name, original_file_hash, created_by) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) RETURNING *"
         )?;
 
         let association = stmt.query_row(
@@ -449,6 +544,8 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
                 request.file_type.as_str(),
                 &association_order,
                 &request.metadata,
+                &original_file_name,
+                &original_file_hash,
                 &request.created_by,
             ),
             Self::row_to_association,
@@ -456,6 +553,57 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
 
         Ok(association)
     }
+}
+
+impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
+    fn create_file_association(&self, request: CreateAssociationRequest) -> Result<AssetFileAssociation> {
+        // Validate constraints
+        self.validate_association_constraints(&request)?;
+        
+        self.insert_association(&request)
+    }
+
+    fn create_file_associations_bulk(&self, requests: Vec<CreateAssociationRequest>) -> Result<BulkAssociationResult> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut seen: HashMap<(i64, i64, &'static str), usize> = HashMap::new();
+        let mut outcomes = Vec::with_capacity(requests.len());
+        
+        for (index, request) in requests.iter().enumerate() {
+            let key = (request.asset_id, request.file_id, request.file_type.as_str());
+            
+            // Valid requests are inserted as they go, so later ones are assigned the next order
+            let result = match seen.get(&key) {
+                Some(first) => Err(anyhow::anyhow!("Duplicate of request {} in this batch", first)),
+                None => {
+                    seen.insert(key, index);
+                    self.validate_association_constraints(request)
+                        .and_then(|_| self.insert_association(request))
+                }
+            };
+            
+            let (association, error) = match result {
+                Ok(association) => (Some(association), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            outcomes.push(BulkAssociationOutcome {
+                index,
+                asset_id: request.asset_id,
+                file_id: request.file_id,
+                file_type: request.file_type.clone(),
+                association,
+                error,
+            });
+        }
+        
+        tx.commit()?;
+        
+        let created = outcomes.iter().filter(|outcome| outcome.association.is_some()).count();
+        Ok(BulkAssociationResult {
+            created,
+            failed: outcomes.len() - created,
+            outcomes,
+        })
+    }
 
     fn get_asset_associations(&self, asset_id: i64) -> Result<Vec<AssociationInfo>> {
         let mut stmt = self.conn.prepare(
@@ -547,23 +695,35 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
     }
 
     fn validate_file_association(&self, asset_id: i64, file_id: i64, file_type: &AssociationType) -> Result<Vec<AssociationValidation>> {
-        let mut validations = Vec::new();
-        
-        // This is a placeholder implementation
-        // In a real implementation, you would run various validation checks
-        
-        // For now, create a basic validation entry indicating success
-        let validation = AssociationValidation {
-            id: 0, // This would be assigned by the database
-            association_id: 0, // This would be the actual association ID
-            validation_type: ValidationType::ReferentialIntegrity,
-            validation_result: ValidationResult::Passed,
-            validation_message: "Basic validation passed".to_string(),
-            validated_at: "2024-01-01T00:00:00Z".to_string(),
+        let association_id: i64 = self.conn.query_row(
+            "SELECT id FROM asset_file_associations WHERE asset_id = ?1 AND file_id = ?2 AND file_type = ?3",
+            (asset_id, file_id, file_type.as_str()),
+            |row| row.get(0),
+        ).optional()?.unwrap_or(0);
+
+        let (owner_sql, label) = match file_type {
+            AssociationType::Configuration => ("SELECT asset_id FROM configuration_versions WHERE id = ?1", "Configuration"),
+            AssociationType::Firmware => ("SELECT asset_id FROM firmware_versions WHERE id = ?1", "Firmware"),
         };
-        
-        validations.push(validation);
-        Ok(validations)
+        let owner: Option<i64> = self.conn.query_row(owner_sql, [file_id], |row| row.get(0)).optional()?;
+
+        let (validation_result, validation_message) = match owner {
+            None => (ValidationResult::Failed, format!("{} file {} no longer exists", label, file_id)),
+            Some(owner) if owner != asset_id => (
+                ValidationResult::Warning,
+                format!("{} file {} belongs to asset {}", label, file_id, owner),
+            ),
+            Some(_) => (ValidationResult::Passed, format!("{} file {} is present", label, file_id)),
+        };
+
+        Ok(vec![AssociationValidation {
+            id: 0, // Assigned when the result is stored
+            association_id,
+            validation_type: ValidationType::ReferentialIntegrity,
+            validation_result,
+            validation_message,
+            validated_at: chrono::Utc::now().to_rfc3339(),
+        }])
     }
 
     fn get_association_health_status(&self, asset_id: i64) -> Result<HealthStatus> {
@@ -607,14 +767,35 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
             warnings.push(format!("{} validation warning(s)", warning_validations));
         }
 
+        let checked_at = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO association_health_checks (asset_id, healthy, issue_count, warning_count, checked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            (asset_id, issues.is_empty(), issues.len() as i64, warnings.len() as i64, &checked_at),
+        )?;
+
         Ok(HealthStatus {
             healthy: issues.is_empty(),
             issues,
             warnings,
-            last_checked: "2024-01-01T00:00:00Z".to_string(),
+            last_checked: checked_at,
         })
     }
 
+    fn get_last_health_check(&self, asset_id: i64) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT checked_at FROM association_health_checks WHERE asset_id = ?1 ORDER BY id DESC LIMIT 1",
+            [asset_id],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(checked_at) => Ok(Some(checked_at)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn get_broken_associations(&self) -> Result<Vec<AssociationInfo>> {
         let mut stmt = self.conn.prepare(
             "SELECT afa.id, afa.asset_id, a.name as asset_name, afa.file_id, 
@@ -641,28 +822,126 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
         Ok(associations)
     }
 
-    fn repair_association(&self, association_id: i64) -> Result<()> {
-        // This is a placeholder implementation
-        // In a real implementation, you would attempt to repair the association
-        // For now, we'll just remove broken associations
-        
-        // Check if the association is broken
+    fn repair_association(&self, association_id: i64, remove_if_unresolved: bool) -> Result<RepairOutcome> {
+        let (asset_id, file_id, file_type, original_file_name, original_file_hash) = self.conn.query_row(
+            "SELECT asset_id, file_id, file_type, original_file_name, original_file_hash
+             FROM asset_file_associations WHERE id = ?1",
+            [association_id],
+            |row| Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            )),
+        ).optional()?
+            .ok_or_else(|| anyhow::anyhow!("Association {} not found", association_id))?;
+        let file_type = AssociationType::from_str(&file_type)?;
+
+        if self.file_identity(file_id, &file_type)?.is_some() {
+            return Err(anyhow::anyhow!("Association {} is not broken", association_id));
+        }
+
+        // Files already linked to this asset are not candidates; relinking would duplicate them
         let mut stmt = self.conn.prepare(
-            "SELECT afa.file_type, afa.file_id FROM asset_file_associations afa
-             LEFT JOIN configuration_versions cv ON afa.file_type = 'configuration' AND afa.file_id = cv.id
-             LEFT JOIN firmware_versions fv ON afa.file_type = 'firmware' AND afa.file_id = fv.id
-             WHERE afa.id = ?1 AND cv.id IS NULL AND fv.id IS NULL"
+            "SELECT file_id FROM asset_file_associations WHERE asset_id = ?1 AND file_type = ?2"
         )?;
+        let linked = stmt.query_map((asset_id, file_type.as_str()), |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let files: Vec<_> = self.asset_file_identities(asset_id, &file_type)?
+            .into_iter()
+            .filter(|(id, _, _)| !linked.contains(id))
+            .collect();
+
+        // An identical hash is the stronger match; the file name is the fallback
+        let by_hash: Vec<_> = files.iter().filter(|(_, _, hash)| Some(hash) == original_file_hash.as_ref()).collect();
+        let candidates = if by_hash.is_empty() {
+            files.iter().filter(|(_, name, _)| Some(name) == original_file_name.as_ref()).collect()
+        } else {
+            by_hash
+        };
 
-        let is_broken = stmt.query_row([association_id], |_| Ok(()))
-            .optional()?
-            .is_some();
+        let outcome = match candidates.as_slice() {
+            [(new_file_id, name, hash)] => {
+                self.conn.execute(
+                    "UPDATE asset_file_associations
+                     SET file_id = ?1, original_file_name = ?2, original_file_hash = ?3, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?4",
+                    (new_file_id, name, hash, association_id),
+                )?;
+                self.revalidate_file_associations(*new_file_id, &file_type)?;
+                tracing::info!("Association {} relinked from {} file {} to {}", association_id, file_type, file_id, new_file_id);
+                return Ok(RepairOutcome::Relinked);
+            }
+            [] => RepairOutcome::NoCandidate,
+            _ => RepairOutcome::Ambiguous,
+        };
 
-        if is_broken {
-            // Remove the broken association
+        if remove_if_unresolved {
             self.remove_association(association_id)?;
+            return Ok(RepairOutcome::Removed);
         }
+        Ok(outcome)
+    }
 
+    fn revalidate_file_associations(&self, file_id: i64, file_type: &AssociationType) -> Result<Vec<AssociationValidation>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, asset_id FROM asset_file_associations WHERE file_id = ?1 AND file_type = ?2"
+        )?;
+        let associations = stmt.query_map((file_id, file_type.as_str()), |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut stored = Vec::new();
+
+        for (association_id, asset_id) in associations {
+            // Earlier results describe the old file, so they are replaced rather than appended to
+            tx.execute("DELETE FROM association_validations WHERE association_id = ?1", [association_id])?;
+
+            for validation in self.validate_file_association(asset_id, file_id, file_type)? {
+                let row = tx.query_row(
+                    "INSERT INTO association_validations (association_id, validation_type, validation_result, validation_message, validated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5) RETURNING *",
+                    (
+                        association_id,
+                        validation.validation_type.as_str(),
+                        validation.validation_result.as_str(),
+                        &validation.validation_message,
+                        &validation.validated_at,
+                    ),
+                    Self::row_to_validation,
+                )?;
+                stored.push(row);
+            }
+        }
+
+        tx.commit()?;
+        Ok(stored)
+    }
+
+    fn get_auto_revalidate_enabled(&self) -> Result<bool> {
+        let result = self.conn.query_row(
+            "SELECT auto_revalidate FROM association_revalidation_policy WHERE id = 1",
+            [],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(enabled) => Ok(enabled),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(true),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_auto_revalidate_enabled(&self, enabled: bool) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO association_revalidation_policy (id, auto_revalidate) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET auto_revalidate = excluded.auto_revalidate,
+                 updated_at = CURRENT_TIMESTAMP",
+            [enabled],
+        )?;
+
+        tracing::info!("Automatic association re-validation set to {}", enabled);
         Ok(())
     }
 
@@ -783,6 +1062,27 @@ impl<'a> AssociationRepository for SqliteAssociationRepository<'a> {
     }
 }
 
+/// Hook for repositories that replace a file's content in place. Re-validates every
+/// association of the file when automatic re-validation is enabled, returning how many
+/// validation results were stored. Does nothing while the association schema has not
+/// been created.
+pub fn on_associated_file_changed(conn: &Connection, file_id: i64, file_type: &AssociationType) -> Result<usize> {
+    let schema_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'association_revalidation_policy')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !schema_exists {
+        return Ok(0);
+    }
+
+    let repo = SqliteAssociationRepository::new(conn);
+    if !repo.get_auto_revalidate_enabled()? {
+        return Ok(0);
+    }
+    Ok(repo.revalidate_file_associations(file_id, file_type)?.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -953,6 +1253,54 @@ mod tests {
         assert_eq!(firmware_assoc.association_order, 1);
     }
 
+    #[test]
+    fn test_create_file_associations_bulk_reports_partial_success() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssociationRepository::new(&conn);
+        conn.execute(
+            "INSERT INTO configuration_versions (id, asset_id, version_number, file_name, file_content, file_size, content_hash, author)
+             VALUES (2, 1, 'v2', 'config2.json', 'test', 4, 'hash3', 1)",
+            [],
+        ).unwrap();
+
+        let request = |file_id: i64, file_type: AssociationType| CreateAssociationRequest {
+            asset_id: 1,
+            file_id,
+            file_type,
+            metadata: None,
+            created_by: 1,
+        };
+
+        let result = repo.create_file_associations_bulk(vec![
+            request(1, AssociationType::Configuration),
+            request(2, AssociationType::Configuration),
+            request(1, AssociationType::Configuration),
+            request(99, AssociationType::Configuration),
+            request(1, AssociationType::Firmware),
+        ]).unwrap();
+
+        assert_eq!(result.created, 3);
+        assert_eq!(result.failed, 2);
+        assert_eq!(result.outcomes.len(), 5);
+
+        let orders: Vec<i64> = result.outcomes.iter()
+            .filter_map(|outcome| outcome.association.as_ref().map(|a| a.association_order))
+            .collect();
+        assert_eq!(orders, vec![0, 1, 2]);
+
+        assert!(result.outcomes[2].error.as_ref().unwrap().contains("Duplicate of request 0"));
+        assert!(result.outcomes[3].error.as_ref().unwrap().contains("does not exist"));
+        assert_eq!(result.outcomes[4].file_type, AssociationType::Firmware);
+        assert!(result.outcomes[4].error.is_none());
+
+        assert_eq!(repo.get_asset_associations(1).unwrap().len(), 3);
+
+        // Associations that already exist are rejected on a later batch
+        let result = repo.create_file_associations_bulk(vec![request(2, AssociationType::Configuration)]).unwrap();
+        assert_eq!(result.created, 0);
+        assert!(result.outcomes[0].error.as_ref().unwrap().contains("Association already exists"));
+    }
+
     #[test]
     fn test_get_asset_associations() {
         let (_temp_file, conn) = setup_test_db();
@@ -1017,6 +1365,70 @@ mod tests {
         assert_eq!(associations.len(), 0);
     }
 
+    #[test]
+    fn test_repair_association_outcomes() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssociationRepository::new(&conn);
+        let associate = |file_id: i64, file_type: AssociationType| repo.create_file_association(CreateAssociationRequest {
+            asset_id: 1,
+            file_id,
+            file_type,
+            metadata: None,
+            created_by: 1,
+        }).unwrap();
+
+        conn.execute_batch(
+            r#"
+            INSERT INTO configuration_versions (id, asset_id, version_number, file_name, file_content, file_size, content_hash, author)
+                VALUES (2, 1, 'v2', 'plc.cfg', 'old', 3, 'hash-plc', 1);
+            INSERT INTO firmware_versions (id, asset_id, author_id, version, file_path, file_hash, file_size)
+                VALUES (2, 1, 1, '2.0.0', '/firmware/drive.bin', 'hash-drive', 2048);
+            "#,
+        ).unwrap();
+        let config = associate(2, AssociationType::Configuration);
+        let firmware = associate(2, AssociationType::Firmware);
+        let unrelated = associate(1, AssociationType::Configuration);
+
+        // Healthy associations are not touched
+        assert!(repo.repair_association(unrelated.id, true).is_err());
+
+        // The configuration is re-imported under the same name with new content: one candidate
+        conn.execute_batch(
+            r#"
+            DELETE FROM configuration_versions WHERE id = 2;
+            INSERT INTO configuration_versions (id, asset_id, version_number, file_name, file_content, file_size, content_hash, author)
+                VALUES (3, 1, 'v3', 'plc.cfg', 'new', 3, 'hash-plc-new', 1);
+            "#,
+        ).unwrap();
+        assert_eq!(repo.repair_association(config.id, false).unwrap(), RepairOutcome::Relinked);
+        assert_eq!(repo.get_association_by_id(config.id).unwrap().unwrap().file_id, 3);
+
+        // Firmware re-uploaded twice with the same file name: no unique candidate
+        conn.execute_batch(
+            r#"
+            DELETE FROM firmware_versions WHERE id = 2;
+            INSERT INTO firmware_versions (id, asset_id, author_id, version, file_path, file_hash, file_size)
+                VALUES (3, 1, 1, '2.0.1', '/firmware/a/drive.bin', 'hash-a', 2048);
+            INSERT INTO firmware_versions (id, asset_id, author_id, version, file_path, file_hash, file_size)
+                VALUES (4, 1, 1, '2.0.2', '/firmware/b/drive.bin', 'hash-b', 2048);
+            "#,
+        ).unwrap();
+        assert_eq!(repo.repair_association(firmware.id, false).unwrap(), RepairOutcome::Ambiguous);
+        assert_eq!(repo.get_association_by_id(firmware.id).unwrap().unwrap().file_id, 2);
+
+        // A matching hash settles it even when names differ
+        conn.execute("UPDATE firmware_versions SET file_hash = 'hash-drive', file_path = '/firmware/renamed.bin' WHERE id = 4", []).unwrap();
+        assert_eq!(repo.repair_association(firmware.id, false).unwrap(), RepairOutcome::Relinked);
+        assert_eq!(repo.get_association_by_id(firmware.id).unwrap().unwrap().file_id, 4);
+
+        // Nothing matches: the association is kept unless removal is requested
+        conn.execute("DELETE FROM configuration_versions WHERE id = 3", []).unwrap();
+        assert_eq!(repo.repair_association(config.id, false).unwrap(), RepairOutcome::NoCandidate);
+        assert!(repo.get_association_by_id(config.id).unwrap().is_some());
+        assert_eq!(repo.repair_association(config.id, true).unwrap(), RepairOutcome::Removed);
+        assert!(repo.get_association_by_id(config.id).unwrap().is_none());
+    }
+
     #[test]
     fn test_reorder_associations() {
         let (_temp_file, conn) = setup_test_db();
@@ -1122,6 +1534,72 @@ mod tests {
         assert!(health.warnings.is_empty());
     }
 
+    #[test]
+    fn test_health_checks_record_real_timestamps() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssociationRepository::new(&conn);
+
+        assert!(repo.get_last_health_check(1).unwrap().is_none());
+
+        let first = repo.get_association_health_status(1).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let second = repo.get_association_health_status(1).unwrap();
+
+        assert_ne!(first.last_checked, second.last_checked);
+        assert!(chrono::DateTime::parse_from_rfc3339(&first.last_checked).unwrap()
+            < chrono::DateTime::parse_from_rfc3339(&second.last_checked).unwrap());
+        assert_eq!(repo.get_last_health_check(1).unwrap(), Some(second.last_checked));
+        assert!(repo.get_last_health_check(2).unwrap().is_none());
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM association_health_checks WHERE asset_id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+
+        let validations = repo.validate_file_association(1, 1, &AssociationType::Configuration).unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(&validations[0].validated_at).is_ok());
+    }
+
+    #[test]
+    fn test_revalidate_file_associations_replaces_stale_results() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssociationRepository::new(&conn);
+
+        let association = repo.create_file_association(CreateAssociationRequest {
+            asset_id: 1,
+            file_id: 1,
+            file_type: AssociationType::Firmware,
+            metadata: None,
+            created_by: 1,
+        }).unwrap();
+        conn.execute(
+            "INSERT INTO association_validations (association_id, validation_type, validation_result, validation_message)
+             VALUES (?1, 'referential_integrity', 'failed', 'Stale result')",
+            [association.id],
+        ).unwrap();
+        assert!(!repo.get_association_health_status(1).unwrap().healthy);
+
+        assert_eq!(on_associated_file_changed(&conn, 1, &AssociationType::Firmware).unwrap(), 1);
+        let validations = repo.get_associations_by_validation_status(ValidationResult::Passed).unwrap();
+        assert_eq!(validations.len(), 1);
+        assert_eq!(validations[0].id, association.id);
+        assert!(repo.get_association_health_status(1).unwrap().healthy);
+
+        // Moving the file to another asset is reported on the next change
+        conn.execute("INSERT INTO assets (id, name, created_by) VALUES (2, 'Other Asset', 1)", []).unwrap();
+        conn.execute("UPDATE firmware_versions SET asset_id = 2 WHERE id = 1", []).unwrap();
+        let stored = repo.revalidate_file_associations(1, &AssociationType::Firmware).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].association_id, association.id);
+        assert_eq!(stored[0].validation_result, ValidationResult::Warning);
+        assert_eq!(repo.get_association_health_status(1).unwrap().warnings, vec!["1 validation warning(s)".to_string()]);
+
+        // Disabled re-validation leaves the stored results untouched
+        repo.set_auto_revalidate_enabled(false).unwrap();
+        assert!(!repo.get_auto_revalidate_enabled().unwrap());
+        conn.execute("UPDATE firmware_versions SET asset_id = 1 WHERE id = 1", []).unwrap();
+        assert_eq!(on_associated_file_changed(&conn, 1, &AssociationType::Firmware).unwrap(), 0);
+        assert_eq!(repo.get_association_health_status(1).unwrap().warnings.len(), 1);
+    }
+
     #[test]
     fn test_import_session_management() {
         let (_temp_file, conn) = setup_test_db();
@@ -0,0 +1,349 @@
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::{error, info};
+
+/// Event name prefix for targeted asset events; the subscriber's user id is appended.
+pub const ASSET_EVENT_PREFIX: &str = "asset-event-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetEventType {
+    ConfigurationImported,
+    ConfigurationStatusChanged,
+    ConfigurationPromoted,
+    ApprovalEscalated,
+    FirmwareUploaded,
+    AssetMoved,
+}
+
+impl AssetEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssetEventType::ConfigurationImported => "configuration_imported",
+            AssetEventType::ConfigurationStatusChanged => "configuration_status_changed",
+            AssetEventType::ConfigurationPromoted => "configuration_promoted",
+            AssetEventType::ApprovalEscalated => "approval_escalated",
+            AssetEventType::FirmwareUploaded => "firmware_uploaded",
+            AssetEventType::AssetMoved => "asset_moved",
+        }
+    }
+}
+
+impl std::fmt::Display for AssetEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AssetEventType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "configuration_imported" => Ok(AssetEventType::ConfigurationImported),
+            "configuration_status_changed" => Ok(AssetEventType::ConfigurationStatusChanged),
+            "configuration_promoted" => Ok(AssetEventType::ConfigurationPromoted),
+            "approval_escalated" => Ok(AssetEventType::ApprovalEscalated),
+            "firmware_uploaded" => Ok(AssetEventType::FirmwareUploaded),
+            "asset_moved" => Ok(AssetEventType::AssetMoved),
+            _ => Err(anyhow::anyhow!("Invalid asset event type: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetEventSubscription {
+    pub subscription_id: i64,
+    pub user_id: i64,
+    pub asset_id: i64,
+    pub event_types: Vec<AssetEventType>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetEvent {
+    pub asset_id: i64,
+    pub event_type: AssetEventType,
+    pub actor_user_id: Option<i64>,
+    pub description: String,
+    pub timestamp: String,
+}
+
+impl AssetEvent {
+    pub fn new(asset_id: i64, event_type: AssetEventType, actor_user_id: Option<i64>, description: String) -> Self {
+        Self {
+            asset_id,
+            event_type,
+            actor_user_id,
+            description,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// A single asset event addressed to one subscribed user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetEventDelivery {
+    pub recipient_user_id: i64,
+    pub event: AssetEvent,
+}
+
+impl AssetEventDelivery {
+    pub fn event_name(&self) -> String {
+        format!("{}{}", ASSET_EVENT_PREFIX, self.recipient_user_id)
+    }
+}
+
+pub trait AssetSubscriptionRepository {
+    fn initialize_schema(&self) -> Result<()>;
+    fn subscribe_asset_events(&self, user_id: i64, asset_id: i64, event_types: &[AssetEventType]) -> Result<AssetEventSubscription>;
+    fn unsubscribe_asset_events(&self, user_id: i64, asset_id: i64) -> Result<bool>;
+    fn get_user_subscriptions(&self, user_id: i64) -> Result<Vec<AssetEventSubscription>>;
+    fn get_asset_subscribers(&self, asset_id: i64, event_type: AssetEventType) -> Result<Vec<i64>>;
+    fn route_asset_event(&self, event: &AssetEvent) -> Result<Vec<AssetEventDelivery>>;
+}
+
+pub struct SqliteAssetSubscriptionRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteAssetSubscriptionRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    fn row_to_subscription(row: &Row) -> rusqlite::Result<AssetEventSubscription> {
+        let event_types_str: String = row.get("event_types")?;
+        let event_types = event_types_str
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| s.parse::<AssetEventType>().ok())
+            .collect();
+
+        Ok(AssetEventSubscription {
+            subscription_id: row.get("subscription_id")?,
+            user_id: row.get("user_id")?,
+            asset_id: row.get("asset_id")?,
+            event_types,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+}
+
+impl<'a> AssetSubscriptionRepository for SqliteAssetSubscriptionRepository<'a> {
+    fn initialize_schema(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS asset_event_subscriptions (
+                subscription_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id INTEGER NOT NULL,
+                asset_id INTEGER NOT NULL,
+                event_types TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE(user_id, asset_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_asset_event_subscriptions_asset_id ON asset_event_subscriptions(asset_id);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    fn subscribe_asset_events(&self, user_id: i64, asset_id: i64, event_types: &[AssetEventType]) -> Result<AssetEventSubscription> {
+        if event_types.is_empty() {
+            return Err(anyhow::anyhow!("At least one event type is required"));
+        }
+
+        let mut unique_types: Vec<AssetEventType> = Vec::new();
+        for event_type in event_types {
+            if !unique_types.contains(event_type) {
+                unique_types.push(*event_type);
+            }
+        }
+        let event_types_str = unique_types
+            .iter()
+            .map(|t| t.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.conn.execute(
+            "INSERT INTO asset_event_subscriptions (user_id, asset_id, event_types)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(user_id, asset_id) DO UPDATE SET
+                event_types = excluded.event_types,
+                updated_at = CURRENT_TIMESTAMP",
+            (user_id, asset_id, &event_types_str),
+        )?;
+
+        let subscription = self.conn.query_row(
+            "SELECT subscription_id, user_id, asset_id, event_types, created_at, updated_at
+             FROM asset_event_subscriptions WHERE user_id = ?1 AND asset_id = ?2",
+            (user_id, asset_id),
+            Self::row_to_subscription,
+        )?;
+
+        info!("User {} subscribed to asset {} events: {}", user_id, asset_id, event_types_str);
+        Ok(subscription)
+    }
+
+    fn unsubscribe_asset_events(&self, user_id: i64, asset_id: i64) -> Result<bool> {
+        let rows_affected = self.conn.execute(
+            "DELETE FROM asset_event_subscriptions WHERE user_id = ?1 AND asset_id = ?2",
+            (user_id, asset_id),
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    fn get_user_subscriptions(&self, user_id: i64) -> Result<Vec<AssetEventSubscription>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT subscription_id, user_id, asset_id, event_types, created_at, updated_at
+             FROM asset_event_subscriptions WHERE user_id = ?1 ORDER BY asset_id"
+        )?;
+
+        let subscription_iter = stmt.query_map([user_id], Self::row_to_subscription)?;
+        let mut subscriptions = Vec::new();
+
+        for subscription in subscription_iter {
+            subscriptions.push(subscription?);
+        }
+
+        Ok(subscriptions)
+    }
+
+    fn get_asset_subscribers(&self, asset_id: i64, event_type: AssetEventType) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT user_id, event_types FROM asset_event_subscriptions
+             WHERE asset_id = ?1 ORDER BY user_id"
+        )?;
+
+        let rows = stmt.query_map([asset_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut subscribers = Vec::new();
+        for row in rows {
+            let (user_id, event_types) = row?;
+            if event_types.split(',').any(|t| t == event_type.as_str()) {
+                subscribers.push(user_id);
+            }
+        }
+
+        Ok(subscribers)
+    }
+
+    fn route_asset_event(&self, event: &AssetEvent) -> Result<Vec<AssetEventDelivery>> {
+        let subscribers = self.get_asset_subscribers(event.asset_id, event.event_type)?;
+        Ok(subscribers
+            .into_iter()
+            .map(|recipient_user_id| AssetEventDelivery {
+                recipient_user_id,
+                event: event.clone(),
+            })
+            .collect())
+    }
+}
+
+/// Look up the subscribers for an asset event and emit it to each of them.
+/// Failures are logged rather than returned so a mutation never fails because
+/// a notification could not be delivered.
+pub fn emit_asset_event<R: Runtime>(app: &AppHandle<R>, conn: &Connection, event: AssetEvent) {
+    let repo = SqliteAssetSubscriptionRepository::new(conn);
+    let deliveries = match repo.route_asset_event(&event) {
+        Ok(deliveries) => deliveries,
+        Err(e) => {
+            error!("Failed to route asset event for asset {}: {}", event.asset_id, e);
+            return;
+        }
+    };
+
+    for delivery in deliveries {
+        if let Err(e) = app.emit(&delivery.event_name(), &delivery) {
+            error!("Failed to emit asset event to user {}: {}", delivery.recipient_user_id, e);
+        }
+    }
+}
+
+/// Emit an asset event to one user regardless of their subscriptions, for
+/// notifications that have a designated recipient.
+pub fn emit_asset_event_to_user<R: Runtime>(app: &AppHandle<R>, recipient_user_id: i64, event: AssetEvent) {
+    let delivery = AssetEventDelivery { recipient_user_id, event };
+    if let Err(e) = app.emit(&delivery.event_name(), &delivery) {
+        error!("Failed to emit asset event to user {}: {}", recipient_user_id, e);
+    }
+}
+
+/// Resolve the asset that owns a configuration version, for routing version-level events.
+pub fn asset_id_for_version(conn: &Connection, version_id: i64) -> Option<i64> {
+    conn.query_row(
+        "SELECT asset_id FROM configuration_versions WHERE id = ?1",
+        [version_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .ok()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn setup_test_db() -> (NamedTempFile, Connection) {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(temp_file.path()).unwrap();
+        let repo = SqliteAssetSubscriptionRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+        (temp_file, conn)
+    }
+
+    #[test]
+    fn test_subscriber_receives_events_for_its_asset_only() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssetSubscriptionRepository::new(&conn);
+
+        repo.subscribe_asset_events(1, 10, &[AssetEventType::ConfigurationImported, AssetEventType::FirmwareUploaded]).unwrap();
+        repo.subscribe_asset_events(2, 20, &[AssetEventType::ConfigurationImported]).unwrap();
+
+        let event = AssetEvent::new(10, AssetEventType::ConfigurationImported, Some(3), "Imported config".to_string());
+        let deliveries = repo.route_asset_event(&event).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].recipient_user_id, 1);
+        assert_eq!(deliveries[0].event.asset_id, 10);
+        assert_eq!(deliveries[0].event_name(), "asset-event-1");
+
+        let other = AssetEvent::new(20, AssetEventType::ConfigurationImported, Some(3), "Imported config".to_string());
+        let deliveries = repo.route_asset_event(&other).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].recipient_user_id, 2);
+
+        let unwatched = AssetEvent::new(30, AssetEventType::ConfigurationImported, None, "Imported config".to_string());
+        assert!(repo.route_asset_event(&unwatched).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_subscription_filters_by_event_type() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteAssetSubscriptionRepository::new(&conn);
+
+        repo.subscribe_asset_events(1, 10, &[AssetEventType::FirmwareUploaded]).unwrap();
+
+        let moved = AssetEvent::new(10, AssetEventType::AssetMoved, None, "Moved".to_string());
+        assert!(repo.route_asset_event(&moved).unwrap().is_empty());
+
+        // Resubscribing replaces the event type selection
+        let subscription = repo.subscribe_asset_events(1, 10, &[AssetEventType::AssetMoved, AssetEventType::AssetMoved]).unwrap();
+        assert_eq!(subscription.event_types, vec![AssetEventType::AssetMoved]);
+        assert_eq!(repo.route_asset_event(&moved).unwrap().len(), 1);
+        assert_eq!(repo.get_user_subscriptions(1).unwrap().len(), 1);
+
+        assert!(repo.unsubscribe_asset_events(1, 10).unwrap());
+        assert!(repo.route_asset_event(&moved).unwrap().is_empty());
+        assert!(repo.subscribe_asset_events(1, 10, &[]).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::Result;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::warn;
+
+use super::SessionToken;
+use crate::users::UserRole;
+
+/// Commands that need more than an authenticated session. Anything not listed here is
+/// open to every role, matching the behaviour of commands that never checked roles.
+const DEFAULT_COMMAND_ROLES: &[(&str, UserRole)] = &[
+    // User management
+    ("create_engineer_user", UserRole::Administrator),
+    ("list_users", UserRole::Administrator),
+    ("deactivate_user", UserRole::Administrator),
+    ("reactivate_user", UserRole::Administrator),
+    ("list_active_sessions", UserRole::Administrator),
+    ("revoke_session", UserRole::Administrator),
+    ("get_command_policy", UserRole::Administrator),
+    ("set_command_minimum_role", UserRole::Administrator),
+    ("get_rate_limit_policies", UserRole::Administrator),
+    ("update_rate_limit_policy", UserRole::Administrator),
+    ("get_login_audit_settings", UserRole::Administrator),
+    ("update_login_audit_settings", UserRole::Administrator),
+    // Configuration and firmware lifecycle
+    ("apply_retention_policy", UserRole::Administrator),
+    ("set_configuration_lint_mode", UserRole::Administrator),
+    ("get_max_upload_bytes", UserRole::Administrator),
+    ("set_max_upload_bytes", UserRole::Administrator),
+    ("sign_configuration_version", UserRole::Administrator),
+    ("register_configuration_signer_key", UserRole::Administrator),
+    ("revoke_configuration_signer_key", UserRole::Administrator),
+    ("list_configuration_signer_keys", UserRole::Administrator),
+    ("set_golden_signature_required", UserRole::Administrator),
+    ("set_auto_version_notes_enabled", UserRole::Administrator),
+    ("set_auto_golden_settings", UserRole::Administrator),
+    ("set_approval_escalation_settings", UserRole::Administrator),
+    ("create_approval_delegation", UserRole::Administrator),
+    ("get_configurations_by_inactive_authors", UserRole::Administrator),
+    ("get_configuration_export_history", UserRole::Administrator),
+    ("export_version_timeline", UserRole::Administrator),
+    ("promote_firmware_to_golden", UserRole::Administrator),
+    ("set_max_active_branches", UserRole::Administrator),
+    ("prune_branch_versions", UserRole::Administrator),
+    ("set_metadata_size_policy", UserRole::Administrator),
+    // Bulk operations
+    ("start_bulk_delete", UserRole::Administrator),
+    // Vault administration
+    ("grant_vault_access", UserRole::Administrator),
+    ("revoke_vault_access", UserRole::Administrator),
+    ("get_vault_permissions", UserRole::Administrator),
+    ("get_vault_access_log", UserRole::Administrator),
+    ("get_vault_access_log_page", UserRole::Administrator),
+    ("get_vault_access_log_count", UserRole::Administrator),
+    ("get_decrypt_audit_policy", UserRole::Administrator),
+    ("update_decrypt_audit_policy", UserRole::Administrator),
+    ("prune_vault_access_log", UserRole::Administrator),
+    ("get_access_log_retention_policy", UserRole::Administrator),
+    ("update_access_log_retention_policy", UserRole::Administrator),
+    ("get_export_redaction_policy", UserRole::Administrator),
+    ("update_export_redaction_policy", UserRole::Administrator),
+    ("set_export_transform", UserRole::Administrator),
+    ("register_asset_type", UserRole::Administrator),
+    ("unregister_asset_type", UserRole::Administrator),
+    ("set_vault_constraints", UserRole::Administrator),
+    ("get_password_reuse_policy", UserRole::Administrator),
+    ("update_password_reuse_policy", UserRole::Administrator),
+    ("find_reused_secret_values", UserRole::Administrator),
+    ("find_secrets_by_ip", UserRole::Administrator),
+    ("find_ip_conflicts", UserRole::Administrator),
+    ("rebuild_ip_index", UserRole::Administrator),
+    ("dedupe_password_history", UserRole::Administrator),
+    ("set_max_category_depth", UserRole::Administrator),
+    ("detect_category_cycles", UserRole::Administrator),
+    ("break_category_cycle", UserRole::Administrator),
+    ("export_credentials_by_category", UserRole::Administrator),
+    // Not a command of its own: gates adding standalone credentials to a KDBX vault export
+    ("export_standalone_credentials", UserRole::Administrator),
+    ("create_rotation_schedule", UserRole::Administrator),
+    ("update_rotation_policy", UserRole::Administrator),
+    ("create_rotation_reason_template", UserRole::Administrator),
+    ("set_rotation_reason_template_active", UserRole::Administrator),
+    ("update_rotation_throttle_settings", UserRole::Administrator),
+    ("update_compliance_alert_settings", UserRole::Administrator),
+    ("export_policy_compliance_report", UserRole::Administrator),
+    ("get_policy_drift", UserRole::Administrator),
+    ("get_effective_password_policy", UserRole::Administrator),
+    ("update_password_policy", UserRole::Administrator),
+    ("verify_audit_chain", UserRole::Administrator),
+];
+
+/// Commands whose minimum role cannot be lowered, so an administrator cannot hand
+/// control of the policy itself to a lower role
+const LOCKED_COMMANDS: &[&str] = &["get_command_policy", "set_command_minimum_role"];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AuthorizationError {
+    #[error("Permission denied: {command} requires the {required} role")]
+    PermissionDenied { command: String, required: UserRole },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandPolicy {
+    /// Minimum role per command name; unlisted commands are open to every role
+    pub minimum_roles: HashMap<String, UserRole>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self::from_entries(DEFAULT_COMMAND_ROLES.iter().cloned())
+    }
+}
+
+impl CommandPolicy {
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = (&'a str, UserRole)>) -> Self {
+        Self {
+            minimum_roles: entries
+                .into_iter()
+                .map(|(command, role)| (command.to_string(), role))
+                .collect(),
+        }
+    }
+
+    pub fn minimum_role(&self, command: &str) -> Option<&UserRole> {
+        self.minimum_roles.get(command)
+    }
+
+    pub fn set_minimum_role(&mut self, command: &str, role: UserRole) -> anyhow::Result<()> {
+        // Only commands that consult the policy are listed, so any other name would be a no-op
+        if !is_known_command(command) {
+            return Err(anyhow::anyhow!("Unknown command: {}", command));
+        }
+        if LOCKED_COMMANDS.contains(&command) && role != UserRole::Administrator {
+            return Err(anyhow::anyhow!("The minimum role for {} cannot be lowered", command));
+        }
+
+        self.minimum_roles.insert(command.to_string(), role);
+        Ok(())
+    }
+
+    pub fn authorize(&self, command: &str, role: &UserRole) -> Result<(), AuthorizationError> {
+        match self.minimum_role(command) {
+            Some(required) if role_rank(role) < role_rank(required) => Err(AuthorizationError::PermissionDenied {
+                command: command.to_string(),
+                required: required.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn role_rank(role: &UserRole) -> u8 {
+    match role {
+        UserRole::Engineer => 1,
+        UserRole::Administrator => 2,
+    }
+}
+
+fn is_known_command(command: &str) -> bool {
+    DEFAULT_COMMAND_ROLES.iter().any(|(name, _)| *name == command)
+}
+
+/// Checks the caller's role against the active command policy. Call after validating
+/// the session and before touching any state.
+pub fn authorize_command(
+    policy: &RwLock<CommandPolicy>,
+    session: &SessionToken,
+    command: &str,
+) -> Result<(), AuthorizationError> {
+    let policy = policy.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    policy.authorize(command, &session.role).inspect_err(|_| {
+        warn!("User {} ({}) denied access to {}", session.username, session.role, command);
+    })
+}
+
+pub trait CommandPolicyRepository {
+    fn load_command_policy(&self) -> Result<CommandPolicy>;
+    fn set_command_minimum_role(&self, command: &str, role: &UserRole) -> Result<()>;
+}
+
+/// Persists the command policy so administrator changes survive a restart. The table is
+/// seeded with the defaults and loaded into the managed policy when the database opens.
+pub struct SqliteCommandPolicyRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteCommandPolicyRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn initialize_schema(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS command_policies (
+                command_name TEXT PRIMARY KEY,
+                minimum_role TEXT NOT NULL CHECK (minimum_role IN ('Administrator', 'Engineer')),
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+        )?;
+
+        // Seed the defaults once; later edits are left alone and new commands pick up theirs
+        for (command, role) in DEFAULT_COMMAND_ROLES {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO command_policies (command_name, minimum_role) VALUES (?1, ?2)",
+                (command, role.to_string()),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn row_to_entry(row: &Row) -> rusqlite::Result<(String, UserRole)> {
+        let role: String = row.get("minimum_role")?;
+        let role = role.parse::<UserRole>().map_err(|e| rusqlite::Error::FromSqlConversionFailure(
+            0,
+            rusqlite::types::Type::Text,
+            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())),
+        ))?;
+        Ok((row.get("command_name")?, role))
+    }
+}
+
+impl<'a> CommandPolicyRepository for SqliteCommandPolicyRepository<'a> {
+    fn load_command_policy(&self) -> Result<CommandPolicy> {
+        let mut stmt = self.conn.prepare("SELECT command_name, minimum_role FROM command_policies")?;
+        let entries = stmt.query_map([], Self::row_to_entry)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Rows left behind by commands that no longer consult the policy are ignored
+        let mut policy = CommandPolicy::default();
+        for (command, role) in entries {
+            if is_known_command(&command) {
+                policy.minimum_roles.insert(command, role);
+            }
+        }
+        Ok(policy)
+    }
+
+    fn set_command_minimum_role(&self, command: &str, role: &UserRole) -> Result<()> {
+        self.load_command_policy()?.set_minimum_role(command, role.clone())?;
+
+        self.conn.execute(
+            "INSERT INTO command_policies (command_name, minimum_role, updated_at)
+             VALUES (?1, ?2, CURRENT_TIMESTAMP)
+             ON CONFLICT (command_name) DO UPDATE SET
+                minimum_role = excluded.minimum_role,
+                updated_at = CURRENT_TIMESTAMP",
+            (command, role.to_string()),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(role: UserRole) -> SessionToken {
+        SessionToken {
+            token: "token".to_string(),
+            user_id: 7,
+            username: "operator".to_string(),
+            role,
+            expires_at: u64::MAX,
+            session_id: "session".to_string(),
+            created_at: 0,
+            last_activity: 0,
+            source: "login".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_engineer_blocked_from_admin_command() {
+        let policy = CommandPolicy::default();
+
+        let result = policy.authorize("create_engineer_user", &UserRole::Engineer);
+        assert_eq!(result, Err(AuthorizationError::PermissionDenied {
+            command: "create_engineer_user".to_string(),
+            required: UserRole::Administrator,
+        }));
+        assert!(policy.authorize("create_engineer_user", &UserRole::Administrator).is_ok());
+
+        let policy = RwLock::new(policy);
+        assert!(authorize_command(&policy, &session(UserRole::Engineer), "deactivate_user").is_err());
+    }
+
+    #[test]
+    fn test_engineer_allowed_on_shared_command() {
+        let policy = CommandPolicy::default();
+
+        assert!(policy.minimum_role("get_configuration_versions").is_none());
+        assert!(policy.authorize("get_configuration_versions", &UserRole::Engineer).is_ok());
+        let policy = RwLock::new(policy);
+        assert!(authorize_command(&policy, &session(UserRole::Engineer), "get_configuration_versions").is_ok());
+    }
+
+    #[test]
+    fn test_bulk_delete_requires_administrator() {
+        let policy = RwLock::new(CommandPolicy::default());
+        assert!(authorize_command(&policy, &session(UserRole::Engineer), "start_bulk_delete").is_err());
+        assert!(authorize_command(&policy, &session(UserRole::Administrator), "start_bulk_delete").is_ok());
+    }
+
+    #[test]
+    fn test_policy_is_data_driven() {
+        let mut policy = CommandPolicy::from_entries([("verify_audit_chain", UserRole::Administrator)]);
+        assert!(policy.authorize("verify_audit_chain", &UserRole::Engineer).is_err());
+
+        policy.set_minimum_role("verify_audit_chain", UserRole::Engineer).unwrap();
+        assert!(policy.authorize("verify_audit_chain", &UserRole::Engineer).is_ok());
+
+        let mut policy = CommandPolicy::default();
+        assert!(policy.set_minimum_role("set_command_minimum_role", UserRole::Engineer).is_err());
+        assert!(policy.set_minimum_role("", UserRole::Engineer).is_err());
+        assert!(policy.set_minimum_role("export_vault", UserRole::Administrator).is_err());
+    }
+
+    #[test]
+    fn test_command_policy_persists() {
+        let conn = Connection::open_in_memory().unwrap();
+        let repo = SqliteCommandPolicyRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+        assert_eq!(repo.load_command_policy().unwrap().minimum_roles, CommandPolicy::default().minimum_roles);
+
+        repo.set_command_minimum_role("verify_audit_chain", &UserRole::Engineer).unwrap();
+        assert!(repo.set_command_minimum_role("get_command_policy", &UserRole::Engineer).is_err());
+        assert!(repo.set_command_minimum_role("no_such_command", &UserRole::Administrator).is_err());
+
+        // Re-running the schema setup must not restore the seeded role
+        repo.initialize_schema().unwrap();
+        let policy = repo.load_command_policy().unwrap();
+        assert_eq!(policy.minimum_role("verify_audit_chain"), Some(&UserRole::Engineer));
+        assert_eq!(policy.minimum_role("get_command_policy"), Some(&UserRole::Administrator));
+        assert!(policy.minimum_role("no_such_command").is_none());
+    }
+}
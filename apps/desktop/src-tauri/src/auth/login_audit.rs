@@ -0,0 +1,216 @@
+// Login audit: a hashed fingerprint of the device behind each successful login, so logins can
+// be correlated to machines without recording where they came from
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Properties of the machine a login came from. Only their hash is stored, and an app upgrade
+/// changes it, so the first login after an upgrade shows as a new device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceFingerprint {
+    pub os: String,
+    pub app_version: String,
+    pub machine_id: String,
+}
+
+impl DeviceFingerprint {
+    /// Fingerprint of the machine this app is running on
+    pub fn current() -> Self {
+        Self {
+            os: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            machine_id: machine_id(),
+        }
+    }
+
+    /// Hex SHA-256 of the fields, each length-prefixed so values cannot run into each other
+    pub fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for field in [&self.os, &self.app_version, &self.machine_id] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// The systemd or D-Bus machine id where one exists, otherwise the host name
+fn machine_id() -> String {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"].iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginAuditEntry {
+    pub id: i64,
+    pub user_id: i64,
+    pub fingerprint_hash: String,
+    /// First login by this user from this fingerprint
+    pub is_new_device: bool,
+    pub logged_in_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoginAuditSettings {
+    pub enabled: bool,
+}
+
+impl Default for LoginAuditSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+pub trait LoginAuditRepository {
+    /// Record a successful login; `None` when the login audit is disabled
+    fn record_login(&self, user_id: i64, fingerprint: &DeviceFingerprint) -> Result<Option<LoginAuditEntry>>;
+    fn get_login_history(&self, user_id: i64) -> Result<Vec<LoginAuditEntry>>;
+    fn get_login_audit_settings(&self) -> Result<LoginAuditSettings>;
+    fn update_login_audit_settings(&self, settings: &LoginAuditSettings) -> Result<()>;
+}
+
+pub struct SqliteLoginAuditRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteLoginAuditRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn initialize_schema(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS login_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id INTEGER NOT NULL,
+                fingerprint_hash TEXT NOT NULL,
+                is_new_device BOOLEAN NOT NULL,
+                logged_in_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_login_audit_user_fingerprint ON login_audit(user_id, fingerprint_hash);
+
+            CREATE TABLE IF NOT EXISTS login_audit_policy (
+                id INTEGER PRIMARY KEY CHECK(id = 1),
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT OR IGNORE INTO login_audit_policy (id, enabled) VALUES (1, 1);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    fn row_to_entry(row: &Row) -> rusqlite::Result<LoginAuditEntry> {
+        Ok(LoginAuditEntry {
+            id: row.get("id")?,
+            user_id: row.get("user_id")?,
+            fingerprint_hash: row.get("fingerprint_hash")?,
+            is_new_device: row.get("is_new_device")?,
+            logged_in_at: row.get("logged_in_at")?,
+        })
+    }
+}
+
+impl<'a> LoginAuditRepository for SqliteLoginAuditRepository<'a> {
+    fn record_login(&self, user_id: i64, fingerprint: &DeviceFingerprint) -> Result<Option<LoginAuditEntry>> {
+        if !self.get_login_audit_settings()?.enabled {
+            return Ok(None);
+        }
+
+        let entry = self.conn.query_row(
+            "INSERT INTO login_audit (user_id, fingerprint_hash, is_new_device)
+             SELECT ?1, ?2, NOT EXISTS (SELECT 1 FROM login_audit WHERE user_id = ?1 AND fingerprint_hash = ?2)
+             RETURNING id, user_id, fingerprint_hash, is_new_device, logged_in_at",
+            (user_id, fingerprint.hash()),
+            Self::row_to_entry,
+        )?;
+        Ok(Some(entry))
+    }
+
+    fn get_login_history(&self, user_id: i64) -> Result<Vec<LoginAuditEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, user_id, fingerprint_hash, is_new_device, logged_in_at FROM login_audit
+             WHERE user_id = ?1
+             ORDER BY logged_in_at DESC, id DESC"
+        )?;
+        let entries = stmt.query_map([user_id], Self::row_to_entry)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    fn get_login_audit_settings(&self) -> Result<LoginAuditSettings> {
+        let enabled: Option<bool> = self.conn.query_row(
+            "SELECT enabled FROM login_audit_policy WHERE id = 1",
+            [],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(enabled.map_or_else(LoginAuditSettings::default, |enabled| LoginAuditSettings { enabled }))
+    }
+
+    fn update_login_audit_settings(&self, settings: &LoginAuditSettings) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO login_audit_policy (id, enabled) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET enabled = excluded.enabled, updated_at = CURRENT_TIMESTAMP",
+            [settings.enabled],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(machine_id: &str) -> DeviceFingerprint {
+        DeviceFingerprint {
+            os: "windows-x86_64".to_string(),
+            app_version: "0.4.0".to_string(),
+            machine_id: machine_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_new_fingerprint_is_flagged() {
+        let conn = Connection::open_in_memory().unwrap();
+        let repo = SqliteLoginAuditRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+
+        let first = repo.record_login(1, &fingerprint("engineering-laptop")).unwrap().unwrap();
+        assert!(first.is_new_device);
+        assert_eq!(first.fingerprint_hash, fingerprint("engineering-laptop").hash());
+        assert!(!first.fingerprint_hash.contains("engineering-laptop"));
+
+        let repeat = repo.record_login(1, &fingerprint("engineering-laptop")).unwrap().unwrap();
+        assert!(!repeat.is_new_device);
+        assert!(repo.record_login(1, &fingerprint("control-room-hmi")).unwrap().unwrap().is_new_device);
+        // Devices are tracked per user
+        assert!(repo.record_login(2, &fingerprint("engineering-laptop")).unwrap().unwrap().is_new_device);
+
+        let history = repo.get_login_history(1).unwrap();
+        let flags: Vec<bool> = history.iter().map(|entry| entry.is_new_device).collect();
+        assert_eq!(flags, vec![true, false, true]);
+
+        // Disabling the audit stops recording without losing history
+        repo.update_login_audit_settings(&LoginAuditSettings { enabled: false }).unwrap();
+        assert!(repo.record_login(1, &fingerprint("unknown")).unwrap().is_none());
+        assert_eq!(repo.get_login_history(1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_fingerprint_hash_covers_every_field() {
+        let base = fingerprint("plant-pc");
+        assert_eq!(base.hash(), fingerprint("plant-pc").hash());
+        assert_ne!(base.hash(), DeviceFingerprint { app_version: "0.5.0".to_string(), ..base.clone() }.hash());
+        assert_ne!(base.hash(), DeviceFingerprint { os: "linux-x86_64".to_string(), ..base.clone() }.hash());
+        assert_ne!(base.hash(), fingerprint("plant-pc2").hash());
+    }
+}
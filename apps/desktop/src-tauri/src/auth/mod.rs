@@ -9,6 +9,11 @@ use uuid::Uuid;
 
 use crate::users::{User, UserInfo, UserRole};
 
+pub mod authorization;
+pub use authorization::{authorize_command, CommandPolicy, CommandPolicyRepository, SqliteCommandPolicyRepository};
+pub mod login_audit;
+pub use login_audit::{DeviceFingerprint, LoginAuditEntry, LoginAuditRepository, LoginAuditSettings, SqliteLoginAuditRepository};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionToken {
     pub token: String,
@@ -16,6 +21,43 @@ pub struct SessionToken {
     pub username: String,
     pub role: UserRole,
     pub expires_at: u64,
+    /// Identifier shown to administrators so the bearer token itself is never listed
+    #[serde(default)]
+    pub session_id: String,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_activity: u64,
+    #[serde(default)]
+    pub source: String,
+}
+
+/// Admin-facing view of an active session, without the token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub user_id: i64,
+    pub username: String,
+    pub role: UserRole,
+    pub created_at: u64,
+    pub last_activity: u64,
+    pub expires_at: u64,
+    pub source: String,
+}
+
+impl From<&SessionToken> for SessionInfo {
+    fn from(session: &SessionToken) -> Self {
+        Self {
+            session_id: session.session_id.clone(),
+            user_id: session.user_id,
+            username: session.username.clone(),
+            role: session.role.clone(),
+            created_at: session.created_at,
+            last_activity: session.last_activity,
+            expires_at: session.expires_at,
+            source: session.source.clone(),
+        }
+    }
 }
 
 // Type alias for compatibility
@@ -46,6 +88,11 @@ impl SessionManager {
     }
 
     pub fn create_session(&self, user: &User) -> Result<SessionToken> {
+        self.create_session_with_source(user, "login")
+    }
+
+    /// Creates a session tagged with where it originated (e.g. "login", "account_setup")
+    pub fn create_session_with_source(&self, user: &User, source: &str) -> Result<SessionToken> {
         let token = Uuid::new_v4().to_string();
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -58,6 +105,10 @@ impl SessionManager {
             username: user.username.clone(),
             role: user.role.clone(),
             expires_at: now + self.session_duration,
+            session_id: Uuid::new_v4().to_string(),
+            created_at: now,
+            last_activity: now,
+            source: source.to_string(),
         };
 
         {
@@ -77,9 +128,10 @@ impl SessionManager {
 
         let mut sessions = self.sessions.lock().unwrap();
         
-        if let Some(session) = sessions.get(token).cloned() {
+        if let Some(session) = sessions.get_mut(token) {
             if session.expires_at > now {
-                Ok(Some(session))
+                session.last_activity = now;
+                Ok(Some(session.clone()))
             } else {
                 let username = session.username.clone();
                 sessions.remove(token);
@@ -119,6 +171,41 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Lists unexpired sessions, most recently active first
+    pub fn list_active_sessions(&self) -> Vec<SessionInfo> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let sessions = self.sessions.lock().unwrap();
+        let mut active: Vec<SessionInfo> = sessions
+            .values()
+            .filter(|session| session.expires_at > now)
+            .map(SessionInfo::from)
+            .collect();
+        active.sort_by_key(|session| std::cmp::Reverse(session.last_activity));
+        active
+    }
+
+    /// Removes the session with the given id so its token is rejected on the next check.
+    /// Returns the revoked session, or None if no such session exists.
+    pub fn revoke_session(&self, session_id: &str) -> Result<Option<SessionInfo>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let token = sessions
+            .iter()
+            .find(|(_, session)| session.session_id == session_id)
+            .map(|(token, _)| token.clone());
+
+        match token.and_then(|token| sessions.remove(&token)) {
+            Some(session) => {
+                info!("Revoked session {} for user: {}", session_id, session.username);
+                Ok(Some(SessionInfo::from(&session)))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Get current user ID from any active session (for backward compatibility)
     /// Note: This method assumes there's only one active session at a time
     pub fn get_current_user_id(&self) -> Option<i64> {
@@ -409,6 +496,56 @@ pub mod tests {
         assert!(validated.is_none());
     }
 
+    fn test_user(id: i64, username: &str, role: UserRole) -> User {
+        User {
+            id,
+            username: username.to_string(),
+            password_hash: "hash".to_string(),
+            role,
+            created_at: "2023-01-01".to_string(),
+            updated_at: "2023-01-01".to_string(),
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn test_list_active_sessions() {
+        let session_manager = SessionManager::new();
+        let admin = session_manager.create_session_with_source(&test_user(1, "admin", UserRole::Administrator), "account_setup").unwrap();
+        let engineer = session_manager.create_session(&test_user(2, "engineer", UserRole::Engineer)).unwrap();
+
+        let sessions = session_manager.list_active_sessions();
+        assert_eq!(sessions.len(), 2);
+
+        let listed = sessions.iter().find(|s| s.session_id == engineer.session_id).unwrap();
+        assert_eq!(listed.username, "engineer");
+        assert_eq!(listed.source, "login");
+        assert!(listed.created_at > 0);
+        assert_eq!(listed.created_at, listed.last_activity);
+        assert_ne!(listed.session_id, engineer.token);
+
+        let listed = sessions.iter().find(|s| s.session_id == admin.session_id).unwrap();
+        assert_eq!(listed.source, "account_setup");
+    }
+
+    #[test]
+    fn test_revoked_session_is_rejected() {
+        let session_manager = SessionManager::new();
+        let admin = session_manager.create_session(&test_user(1, "admin", UserRole::Administrator)).unwrap();
+        let engineer = session_manager.create_session(&test_user(2, "engineer", UserRole::Engineer)).unwrap();
+        assert!(session_manager.validate_session(&engineer.token).unwrap().is_some());
+
+        let revoked = session_manager.revoke_session(&engineer.session_id).unwrap().unwrap();
+        assert_eq!(revoked.user_id, 2);
+
+        assert!(session_manager.validate_session(&engineer.token).unwrap().is_none());
+        assert!(session_manager.validate_session(&admin.token).unwrap().is_some());
+        assert_eq!(session_manager.list_active_sessions().len(), 1);
+
+        // Revoking an unknown or already revoked session is a no-op
+        assert!(session_manager.revoke_session(&engineer.session_id).unwrap().is_none());
+    }
+
     #[test]
     fn test_login_attempt_tracking() {
         let tracker = LoginAttemptTracker::new();
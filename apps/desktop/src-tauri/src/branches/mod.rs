@@ -2,6 +2,10 @@ use anyhow::Result;
 use rusqlite::{Connection, Row, params};
 use serde::{Deserialize, Serialize};
 use tracing::{info, error};
+use crate::configurations::ConfigurationVersionInfo;
+
+/// Active branch limit applied when no policy row has been configured
+pub const DEFAULT_MAX_ACTIVE_BRANCHES: i64 = 10;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Branch {
@@ -70,6 +74,8 @@ pub struct BranchVersion {
     pub branch_version_number: String,
     pub is_latest: bool,
     pub created_at: String,
+    /// Set once the version's content has been dropped by `prune_branch_versions`
+    pub content_pruned_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +88,7 @@ pub struct BranchVersionInfo {
     pub is_latest: bool,
     pub is_branch_latest: bool,
     pub created_at: String,
+    pub content_pruned_at: Option<String>,
     // Configuration version details
     pub asset_id: i64,
     pub version_number: String,
@@ -94,6 +101,44 @@ pub struct BranchVersionInfo {
     pub version_created_at: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineageEdgeKind {
+    /// The previous version on the same line, mainline or branch
+    Parent,
+    /// A branch's first version, drawn from the version the branch was cut at
+    BranchFrom,
+    /// A branch's final version, drawn to the mainline version its merge produced
+    Merge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageNode {
+    pub version_id: i64,
+    pub version_number: String,
+    pub status: String,
+    pub created_at: String,
+    /// Unset for mainline versions
+    pub branch_id: Option<i64>,
+    pub branch_name: Option<String>,
+    pub branch_version_number: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineageEdge {
+    pub from_version_id: i64,
+    pub to_version_id: i64,
+    pub kind: LineageEdgeKind,
+}
+
+/// Every configuration version of an asset as a DAG, for rendering branches and merges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionLineage {
+    pub asset_id: i64,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateBranchVersionRequest {
     pub branch_id: i64,
@@ -110,13 +155,20 @@ pub trait BranchRepository {
     fn delete_branch(&self, branch_id: i64) -> Result<()>;
     fn get_branch_count(&self, asset_id: i64) -> Result<i64>;
     fn validate_branch_name(&self, asset_id: i64, name: &str, exclude_id: Option<i64>) -> Result<bool>;
+    fn get_max_active_branches(&self) -> Result<i64>;
+    fn set_max_active_branches(&self, limit: i64) -> Result<()>;
     
     // Branch version methods
     fn import_version_to_branch(&self, request: CreateBranchVersionRequest) -> Result<BranchVersion>;
+    fn import_configuration_to_new_branch(&self, asset_id: i64, branch_name: &str, file_path: &str, author: i64, notes: &str) -> Result<(Branch, BranchVersion)>;
+    fn restore_version_to_branch(&self, version_id: i64, branch_name: &str, author_id: i64) -> Result<(Branch, BranchVersion)>;
     fn get_branch_versions(&self, branch_id: i64, page: Option<i32>, limit: Option<i32>) -> Result<Vec<BranchVersionInfo>>;
     fn get_branch_latest_version(&self, branch_id: i64) -> Result<Option<BranchVersionInfo>>;
     fn compare_branch_versions(&self, branch_id: i64, version1_id: i64, version2_id: i64) -> Result<Vec<u8>>;
     fn get_branch_version_count(&self, branch_id: i64) -> Result<i64>;
+    fn prune_branch_versions(&self, branch_id: i64, keep_recent: usize) -> Result<Vec<i64>>;
+    fn merge_branch_to_main(&self, branch_id: i64, author: i64, notes: &str) -> Result<ConfigurationVersionInfo>;
+    fn get_version_lineage(&self, asset_id: i64) -> Result<VersionLineage>;
 }
 
 pub struct SqliteBranchRepository<'a> {
@@ -143,6 +195,7 @@ impl<'a> SqliteBranchRepository<'a> {
                 is_active BOOLEAN DEFAULT 1,
                 latest_version_id INTEGER,
                 latest_branch_version TEXT,
+                merged_version_id INTEGER REFERENCES configuration_versions(id) ON DELETE SET NULL,
                 FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE,
                 FOREIGN KEY (parent_version_id) REFERENCES configuration_versions(id),
                 FOREIGN KEY (created_by) REFERENCES users(id),
@@ -157,12 +210,20 @@ impl<'a> SqliteBranchRepository<'a> {
                 branch_version_number TEXT NOT NULL,
                 is_latest BOOLEAN DEFAULT 0,
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                content_pruned_at DATETIME,
                 FOREIGN KEY (branch_id) REFERENCES branches(id) ON DELETE CASCADE,
                 FOREIGN KEY (version_id) REFERENCES configuration_versions(id) ON DELETE CASCADE,
                 UNIQUE(branch_id, version_id),
                 UNIQUE(branch_id, branch_version_number)
             );
 
+            -- Branch policy configuration table
+            CREATE TABLE IF NOT EXISTS branch_policies (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                max_active_branches INTEGER NOT NULL DEFAULT 10,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
             CREATE INDEX IF NOT EXISTS idx_branches_asset_id ON branches(asset_id);
             CREATE INDEX IF NOT EXISTS idx_branches_parent_version ON branches(parent_version_id);
             CREATE INDEX IF NOT EXISTS idx_branches_created_by ON branches(created_by);
@@ -174,6 +235,9 @@ impl<'a> SqliteBranchRepository<'a> {
             CREATE INDEX IF NOT EXISTS idx_branch_versions_version_id ON branch_versions(version_id);
             CREATE INDEX IF NOT EXISTS idx_branch_versions_latest ON branch_versions(branch_id, is_latest);
             CREATE INDEX IF NOT EXISTS idx_branch_versions_created_at ON branch_versions(created_at);
+
+            -- Insert default branch policy if none exists
+            INSERT OR IGNORE INTO branch_policies (id, max_active_branches) VALUES (1, 10);
             "#,
         )?;
         Ok(())
@@ -219,6 +283,7 @@ impl<'a> SqliteBranchRepository<'a> {
             branch_version_number: row.get("branch_version_number")?,
             is_latest: row.get("is_latest")?,
             created_at: row.get("created_at")?,
+            content_pruned_at: row.get("content_pruned_at")?,
         })
     }
 
@@ -232,6 +297,7 @@ impl<'a> SqliteBranchRepository<'a> {
             is_latest: row.get("is_latest")?,
             is_branch_latest: row.get("is_branch_latest")?,
             created_at: row.get("created_at")?,
+            content_pruned_at: row.get("content_pruned_at")?,
             asset_id: row.get("asset_id")?,
             version_number: row.get("version_number")?,
             file_name: row.get("file_name")?,
@@ -318,57 +384,8 @@ impl<'a> SqliteBranchRepository<'a> {
         Ok(())
     }
 
-    fn copy_parent_version_to_branch(&self, branch_id: i64, parent_version_id: i64, created_by: i64) -> Result<BranchVersion> {
-        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
-        
-        // Get the parent version details and content
-        let config_repo = SqliteConfigurationRepository::new(self.conn);
-        
-        let parent_config = config_repo.get_configuration_by_id(parent_version_id)?
-            .ok_or_else(|| anyhow::anyhow!("Parent version not found"))?;
-        
-        let parent_content = config_repo.get_configuration_content(parent_version_id)?;
-        
-        // Get the asset_id from the branch
-        let asset_id: i64 = self.conn.query_row(
-            "SELECT asset_id FROM branches WHERE id = ?1",
-            [branch_id],
-            |row| row.get(0)
-        )?;
-        
-        // Create a new configuration version as a copy of the parent
-        let config_request = CreateConfigurationRequest {
-            asset_id,
-            file_name: parent_config.file_name.clone(),
-            file_content: parent_content,
-            author: created_by,
-            notes: format!("Initial branch version created from parent version {}", parent_config.version_number),
-        };
-        
-        let new_config = config_repo.store_configuration(config_request)?;
-        
-        // Create the branch version entry
-        let branch_version_number = self.generate_next_branch_version_number(branch_id)?;
-        
-        let mut stmt = self.conn.prepare(
-            "INSERT INTO branch_versions (branch_id, version_id, branch_version_number, is_latest)
-             VALUES (?1, ?2, ?3, 1) RETURNING *"
-        )?;
-        
-        let branch_version = stmt.query_row(
-            params![branch_id, new_config.id, &branch_version_number],
-            Self::row_to_branch_version,
-        )?;
-        
-        // Update branch with latest version info
-        self.update_branch_latest_version(branch_id, new_config.id, &branch_version_number)?;
-        
-        Ok(branch_version)
-    }
-}
-
-impl<'a> BranchRepository for SqliteBranchRepository<'a> {
-    fn create_branch(&self, request: CreateBranchRequest) -> Result<Branch> {
+    /// Validates and inserts a branch row without giving it any versions
+    fn insert_branch(&self, request: &CreateBranchRequest) -> Result<Branch> {
         // Validate inputs
         self.validate_branch_name_input(&request.name)?;
         self.validate_description_input(&request.description)?;
@@ -377,6 +394,17 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         if !self.validate_branch_name(request.asset_id, &request.name, None)? {
             return Err(anyhow::anyhow!("Branch name '{}' already exists for this asset", request.name));
         }
+
+        // Enforce the active branch limit; deactivated branches do not count
+        let max_active_branches = self.get_max_active_branches()?;
+        let active_branches = self.get_branch_count(request.asset_id)?;
+        if active_branches >= max_active_branches {
+            return Err(anyhow::anyhow!(
+                "Asset has reached the maximum of {} active branches ({} currently active). Deactivate an existing branch before creating a new one.",
+                max_active_branches,
+                active_branches
+            ));
+        }
         
         // Verify parent version exists and is not archived
         let mut version_check_stmt = self.conn.prepare(
@@ -419,6 +447,79 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
             Self::row_to_branch,
         )?;
 
+        Ok(branch)
+    }
+
+    fn copy_parent_version_to_branch(&self, branch_id: i64, parent_version_id: i64, created_by: i64) -> Result<BranchVersion> {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+        
+        // Get the parent version details and content
+        let config_repo = SqliteConfigurationRepository::new(self.conn);
+        
+        let parent_config = config_repo.get_configuration_by_id(parent_version_id)?
+            .ok_or_else(|| anyhow::anyhow!("Parent version not found"))?;
+        
+        let mut parent_content = config_repo.get_configuration_content(parent_version_id)?;
+        
+        // Get the asset_id from the branch
+        let asset_id: i64 = self.conn.query_row(
+            "SELECT asset_id FROM branches WHERE id = ?1",
+            [branch_id],
+            |row| row.get(0)
+        )?;
+        
+        // Create a new configuration version as a copy of the parent
+        let config_request = CreateConfigurationRequest {
+            asset_id,
+            file_name: parent_config.file_name.clone(),
+            file_content: std::mem::take(&mut *parent_content),
+            author: created_by,
+            notes: format!("Initial branch version created from parent version {}", parent_config.version_number),
+        };
+        
+        let new_config = config_repo.store_configuration(config_request)?;
+        
+        // Create the branch version entry
+        let branch_version_number = self.generate_next_branch_version_number(branch_id)?;
+        
+        let mut stmt = self.conn.prepare(
+            "INSERT INTO branch_versions (branch_id, version_id, branch_version_number, is_latest)
+             VALUES (?1, ?2, ?3, 1) RETURNING *"
+        )?;
+        
+        let branch_version = stmt.query_row(
+            params![branch_id, new_config.id, &branch_version_number],
+            Self::row_to_branch_version,
+        )?;
+        
+        // Update branch with latest version info
+        self.update_branch_latest_version(branch_id, new_config.id, &branch_version_number)?;
+        
+        Ok(branch_version)
+    }
+
+    /// Newest configuration version of the asset that does not belong to any branch
+    fn get_mainline_head(&self, asset_id: i64) -> Result<Option<(i64, String)>> {
+        let result = self.conn.query_row(
+            "SELECT id, version_number FROM configuration_versions 
+             WHERE asset_id = ?1 AND id NOT IN (SELECT version_id FROM branch_versions) 
+             ORDER BY created_at DESC, id DESC LIMIT 1",
+            [asset_id],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        );
+
+        match result {
+            Ok(head) => Ok(Some(head)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<'a> BranchRepository for SqliteBranchRepository<'a> {
+    fn create_branch(&self, request: CreateBranchRequest) -> Result<Branch> {
+        let branch = self.insert_branch(&request)?;
+
         // Automatically create an initial branch version from the parent version
         let result = self.copy_parent_version_to_branch(branch.id, request.parent_version_id, request.created_by);
         
@@ -550,6 +651,35 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         Ok(count)
     }
 
+    fn get_max_active_branches(&self) -> Result<i64> {
+        let result = self.conn.query_row(
+            "SELECT max_active_branches FROM branch_policies WHERE id = 1",
+            [],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(limit) => Ok(limit),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(DEFAULT_MAX_ACTIVE_BRANCHES),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_max_active_branches(&self, limit: i64) -> Result<()> {
+        if limit < 1 {
+            return Err(anyhow::anyhow!("Maximum active branches must be at least 1"));
+        }
+
+        self.conn.execute(
+            "INSERT INTO branch_policies (id, max_active_branches) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET max_active_branches = excluded.max_active_branches, updated_at = CURRENT_TIMESTAMP",
+            [limit],
+        )?;
+
+        info!("Maximum active branches per asset set to {}", limit);
+        Ok(())
+    }
+
     fn validate_branch_name(&self, asset_id: i64, name: &str, exclude_id: Option<i64>) -> Result<bool> {
         let name = name.trim();
         
@@ -580,8 +710,10 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         // Import configuration file using existing configuration repository
         use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest, file_utils};
         
+        let config_repo = SqliteConfigurationRepository::new(self.conn);
+
         // Read and validate file
-        let file_content = file_utils::read_file_content(&request.file_path)?;
+        let file_content = file_utils::read_file_content(&request.file_path, config_repo.get_max_upload_bytes()?)?;
         let file_name = std::path::Path::new(&request.file_path)
             .file_name()
             .and_then(|n| n.to_str())
@@ -589,7 +721,6 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
             .to_string();
 
         // Create configuration version
-        let config_repo = SqliteConfigurationRepository::new(self.conn);
         let config_request = CreateConfigurationRequest {
             asset_id,
             file_name,
@@ -624,6 +755,87 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         Ok(branch_version)
     }
 
+    fn import_configuration_to_new_branch(&self, asset_id: i64, branch_name: &str, file_path: &str, author: i64, notes: &str) -> Result<(Branch, BranchVersion)> {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository};
+
+        // Branch off the golden version, falling back to the newest non-archived one
+        let config_repo = SqliteConfigurationRepository::new(self.conn);
+        let parent_version_id = match config_repo.get_golden_version(asset_id)? {
+            Some(golden) => golden.id,
+            None => {
+                let latest = self.conn.query_row(
+                    "SELECT id FROM configuration_versions 
+                     WHERE asset_id = ?1 AND status != 'Archived' 
+                     ORDER BY created_at DESC, id DESC LIMIT 1",
+                    [asset_id],
+                    |row| row.get::<_, i64>(0),
+                );
+                match latest {
+                    Ok(version_id) => version_id,
+                    Err(rusqlite::Error::QueryReturnedNoRows) => {
+                        return Err(anyhow::anyhow!("Asset has no configuration version to branch from"));
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        };
+
+        // The branch only exists if the file imports, so both writes share one transaction
+        let tx = self.conn.unchecked_transaction()?;
+
+        let branch = self.insert_branch(&CreateBranchRequest {
+            name: branch_name.to_string(),
+            description: None,
+            asset_id,
+            parent_version_id,
+            created_by: author,
+        })?;
+
+        let branch_version = self.import_version_to_branch(CreateBranchVersionRequest {
+            branch_id: branch.id,
+            file_path: file_path.to_string(),
+            notes: notes.to_string(),
+            author,
+        })?;
+
+        tx.commit()?;
+
+        info!("Imported {} into new branch '{}' for asset {}", file_path, branch.name, asset_id);
+        Ok((branch, branch_version))
+    }
+
+    fn restore_version_to_branch(&self, version_id: i64, branch_name: &str, author_id: i64) -> Result<(Branch, BranchVersion)> {
+        let (asset_id, version_number) = match self.conn.query_row(
+            "SELECT asset_id, version_number FROM configuration_versions WHERE id = ?1",
+            [version_id],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        ) {
+            Ok(version) => version,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                return Err(anyhow::anyhow!("Configuration version not found"));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // The branch only exists if its seed version is stored, so both writes share one transaction
+        let tx = self.conn.unchecked_transaction()?;
+
+        let branch = self.insert_branch(&CreateBranchRequest {
+            name: branch_name.to_string(),
+            description: Some(format!("Restored from version {}", version_number)),
+            asset_id,
+            parent_version_id: version_id,
+            created_by: author_id,
+        })?;
+
+        let branch_version = self.copy_parent_version_to_branch(branch.id, version_id, author_id)?;
+
+        tx.commit()?;
+
+        info!("Restored version {} into new branch '{}' for asset {}", version_number, branch.name, asset_id);
+        Ok((branch, branch_version))
+    }
+
     fn get_branch_versions(&self, branch_id: i64, page: Option<i32>, limit: Option<i32>) -> Result<Vec<BranchVersionInfo>> {
         let page = page.unwrap_or(1);
         let limit = limit.unwrap_or(50);
@@ -632,7 +844,7 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         let mut stmt = self.conn.prepare(
             "SELECT bv.id, bv.branch_id, b.name as branch_name, bv.version_id, 
                     bv.branch_version_number, bv.is_latest, 
-                    (bv.is_latest = 1) as is_branch_latest, bv.created_at,
+                    (bv.is_latest = 1) as is_branch_latest, bv.created_at, bv.content_pruned_at,
                     cv.asset_id, cv.version_number, cv.file_name, cv.file_size, 
                     cv.content_hash, cv.author, u.username as author_username, 
                     cv.notes, cv.created_at as version_created_at
@@ -659,7 +871,7 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         let mut stmt = self.conn.prepare(
             "SELECT bv.id, bv.branch_id, b.name as branch_name, bv.version_id, 
                     bv.branch_version_number, bv.is_latest, 
-                    (bv.is_latest = 1) as is_branch_latest, bv.created_at,
+                    (bv.is_latest = 1) as is_branch_latest, bv.created_at, bv.content_pruned_at,
                     cv.asset_id, cv.version_number, cv.file_name, cv.file_size, 
                     cv.content_hash, cv.author, u.username as author_username, 
                     cv.notes, cv.created_at as version_created_at
@@ -744,6 +956,155 @@ impl<'a> BranchRepository for SqliteBranchRepository<'a> {
         let count: i64 = stmt.query_row([branch_id], |row| row.get(0))?;
         Ok(count)
     }
+
+    fn prune_branch_versions(&self, branch_id: i64, keep_recent: usize) -> Result<Vec<i64>> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        // Everything past the most recent `keep_recent` versions is a candidate; the branch
+        // latest, already pruned versions and content other records still rely on are kept.
+        let candidates: Vec<(i64, i64)> = tx.prepare(
+            "SELECT bv.id, bv.version_id FROM branch_versions bv
+             JOIN configuration_versions cv ON bv.version_id = cv.id
+             WHERE bv.branch_id = ?1
+               AND bv.is_latest = 0
+               AND bv.content_pruned_at IS NULL
+               AND cv.status != 'Golden'
+               AND cv.pinned = 0
+               AND NOT EXISTS (SELECT 1 FROM branches b WHERE b.parent_version_id = bv.version_id)
+               AND bv.id NOT IN (
+                   SELECT id FROM branch_versions WHERE branch_id = ?1
+                   ORDER BY created_at DESC, id DESC LIMIT ?2
+               )"
+        )?.query_map(params![branch_id, keep_recent as i64], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        let mut pruned = Vec::new();
+        for (branch_version_id, version_id) in candidates {
+            // An empty blob marks pruned content; file size and hash stay as the record of what was there
+            tx.execute(
+                "UPDATE configuration_versions SET file_content = X'' WHERE id = ?1",
+                [version_id],
+            )?;
+            tx.execute(
+                "UPDATE branch_versions SET content_pruned_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                [branch_version_id],
+            )?;
+            pruned.push(version_id);
+        }
+
+        tx.commit()?;
+
+        if !pruned.is_empty() {
+            info!("Pruned content of {} versions on branch {}", pruned.len(), branch_id);
+        }
+        Ok(pruned)
+    }
+
+    fn merge_branch_to_main(&self, branch_id: i64, author: i64, notes: &str) -> Result<ConfigurationVersionInfo> {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let branch = self.get_branch_by_id(branch_id)?
+            .ok_or_else(|| anyhow::anyhow!("Branch not found or inactive"))?;
+        let latest = self.get_branch_latest_version(branch_id)?
+            .ok_or_else(|| anyhow::anyhow!("Branch has no versions to merge"))?;
+
+        let config_repo = SqliteConfigurationRepository::new(self.conn);
+        let mut content = config_repo.get_configuration_content(latest.version_id)?;
+
+        // Mainline may have moved on since the branch was cut; the merge still takes the
+        // branch content as-is, but records which head it replaced
+        let mut merge_notes = format!("Merged branch '{}' ({})", branch.name, latest.branch_version_number);
+        if let Some((_, head_number)) = self.get_mainline_head(branch.asset_id)?
+            .filter(|(head_id, _)| *head_id != branch.parent_version_id)
+        {
+            merge_notes.push_str(&format!(
+                "; mainline head was {} (branched from {})", head_number, branch.parent_version_number
+            ));
+        }
+        if !notes.trim().is_empty() {
+            merge_notes.push_str(&format!(": {}", notes.trim()));
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        let version = config_repo.store_configuration(CreateConfigurationRequest {
+            asset_id: branch.asset_id,
+            file_name: latest.file_name.clone(),
+            file_content: std::mem::take(&mut *content),
+            author,
+            notes: merge_notes,
+        })?;
+        self.conn.execute(
+            "UPDATE branches SET is_active = 0, merged_version_id = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            (version.id, branch_id),
+        )?;
+        tx.commit()?;
+
+        info!("Merged branch '{}' ({}) into mainline of asset {} as {}",
+              branch.name, latest.branch_version_number, branch.asset_id, version.version_number);
+        Ok(version.into())
+    }
+
+    fn get_version_lineage(&self, asset_id: i64) -> Result<VersionLineage> {
+        let mut stmt = self.conn.prepare(
+            "SELECT cv.id, cv.version_number, cv.status, cv.created_at,
+                    bv.branch_id, b.name as branch_name, bv.branch_version_number
+             FROM configuration_versions cv
+             LEFT JOIN branch_versions bv ON bv.version_id = cv.id
+             LEFT JOIN branches b ON b.id = bv.branch_id
+             WHERE cv.asset_id = ?1
+             ORDER BY cv.created_at, cv.id"
+        )?;
+        let nodes = stmt.query_map([asset_id], |row| {
+            Ok(LineageNode {
+                version_id: row.get("id")?,
+                version_number: row.get("version_number")?,
+                status: row.get("status")?,
+                created_at: row.get("created_at")?,
+                branch_id: row.get("branch_id")?,
+                branch_name: row.get("branch_name")?,
+                branch_version_number: row.get("branch_version_number")?,
+            })
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut edges = Vec::new();
+        let mainline: Vec<i64> = nodes.iter()
+            .filter(|node| node.branch_id.is_none())
+            .map(|node| node.version_id)
+            .collect();
+        for pair in mainline.windows(2) {
+            edges.push(LineageEdge { from_version_id: pair[0], to_version_id: pair[1], kind: LineageEdgeKind::Parent });
+        }
+
+        // Closed branches are included; their versions and merges are still history
+        let mut stmt = self.conn.prepare(
+            "SELECT id, parent_version_id, merged_version_id FROM branches WHERE asset_id = ?1 ORDER BY id"
+        )?;
+        let branches = stmt.query_map([asset_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, Option<i64>>(2)?))
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT version_id FROM branch_versions WHERE branch_id = ?1 ORDER BY id"
+        )?;
+        for (branch_id, parent_version_id, merged_version_id) in branches {
+            let versions = stmt.query_map([branch_id], |row| row.get::<_, i64>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let (Some(&first), Some(&last)) = (versions.first(), versions.last()) else {
+                continue;
+            };
+
+            edges.push(LineageEdge { from_version_id: parent_version_id, to_version_id: first, kind: LineageEdgeKind::BranchFrom });
+            for pair in versions.windows(2) {
+                edges.push(LineageEdge { from_version_id: pair[0], to_version_id: pair[1], kind: LineageEdgeKind::Parent });
+            }
+            if let Some(merged_version_id) = merged_version_id {
+                edges.push(LineageEdge { from_version_id: last, to_version_id: merged_version_id, kind: LineageEdgeKind::Merge });
+            }
+        }
+
+        Ok(VersionLineage { asset_id, nodes, edges })
+    }
 }
 
 #[cfg(test)]
@@ -1122,4 +1483,400 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("do not belong to the specified branch"));
     }
-}
\ No newline at end of file
+
+    fn branch_request(name: &str) -> CreateBranchRequest {
+        CreateBranchRequest {
+            name: name.to_string(),
+            description: None,
+            asset_id: 1,
+            parent_version_id: 1,
+            created_by: 1,
+        }
+    }
+
+    #[test]
+    fn test_max_active_branches_enforced() {
+        let (_temp_file, conn) = setup_test_db();
+        conn.execute("ALTER TABLE configuration_versions ADD COLUMN status TEXT NOT NULL DEFAULT 'Draft'", []).unwrap();
+        let repo = SqliteBranchRepository::new(&conn);
+
+        assert_eq!(repo.get_max_active_branches().unwrap(), DEFAULT_MAX_ACTIVE_BRANCHES);
+        repo.set_max_active_branches(2).unwrap();
+        assert_eq!(repo.get_max_active_branches().unwrap(), 2);
+
+        let first = repo.create_branch(branch_request("branch-one")).unwrap();
+        repo.create_branch(branch_request("branch-two")).unwrap();
+
+        let result = repo.create_branch(branch_request("branch-three"));
+        assert!(result.is_err());
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("maximum of 2 active branches"));
+        assert!(message.contains("2 currently active"));
+
+        // Deactivated branches no longer count towards the limit
+        repo.delete_branch(first.id).unwrap();
+        let branch = repo.create_branch(branch_request("branch-three")).unwrap();
+        assert!(branch.is_active);
+        assert_eq!(repo.get_branch_count(1).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_set_max_active_branches_validation() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+
+        assert!(repo.set_max_active_branches(0).is_err());
+        assert!(repo.set_max_active_branches(-3).is_err());
+        assert_eq!(repo.get_max_active_branches().unwrap(), DEFAULT_MAX_ACTIVE_BRANCHES);
+    }
+
+    fn setup_import_test_db() -> (NamedTempFile, Connection) {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(temp_file.path()).unwrap();
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL,
+                password_hash TEXT NOT NULL,
+                role TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                is_active BOOLEAN DEFAULT 1
+            );
+
+            CREATE TABLE assets (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                created_by INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (created_by) REFERENCES users(id)
+            );
+
+            INSERT INTO users (id, username, password_hash, role) VALUES (1, 'testuser', 'hash', 'Engineer');
+            INSERT INTO assets (id, name, description, created_by) VALUES (1, 'Test Asset', 'Test Description', 1);
+            "#,
+        ).unwrap();
+
+        crate::firmware::SqliteFirmwareRepository::new(&conn).initialize_schema().unwrap();
+        crate::configurations::SqliteConfigurationRepository::new(&conn).initialize_schema().unwrap();
+        SqliteBranchRepository::new(&conn).initialize_schema().unwrap();
+
+        (temp_file, conn)
+    }
+
+    #[test]
+    fn test_import_configuration_to_new_branch() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let store = |content: &str| {
+            config_repo.store_configuration(CreateConfigurationRequest {
+                asset_id: 1,
+                file_name: "plc.cfg".to_string(),
+                file_content: content.as_bytes().to_vec(),
+                author: 1,
+                notes: "Baseline".to_string(),
+            }).unwrap()
+        };
+        let golden = store("setpoint=10");
+        let latest = store("setpoint=12");
+
+        let experiment_file = NamedTempFile::new().unwrap();
+        std::fs::write(experiment_file.path(), "setpoint=15").unwrap();
+        let experiment_path = experiment_file.path().to_str().unwrap();
+
+        // Without a golden version the branch starts from the latest one
+        let (branch, branch_version) = repo.import_configuration_to_new_branch(
+            1, "experiment-a", experiment_path, 1, "Trying a higher setpoint"
+        ).unwrap();
+        assert_eq!(branch.parent_version_id, latest.id);
+        assert_eq!(branch_version.branch_id, branch.id);
+        assert_eq!(branch_version.branch_version_number, "branch-v1");
+        assert!(branch_version.is_latest);
+
+        let versions = repo.get_branch_versions(branch.id, None, None).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version_id, branch_version.version_id);
+        assert_eq!(versions[0].notes, "Trying a higher setpoint");
+        assert_eq!(
+            config_repo.get_configuration_content(branch_version.version_id).unwrap().as_slice(),
+            b"setpoint=15"
+        );
+
+        conn.execute("UPDATE configuration_versions SET status = 'Golden' WHERE id = ?1", [golden.id]).unwrap();
+        let (branch, _) = repo.import_configuration_to_new_branch(
+            1, "experiment-b", experiment_path, 1, "From golden"
+        ).unwrap();
+        assert_eq!(branch.parent_version_id, golden.id);
+
+        // A colliding name is rejected before anything is written
+        let result = repo.import_configuration_to_new_branch(1, "experiment-a", experiment_path, 1, "Duplicate");
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+        assert_eq!(repo.get_branch_count(1).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_import_configuration_to_new_branch_rolls_back_on_failure() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        config_repo.store_configuration(CreateConfigurationRequest {
+            asset_id: 1,
+            file_name: "plc.cfg".to_string(),
+            file_content: b"setpoint=10".to_vec(),
+            author: 1,
+            notes: "Baseline".to_string(),
+        }).unwrap();
+
+        // The branch row is inserted before the file is read, so a failed import must undo it
+        let empty_file = NamedTempFile::new().unwrap();
+        let result = repo.import_configuration_to_new_branch(
+            1, "experiment-a", empty_file.path().to_str().unwrap(), 1, "Empty file"
+        );
+        assert!(result.is_err());
+
+        assert_eq!(repo.get_branch_count(1).unwrap(), 0);
+        assert!(repo.validate_branch_name(1, "experiment-a", None).unwrap());
+        assert_eq!(config_repo.get_configuration_count(1).unwrap(), 1);
+
+        let missing = repo.import_configuration_to_new_branch(1, "experiment-b", "/nonexistent/plc.cfg", 1, "Missing");
+        assert!(missing.is_err());
+        assert_eq!(repo.get_branch_count(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_restore_version_to_branch() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let store = |content: &str| {
+            config_repo.store_configuration(CreateConfigurationRequest {
+                asset_id: 1,
+                file_name: "plc.cfg".to_string(),
+                file_content: content.as_bytes().to_vec(),
+                author: 1,
+                notes: "Baseline".to_string(),
+            }).unwrap()
+        };
+        let old = store("setpoint=10");
+        store("setpoint=12");
+
+        let (branch, branch_version) = repo.restore_version_to_branch(old.id, "rework", 1).unwrap();
+        assert_eq!(branch.asset_id, 1);
+        assert_eq!(branch.parent_version_id, old.id);
+        assert_eq!(branch.description, Some(format!("Restored from version {}", old.version_number)));
+        assert_eq!(branch_version.branch_id, branch.id);
+        assert_eq!(branch_version.branch_version_number, "branch-v1");
+        assert!(branch_version.is_latest);
+
+        // The branch gets its own copy of the old content rather than pointing at the old version
+        assert_ne!(branch_version.version_id, old.id);
+        assert_eq!(
+            config_repo.get_configuration_content(branch_version.version_id).unwrap().as_slice(),
+            b"setpoint=10"
+        );
+        assert_eq!(repo.get_branch_version_count(branch.id).unwrap(), 1);
+
+        assert!(repo.restore_version_to_branch(9999, "missing", 1).unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_restore_version_to_branch_rolls_back_on_failure() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest, LintMode};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let old = config_repo.store_configuration(CreateConfigurationRequest {
+            asset_id: 1,
+            file_name: "plc.json".to_string(),
+            file_content: b"{\"setpoint\": ".to_vec(),
+            author: 1,
+            notes: "Stored before linting was enforced".to_string(),
+        }).unwrap();
+
+        // The branch row is inserted before the content is copied, so a rejected copy must undo it
+        config_repo.set_lint_mode(LintMode::Reject).unwrap();
+        assert!(repo.restore_version_to_branch(old.id, "rework", 1).is_err());
+
+        assert_eq!(repo.get_branch_count(1).unwrap(), 0);
+        assert!(repo.validate_branch_name(1, "rework", None).unwrap());
+        assert_eq!(config_repo.get_configuration_count(1).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_prune_branch_versions_keeps_recent_and_latest() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let base = config_repo.store_configuration(CreateConfigurationRequest {
+            asset_id: 1,
+            file_name: "plc.cfg".to_string(),
+            file_content: b"setpoint=10".to_vec(),
+            author: 1,
+            notes: "Baseline".to_string(),
+        }).unwrap();
+
+        let (branch, first) = repo.restore_version_to_branch(base.id, "tuning", 1).unwrap();
+        let mut versions = vec![first];
+        for setpoint in 11..=14 {
+            let file = NamedTempFile::new().unwrap();
+            std::fs::write(file.path(), format!("setpoint={}", setpoint)).unwrap();
+            versions.push(repo.import_version_to_branch(CreateBranchVersionRequest {
+                branch_id: branch.id,
+                file_path: file.path().to_str().unwrap().to_string(),
+                notes: format!("Setpoint {}", setpoint),
+                author: 1,
+            }).unwrap());
+        }
+
+        let pruned = repo.prune_branch_versions(branch.id, 2).unwrap();
+        let expected: Vec<i64> = versions[..3].iter().map(|v| v.version_id).collect();
+        let mut sorted = pruned.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+
+        // Pruned versions keep their metadata but no longer have readable content
+        let infos = repo.get_branch_versions(branch.id, None, None).unwrap();
+        assert_eq!(infos.len(), 5);
+        for info in &infos {
+            assert_eq!(info.content_pruned_at.is_some(), expected.contains(&info.version_id));
+            assert!(info.file_size > 0);
+        }
+        let error = config_repo.get_configuration_content(versions[0].version_id).unwrap_err();
+        assert!(error.to_string().contains("pruned"));
+        assert_eq!(
+            config_repo.get_configuration_content(versions[3].version_id).unwrap().as_slice(),
+            b"setpoint=13"
+        );
+
+        // The branch latest survives even when nothing else is kept
+        let pruned = repo.prune_branch_versions(branch.id, 0).unwrap();
+        assert_eq!(pruned, vec![versions[3].version_id]);
+        let latest = repo.get_branch_latest_version(branch.id).unwrap().unwrap();
+        assert_eq!(latest.version_id, versions[4].version_id);
+        assert!(latest.content_pruned_at.is_none());
+        assert_eq!(
+            config_repo.get_configuration_content(latest.version_id).unwrap().as_slice(),
+            b"setpoint=14"
+        );
+        assert!(repo.prune_branch_versions(branch.id, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_merge_branch_to_main() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let store = |content: &str| {
+            config_repo.store_configuration(CreateConfigurationRequest {
+                asset_id: 1,
+                file_name: "plc.cfg".to_string(),
+                file_content: content.as_bytes().to_vec(),
+                author: 1,
+                notes: "Mainline".to_string(),
+            }).unwrap()
+        };
+        let base = store("setpoint=10");
+
+        let experiment_file = NamedTempFile::new().unwrap();
+        std::fs::write(experiment_file.path(), "setpoint=15").unwrap();
+        let (branch, _) = repo.import_configuration_to_new_branch(
+            1, "experiment", experiment_file.path().to_str().unwrap(), 1, "Higher setpoint"
+        ).unwrap();
+        assert_eq!(branch.parent_version_id, base.id);
+
+        // Mainline moves on after the branch was cut
+        let head = store("setpoint=11");
+
+        let merged = repo.merge_branch_to_main(branch.id, 1, "Commissioning sign-off").unwrap();
+        assert_eq!(
+            config_repo.get_configuration_content(merged.id).unwrap().as_slice(),
+            b"setpoint=15"
+        );
+        assert_eq!(
+            merged.notes,
+            format!("Merged branch 'experiment' (branch-v1); mainline head was {} (branched from {}): Commissioning sign-off",
+                    head.version_number, base.version_number)
+        );
+
+        let versions = config_repo.get_configuration_versions(1).unwrap();
+        let listed = versions.iter().find(|v| v.id == merged.id).unwrap();
+        assert_eq!(listed.notes, merged.notes);
+        assert_eq!(listed.file_name, "plc.cfg");
+
+        // The branch is closed, so it cannot be merged twice
+        assert!(repo.get_branch_by_id(branch.id).unwrap().is_none());
+        assert!(repo.merge_branch_to_main(branch.id, 1, "").unwrap_err().to_string().contains("not found or inactive"));
+        assert_eq!(repo.get_branch_count(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_version_lineage_with_branch_and_merge() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest};
+
+        let (_temp_file, conn) = setup_import_test_db();
+        let repo = SqliteBranchRepository::new(&conn);
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let store = |content: &str| {
+            config_repo.store_configuration(CreateConfigurationRequest {
+                asset_id: 1,
+                file_name: "plc.cfg".to_string(),
+                file_content: content.as_bytes().to_vec(),
+                author: 1,
+                notes: "Mainline".to_string(),
+            }).unwrap().id
+        };
+        let base = store("setpoint=10");
+
+        let branch_file = NamedTempFile::new().unwrap();
+        std::fs::write(branch_file.path(), "setpoint=15").unwrap();
+        let (branch, first) = repo.import_configuration_to_new_branch(
+            1, "experiment", branch_file.path().to_str().unwrap(), 1, "Higher setpoint"
+        ).unwrap();
+        std::fs::write(branch_file.path(), "setpoint=16").unwrap();
+        let second = repo.import_version_to_branch(CreateBranchVersionRequest {
+            branch_id: branch.id,
+            file_path: branch_file.path().to_str().unwrap().to_string(),
+            notes: "Tuned".to_string(),
+            author: 1,
+        }).unwrap();
+
+        let head = store("setpoint=11");
+        let merged = repo.merge_branch_to_main(branch.id, 1, "").unwrap().id;
+
+        let lineage = repo.get_version_lineage(1).unwrap();
+        assert_eq!(lineage.nodes.len(), 5);
+        let node = |id: i64| lineage.nodes.iter().find(|node| node.version_id == id).unwrap();
+        assert_eq!(node(first.version_id).branch_id, Some(branch.id));
+        assert_eq!(node(second.version_id).branch_version_number.as_deref(), Some("branch-v2"));
+        assert!(node(merged).branch_id.is_none());
+
+        let edge = |from, to, kind| LineageEdge { from_version_id: from, to_version_id: to, kind };
+        let mut edges = lineage.edges.clone();
+        edges.sort_by_key(|edge| (edge.from_version_id, edge.to_version_id));
+        let mut expected = vec![
+            edge(base, head, LineageEdgeKind::Parent),
+            edge(head, merged, LineageEdgeKind::Parent),
+            edge(base, first.version_id, LineageEdgeKind::BranchFrom),
+            edge(first.version_id, second.version_id, LineageEdgeKind::Parent),
+            edge(second.version_id, merged, LineageEdgeKind::Merge),
+        ];
+        expected.sort_by_key(|edge| (edge.from_version_id, edge.to_version_id));
+        assert_eq!(edges, expected);
+    }
+}
@@ -239,6 +239,60 @@ pub struct ImportTemplate {
     pub updated_at: String,
 }
 
+impl ImportTemplate {
+    /// Rename the columns of a parsed CSV from the template's source column names to the
+    /// internal field names expected by validation and processing. Header matching ignores
+    /// case and surrounding whitespace; columns without a mapping keep their original name.
+    pub fn apply_field_mapping(&self, csv_result: CSVParseResult) -> CSVParseResult {
+        apply_field_mapping(csv_result, &self.field_mapping)
+    }
+}
+
+pub fn apply_field_mapping(csv_result: CSVParseResult, field_mapping: &HashMap<String, String>) -> CSVParseResult {
+    if field_mapping.is_empty() {
+        return csv_result;
+    }
+
+    let normalized: HashMap<String, &String> = field_mapping
+        .iter()
+        .map(|(source, target)| (source.trim().to_lowercase(), target))
+        .collect();
+
+    let map_header = |header: &str| -> String {
+        normalized
+            .get(&header.trim().to_lowercase())
+            .map(|target| target.to_string())
+            .unwrap_or_else(|| header.to_string())
+    };
+
+    let headers = csv_result.headers.iter().map(|h| map_header(h)).collect();
+    let rows = csv_result
+        .rows
+        .into_iter()
+        .map(|row| row.into_iter().map(|(key, value)| (map_header(&key), value)).collect())
+        .collect();
+
+    CSVParseResult {
+        headers,
+        rows,
+        total_rows: csv_result.total_rows,
+        errors: csv_result.errors,
+    }
+}
+
+fn validate_field_mapping(field_mapping: &HashMap<String, String>) -> Result<()> {
+    let mut targets = std::collections::HashSet::new();
+    for (source, target) in field_mapping {
+        if source.trim().is_empty() || target.trim().is_empty() {
+            return Err(anyhow::anyhow!("Field mapping entries cannot be empty"));
+        }
+        if !targets.insert(target.trim().to_lowercase()) {
+            return Err(anyhow::anyhow!("Field '{}' is mapped from more than one column", target));
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportTemplateConfig {
     pub template_name: String,
@@ -704,6 +758,7 @@ impl<'a> ImportTemplateRepository for SqliteBulkImportRepository<'a> {
         if config.template_name.len() > 100 {
             return Err(anyhow::anyhow!("Template name cannot exceed 100 characters"));
         }
+        validate_field_mapping(&config.field_mapping)?;
 
         // Serialize JSON fields
         let field_mapping_json = serde_json::to_string(&config.field_mapping)
@@ -859,6 +914,8 @@ impl<'a> ImportTemplateRepository for SqliteBulkImportRepository<'a> {
     }
 
     fn update_template(&self, template: &ImportTemplate) -> Result<()> {
+        validate_field_mapping(&template.field_mapping)?;
+
         // Serialize JSON fields
         let field_mapping_json = serde_json::to_string(&template.field_mapping)
             .map_err(|e| anyhow::anyhow!("Failed to serialize field mapping: {}", e))?;
@@ -1085,4 +1142,78 @@ mod tests {
         let updated_session = repo.get_session_by_id(session.id).unwrap().unwrap();
         assert_eq!(updated_session.total_items, 2);
     }
-}
\ No newline at end of file
+
+    fn parse_csv(content: &str) -> CSVParseResult {
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        let headers: Vec<String> = reader.headers().unwrap().iter().map(|h| h.to_string()).collect();
+        let rows: Vec<HashMap<String, String>> = reader
+            .records()
+            .map(|record| {
+                let record = record.unwrap();
+                headers.iter().cloned().zip(record.iter().map(|f| f.to_string())).collect()
+            })
+            .collect();
+        let total_rows = rows.len() as i64;
+        CSVParseResult { headers, rows, total_rows, errors: Vec::new() }
+    }
+
+    fn template_config(field_mapping: HashMap<String, String>) -> ImportTemplateConfig {
+        ImportTemplateConfig {
+            template_name: "Plant Spreadsheet".to_string(),
+            template_type: "assets".to_string(),
+            asset_type: "Device".to_string(),
+            field_mapping,
+            required_fields: vec!["name".to_string(), "asset_type".to_string()],
+            optional_fields: vec!["description".to_string(), "parent_name".to_string()],
+            validation_rules: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_import_with_renamed_columns_uses_template_mapping() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteBulkImportRepository::new(&conn);
+
+        let mut field_mapping = HashMap::new();
+        field_mapping.insert("Device Tag".to_string(), "name".to_string());
+        field_mapping.insert("Kind".to_string(), "asset_type".to_string());
+        field_mapping.insert("Notes".to_string(), "description".to_string());
+        let template = repo.create_template(template_config(field_mapping), 1).unwrap();
+        let template = repo.get_template_by_id(template.id).unwrap().unwrap();
+
+        // Columns are renamed and reordered relative to the default template
+        let csv = "Notes,Location,device tag,KIND\nMain PLC,Line 1,PLC-001,Device\n";
+        let mapped = template.apply_field_mapping(parse_csv(csv));
+
+        assert_eq!(mapped.headers, vec!["description", "Location", "name", "asset_type"]);
+        assert_eq!(mapped.total_rows, 1);
+        let row = &mapped.rows[0];
+        assert_eq!(row.get("name").map(String::as_str), Some("PLC-001"));
+        assert_eq!(row.get("asset_type").map(String::as_str), Some("Device"));
+        assert_eq!(row.get("description").map(String::as_str), Some("Main PLC"));
+        // Unmapped columns pass through untouched so they can be kept as metadata
+        assert_eq!(row.get("Location").map(String::as_str), Some("Line 1"));
+        assert!(row.get("Device Tag").is_none());
+    }
+
+    #[test]
+    fn test_template_rejects_ambiguous_field_mapping() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteBulkImportRepository::new(&conn);
+
+        let mut field_mapping = HashMap::new();
+        field_mapping.insert("Tag".to_string(), "name".to_string());
+        field_mapping.insert("Label".to_string(), "name".to_string());
+
+        assert!(repo.create_template(template_config(field_mapping), 1).is_err());
+    }
+
+    #[test]
+    fn test_empty_field_mapping_leaves_columns_unchanged() {
+        let csv = "name,asset_type\nPLC-001,Device\n";
+        let mapped = apply_field_mapping(parse_csv(csv), &HashMap::new());
+
+        assert_eq!(mapped.headers, vec!["name", "asset_type"]);
+        assert_eq!(mapped.rows[0].get("name").map(String::as_str), Some("PLC-001"));
+    }
+}
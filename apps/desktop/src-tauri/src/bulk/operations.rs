@@ -1,10 +1,32 @@
 use anyhow::{anyhow, Result};
-use rusqlite::{Connection, Row};
+use rusqlite::{Connection, OptionalExtension, Row, Transaction, TransactionBehavior};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::assets::{AssetRepository, AssetType, MoveAssetRequest, SqliteAssetRepository};
+use crate::audit::{AuditEventRequest, AuditEventType, AuditRepository, SqliteAuditRepository};
+use crate::firmware::FirmwareFileStorage;
+
+/// Upper bound on items processed at once, whatever an operation requests
+pub const MAX_BULK_CONCURRENCY: usize = 16;
+
+/// How long an item waits for another connection's write to finish
+const WORKER_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+const PROTECTED_MESSAGE: &str = "Asset holds Golden or pinned configuration versions";
+
+/// Binds `subtree` to asset `?1` and every asset below it
+const ASSET_SUBTREE: &str = "WITH RECURSIVE subtree(id) AS (
+    SELECT ?1
+    UNION ALL
+    SELECT a.id FROM assets a JOIN subtree s ON a.parent_id = s.id
+)";
+
 // Bulk Operations Types - separate from bulk import functionality
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -124,6 +146,9 @@ pub struct BulkMoveOptions {
     pub new_parent_id: Option<i32>,
     pub validate_hierarchy: bool,
     pub skip_conflicts: bool,
+    /// Items processed at once; unset runs them one at a time
+    #[serde(default)]
+    pub concurrency: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +156,9 @@ pub struct BulkMoveRequest {
     pub asset_ids: Vec<i32>,
     pub new_parent_id: Option<i32>,
     pub options: BulkMoveOptions,
+    /// Report what the move would do without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 // Delete Operation Types
@@ -139,12 +167,18 @@ pub struct BulkDeleteOptions {
     pub force_delete: bool,
     pub delete_children: bool,
     pub skip_protected: bool,
+    /// Items processed at once; unset runs them one at a time
+    #[serde(default)]
+    pub concurrency: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkDeleteRequest {
     pub asset_ids: Vec<i32>,
     pub options: BulkDeleteOptions,
+    /// Report what the delete would do without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 // Export Operation Types
@@ -184,6 +218,9 @@ pub struct BulkExportOptions {
     pub include_children: bool,
     pub include_configurations: bool,
     pub export_path: Option<String>,
+    /// Items processed at once; unset runs them one at a time
+    #[serde(default)]
+    pub concurrency: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -246,6 +283,24 @@ pub struct ConflictResolutionOption {
     pub parameters: Option<serde_json::Value>,
 }
 
+/// What a dry-run bulk operation would have changed. Affected assets include children
+/// removed by a cascading delete; items that would fail are reported as blocking errors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkPreview {
+    pub operation_type: BulkOperationType,
+    pub affected_asset_ids: Vec<i32>,
+    pub affected_count: i32,
+    pub validation: ValidationResult,
+}
+
+/// Result of starting a bulk operation: the new operation's ID, or a preview for a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BulkStartResponse {
+    Started(String),
+    Preview(BulkPreview),
+}
+
 // History and Undo Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkOperationHistory {
@@ -589,7 +644,10 @@ impl<'a> BulkOperationService<'a> {
 
     pub fn create_bulk_move_operation(&self, request: BulkMoveRequest, user_id: i32) -> Result<String> {
         let operation_id = Uuid::new_v4().to_string();
-        let metadata = serde_json::to_value(&request.options)?;
+        // The request's target is the one the move is run against
+        let mut options = request.options;
+        options.new_parent_id = request.new_parent_id;
+        let metadata = serde_json::to_value(&options)?;
         
         let operation = BulkOperation {
             id: operation_id.clone(),
@@ -713,6 +771,548 @@ impl<'a> BulkOperationService<'a> {
     pub fn cancel_operation(&self, operation_id: &str) -> Result<()> {
         self.repo.update_operation_status(operation_id, BulkOperationStatus::Cancelled)
     }
+
+    /// Run `process` over every asset in the operation, up to the operation's `concurrency`
+    /// items at a time. Each result is logged as it arrives and the operation is checked for
+    /// cancellation after every item; once cancelled no further items are started, and items
+    /// already in flight are still recorded so progress matches the work actually done.
+    pub fn execute_operation<F>(&self, operation_id: &str, process: F) -> Result<BulkOperationProgress>
+    where
+        F: Fn(i32) -> Result<()> + Sync,
+    {
+        let operation = self.repo.get_operation_by_id(operation_id)?
+            .ok_or_else(|| anyhow!("Operation not found"))?;
+        if operation.status == BulkOperationStatus::Cancelled {
+            return self.get_operation_progress(operation_id);
+        }
+        if operation.status != BulkOperationStatus::Pending {
+            return Err(anyhow!("Operation has already been started"));
+        }
+
+        let concurrency = operation.metadata.as_ref()
+            .and_then(|metadata| metadata.get("concurrency"))
+            .and_then(|value| value.as_u64())
+            .map_or(1, |value| (value as usize).clamp(1, MAX_BULK_CONCURRENCY));
+        let asset_ids = &operation.asset_ids;
+        let action = operation.operation_type.as_str();
+
+        self.repo.update_operation_status(operation_id, BulkOperationStatus::Processing)?;
+
+        let mut remaining = asset_ids.iter().copied();
+        let mut cancelled = false;
+        let mut processed = 0;
+        let mut failed = 0;
+
+        // Items are handed out one at a time from this thread, which owns the repository
+        // connection, so a cancellation seen here stops work before the next item starts
+        let (work_sender, work_receiver) = mpsc::channel::<i32>();
+        let work_receiver = Mutex::new(work_receiver);
+        std::thread::scope(|scope| -> Result<()> {
+            let (result_sender, result_receiver) = mpsc::channel();
+            for _ in 0..concurrency.min(asset_ids.len()) {
+                let (work_receiver, result_sender, process) = (&work_receiver, result_sender.clone(), &process);
+                scope.spawn(move || {
+                    loop {
+                        let next = work_receiver.lock().unwrap().recv();
+                        let Ok(asset_id) = next else { break };
+                        if result_sender.send((asset_id, process(asset_id))).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(result_sender);
+
+            let mut in_flight = 0;
+            for asset_id in remaining.by_ref().take(concurrency) {
+                work_sender.send(asset_id)?;
+                in_flight += 1;
+            }
+
+            while in_flight > 0 {
+                let (asset_id, result) = result_receiver.recv()?;
+                in_flight -= 1;
+
+                if !self.record_item(operation_id, asset_id, action, result)? {
+                    failed += 1;
+                }
+                processed += 1;
+                self.repo.update_operation_progress(
+                    operation_id,
+                    processed as f64 / asset_ids.len() as f64 * 100.0,
+                    processed,
+                    failed,
+                )?;
+
+                let still_running = self.repo.get_operation_by_id(operation_id)?
+                    .is_some_and(|operation| operation.status != BulkOperationStatus::Cancelled);
+                cancelled = cancelled || !still_running;
+                if !cancelled {
+                    if let Some(next_asset_id) = remaining.next() {
+                        work_sender.send(next_asset_id)?;
+                        in_flight += 1;
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        if !cancelled {
+            self.repo.update_operation_status(operation_id, BulkOperationStatus::Completed)?;
+        }
+        self.get_operation_progress(operation_id)
+    }
+
+    /// Log one item's outcome, returning whether it succeeded
+    fn record_item(&self, operation_id: &str, asset_id: i32, action: &str, result: Result<()>) -> Result<bool> {
+        let succeeded = result.is_ok();
+        self.repo.add_operation_log(&BulkOperationLog {
+            id: Uuid::new_v4().to_string(),
+            bulk_operation_id: operation_id.to_string(),
+            asset_id,
+            action: action.to_string(),
+            status: if succeeded { "success" } else { "failed" }.to_string(),
+            error_message: result.err().map(|e| e.to_string()),
+            timestamp: Utc::now().to_rfc3339(),
+        })?;
+        Ok(succeeded)
+    }
+}
+
+impl ValidationResult {
+    fn valid() -> Self {
+        Self {
+            is_valid: true,
+            warnings: vec![],
+            errors: vec![],
+            conflicts: vec![],
+        }
+    }
+
+    fn push_error(&mut self, asset_id: i32, asset_name: String, error_type: &str, message: String, suggested_action: &str) {
+        self.errors.push(ValidationError {
+            asset_id,
+            asset_name,
+            error_type: error_type.to_string(),
+            message,
+            blocking: true,
+            suggested_action: Some(suggested_action.to_string()),
+        });
+        self.is_valid = false;
+    }
+}
+
+/// Check that every asset exists and that the target parent can take them
+pub fn validate_bulk_move(conn: &Connection, asset_ids: &[i32], new_parent_id: Option<i32>) -> Result<ValidationResult> {
+    let asset_repo = SqliteAssetRepository::new(conn);
+    let mut result = ValidationResult::valid();
+
+    for &asset_id in asset_ids {
+        match asset_repo.get_asset_by_id(asset_id as i64) {
+            Ok(Some(asset)) => {
+                if asset.parent_id == new_parent_id.map(i64::from) {
+                    result.conflicts.push(ValidationConflict {
+                        asset_id,
+                        asset_name: asset.name,
+                        conflict_type: "already_in_target".to_string(),
+                        message: "Asset is already in the target location".to_string(),
+                        resolution_options: vec![ConflictResolutionOption {
+                            option_id: "skip".to_string(),
+                            description: "Leave the asset where it is".to_string(),
+                            action: "skip".to_string(),
+                            parameters: None,
+                        }],
+                    });
+                }
+            }
+            Ok(None) => result.push_error(asset_id, format!("Asset {}", asset_id), "not_found",
+                "Asset not found or inaccessible".to_string(), "Remove from selection"),
+            Err(_) => result.push_error(asset_id, format!("Asset {}", asset_id), "access_error",
+                "Cannot access asset".to_string(), "Check permissions"),
+        }
+    }
+
+    if let Some(parent_id) = new_parent_id {
+        match asset_repo.get_asset_by_id(parent_id as i64) {
+            Ok(Some(parent)) => {
+                if parent.asset_type != AssetType::Folder {
+                    result.push_error(parent_id, parent.name, "invalid_parent",
+                        "Parent must be a folder".to_string(), "Select a folder as parent");
+                }
+            }
+            Ok(None) => result.push_error(parent_id, format!("Asset {}", parent_id), "parent_not_found",
+                "Parent folder not found".to_string(), "Select a valid parent folder"),
+            Err(_) => result.push_error(parent_id, format!("Asset {}", parent_id), "parent_access_error",
+                "Cannot access parent folder".to_string(), "Check parent folder permissions"),
+        }
+
+        // Check for circular references when moving to a specific parent
+        for &asset_id in asset_ids {
+            if asset_id == parent_id {
+                result.push_error(asset_id, format!("Asset {}", asset_id), "circular_reference",
+                    "Cannot move asset into itself".to_string(), "Remove from selection or choose different parent");
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Check that every asset exists and that deleting it will not silently take children with it
+pub fn validate_bulk_delete(conn: &Connection, asset_ids: &[i32], options: &BulkDeleteOptions) -> Result<ValidationResult> {
+    let asset_repo = SqliteAssetRepository::new(conn);
+    let mut result = ValidationResult::valid();
+
+    for &asset_id in asset_ids {
+        match asset_repo.get_asset_by_id(asset_id as i64) {
+            Ok(Some(asset)) => {
+                let children = asset_repo.get_children_assets(Some(asset.id))?;
+                if !children.is_empty() && !options.delete_children {
+                    result.push_error(asset_id, asset.name, "has_children",
+                        format!("Asset has {} child asset(s)", children.len()),
+                        "Enable deleting children or move them first");
+                } else if !options.force_delete && is_protected(conn, asset_id)? {
+                    if options.skip_protected {
+                        result.warnings.push(ValidationWarning {
+                            asset_id,
+                            asset_name: asset.name,
+                            warning_type: "protected".to_string(),
+                            message: PROTECTED_MESSAGE.to_string(),
+                            can_proceed: true,
+                        });
+                    } else {
+                        result.push_error(asset_id, asset.name, "protected", PROTECTED_MESSAGE.to_string(),
+                            "Skip protected assets or force the delete");
+                    }
+                }
+            }
+            Ok(None) => result.push_error(asset_id, format!("Asset {}", asset_id), "not_found",
+                "Asset not found or inaccessible".to_string(), "Remove from selection"),
+            Err(_) => result.push_error(asset_id, format!("Asset {}", asset_id), "access_error",
+                "Cannot access asset".to_string(), "Check permissions"),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Validate a bulk move, then apply it inside a transaction that is always rolled back,
+/// so the preview reflects exactly what the database would accept
+pub fn preview_bulk_move(conn: &Connection, request: &BulkMoveRequest) -> Result<BulkPreview> {
+    let mut validation = validate_bulk_move(conn, &request.asset_ids, request.new_parent_id)?;
+    let blocked: Vec<i32> = validation.errors.iter().map(|error| error.asset_id).collect();
+    let already_in_place: Vec<i32> = validation.conflicts.iter().map(|conflict| conflict.asset_id).collect();
+    // Nothing can move into an unusable parent, and each item would only repeat that error
+    let parent_blocked = validation.errors.iter()
+        .any(|error| matches!(error.error_type.as_str(), "invalid_parent" | "parent_not_found" | "parent_access_error"));
+
+    let tx = conn.unchecked_transaction()?;
+    let asset_repo = SqliteAssetRepository::new(&tx);
+    let mut affected_asset_ids = Vec::new();
+    for &asset_id in &request.asset_ids {
+        if parent_blocked || blocked.contains(&asset_id) || already_in_place.contains(&asset_id) {
+            continue;
+        }
+        let moved = asset_repo.move_asset(MoveAssetRequest {
+            asset_id: asset_id as i64,
+            new_parent_id: request.new_parent_id.map(i64::from),
+            new_sort_order: None,
+        });
+        match moved {
+            Ok(()) => affected_asset_ids.push(asset_id),
+            Err(e) => validation.push_error(asset_id, format!("Asset {}", asset_id), "would_fail",
+                e.to_string(), "Remove from selection"),
+        }
+    }
+    tx.rollback()?;
+
+    Ok(BulkPreview {
+        operation_type: BulkOperationType::Move,
+        affected_count: affected_asset_ids.len() as i32,
+        affected_asset_ids,
+        validation,
+    })
+}
+
+/// Validate a bulk delete, then apply it inside a transaction that is always rolled back.
+/// Children removed by the cascade are counted as affected.
+pub fn preview_bulk_delete(conn: &Connection, request: &BulkDeleteRequest) -> Result<BulkPreview> {
+    let mut validation = validate_bulk_delete(conn, &request.asset_ids, &request.options)?;
+    let blocked: Vec<i32> = validation.errors.iter().map(|error| error.asset_id).collect();
+    let skipped: Vec<i32> = validation.warnings.iter()
+        .filter(|warning| warning.warning_type == "protected")
+        .map(|warning| warning.asset_id)
+        .collect();
+
+    let all_asset_ids = |conn: &Connection| -> Result<Vec<i32>> {
+        let mut stmt = conn.prepare("SELECT id FROM assets ORDER BY id")?;
+        let ids = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<i32>>>()?;
+        Ok(ids)
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    let before = all_asset_ids(&tx)?;
+    let asset_repo = SqliteAssetRepository::new(&tx);
+    for &asset_id in &request.asset_ids {
+        if blocked.contains(&asset_id) || skipped.contains(&asset_id) {
+            continue;
+        }
+        // Already removed along with the children of an earlier selection
+        if before.contains(&asset_id) && !all_asset_ids(&tx)?.contains(&asset_id) {
+            continue;
+        }
+        if let Err(e) = asset_repo.delete_asset(asset_id as i64) {
+            validation.push_error(asset_id, format!("Asset {}", asset_id), "would_fail",
+                e.to_string(), "Remove from selection");
+        }
+    }
+    let after = all_asset_ids(&tx)?;
+    tx.rollback()?;
+
+    let affected_asset_ids: Vec<i32> = before.into_iter().filter(|id| !after.contains(id)).collect();
+    Ok(BulkPreview {
+        operation_type: BulkOperationType::Delete,
+        affected_count: affected_asset_ids.len() as i32,
+        affected_asset_ids,
+        validation,
+    })
+}
+
+/// Path of the database file behind `conn`, for opening the connections a running
+/// operation works through
+pub fn database_path(conn: &Connection) -> Result<PathBuf> {
+    conn.path()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Bulk operations need a file-backed database"))
+}
+
+fn open_worker_connection(database_path: &Path) -> Result<Connection> {
+    let conn = Connection::open(database_path)?;
+    conn.busy_timeout(WORKER_BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+/// Items run with foreign keys enforced, so deletes cascade to children as they do in the app
+fn open_item_connection(database_path: &Path) -> Result<Connection> {
+    let conn = open_worker_connection(database_path)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    Ok(conn)
+}
+
+/// Run a created move, delete or export operation to completion. The operation is driven
+/// through its own connection and every item opens another, so the caller need not hold
+/// the application's connection and progress and cancellation requests get through while
+/// it runs. Deletes remove the firmware files of deleted assets from `firmware_dir`.
+pub fn run_bulk_operation(database_path: &Path, firmware_dir: &Path, operation_id: &str) -> Result<BulkOperationProgress> {
+    // The log names assets an item may just have deleted, so this connection leaves
+    // foreign keys unenforced
+    let conn = open_worker_connection(database_path)?;
+    let repo = SqliteBulkOperationsRepository::new(&conn);
+    let service = BulkOperationService::new(&repo);
+    let operation = repo.get_operation_by_id(operation_id)?
+        .ok_or_else(|| anyhow!("Operation not found"))?;
+    let metadata = operation.metadata.clone().unwrap_or(serde_json::Value::Null);
+
+    let result = match operation.operation_type {
+        BulkOperationType::Move => {
+            let options: BulkMoveOptions = serde_json::from_value(metadata)?;
+            service.execute_operation(operation_id, |asset_id| {
+                move_item(&open_item_connection(database_path)?, asset_id, &options)
+            })
+        }
+        BulkOperationType::Delete => {
+            let options: BulkDeleteOptions = serde_json::from_value(metadata)?;
+            let username: Option<String> = conn.query_row(
+                "SELECT username FROM users WHERE id = ?1",
+                [operation.created_by],
+                |row| row.get(0),
+            ).optional()?;
+            let deleted_by = (operation.created_by as i64, username);
+            service.execute_operation(operation_id, |asset_id| {
+                delete_item(&open_item_connection(database_path)?, firmware_dir, operation_id, &deleted_by, asset_id, &options)
+            })
+        }
+        BulkOperationType::Export => {
+            let options: BulkExportOptions = serde_json::from_value(metadata)?;
+            service.execute_operation(operation_id, |asset_id| {
+                export_item(&open_item_connection(database_path)?, asset_id, &options)
+            })
+        }
+        BulkOperationType::Classify => Err(anyhow!("Classify operations are not run item by item")),
+    };
+
+    if result.is_err() {
+        repo.update_operation_status(operation_id, BulkOperationStatus::Failed)?;
+    }
+    result
+}
+
+/// Move one asset of a bulk move under the target parent
+fn move_item(conn: &Connection, asset_id: i32, options: &BulkMoveOptions) -> Result<()> {
+    let asset_repo = SqliteAssetRepository::new(conn);
+    let asset = asset_repo.get_asset_by_id(asset_id as i64)?
+        .ok_or_else(|| anyhow!("Asset not found"))?;
+    let new_parent_id = options.new_parent_id.map(i64::from);
+    if asset.parent_id == new_parent_id {
+        return if options.skip_conflicts {
+            Ok(())
+        } else {
+            Err(anyhow!("Asset is already in the target location"))
+        };
+    }
+    if let Some(parent_id) = new_parent_id {
+        let parent = asset_repo.get_asset_by_id(parent_id)?
+            .ok_or_else(|| anyhow!("Parent folder not found"))?;
+        if parent.asset_type != AssetType::Folder {
+            return Err(anyhow!("Parent must be a folder"));
+        }
+    }
+
+    asset_repo.move_asset(MoveAssetRequest {
+        asset_id: asset_id as i64,
+        new_parent_id,
+        new_sort_order: None,
+    })
+}
+
+/// Whether deleting the asset, with everything below it, would take a Golden or pinned
+/// configuration version with it
+fn is_protected(conn: &Connection, asset_id: i32) -> Result<bool> {
+    let protected = conn.query_row(
+        &format!(
+            "{} SELECT EXISTS(SELECT 1 FROM configuration_versions
+                              WHERE asset_id IN subtree AND (status = 'Golden' OR pinned = 1))",
+            ASSET_SUBTREE,
+        ),
+        [asset_id],
+        |row| row.get(0),
+    )?;
+    Ok(protected)
+}
+
+/// Delete one asset of a bulk delete. Children go with it only when the options allow it,
+/// and protected assets only when the delete is forced; otherwise they are skipped or
+/// refused as the options say. Every asset removed, children included, is audited, and
+/// its firmware files are removed from disk once the rows are gone.
+fn delete_item(
+    conn: &Connection,
+    firmware_dir: &Path,
+    operation_id: &str,
+    deleted_by: &(i64, Option<String>),
+    asset_id: i32,
+    options: &BulkDeleteOptions,
+) -> Result<()> {
+    // Taking the write lock up front keeps concurrent items from interleaving audit entries
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let asset_repo = SqliteAssetRepository::new(&tx);
+    // Already removed along with the children of an earlier selection
+    if asset_repo.get_asset_by_id(asset_id as i64)?.is_none() {
+        return Ok(());
+    }
+    let children = asset_repo.get_children_assets(Some(asset_id as i64))?;
+    if !children.is_empty() && !options.delete_children {
+        return Err(anyhow!("Asset has {} child asset(s)", children.len()));
+    }
+    if !options.force_delete && is_protected(&tx, asset_id)? {
+        return if options.skip_protected {
+            Ok(())
+        } else {
+            Err(anyhow!(PROTECTED_MESSAGE))
+        };
+    }
+
+    let mut stmt = tx.prepare(&format!("{} SELECT id, name FROM assets WHERE id IN subtree ORDER BY id", ASSET_SUBTREE))?;
+    let removed = stmt.query_map([asset_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
+    let mut stmt = tx.prepare(&format!("{} SELECT file_path FROM firmware_versions WHERE asset_id IN subtree", ASSET_SUBTREE))?;
+    let firmware_files = stmt.query_map([asset_id], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
+
+    asset_repo.delete_asset(asset_id as i64)?;
+
+    let audit_repo = SqliteAuditRepository::new(&tx);
+    let (user_id, username) = deleted_by;
+    for (removed_id, name) in &removed {
+        audit_repo.log_event(&AuditEventRequest {
+            event_type: AuditEventType::DatabaseOperation,
+            user_id: Some(*user_id),
+            username: username.clone(),
+            admin_user_id: None,
+            admin_username: None,
+            target_user_id: None,
+            target_username: None,
+            description: format!("Asset '{}' deleted by bulk operation", name),
+            metadata: Some(serde_json::json!({
+                "asset_id": removed_id,
+                "selected_asset_id": asset_id,
+                "bulk_operation_id": operation_id,
+                "force_delete": options.force_delete,
+            }).to_string()),
+            ip_address: None,
+            user_agent: None,
+        })?;
+    }
+    tx.commit()?;
+
+    for file_path in &firmware_files {
+        if let Err(e) = FirmwareFileStorage::delete_firmware_file_in(firmware_dir, file_path) {
+            tracing::warn!("Failed to remove firmware file {} of deleted asset {}: {}", file_path, asset_id, e);
+        }
+    }
+    Ok(())
+}
+
+/// Write one asset, and its direct children when asked, to `asset-<id>.<format>` in the
+/// export directory
+fn export_item(conn: &Connection, asset_id: i32, options: &BulkExportOptions) -> Result<()> {
+    let export_dir = options.export_path.as_deref()
+        .ok_or_else(|| anyhow!("No export path was given"))?;
+    let asset_repo = SqliteAssetRepository::new(conn);
+    let asset = asset_repo.get_asset_by_id(asset_id as i64)?
+        .ok_or_else(|| anyhow!("Asset not found"))?;
+    let mut assets = vec![asset];
+    if options.include_children {
+        assets.extend(asset_repo.get_children_assets(Some(asset_id as i64))?);
+    }
+
+    let content = match options.format {
+        ExportFormat::Json => serde_json::to_string_pretty(&assets)?,
+        ExportFormat::Yaml => serde_yaml::to_string(&assets)?,
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for asset in &assets {
+                writer.serialize(asset)?;
+            }
+            String::from_utf8(writer.into_inner().map_err(|e| anyhow!("Failed to write CSV: {}", e))?)?
+        }
+        ExportFormat::Xml => {
+            let escape = |value: &str| value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;");
+            let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<assets>\n");
+            for asset in &assets {
+                xml.push_str(&format!(
+                    "  <asset id=\"{}\" type=\"{}\" parent_id=\"{}\">\n    <name>{}</name>\n    <description>{}</description>\n  </asset>\n",
+                    asset.id,
+                    asset.asset_type.as_str(),
+                    asset.parent_id.map(|id| id.to_string()).unwrap_or_default(),
+                    escape(&asset.name),
+                    escape(&asset.description),
+                ));
+            }
+            xml.push_str("</assets>\n");
+            xml
+        }
+    };
+
+    let file_name = format!("asset-{}.{}", asset_id, options.format.as_str());
+    std::fs::write(Path::new(export_dir).join(file_name), content)?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -777,7 +1377,9 @@ mod tests {
                 new_parent_id: None,
                 validate_hierarchy: true,
                 skip_conflicts: false,
+                concurrency: None,
             },
+            dry_run: false,
         };
 
         let operation_id = service.create_bulk_move_operation(request, 1).unwrap();
@@ -802,7 +1404,9 @@ mod tests {
                 force_delete: false,
                 delete_children: false,
                 skip_protected: true,
+                concurrency: None,
             },
+            dry_run: false,
         };
 
         let operation_id = service.create_bulk_delete_operation(request, 1).unwrap();
@@ -836,4 +1440,322 @@ mod tests {
         assert_eq!(logs[0].action, "move");
         assert_eq!(logs[0].status, "success");
     }
-}
\ No newline at end of file
+
+    fn create_timed_delete(repo: &SqliteBulkOperationsRepository, asset_count: i32, concurrency: Option<usize>) -> String {
+        BulkOperationService::new(repo).create_bulk_delete_operation(BulkDeleteRequest {
+            asset_ids: (1..=asset_count).collect(),
+            options: BulkDeleteOptions {
+                force_delete: false,
+                delete_children: false,
+                skip_protected: true,
+                concurrency,
+            },
+            dry_run: false,
+        }, 1).unwrap()
+    }
+
+    #[test]
+    fn test_execute_operation_parallelism_speeds_batch() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteBulkOperationsRepository::new(&conn);
+        let service = BulkOperationService::new(&repo);
+        let slow_item = |_asset_id: i32| -> Result<()> {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            Ok(())
+        };
+
+        let serial_id = create_timed_delete(&repo, 8, None);
+        let started = std::time::Instant::now();
+        let serial = service.execute_operation(&serial_id, slow_item).unwrap();
+        let serial_elapsed = started.elapsed();
+
+        let parallel_id = create_timed_delete(&repo, 8, Some(4));
+        let started = std::time::Instant::now();
+        let parallel = service.execute_operation(&parallel_id, slow_item).unwrap();
+        let parallel_elapsed = started.elapsed();
+
+        assert_eq!(serial.processed_items, 8);
+        assert_eq!(parallel.processed_items, 8);
+        assert_eq!(parallel.status, BulkOperationStatus::Completed);
+        assert!(serial_elapsed >= std::time::Duration::from_millis(400));
+        assert!(parallel_elapsed * 2 < serial_elapsed, "parallel {:?} vs serial {:?}", parallel_elapsed, serial_elapsed);
+    }
+
+    #[test]
+    fn test_execute_operation_cancellation_stops_between_items() {
+        let (temp_file, conn) = setup_test_db();
+        let repo = SqliteBulkOperationsRepository::new(&conn);
+        let service = BulkOperationService::new(&repo);
+        let operation_id = create_timed_delete(&repo, 6, None);
+
+        // Cancel from another connection while the third item is being processed
+        let started_items = std::sync::Mutex::new(Vec::new());
+        let progress = service.execute_operation(&operation_id, |asset_id| {
+            started_items.lock().unwrap().push(asset_id);
+            if asset_id == 3 {
+                let other_conn = Connection::open(temp_file.path())?;
+                BulkOperationService::new(&SqliteBulkOperationsRepository::new(&other_conn)).cancel_operation(&operation_id)?;
+            }
+            if asset_id == 2 {
+                return Err(anyhow!("Asset is protected"));
+            }
+            Ok(())
+        }).unwrap();
+
+        assert_eq!(*started_items.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(progress.status, BulkOperationStatus::Cancelled);
+        assert_eq!(progress.total_items, 6);
+        assert_eq!(progress.processed_items, 3);
+        assert_eq!(progress.failed_items, 1);
+        assert_eq!(progress.errors[0].asset_id, 2);
+
+        let operation = repo.get_operation_by_id(&operation_id).unwrap().unwrap();
+        assert_eq!(operation.progress_percent, 50.0);
+    }
+
+    fn setup_hierarchy_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        seed_hierarchy(&conn);
+        conn
+    }
+
+    fn seed_hierarchy(conn: &Connection) {
+        conn.execute_batch(
+            r#"
+            PRAGMA foreign_keys = ON;
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL
+            );
+            INSERT INTO users (id, username) VALUES (1, 'testuser');
+            "#,
+        ).unwrap();
+        SqliteAssetRepository::new(conn).initialize_schema().unwrap();
+        SqliteAuditRepository::new(conn).initialize_schema().unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE configuration_versions (
+                id INTEGER PRIMARY KEY,
+                asset_id INTEGER NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                status TEXT NOT NULL DEFAULT 'Draft',
+                pinned BOOLEAN NOT NULL DEFAULT 0
+            );
+            CREATE TABLE firmware_versions (
+                id INTEGER PRIMARY KEY,
+                asset_id INTEGER NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                file_path TEXT NOT NULL
+            );
+            INSERT INTO assets (id, name, asset_type, parent_id, created_by) VALUES (1, 'Plant', 'folder', NULL, 1);
+            INSERT INTO assets (id, name, asset_type, parent_id, created_by) VALUES (2, 'Line A', 'folder', NULL, 1);
+            INSERT INTO assets (id, name, asset_type, parent_id, created_by) VALUES (3, 'PLC-1', 'device', 1, 1);
+            INSERT INTO assets (id, name, asset_type, parent_id, created_by) VALUES (4, 'PLC-2', 'device', 1, 1);
+            INSERT INTO assets (id, name, asset_type, parent_id, created_by) VALUES (5, 'HMI', 'device', 2, 1);
+            "#,
+        ).unwrap();
+    }
+
+    fn parents(conn: &Connection) -> Vec<(i32, Option<i32>)> {
+        let mut stmt = conn.prepare("SELECT id, parent_id FROM assets ORDER BY id").unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>().unwrap()
+    }
+
+    #[test]
+    fn test_dry_run_move_reports_without_changing() {
+        let conn = setup_hierarchy_db();
+        let before = parents(&conn);
+
+        let preview = preview_bulk_move(&conn, &BulkMoveRequest {
+            asset_ids: vec![3, 4, 5, 99],
+            new_parent_id: Some(2),
+            options: BulkMoveOptions {
+                new_parent_id: Some(2),
+                validate_hierarchy: true,
+                skip_conflicts: true,
+                concurrency: None,
+            },
+            dry_run: true,
+        }).unwrap();
+
+        assert_eq!(preview.operation_type, BulkOperationType::Move);
+        assert_eq!(preview.affected_asset_ids, vec![3, 4]);
+        assert_eq!(preview.affected_count, 2);
+        assert!(!preview.validation.is_valid);
+        assert_eq!(preview.validation.conflicts.len(), 1);
+        assert_eq!(preview.validation.conflicts[0].asset_id, 5);
+        assert_eq!(preview.validation.errors.len(), 1);
+        assert_eq!(preview.validation.errors[0].error_type, "not_found");
+        assert_eq!(parents(&conn), before);
+
+        // A device cannot take children, so nothing would move
+        let preview = preview_bulk_move(&conn, &BulkMoveRequest {
+            asset_ids: vec![4],
+            new_parent_id: Some(3),
+            options: BulkMoveOptions {
+                new_parent_id: Some(3),
+                validate_hierarchy: true,
+                skip_conflicts: false,
+                concurrency: None,
+            },
+            dry_run: true,
+        }).unwrap();
+        assert_eq!(preview.affected_count, 0);
+        assert_eq!(preview.validation.errors[0].error_type, "invalid_parent");
+    }
+
+    #[test]
+    fn test_dry_run_delete_counts_cascaded_children() {
+        let conn = setup_hierarchy_db();
+        let before = parents(&conn);
+        let request = |delete_children| BulkDeleteRequest {
+            asset_ids: vec![1, 3],
+            options: BulkDeleteOptions {
+                force_delete: false,
+                delete_children,
+                skip_protected: true,
+                concurrency: None,
+            },
+            dry_run: true,
+        };
+
+        let preview = preview_bulk_delete(&conn, &request(true)).unwrap();
+        assert_eq!(preview.affected_asset_ids, vec![1, 3, 4]);
+        assert_eq!(preview.affected_count, 3);
+        assert!(preview.validation.is_valid);
+
+        let preview = preview_bulk_delete(&conn, &request(false)).unwrap();
+        assert_eq!(preview.affected_asset_ids, vec![3]);
+        assert_eq!(preview.validation.errors[0].error_type, "has_children");
+
+        assert_eq!(parents(&conn), before);
+    }
+
+    #[test]
+    fn test_run_bulk_operation_applies_items() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(temp_file.path()).unwrap();
+        seed_hierarchy(&conn);
+        let repo = SqliteBulkOperationsRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+        let service = BulkOperationService::new(&repo);
+        let path = database_path(&conn).unwrap();
+        let firmware_dir = tempfile::tempdir().unwrap();
+
+        let move_id = service.create_bulk_move_operation(BulkMoveRequest {
+            asset_ids: vec![3, 4, 5],
+            new_parent_id: Some(2),
+            options: BulkMoveOptions {
+                new_parent_id: None,
+                validate_hierarchy: true,
+                skip_conflicts: true,
+                concurrency: Some(2),
+            },
+            dry_run: false,
+        }, 1).unwrap();
+        let progress = run_bulk_operation(&path, firmware_dir.path(), &move_id).unwrap();
+        assert_eq!(progress.status, BulkOperationStatus::Completed);
+        assert_eq!((progress.processed_items, progress.failed_items), (3, 0));
+        assert_eq!(parents(&conn), vec![(1, None), (2, None), (3, Some(2)), (4, Some(2)), (5, Some(2))]);
+
+        let export_dir = tempfile::tempdir().unwrap();
+        let export_id = service.create_bulk_export_operation(BulkExportRequest {
+            asset_ids: vec![2],
+            format: ExportFormat::Json,
+            options: BulkExportOptions {
+                format: ExportFormat::Json,
+                include_metadata: false,
+                include_children: true,
+                include_configurations: false,
+                export_path: Some(export_dir.path().to_string_lossy().to_string()),
+                concurrency: None,
+            },
+        }, 1).unwrap();
+        run_bulk_operation(&path, firmware_dir.path(), &export_id).unwrap();
+        let exported = std::fs::read_to_string(export_dir.path().join("asset-2.json")).unwrap();
+        assert!(exported.contains("Line A") && exported.contains("PLC-1") && exported.contains("HMI"));
+
+        // The emptied folder goes; the one that now has children is refused
+        let delete_id = service.create_bulk_delete_operation(BulkDeleteRequest {
+            asset_ids: vec![1, 2],
+            options: BulkDeleteOptions {
+                force_delete: false,
+                delete_children: false,
+                skip_protected: true,
+                concurrency: Some(2),
+            },
+            dry_run: false,
+        }, 1).unwrap();
+        let progress = run_bulk_operation(&path, firmware_dir.path(), &delete_id).unwrap();
+        assert_eq!((progress.processed_items, progress.failed_items), (2, 1));
+        assert_eq!(progress.errors[0].asset_id, 2);
+        assert_eq!(parents(&conn), vec![(2, None), (3, Some(2)), (4, Some(2)), (5, Some(2))]);
+    }
+
+    #[test]
+    fn test_bulk_delete_skips_protected_assets_and_cleans_up() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(temp_file.path()).unwrap();
+        seed_hierarchy(&conn);
+        conn.execute_batch(
+            r#"
+            INSERT INTO configuration_versions (asset_id, status, pinned) VALUES (3, 'Golden', 0);
+            INSERT INTO configuration_versions (asset_id, status, pinned) VALUES (5, 'Draft', 1);
+            INSERT INTO firmware_versions (asset_id, file_path) VALUES (4, '4/1.enc');
+            "#,
+        ).unwrap();
+        let repo = SqliteBulkOperationsRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+        let service = BulkOperationService::new(&repo);
+        let path = database_path(&conn).unwrap();
+        let firmware_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(firmware_dir.path().join("4")).unwrap();
+        std::fs::write(firmware_dir.path().join("4/1.enc"), b"firmware").unwrap();
+
+        let request = |force_delete: bool, skip_protected: bool| BulkDeleteRequest {
+            asset_ids: vec![2, 3, 4],
+            options: BulkDeleteOptions {
+                force_delete,
+                delete_children: true,
+                skip_protected,
+                concurrency: Some(2),
+            },
+            dry_run: false,
+        };
+
+        // A Golden version on PLC-1 and a pinned one below Line A protect both
+        let preview = preview_bulk_delete(&conn, &BulkDeleteRequest { dry_run: true, ..request(false, true) }).unwrap();
+        assert_eq!(preview.affected_asset_ids, vec![4]);
+        assert_eq!(preview.validation.warnings.len(), 2);
+        let preview = preview_bulk_delete(&conn, &BulkDeleteRequest { dry_run: true, ..request(false, false) }).unwrap();
+        assert_eq!(preview.validation.errors.iter().filter(|e| e.error_type == "protected").count(), 2);
+
+        let refused_id = service.create_bulk_delete_operation(request(false, false), 1).unwrap();
+        let progress = run_bulk_operation(&path, firmware_dir.path(), &refused_id).unwrap();
+        assert_eq!((progress.processed_items, progress.failed_items), (3, 2));
+        assert_eq!(parents(&conn), vec![(1, None), (2, None), (3, Some(1)), (5, Some(2))]);
+        assert!(!firmware_dir.path().join("4/1.enc").exists());
+
+        let skipped_id = service.create_bulk_delete_operation(request(false, true), 1).unwrap();
+        let progress = run_bulk_operation(&path, firmware_dir.path(), &skipped_id).unwrap();
+        assert_eq!(progress.failed_items, 0);
+        assert_eq!(parents(&conn), vec![(1, None), (2, None), (3, Some(1)), (5, Some(2))]);
+
+        let forced_id = service.create_bulk_delete_operation(request(true, true), 1).unwrap();
+        run_bulk_operation(&path, firmware_dir.path(), &forced_id).unwrap();
+        assert_eq!(parents(&conn), vec![(1, None)]);
+
+        // One audit event per removed asset, the cascaded HMI included
+        let audit_repo = SqliteAuditRepository::new(&conn);
+        let mut deleted: Vec<i64> = audit_repo.get_events_by_type(&AuditEventType::DatabaseOperation).unwrap()
+            .iter()
+            .map(|event| {
+                assert_eq!(event.user_id, Some(1));
+                assert_eq!(event.username.as_deref(), Some("testuser"));
+                let metadata: serde_json::Value = serde_json::from_str(event.metadata.as_deref().unwrap()).unwrap();
+                metadata["asset_id"].as_i64().unwrap()
+            })
+            .collect();
+        deleted.sort();
+        assert_eq!(deleted, vec![2, 3, 4, 5]);
+    }
+}
@@ -1,15 +1,17 @@
 // Asset management commands
 
-use crate::auth::SessionManager;
-use crate::assets::{AssetRepository, SqliteAssetRepository, CreateAssetRequest, AssetInfo, DashboardStats, AssetType, AssetHierarchy, MoveAssetRequest};
+use crate::auth::{SessionManager, authorize_command, CommandPolicy};
+use crate::assets::{AssetRepository, SqliteAssetRepository, CreateAssetRequest, AssetInfo, DashboardStats, AssetType, AssetHierarchy, MoveAssetRequest, AssetSearchHit, HierarchyExportFormat, Asset, AssetTypeDefinition, AssetActivity};
+use crate::audit::subscriptions::{AssetSubscriptionRepository, SqliteAssetSubscriptionRepository, AssetEventSubscription, AssetEventType, AssetEvent, emit_asset_event};
 use crate::database::Database;
 use crate::validation::InputSanitizer;
-use std::sync::Mutex;
-use tauri::State;
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, State};
 use tracing::{error, info};
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 
 #[tauri::command]
 pub async fn create_asset(
@@ -20,6 +22,20 @@ pub async fn create_asset(
     parent_id: Option<i64>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+) -> Result<AssetInfo, String> {
+    create_asset_with_options(token, name, description, asset_type, parent_id, false, db_state, session_manager).await
+}
+
+/// Shared body of the asset creation commands; `create_vault` also provisions an empty vault
+async fn create_asset_with_options(
+    token: String,
+    name: String,
+    description: String,
+    asset_type: String,
+    parent_id: Option<i64>,
+    create_vault: bool,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
 ) -> Result<AssetInfo, String> {
     // Validate session and get user info
     let session_manager_guard = session_manager.lock()
@@ -71,9 +87,12 @@ pub async fn create_asset(
                 created_by: session.user_id,
             };
 
-            match asset_repo.create_asset(request) {
-                Ok(asset) => {
+            match asset_repo.create_asset_with_vault(request, create_vault) {
+                Ok((asset, vault)) => {
                     info!("Asset created by {}: {} (ID: {})", session.username, asset.name, asset.id);
+                    if let Some(vault) = vault {
+                        info!("Vault '{}' (ID: {}) created with asset {}", vault.name, vault.id, asset.id);
+                    }
                     Ok(asset.into())
                 }
                 Err(e) => {
@@ -242,10 +261,11 @@ pub async fn create_device_asset(
     name: String,
     description: String,
     parent_id: Option<i64>,
+    create_vault: Option<bool>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
 ) -> Result<AssetInfo, String> {
-    create_asset(token, name, description, "device".to_string(), parent_id, db_state, session_manager).await
+    create_asset_with_options(token, name, description, "device".to_string(), parent_id, create_vault.unwrap_or(false), db_state, session_manager).await
 }
 
 #[tauri::command]
@@ -288,6 +308,258 @@ pub async fn get_asset_hierarchy(
     }
 }
 
+#[tauri::command]
+pub async fn export_asset_hierarchy(
+    token: String,
+    root_asset_id: Option<i64>,
+    format: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<u8>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let export_format = format.parse::<HierarchyExportFormat>().map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.export_asset_hierarchy(root_asset_id, export_format) {
+                Ok(document) => {
+                    info!("Asset hierarchy exported ({}) by: {}", format, session.username);
+                    Ok(document)
+                }
+                Err(e) => {
+                    error!("Failed to export asset hierarchy: {}", e);
+                    Err(format!("Failed to export asset hierarchy: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_assets_missing_vaults(
+    token: String,
+    asset_type: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<Asset>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let asset_type_filter = match asset_type {
+        Some(asset_type) => Some(AssetType::from_str(&asset_type).map_err(|e| e.to_string())?),
+        None => None,
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.get_assets_missing_vaults(asset_type_filter) {
+                Ok(assets) => {
+                    info!("Found {} assets missing vaults for: {}", assets.len(), session.username);
+                    Ok(assets)
+                }
+                Err(e) => {
+                    error!("Failed to find assets missing vaults: {}", e);
+                    Err(format!("Failed to find assets missing vaults: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_dormant_assets(
+    token: String,
+    inactive_days: u32,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<AssetActivity>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.get_dormant_assets(inactive_days) {
+                Ok(assets) => {
+                    info!("Found {} assets inactive for {} days for: {}", assets.len(), inactive_days, session.username);
+                    Ok(assets)
+                }
+                Err(e) => {
+                    error!("Failed to find dormant assets: {}", e);
+                    Err(format!("Failed to find dormant assets: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_asset_types(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<AssetTypeDefinition>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+            asset_repo.get_asset_types().map_err(|e| {
+                error!("Failed to get asset types: {}", e);
+                format!("Failed to get asset types: {}", e)
+            })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn register_asset_type(
+    token: String,
+    definition: AssetTypeDefinition,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "register_asset_type").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.register_asset_type(&definition) {
+                Ok(()) => {
+                    info!("Asset type '{}' registered by {}", definition.name, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to register asset type '{}': {}", definition.name, e);
+                    Err(format!("Failed to register asset type: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn unregister_asset_type(
+    token: String,
+    name: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "unregister_asset_type").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.unregister_asset_type(&name) {
+                Ok(()) => {
+                    info!("Asset type '{}' unregistered by {}", name, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to unregister asset type '{}': {}", name, e);
+                    Err(format!("Failed to unregister asset type: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_children_assets(
     token: String,
@@ -332,6 +604,7 @@ pub async fn get_children_assets(
 
 #[tauri::command]
 pub async fn move_asset(
+    app: AppHandle,
     token: String,
     asset_id: i64,
     new_parent_id: Option<i64>,
@@ -367,6 +640,12 @@ pub async fn move_asset(
             match asset_repo.move_asset(request) {
                 Ok(()) => {
                     info!("Asset moved by {}: asset_id = {}, new_parent_id = {:?}", session.username, asset_id, new_parent_id);
+                    emit_asset_event(&app, db.get_connection(), AssetEvent::new(
+                        asset_id,
+                        AssetEventType::AssetMoved,
+                        Some(session.user_id),
+                        format!("Asset moved by {}", session.username),
+                    ));
                     Ok(())
                 }
                 Err(e) => {
@@ -460,6 +739,185 @@ pub async fn get_asset_path(
     }
 }
 
+#[tauri::command]
+pub async fn search_assets(
+    token: String,
+    query: String,
+    include_metadata: bool,
+    limit: Option<usize>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<AssetSearchHit>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let query = InputSanitizer::sanitize_string(&query);
+    if query.trim().is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+    let limit = limit.unwrap_or(50).min(500);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            match asset_repo.search_assets(&query, include_metadata, limit) {
+                Ok(hits) => {
+                    info!("Asset search by {}: {} results (metadata: {})", session.username, hits.len(), include_metadata);
+                    Ok(hits)
+                }
+                Err(e) => {
+                    error!("Failed to search assets: {}", e);
+                    Err(format!("Failed to search assets: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn subscribe_asset_events(
+    token: String,
+    asset_id: i64,
+    event_types: Vec<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<AssetEventSubscription, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let event_types = event_types
+        .iter()
+        .map(|t| t.parse::<AssetEventType>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+            match asset_repo.get_asset_by_id(asset_id) {
+                Ok(Some(_)) => {}
+                Ok(None) => return Err("Asset not found".to_string()),
+                Err(e) => {
+                    error!("Failed to retrieve asset: {}", e);
+                    return Err("Failed to retrieve asset".to_string());
+                }
+            }
+
+            let subscription_repo = SqliteAssetSubscriptionRepository::new(db.get_connection());
+            match subscription_repo.subscribe_asset_events(session.user_id, asset_id, &event_types) {
+                Ok(subscription) => {
+                    info!("Asset event subscription saved by {}: asset_id = {}", session.username, asset_id);
+                    Ok(subscription)
+                }
+                Err(e) => {
+                    error!("Failed to subscribe to asset events: {}", e);
+                    Err(format!("Failed to subscribe to asset events: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn unsubscribe_asset_events(
+    token: String,
+    asset_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<bool, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let subscription_repo = SqliteAssetSubscriptionRepository::new(db.get_connection());
+            match subscription_repo.unsubscribe_asset_events(session.user_id, asset_id) {
+                Ok(removed) => {
+                    info!("Asset event subscription removed by {}: asset_id = {}", session.username, asset_id);
+                    Ok(removed)
+                }
+                Err(e) => {
+                    error!("Failed to unsubscribe from asset events: {}", e);
+                    Err(format!("Failed to unsubscribe from asset events: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_asset_event_subscriptions(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<AssetEventSubscription>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let subscription_repo = SqliteAssetSubscriptionRepository::new(db.get_connection());
+            subscription_repo.get_user_subscriptions(session.user_id).map_err(|e| {
+                error!("Failed to get asset event subscriptions: {}", e);
+                format!("Failed to get asset event subscriptions: {}", e)
+            })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 // Re-export tree navigation commands
 pub use crate::assets::{
     batch_load_tree_nodes,
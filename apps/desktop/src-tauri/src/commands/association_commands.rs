@@ -2,8 +2,8 @@ use tauri::{command, State};
 use crate::database::Database;
 use crate::associations::{
     AssociationRepository, SqliteAssociationRepository, CreateAssociationRequest,
-    AssetFileAssociation, AssociationInfo, FileImportSession, ImportStatus,
-    HealthStatus, AssociationType, ValidationResult, AssociationValidation
+    AssetFileAssociation, AssociationInfo, FileImportSession, ImportStatus, BulkAssociationResult,
+    HealthStatus, AssociationType, ValidationResult, AssociationValidation, RepairOutcome
 };
 use crate::auth::SessionManager;
 use anyhow::Result;
@@ -36,6 +36,30 @@ pub async fn create_file_association(
         .map_err(|e| format!("Failed to create association: {}", e))
 }
 
+#[command]
+pub async fn create_file_associations_bulk(
+    requests: Vec<CreateAssociationRequest>,
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>
+) -> Result<BulkAssociationResult, String> {
+    // Validate session
+    let session_manager = session_state.lock().map_err(|_| "Failed to acquire session lock")?;
+    if !session_manager.has_active_session() {
+        return Err("No active session".to_string());
+    }
+    drop(session_manager);
+
+    // Get database connection
+    let db_guard = db_state.lock().map_err(|_| "Failed to acquire database lock")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let conn = db.get_connection();
+
+    // Create associations
+    let repo = SqliteAssociationRepository::new(&conn);
+    repo.create_file_associations_bulk(requests)
+        .map_err(|e| format!("Failed to create associations: {}", e))
+}
+
 #[command]
 pub async fn get_asset_file_associations(
     asset_id: i64,
@@ -191,9 +215,10 @@ pub async fn get_broken_associations(
 #[command]
 pub async fn repair_association(
     association_id: i64,
+    remove_if_unresolved: Option<bool>,
     db_state: State<'_, DatabaseState>,
     session_state: State<'_, SessionManagerState>
-) -> Result<(), String> {
+) -> Result<RepairOutcome, String> {
     // Validate session
     let session_manager = session_state.lock().map_err(|_| "Failed to acquire session lock")?;
     if !session_manager.has_active_session() {
@@ -208,7 +233,7 @@ pub async fn repair_association(
 
     // Repair association
     let repo = SqliteAssociationRepository::new(&conn);
-    repo.repair_association(association_id)
+    repo.repair_association(association_id, remove_if_unresolved.unwrap_or(false))
         .map_err(|e| format!("Failed to repair association: {}", e))
 }
 
@@ -242,6 +267,83 @@ pub async fn validate_file_association(
         .map_err(|e| format!("Failed to validate association: {}", e))
 }
 
+#[command]
+pub async fn revalidate_file_associations(
+    file_id: i64,
+    file_type: String,
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>
+) -> Result<Vec<AssociationValidation>, String> {
+    // Validate session
+    let session_manager = session_state.lock().map_err(|_| "Failed to acquire session lock")?;
+    if !session_manager.has_active_session() {
+        return Err("No active session".to_string());
+    }
+    drop(session_manager);
+
+    // Get database connection
+    let db_guard = db_state.lock().map_err(|_| "Failed to acquire database lock")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let conn = db.get_connection();
+
+    // Parse file type
+    let association_type = AssociationType::from_str(&file_type)
+        .map_err(|e| format!("Invalid file type: {}", e))?;
+
+    // Re-validate every association of the file
+    let repo = SqliteAssociationRepository::new(&conn);
+    repo.revalidate_file_associations(file_id, &association_type)
+        .map_err(|e| format!("Failed to re-validate associations: {}", e))
+}
+
+#[command]
+pub async fn get_association_auto_revalidate(
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>
+) -> Result<bool, String> {
+    // Validate session
+    let session_manager = session_state.lock().map_err(|_| "Failed to acquire session lock")?;
+    if !session_manager.has_active_session() {
+        return Err("No active session".to_string());
+    }
+    drop(session_manager);
+
+    // Get database connection
+    let db_guard = db_state.lock().map_err(|_| "Failed to acquire database lock")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let conn = db.get_connection();
+
+    let repo = SqliteAssociationRepository::new(&conn);
+    repo.get_auto_revalidate_enabled()
+        .map_err(|e| format!("Failed to get re-validation policy: {}", e))
+}
+
+#[command]
+pub async fn set_association_auto_revalidate(
+    enabled: bool,
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>
+) -> Result<(), String> {
+    // Validate session; only administrators change the policy
+    let session_manager = session_state.lock().map_err(|_| "Failed to acquire session lock")?;
+    if !session_manager.has_active_session() {
+        return Err("No active session".to_string());
+    }
+    if !session_manager.is_admin() {
+        return Err("Only administrators can change the re-validation policy".to_string());
+    }
+    drop(session_manager);
+
+    // Get database connection
+    let db_guard = db_state.lock().map_err(|_| "Failed to acquire database lock")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let conn = db.get_connection();
+
+    let repo = SqliteAssociationRepository::new(&conn);
+    repo.set_auto_revalidate_enabled(enabled)
+        .map_err(|e| format!("Failed to update re-validation policy: {}", e))
+}
+
 #[command]
 pub async fn create_import_session(
     session_name: String,
@@ -1,4 +1,4 @@
-use crate::auth::{SessionManager, LoginAttemptTracker, LoginResponse, verify_password};
+use crate::auth::{SessionManager, LoginAttemptTracker, LoginResponse, verify_password, DeviceFingerprint, LoginAuditRepository, SqliteLoginAuditRepository};
 use crate::users::{CreateUserRequest, UserRepository, SqliteUserRepository, UserRole, UserInfo};
 use crate::audit::{AuditRepository, SqliteAuditRepository, create_user_created_event};
 use crate::validation::{UsernameValidator, PasswordValidator, InputSanitizer, RateLimiter};
@@ -65,7 +65,14 @@ pub async fn login(
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
     attempt_tracker: State<'_, LoginAttemptTrackerState>,
+    rate_limiter: State<'_, RateLimiterState>,
 ) -> Result<LoginResponse, String> {
+    // Throttle per username before any password verification work
+    let rate_limiter_guard = rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?;
+    rate_limiter_guard.check_command_rate_limit("login", None, &username)?;
+    drop(rate_limiter_guard);
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
@@ -99,6 +106,16 @@ pub async fn login(
 
                             info!("Successful login for user: {}", username);
 
+                            // The audit is best effort; a failure to record it must not block the login
+                            let login_audit_repo = SqliteLoginAuditRepository::new(db.get_connection());
+                            match login_audit_repo.record_login(user.id, &DeviceFingerprint::current()) {
+                                Ok(Some(entry)) if entry.is_new_device => {
+                                    warn!("Login for user {} from a device not seen before", username);
+                                }
+                                Ok(_) => {}
+                                Err(e) => error!("Failed to record login audit: {}", e),
+                            }
+
                             Ok(LoginResponse {
                                 token: session.token,
                                 user: user.into(),
@@ -1,15 +1,18 @@
 // Branch management commands
 
-use crate::auth::SessionManager;
-use crate::branches::{BranchRepository, SqliteBranchRepository, CreateBranchRequest, BranchInfo, CreateBranchVersionRequest, BranchVersionInfo};
+use crate::auth::{SessionManager, authorize_command, CommandPolicy};
+use crate::branches::{BranchRepository, SqliteBranchRepository, CreateBranchRequest, BranchInfo, CreateBranchVersionRequest, BranchVersionInfo, VersionLineage};
+use crate::configurations::ConfigurationVersionInfo;
 use crate::database::Database;
+use crate::users::UserRole;
 use crate::validation::InputSanitizer;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use tauri::State;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 
 #[tauri::command]
 pub async fn create_branch(
@@ -245,6 +248,125 @@ pub async fn import_version_to_branch(
     }
 }
 
+#[tauri::command]
+pub async fn import_configuration_to_new_branch(
+    token: String,
+    asset_id: i64,
+    branch_name: String,
+    file_path: String,
+    notes: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(BranchInfo, BranchVersionInfo), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate inputs
+    let branch_name = InputSanitizer::sanitize_string(&branch_name);
+    let notes = InputSanitizer::sanitize_string(&notes);
+
+    if InputSanitizer::is_potentially_malicious(&branch_name) || InputSanitizer::is_potentially_malicious(&notes) {
+        error!("Potentially malicious input detected in import_configuration_to_new_branch");
+        return Err("Invalid input detected".to_string());
+    }
+
+    if notes.len() > 1000 {
+        return Err("Notes cannot exceed 1000 characters".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            match branch_repo.import_configuration_to_new_branch(asset_id, &branch_name, &file_path, session.user_id, &notes) {
+                Ok((branch, _branch_version)) => {
+                    info!("Configuration imported to new branch by {}: {} for asset {}", session.username, branch.name, asset_id);
+                    let branch_info = branch_repo.get_branch_by_id(branch.id)
+                        .map_err(|e| format!("Failed to retrieve created branch: {}", e))?
+                        .ok_or_else(|| "Failed to retrieve created branch".to_string())?;
+                    let version_info = branch_repo.get_branch_latest_version(branch.id)
+                        .map_err(|e| format!("Failed to get branch version info: {}", e))?
+                        .ok_or_else(|| "Failed to retrieve imported version info".to_string())?;
+                    Ok((branch_info, version_info))
+                }
+                Err(e) => {
+                    error!("Failed to import configuration to new branch: {}", e);
+                    Err(format!("Failed to import configuration to new branch: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn restore_version_to_branch(
+    token: String,
+    version_id: i64,
+    branch_name: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(BranchInfo, BranchVersionInfo), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate inputs
+    let branch_name = InputSanitizer::sanitize_string(&branch_name);
+
+    if InputSanitizer::is_potentially_malicious(&branch_name) {
+        error!("Potentially malicious input detected in restore_version_to_branch");
+        return Err("Invalid input detected".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            match branch_repo.restore_version_to_branch(version_id, &branch_name, session.user_id) {
+                Ok((branch, _branch_version)) => {
+                    info!("Version {} restored to new branch by {}: {}", version_id, session.username, branch.name);
+                    let branch_info = branch_repo.get_branch_by_id(branch.id)
+                        .map_err(|e| format!("Failed to retrieve created branch: {}", e))?
+                        .ok_or_else(|| "Failed to retrieve created branch".to_string())?;
+                    let version_info = branch_repo.get_branch_latest_version(branch.id)
+                        .map_err(|e| format!("Failed to get branch version info: {}", e))?
+                        .ok_or_else(|| "Failed to retrieve restored version info".to_string())?;
+                    Ok((branch_info, version_info))
+                }
+                Err(e) => {
+                    error!("Failed to restore version to branch: {}", e);
+                    Err(format!("Failed to restore version to branch: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_branch_versions(
     token: String,
@@ -376,4 +498,218 @@ pub async fn compare_branch_versions(
         }
         None => Err("Database not initialized".to_string()),
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_max_active_branches(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<i64, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            branch_repo.get_max_active_branches().map_err(|e| {
+                error!("Failed to get branch limit: {}", e);
+                format!("Failed to get branch limit: {}", e)
+            })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_max_active_branches(
+    token: String,
+    limit: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_max_active_branches").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            match branch_repo.set_max_active_branches(limit) {
+                Ok(()) => {
+                    info!("Branch limit set to {} by {}", limit, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to set branch limit: {}", e);
+                    Err(format!("Failed to set branch limit: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn prune_branch_versions(
+    token: String,
+    branch_id: i64,
+    keep_recent: usize,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<i64>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "prune_branch_versions").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            match branch_repo.prune_branch_versions(branch_id, keep_recent) {
+                Ok(pruned) => {
+                    info!("Branch {} pruned by {}: {} versions beyond the newest {} dropped their content",
+                          branch_id, session.username, pruned.len(), keep_recent);
+                    Ok(pruned)
+                }
+                Err(e) => {
+                    error!("Failed to prune branch versions: {}", e);
+                    Err(format!("Failed to prune branch versions: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn merge_branch_to_main(
+    token: String,
+    branch_id: i64,
+    notes: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<ConfigurationVersionInfo, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can merge branches into the mainline
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to merge branch: {}", session.username);
+        return Err("Only Engineers and Administrators can merge branches".to_string());
+    }
+
+    // Validate inputs
+    let notes = InputSanitizer::sanitize_string(&notes.unwrap_or_default());
+    if InputSanitizer::is_potentially_malicious(&notes) {
+        error!("Potentially malicious input detected in merge_branch_to_main");
+        return Err("Invalid input detected".to_string());
+    }
+    if notes.len() > 1000 {
+        return Err("Notes cannot exceed 1000 characters".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+
+            match branch_repo.merge_branch_to_main(branch_id, session.user_id, &notes) {
+                Ok(version) => {
+                    info!("Branch {} merged into mainline by {} as {}", branch_id, session.username, version.version_number);
+                    Ok(version)
+                }
+                Err(e) => {
+                    error!("Failed to merge branch: {}", e);
+                    Err(format!("Failed to merge branch: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_version_lineage(
+    token: String,
+    asset_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<VersionLineage, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
+            branch_repo.get_version_lineage(asset_id)
+                .map_err(|e| format!("Failed to get version lineage: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
@@ -7,17 +7,18 @@ use crate::bulk::{
     operations::{
         BulkOperationsRepository, SqliteBulkOperationsRepository, BulkOperationService,
         BulkMoveRequest, BulkDeleteRequest, BulkExportRequest, BulkClassifyRequest,
-        BulkOperationProgress, ValidationResult, UndoResult, BulkOperationHistory
+        BulkOperationProgress, ValidationResult, UndoResult, BulkOperationHistory,
+        BulkStartResponse, preview_bulk_move, preview_bulk_delete, database_path, run_bulk_operation,
     },
 };
 use crate::assets::{AssetRepository, SqliteAssetRepository, CreateAssetRequest, AssetType};
-use crate::auth::SessionManager;
-use crate::{DatabaseState, SessionManagerState};
+use crate::auth::{SessionManager, authorize_command};
+use crate::{DatabaseState, SessionManagerState, CommandPolicyState};
 use tauri::{command, State, AppHandle};
 use tracing::{info, error, warn};
 use std::collections::HashMap;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[command]
 pub async fn create_bulk_import_session(
@@ -169,6 +170,7 @@ pub async fn upload_bulk_import_file(
     session_state: State<'_, SessionManagerState>,
     session_id: i64,
     file_path: String,
+    template_id: Option<i64>,
 ) -> Result<ValidationSummary, String> {
     info!("Uploading bulk import file for session: {}", session_id);
 
@@ -184,20 +186,29 @@ pub async fn upload_bulk_import_file(
     }
 
     // Parse CSV file
-    let csv_result = parse_csv_file(&file_path)
+    let mut csv_result = parse_csv_file(&file_path)
         .map_err(|e| format!("Failed to parse CSV file: {}", e))?;
 
-    // Validate CSV structure
-    let validation_summary = validate_csv_structure(&csv_result)?;
-
     // Get database connection
     let db_guard = db_state.lock().unwrap();
     let db = db_guard
         .as_ref()
         .ok_or("Database not initialized")?;
+    let bulk_repo = SqliteBulkImportRepository::new(db.get_connection());
+
+    // Translate the spreadsheet's column names into internal fields so that
+    // validation and processing see the same names regardless of the source layout
+    if let Some(template_id) = template_id {
+        let template = bulk_repo.get_template_by_id(template_id)
+            .map_err(|e| format!("Failed to get template: {}", e))?
+            .ok_or("Template not found")?;
+        csv_result = template.apply_field_mapping(csv_result);
+    }
+
+    // Validate CSV structure
+    let validation_summary = validate_csv_structure(&csv_result)?;
 
     // Convert CSV rows to bulk import items
-    let bulk_repo = SqliteBulkImportRepository::new(db.get_connection());
     let items = convert_csv_to_items(&csv_result)?;
     
     // Add items to session
@@ -928,6 +939,20 @@ fn process_import_item(
 
 // Bulk Operations Commands (Multi-select operations on existing assets)
 
+/// Run a created operation on a blocking worker. It works through its own connections, so
+/// the progress and cancel commands can reach the database while it runs.
+fn spawn_bulk_operation(database_path: PathBuf, firmware_dir: PathBuf, operation_id: String) {
+    tauri::async_runtime::spawn_blocking(move || {
+        match run_bulk_operation(&database_path, &firmware_dir, &operation_id) {
+            Ok(progress) => info!(
+                "Bulk operation {} finished: {} processed, {} failed",
+                operation_id, progress.processed_items, progress.failed_items
+            ),
+            Err(e) => error!("Bulk operation {} failed: {}", operation_id, e),
+        }
+    });
+}
+
 #[command]
 pub async fn start_bulk_move(
     app: AppHandle,
@@ -936,7 +961,8 @@ pub async fn start_bulk_move(
     asset_ids: Vec<i32>,
     new_parent_id: Option<i32>,
     options: serde_json::Value,
-) -> Result<String, String> {
+    dry_run: Option<bool>,
+) -> Result<BulkStartResponse, String> {
     info!("Starting bulk move operation for {} assets", asset_ids.len());
 
     // Authentication check
@@ -959,8 +985,21 @@ pub async fn start_bulk_move(
         asset_ids,
         new_parent_id,
         options: bulk_move_options,
+        dry_run: dry_run.unwrap_or(false),
     };
 
+    if request.dry_run {
+        let preview = preview_bulk_move(db.get_connection(), &request)
+            .map_err(|e| format!("Failed to preview bulk move: {}", e))?;
+        info!("Bulk move dry run would affect {} assets", preview.affected_count);
+        return Ok(BulkStartResponse::Preview(preview));
+    }
+
+    let database_path = database_path(db.get_connection())
+        .map_err(|e| format!("Failed to create bulk move operation: {}", e))?;
+    let firmware_dir = crate::firmware::get_firmware_storage_dir(&app)
+        .map_err(|e| format!("Failed to resolve firmware storage: {}", e))?;
+
     // Create bulk operations service
     let bulk_ops_repo = SqliteBulkOperationsRepository::new(db.get_connection());
     let service = BulkOperationService::new(&bulk_ops_repo);
@@ -970,9 +1009,11 @@ pub async fn start_bulk_move(
             error!("Failed to create bulk move operation: {}", e);
             format!("Failed to create bulk move operation: {}", e)
         })?;
+    drop(db_guard);
 
     info!("Created bulk move operation with ID: {}", operation_id);
-    Ok(operation_id)
+    spawn_bulk_operation(database_path, firmware_dir, operation_id.clone());
+    Ok(BulkStartResponse::Started(operation_id))
 }
 
 #[command]
@@ -980,16 +1021,28 @@ pub async fn start_bulk_delete(
     app: AppHandle,
     db_state: State<'_, DatabaseState>,
     session_state: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+    token: String,
     asset_ids: Vec<i32>,
     options: serde_json::Value,
-) -> Result<String, String> {
+    dry_run: Option<bool>,
+) -> Result<BulkStartResponse, String> {
     info!("Starting bulk delete operation for {} assets", asset_ids.len());
 
-    // Authentication check
-    let session_manager = session_state.lock().unwrap();
-    let current_user = session_manager
-        .get_current_user()
-        .ok_or("Authentication required")?;
+    // Validate session
+    let session_manager = session_state.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager);
+
+    authorize_command(&command_policy, &session, "start_bulk_delete").map_err(|e| e.to_string())?;
 
     // Get database connection
     let db_guard = db_state.lock().unwrap();
@@ -1004,20 +1057,35 @@ pub async fn start_bulk_delete(
     let request = BulkDeleteRequest {
         asset_ids,
         options: bulk_delete_options,
+        dry_run: dry_run.unwrap_or(false),
     };
 
+    if request.dry_run {
+        let preview = preview_bulk_delete(db.get_connection(), &request)
+            .map_err(|e| format!("Failed to preview bulk delete: {}", e))?;
+        info!("Bulk delete dry run would affect {} assets", preview.affected_count);
+        return Ok(BulkStartResponse::Preview(preview));
+    }
+
+    let database_path = database_path(db.get_connection())
+        .map_err(|e| format!("Failed to create bulk delete operation: {}", e))?;
+    let firmware_dir = crate::firmware::get_firmware_storage_dir(&app)
+        .map_err(|e| format!("Failed to resolve firmware storage: {}", e))?;
+
     // Create bulk operations service
     let bulk_ops_repo = SqliteBulkOperationsRepository::new(db.get_connection());
     let service = BulkOperationService::new(&bulk_ops_repo);
 
-    let operation_id = service.create_bulk_delete_operation(request, current_user.id as i32)
+    let operation_id = service.create_bulk_delete_operation(request, session.user_id as i32)
         .map_err(|e| {
             error!("Failed to create bulk delete operation: {}", e);
             format!("Failed to create bulk delete operation: {}", e)
         })?;
+    drop(db_guard);
 
     info!("Created bulk delete operation with ID: {}", operation_id);
-    Ok(operation_id)
+    spawn_bulk_operation(database_path, firmware_dir, operation_id.clone());
+    Ok(BulkStartResponse::Started(operation_id))
 }
 
 #[command]
@@ -1057,6 +1125,11 @@ pub async fn start_bulk_export(
         options: bulk_export_options,
     };
 
+    let database_path = database_path(db.get_connection())
+        .map_err(|e| format!("Failed to create bulk export operation: {}", e))?;
+    let firmware_dir = crate::firmware::get_firmware_storage_dir(&app)
+        .map_err(|e| format!("Failed to resolve firmware storage: {}", e))?;
+
     // Create bulk operations service
     let bulk_ops_repo = SqliteBulkOperationsRepository::new(db.get_connection());
     let service = BulkOperationService::new(&bulk_ops_repo);
@@ -1066,8 +1139,10 @@ pub async fn start_bulk_export(
             error!("Failed to create bulk export operation: {}", e);
             format!("Failed to create bulk export operation: {}", e)
         })?;
+    drop(db_guard);
 
     info!("Created bulk export operation with ID: {}", operation_id);
+    spawn_bulk_operation(database_path, firmware_dir, operation_id.clone());
     Ok(operation_id)
 }
 
@@ -1239,104 +1314,8 @@ pub async fn validate_bulk_move(
         .as_ref()
         .ok_or("Database not initialized")?;
 
-    // Validate bulk move operation
-    let asset_repo = crate::assets::SqliteAssetRepository::new(db.get_connection());
-    let mut validation_result = ValidationResult {
-        is_valid: true,
-        warnings: vec![],
-        errors: vec![],
-        conflicts: vec![],
-    };
-
-    // Check if all assets exist and are accessible
-    for asset_id in &asset_ids {
-        match asset_repo.get_asset_by_id(*asset_id as i64) {
-            Ok(Some(_)) => {}, // Asset exists
-            Ok(None) => {
-                validation_result.errors.push(crate::bulk::operations::ValidationError {
-                    asset_id: *asset_id,
-                    asset_name: format!("Asset {}", asset_id),
-                    error_type: "not_found".to_string(),
-                    message: "Asset not found or inaccessible".to_string(),
-                    blocking: true,
-                    suggested_action: Some("Remove from selection".to_string()),
-                });
-                validation_result.is_valid = false;
-            },
-            Err(_) => {
-                validation_result.errors.push(crate::bulk::operations::ValidationError {
-                    asset_id: *asset_id,
-                    asset_name: format!("Asset {}", asset_id),
-                    error_type: "access_error".to_string(),
-                    message: "Cannot access asset".to_string(),
-                    blocking: true,
-                    suggested_action: Some("Check permissions".to_string()),
-                });
-                validation_result.is_valid = false;
-            }
-        }
-    }
-
-    // Validate new parent exists if specified
-    if let Some(parent_id) = new_parent_id {
-        match asset_repo.get_asset_by_id(parent_id as i64) {
-            Ok(Some(parent)) => {
-                // Check if parent is a folder
-                if parent.asset_type != crate::assets::AssetType::Folder {
-                    validation_result.errors.push(crate::bulk::operations::ValidationError {
-                        asset_id: parent_id,
-                        asset_name: parent.name,
-                        error_type: "invalid_parent".to_string(),
-                        message: "Parent must be a folder".to_string(),
-                        blocking: true,
-                        suggested_action: Some("Select a folder as parent".to_string()),
-                    });
-                    validation_result.is_valid = false;
-                }
-            },
-            Ok(None) => {
-                validation_result.errors.push(crate::bulk::operations::ValidationError {
-                    asset_id: parent_id,
-                    asset_name: format!("Asset {}", parent_id),
-                    error_type: "parent_not_found".to_string(),
-                    message: "Parent folder not found".to_string(),
-                    blocking: true,
-                    suggested_action: Some("Select a valid parent folder".to_string()),
-                });
-                validation_result.is_valid = false;
-            },
-            Err(_) => {
-                validation_result.errors.push(crate::bulk::operations::ValidationError {
-                    asset_id: parent_id,
-                    asset_name: format!("Asset {}", parent_id),
-                    error_type: "parent_access_error".to_string(),
-                    message: "Cannot access parent folder".to_string(),
-                    blocking: true,
-                    suggested_action: Some("Check parent folder permissions".to_string()),
-                });
-                validation_result.is_valid = false;
-            }
-        }
-    }
-
-    // Check for circular references when moving to a specific parent
-    if let Some(parent_id) = new_parent_id {
-        for asset_id in &asset_ids {
-            if *asset_id == parent_id {
-                validation_result.errors.push(crate::bulk::operations::ValidationError {
-                    asset_id: *asset_id,
-                    asset_name: format!("Asset {}", asset_id),
-                    error_type: "circular_reference".to_string(),
-                    message: "Cannot move asset into itself".to_string(),
-                    blocking: true,
-                    suggested_action: Some("Remove from selection or choose different parent".to_string()),
-                });
-                validation_result.is_valid = false;
-            }
-        }
-    }
-
-    Ok(validation_result)
+    crate::bulk::operations::validate_bulk_move(db.get_connection(), &asset_ids, new_parent_id)
+        .map_err(|e| format!("Failed to validate bulk move: {}", e))
 }
 
 #[command]
@@ -1345,6 +1324,7 @@ pub async fn validate_bulk_delete(
     db_state: State<'_, DatabaseState>,
     session_state: State<'_, SessionManagerState>,
     asset_ids: Vec<i32>,
+    delete_children: Option<bool>,
 ) -> Result<ValidationResult, String> {
     info!("Validating bulk delete for {} assets", asset_ids.len());
 
@@ -1360,15 +1340,14 @@ pub async fn validate_bulk_delete(
         .as_ref()
         .ok_or("Database not initialized")?;
 
-    // TODO: Implement actual validation logic
-    let validation_result = ValidationResult {
-        is_valid: true,
-        warnings: vec![],
-        errors: vec![],
-        conflicts: vec![],
+    let options = crate::bulk::operations::BulkDeleteOptions {
+        force_delete: false,
+        delete_children: delete_children.unwrap_or(false),
+        skip_protected: false,
+        concurrency: None,
     };
-
-    Ok(validation_result)
+    crate::bulk::operations::validate_bulk_delete(db.get_connection(), &asset_ids, &options)
+        .map_err(|e| format!("Failed to validate bulk delete: {}", e))
 }
 
 #[command]
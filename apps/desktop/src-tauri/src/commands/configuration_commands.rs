@@ -1,19 +1,23 @@
 // Configuration management commands
 
-use crate::auth::SessionManager;
+use crate::auth::{SessionManager, authorize_command, CommandPolicy};
 use crate::assets::{AssetRepository, SqliteAssetRepository, AssetInfo, CreateAssetRequest, AssetType};
-use crate::configurations::{ConfigurationRepository, SqliteConfigurationRepository, ConfigurationVersionInfo, ConfigurationStatus, StatusChangeRecord, FileMetadata, CreateConfigurationRequest};
+use crate::configurations::{ConfigurationRepository, SqliteConfigurationRepository, ConfigurationVersionInfo, ConfigurationStatus, StatusChangeRecord, FileMetadata, CreateConfigurationRequest, TextEncoding, LineEnding, ConfigDiff, ConfigurationPreview, LintMode, ConfigurationSignature, SignatureVerification, SignerKey, ConfigurationComment, ConfigurationVersionTag, AutoGoldenSettings, ApprovalEscalationSettings, ApprovalEscalation, ApprovalDelegation, VersionArchiveManifest, GoldenExportManifest, ConfigurationExportRecord, STREAMED_IMPORT_THRESHOLD, DiffFilterOptions, ImportProvenance};
+use crate::configurations::lint::{self, LintContentType, LintResult};
 use crate::branches::{BranchRepository, SqliteBranchRepository};
 use crate::users::UserRole;
 use crate::validation::{InputSanitizer, RateLimiter};
+use crate::audit::{AuditEventRequest, AuditEventType, AuditRepository, SqliteAuditRepository};
+use crate::audit::subscriptions::{AssetEvent, AssetEventType, emit_asset_event, asset_id_for_version};
 use crate::database::Database;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use std::fs;
-use tauri::State;
+use tauri::{AppHandle, State};
 use tracing::{error, info, warn};
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 type RateLimiterState = Mutex<RateLimiter>;
 
 #[tauri::command]
@@ -22,6 +26,8 @@ pub async fn import_configuration(
     asset_name: String,
     file_path: String,
     notes: String,
+    source_system: Option<String>,
+    source_reference: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
     rate_limiter: State<'_, RateLimiterState>,
@@ -63,6 +69,7 @@ pub async fn import_configuration(
     if notes.len() > 1000 {
         return Err("Notes cannot exceed 1000 characters".to_string());
     }
+    let provenance = import_provenance(source_system, source_reference)?;
 
     // Check for malicious input
     if InputSanitizer::is_potentially_malicious(&asset_name) || InputSanitizer::is_potentially_malicious(&notes) {
@@ -126,8 +133,14 @@ pub async fn import_configuration(
                 notes,
             };
             
-            match config_repo.store_configuration(config_request) {
-                Ok(_) => {
+            let stored = config_repo.store_configuration(config_request)
+                .and_then(|version| if provenance.is_empty() {
+                    Ok(())
+                } else {
+                    config_repo.set_import_provenance(version.id, &provenance)
+                });
+            match stored {
+                Ok(()) => {
                     let duration = start_time.elapsed();
                     
                     // Log performance metrics
@@ -154,11 +167,14 @@ pub async fn import_configuration(
 
 #[tauri::command]
 pub async fn import_configuration_for_asset(
+    app: AppHandle,
     token: String,
     asset_id: i64,
     file_path: String,
     version_notes: String,
     classification: String,
+    source_system: Option<String>,
+    source_reference: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
     rate_limiter: State<'_, RateLimiterState>,
@@ -191,6 +207,7 @@ pub async fn import_configuration_for_asset(
     if version_notes.len() > 1000 {
         return Err("Version notes cannot exceed 1000 characters".to_string());
     }
+    let provenance = import_provenance(source_system, source_reference)?;
 
     // Check for malicious input
     if InputSanitizer::is_potentially_malicious(&version_notes) || InputSanitizer::is_potentially_malicious(&classification) {
@@ -223,32 +240,46 @@ pub async fn import_configuration_for_asset(
                 }
             };
             
-            // Read file content
-            let file_content = match fs::read(&file_path) {
-                Ok(content) => content,
-                Err(e) => {
-                    error!("Failed to read file: {}", e);
-                    return Err(format!("Failed to read file: {}", e));
-                }
-            };
-            
-            let file_name = std::path::Path::new(&file_path)
-                .file_name()
-                .and_then(|name| name.to_str())
-                .unwrap_or("unknown")
-                .to_string();
+            // Large files are streamed so they are never held in memory whole
+            let file_size = fs::metadata(&file_path).map(|metadata| metadata.len()).unwrap_or(0);
+            let stored = if file_size > STREAMED_IMPORT_THRESHOLD {
+                config_repo.store_configuration_from_path(asset_id, std::path::Path::new(&file_path), session.user_id, &version_notes)
+                    .map(|version| version.id)
+            } else {
+                // Read file content
+                let file_content = match fs::read(&file_path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        error!("Failed to read file: {}", e);
+                        return Err(format!("Failed to read file: {}", e));
+                    }
+                };
 
-            // Store configuration
-            let config_request = CreateConfigurationRequest {
-                asset_id,
-                file_name,
-                file_content,
-                author: session.user_id,
-                notes: version_notes,
+                let file_name = std::path::Path::new(&file_path)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+
+                // Store configuration
+                let config_request = CreateConfigurationRequest {
+                    asset_id,
+                    file_name,
+                    file_content,
+                    author: session.user_id,
+                    notes: version_notes,
+                };
+
+                config_repo.store_configuration(config_request).map(|version| version.id)
             };
+            let stored = stored.and_then(|version_id| if provenance.is_empty() {
+                Ok(())
+            } else {
+                config_repo.set_import_provenance(version_id, &provenance)
+            });
             
-            match config_repo.store_configuration(config_request) {
-                Ok(_) => {
+            match stored {
+                Ok(()) => {
                     let duration = start_time.elapsed();
                     
                     // Log performance metrics
@@ -259,6 +290,12 @@ pub async fn import_configuration_for_asset(
                     }
                     
                     info!("Configuration imported by {} for asset ID: {}", session.username, asset_id);
+                    emit_asset_event(&app, db.get_connection(), AssetEvent::new(
+                        asset_id,
+                        AssetEventType::ConfigurationImported,
+                        Some(session.user_id),
+                        format!("Configuration imported by {}", session.username),
+                    ));
                     Ok(())
                 }
                 Err(e) => {
@@ -271,6 +308,21 @@ pub async fn import_configuration_for_asset(
     }
 }
 
+/// Sanitized provenance for an import, checked before anything is stored
+fn import_provenance(source_system: Option<String>, source_reference: Option<String>) -> Result<ImportProvenance, String> {
+    let sanitize = |value: Option<String>| value.map(|value| InputSanitizer::sanitize_string(&value));
+    let provenance = ImportProvenance {
+        source_system: sanitize(source_system),
+        source_reference: sanitize(source_reference),
+    };
+    if [&provenance.source_system, &provenance.source_reference].into_iter()
+        .flatten()
+        .any(|value| InputSanitizer::is_potentially_malicious(value)) {
+        return Err("Invalid input detected. Please avoid using special characters or script-like patterns.".to_string());
+    }
+    provenance.normalized().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_configuration_versions(
     token: String,
@@ -314,6 +366,7 @@ pub async fn get_configuration_versions(
 
 #[tauri::command]
 pub async fn update_configuration_status(
+    app: AppHandle,
     token: String,
     version_id: i64,
     new_status: String,
@@ -366,14 +419,57 @@ pub async fn update_configuration_status(
             let available_transitions = config_repo.get_available_status_transitions(version_id, &session.role.to_string())
                 .map_err(|e| format!("Failed to check available transitions: {}", e))?;
 
-            if !available_transitions.contains(&status) {
-                warn!("User {} attempted unauthorized status transition to {:?} for version {}", session.username, status, version_id);
-                return Err("You don't have permission to change to this status".to_string());
-            }
+            // Without the role's own permission, an active delegation may grant the transition
+            let delegation = if available_transitions.contains(&status) {
+                None
+            } else {
+                match config_repo.find_delegation_for_transition(version_id, session.user_id, &status) {
+                    Ok(Some(delegation)) => Some(delegation),
+                    Ok(None) => {
+                        warn!("User {} attempted unauthorized status transition to {:?} for version {}", session.username, status, version_id);
+                        return Err("You don't have permission to change to this status".to_string());
+                    }
+                    Err(e) => return Err(format!("Failed to check approval delegations: {}", e)),
+                }
+            };
 
             match config_repo.update_configuration_status(version_id, status.clone(), session.user_id, change_reason) {
                 Ok(_) => {
                     info!("Configuration status updated by {}: Version {} to {:?}", session.username, version_id, status);
+                    if let Some(delegation) = delegation {
+                        let audit_repo = SqliteAuditRepository::new(db.get_connection());
+                        let audit_event = AuditEventRequest {
+                            event_type: AuditEventType::ConfigurationStatusChangedUnderDelegation,
+                            user_id: Some(session.user_id),
+                            username: Some(session.username.clone()),
+                            admin_user_id: Some(delegation.from_user_id),
+                            admin_username: Some(delegation.from_username.clone()),
+                            target_user_id: None,
+                            target_username: None,
+                            description: format!(
+                                "{} changed version {} to {} under delegation from {}",
+                                session.username, version_id, status.as_str(), delegation.from_username
+                            ),
+                            metadata: Some(serde_json::json!({
+                                "version_id": version_id,
+                                "new_status": status.as_str(),
+                                "delegation_id": delegation.id,
+                            }).to_string()),
+                            ip_address: None,
+                            user_agent: None,
+                        };
+                        if let Err(e) = audit_repo.log_event(&audit_event) {
+                            error!("Failed to log audit event: {}", e);
+                        }
+                    }
+                    if let Some(asset_id) = asset_id_for_version(db.get_connection(), version_id) {
+                        emit_asset_event(&app, db.get_connection(), AssetEvent::new(
+                            asset_id,
+                            AssetEventType::ConfigurationStatusChanged,
+                            Some(session.user_id),
+                            format!("Version {} changed to {} by {}", version_id, status.as_str(), session.username),
+                        ));
+                    }
                     Ok(())
                 }
                 Err(e) => {
@@ -428,12 +524,13 @@ pub async fn get_configuration_status_history(
 }
 
 #[tauri::command]
-pub async fn get_available_status_transitions(
+pub async fn add_configuration_comment(
     token: String,
     version_id: i64,
+    comment: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<String>, String> {
+) -> Result<ConfigurationComment, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -447,21 +544,26 @@ pub async fn get_available_status_transitions(
     };
     drop(session_manager_guard);
 
+    let comment = InputSanitizer::sanitize_string(&comment);
+    if InputSanitizer::is_potentially_malicious(&comment) {
+        error!("Potentially malicious input detected in add_configuration_comment");
+        return Err("Invalid input detected. Please avoid using special characters or script-like patterns.".to_string());
+    }
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
             
-            match config_repo.get_available_status_transitions(version_id, &session.role.to_string()) {
-                Ok(transitions) => {
-                    let status_strings: Vec<String> = transitions.iter().map(|s| s.as_str().to_string()).collect();
-                    info!("Available status transitions accessed by {}: Version ID {}", session.username, version_id);
-                    Ok(status_strings)
+            match config_repo.add_configuration_comment(version_id, session.user_id, &comment) {
+                Ok(comment) => {
+                    info!("Comment added by {}: Version ID {}", session.username, version_id);
+                    Ok(comment)
                 }
                 Err(e) => {
-                    error!("Failed to get available status transitions: {}", e);
-                    Err(format!("Failed to get available status transitions: {}", e))
+                    error!("Failed to add configuration comment: {}", e);
+                    Err(format!("Failed to add configuration comment: {}", e))
                 }
             }
         }
@@ -470,13 +572,12 @@ pub async fn get_available_status_transitions(
 }
 
 #[tauri::command]
-pub async fn promote_to_golden(
+pub async fn get_configuration_comments(
     token: String,
     version_id: i64,
-    promotion_reason: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+) -> Result<Vec<ConfigurationComment>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -490,47 +591,61 @@ pub async fn promote_to_golden(
     };
     drop(session_manager_guard);
 
-    // Only Engineers and Administrators can promote to Golden
-    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
-        warn!("User without sufficient permissions attempted to promote to Golden: {}", session.username);
-        return Err("Only Engineers and Administrators can promote versions to Golden".to_string());
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_configuration_comments(version_id) {
+                Ok(comments) => {
+                    info!("Configuration comments accessed by {}: Version ID {}", session.username, version_id);
+                    Ok(comments)
+                }
+                Err(e) => {
+                    error!("Failed to get configuration comments: {}", e);
+                    Err(format!("Failed to get configuration comments: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
     }
+}
 
-    // Validate inputs
-    let promotion_reason = promotion_reason.map(|r| InputSanitizer::sanitize_string(&r));
-    
-    if let Some(ref reason) = promotion_reason {
-        if InputSanitizer::is_potentially_malicious(reason) {
-            error!("Potentially malicious input detected in promote_to_golden");
-            return Err("Invalid input detected".to_string());
-        }
-        if reason.len() > 500 {
-            return Err("Promotion reason cannot exceed 500 characters".to_string());
+#[tauri::command]
+pub async fn delete_configuration_comment(
+    token: String,
+    comment_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
         }
-    }
+    };
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
-
-            // Check promotion eligibility
-            let is_eligible = config_repo.get_promotion_eligibility(version_id)
-                .map_err(|e| format!("Failed to check promotion eligibility: {}", e))?;
-
-            if !is_eligible {
-                return Err("Version is not eligible for Golden promotion. Only Approved versions can be promoted.".to_string());
-            }
-
-            match config_repo.promote_to_golden(version_id, session.user_id, promotion_reason) {
-                Ok(_) => {
-                    info!("Version promoted to Golden by {}: Version ID {}", session.username, version_id);
+            
+            match config_repo.delete_configuration_comment(comment_id, session.user_id, &session.role.to_string()) {
+                Ok(()) => {
+                    info!("Comment {} deleted by {}", comment_id, session.username);
                     Ok(())
                 }
                 Err(e) => {
-                    error!("Failed to promote to Golden: {}", e);
-                    Err(format!("Failed to promote to Golden: {}", e))
+                    error!("Failed to delete configuration comment: {}", e);
+                    Err(format!("Failed to delete configuration comment: {}", e))
                 }
             }
         }
@@ -539,13 +654,13 @@ pub async fn promote_to_golden(
 }
 
 #[tauri::command]
-pub async fn promote_branch_to_silver(
+pub async fn add_version_tag(
     token: String,
-    branch_id: i64,
-    promotion_notes: Option<String>,
+    version_id: i64,
+    tag: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<i64, String> {
+) -> Result<ConfigurationVersionTag, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -559,101 +674,26 @@ pub async fn promote_branch_to_silver(
     };
     drop(session_manager_guard);
 
-    // Both Engineers and Administrators can promote branches to Silver
-    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
-        warn!("User without sufficient permissions attempted to promote branch to Silver: {}", session.username);
-        return Err("Insufficient permissions to promote branch to Silver".to_string());
-    }
-
-    // Validate inputs
-    let promotion_notes = promotion_notes.map(|n| InputSanitizer::sanitize_string(&n));
-    
-    if let Some(ref notes) = promotion_notes {
-        if InputSanitizer::is_potentially_malicious(notes) {
-            error!("Potentially malicious input detected in promote_branch_to_silver");
-            return Err("Invalid input detected".to_string());
-        }
-        if notes.len() > 1000 {
-            return Err("Promotion notes cannot exceed 1000 characters".to_string());
-        }
+    let tag = InputSanitizer::sanitize_string(&tag);
+    if InputSanitizer::is_potentially_malicious(&tag) {
+        error!("Potentially malicious input detected in add_version_tag");
+        return Err("Invalid input detected. Please avoid using special characters or script-like patterns.".to_string());
     }
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
-            let branch_repo = SqliteBranchRepository::new(db.get_connection());
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
             
-            // Get the branch details
-            let branch = match branch_repo.get_branch_by_id(branch_id) {
-                Ok(Some(b)) => b,
-                Ok(None) => return Err("Branch not found".to_string()),
-                Err(e) => {
-                    error!("Failed to get branch details: {}", e);
-                    return Err(format!("Failed to get branch details: {}", e));
-                }
-            };
-            
-            // Get the latest version of the branch
-            let latest_version = match branch_repo.get_branch_latest_version(branch_id) {
-                Ok(Some(version)) => version,
-                Ok(None) => return Err("Branch has no versions to promote".to_string()),
-                Err(e) => {
-                    error!("Failed to get branch latest version: {}", e);
-                    return Err(format!("Failed to get branch latest version: {}", e));
-                }
-            };
-            
-            // Get the configuration content
-            let content = match config_repo.get_configuration_content(latest_version.version_id) {
-                Ok(c) => c,
-                Err(e) => {
-                    error!("Failed to get configuration content: {}", e);
-                    return Err(format!("Failed to get configuration content: {}", e));
-                }
-            };
-            
-            // Create a new configuration version in the main line with Silver status
-            let notes = format!(
-                "Promoted from branch '{}' (version {}). {}",
-                branch.name,
-                latest_version.branch_version_number,
-                promotion_notes.unwrap_or_default()
-            );
-            
-            let config_request = CreateConfigurationRequest {
-                asset_id: branch.asset_id,
-                file_name: latest_version.file_name.clone(),
-                file_content: content,
-                author: session.user_id,
-                notes,
-            };
-            
-            // Store the new configuration
-            let new_config = match config_repo.store_configuration(config_request) {
-                Ok(config) => config,
-                Err(e) => {
-                    error!("Failed to create Silver configuration: {}", e);
-                    return Err(format!("Failed to create Silver configuration: {}", e));
-                }
-            };
-            
-            // Update the status to Silver
-            match config_repo.update_configuration_status(
-                new_config.id,
-                ConfigurationStatus::Silver,
-                session.user_id,
-                Some("Promoted from branch".to_string())
-            ) {
-                Ok(_) => {
-                    info!("Branch promoted to Silver by {}: Branch {} -> Config ID {}", 
-                          session.username, branch.name, new_config.id);
-                    Ok(new_config.id)
+            match config_repo.add_version_tag(version_id, &tag, session.user_id) {
+                Ok(version_tag) => {
+                    info!("Tag '{}' added by {}: Version ID {}", version_tag.tag, session.username, version_id);
+                    Ok(version_tag)
                 }
                 Err(e) => {
-                    error!("Failed to set Silver status: {}", e);
-                    Err(format!("Failed to set Silver status: {}", e))
+                    error!("Failed to add version tag: {}", e);
+                    Err(format!("Failed to add version tag: {}", e))
                 }
             }
         }
@@ -662,12 +702,13 @@ pub async fn promote_branch_to_silver(
 }
 
 #[tauri::command]
-pub async fn get_golden_version(
+pub async fn remove_version_tag(
     token: String,
-    asset_id: i64,
+    version_id: i64,
+    tag: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Option<ConfigurationVersionInfo>, String> {
+) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -687,14 +728,14 @@ pub async fn get_golden_version(
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
             
-            match config_repo.get_golden_version(asset_id) {
-                Ok(golden_version) => {
-                    info!("Golden version accessed by {}: Asset ID {}", session.username, asset_id);
-                    Ok(golden_version)
+            match config_repo.remove_version_tag(version_id, &tag, session.user_id, &session.role.to_string()) {
+                Ok(()) => {
+                    info!("Tag '{}' removed by {}: Version ID {}", tag.trim(), session.username, version_id);
+                    Ok(())
                 }
                 Err(e) => {
-                    error!("Failed to get Golden version: {}", e);
-                    Err(format!("Failed to get Golden version: {}", e))
+                    error!("Failed to remove version tag: {}", e);
+                    Err(format!("Failed to remove version tag: {}", e))
                 }
             }
         }
@@ -703,12 +744,12 @@ pub async fn get_golden_version(
 }
 
 #[tauri::command]
-pub async fn get_promotion_eligibility(
+pub async fn get_version_tags(
     token: String,
     version_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<bool, String> {
+) -> Result<Vec<ConfigurationVersionTag>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -728,14 +769,14 @@ pub async fn get_promotion_eligibility(
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
             
-            match config_repo.get_promotion_eligibility(version_id) {
-                Ok(eligibility) => {
-                    info!("Promotion eligibility checked by {}: Version ID {}", session.username, version_id);
-                    Ok(eligibility)
+            match config_repo.get_version_tags(version_id) {
+                Ok(tags) => {
+                    info!("Version tags accessed by {}: Version ID {}", session.username, version_id);
+                    Ok(tags)
                 }
                 Err(e) => {
-                    error!("Failed to check promotion eligibility: {}", e);
-                    Err(format!("Failed to check promotion eligibility: {}", e))
+                    error!("Failed to get version tags: {}", e);
+                    Err(format!("Failed to get version tags: {}", e))
                 }
             }
         }
@@ -744,13 +785,13 @@ pub async fn get_promotion_eligibility(
 }
 
 #[tauri::command]
-pub async fn export_configuration_version(
+pub async fn get_versions_by_tag(
     token: String,
-    version_id: i64,
-    export_path: String,
+    asset_id: i64,
+    tag: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+) -> Result<Vec<ConfigurationVersionInfo>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -764,44 +805,20 @@ pub async fn export_configuration_version(
     };
     drop(session_manager_guard);
 
-    // Validate export path
-    let export_path = export_path.trim();
-    
-    if export_path.is_empty() {
-        return Err("Export path cannot be empty".to_string());
-    }
-    
-    // Use proper file path validation instead of generic malicious input check
-    if let Err(e) = InputSanitizer::validate_file_path(&export_path) {
-        error!("Invalid export path: {}", e);
-        return Err(format!("Invalid export path: {}", e));
-    }
-
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
             
-            let start_time = std::time::Instant::now();
-            
-            match config_repo.export_configuration_version(version_id, &export_path) {
-                Ok(_) => {
-                    let duration = start_time.elapsed();
-                    
-                    // Log performance metrics
-                    if duration.as_secs() >= 2 {
-                        warn!("Export operation took {} seconds, exceeding 2-second requirement", duration.as_secs_f64());
-                    } else {
-                        info!("Export completed in {:.2} seconds", duration.as_secs_f64());
-                    }
-                    
-                    info!("Configuration exported by {}: Version {} to {}", session.username, version_id, export_path);
-                    Ok(())
+            match config_repo.get_versions_by_tag(asset_id, &tag) {
+                Ok(versions) => {
+                    info!("Versions tagged '{}' accessed by {}: Asset ID {}", tag.trim(), session.username, asset_id);
+                    Ok(versions)
                 }
                 Err(e) => {
-                    error!("Failed to export configuration: {}", e);
-                    Err(format!("Failed to export configuration: {}", e))
+                    error!("Failed to get versions by tag: {}", e);
+                    Err(format!("Failed to get versions by tag: {}", e))
                 }
             }
         }
@@ -810,32 +827,53 @@ pub async fn export_configuration_version(
 }
 
 #[tauri::command]
-pub async fn get_file_metadata(
-    file_path: String,
-) -> Result<FileMetadata, String> {
-    // Validate file path
-    if let Err(e) = InputSanitizer::validate_file_path(&file_path) {
-        error!("Invalid file path: {}", e);
-        return Err(format!("Invalid file path: {}", e));
-    }
-
-    match crate::configurations::file_utils::get_file_metadata(&file_path) {
-        Ok(metadata) => {
-            info!("File metadata retrieved: {}", file_path);
-            Ok(metadata)
-        }
+pub async fn get_available_status_transitions(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<String>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
         Err(e) => {
-            error!("Failed to get file metadata: {}", e);
-            Err(format!("Failed to get file metadata: {}", e))
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_available_status_transitions(version_id, &session.role.to_string()) {
+                Ok(transitions) => {
+                    let status_strings: Vec<String> = transitions.iter().map(|s| s.as_str().to_string()).collect();
+                    info!("Available status transitions accessed by {}: Version ID {}", session.username, version_id);
+                    Ok(status_strings)
+                }
+                Err(e) => {
+                    error!("Failed to get available status transitions: {}", e);
+                    Err(format!("Failed to get available status transitions: {}", e))
+                }
+            }
         }
+        None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn archive_version(
+pub async fn promote_to_golden(
+    app: AppHandle,
     token: String,
     version_id: i64,
-    archive_reason: Option<String>,
+    promotion_reason: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
 ) -> Result<(), String> {
@@ -852,22 +890,22 @@ pub async fn archive_version(
     };
     drop(session_manager_guard);
 
-    // Only Engineers and Administrators can archive versions
+    // Only Engineers and Administrators can promote to Golden
     if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
-        warn!("User without sufficient permissions attempted to archive version: {}", session.username);
-        return Err("Only Engineers and Administrators can archive versions".to_string());
+        warn!("User without sufficient permissions attempted to promote to Golden: {}", session.username);
+        return Err("Only Engineers and Administrators can promote versions to Golden".to_string());
     }
 
     // Validate inputs
-    let archive_reason = archive_reason.map(|r| InputSanitizer::sanitize_string(&r));
+    let promotion_reason = promotion_reason.map(|r| InputSanitizer::sanitize_string(&r));
     
-    if let Some(ref reason) = archive_reason {
+    if let Some(ref reason) = promotion_reason {
         if InputSanitizer::is_potentially_malicious(reason) {
-            error!("Potentially malicious input detected in archive_version");
+            error!("Potentially malicious input detected in promote_to_golden");
             return Err("Invalid input detected".to_string());
         }
         if reason.len() > 500 {
-            return Err("Archive reason cannot exceed 500 characters".to_string());
+            return Err("Promotion reason cannot exceed 500 characters".to_string());
         }
     }
 
@@ -877,14 +915,30 @@ pub async fn archive_version(
         Some(db) => {
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
 
-            match config_repo.archive_version(version_id, session.user_id, archive_reason) {
+            // Check promotion eligibility
+            let is_eligible = config_repo.get_promotion_eligibility(version_id)
+                .map_err(|e| format!("Failed to check promotion eligibility: {}", e))?;
+
+            if !is_eligible {
+                return Err("Version is not eligible for Golden promotion. Only Approved versions can be promoted.".to_string());
+            }
+
+            match config_repo.promote_to_golden(version_id, session.user_id, promotion_reason) {
                 Ok(_) => {
-                    info!("Version archived by {}: Version ID {}", session.username, version_id);
+                    info!("Version promoted to Golden by {}: Version ID {}", session.username, version_id);
+                    if let Some(asset_id) = asset_id_for_version(db.get_connection(), version_id) {
+                        emit_asset_event(&app, db.get_connection(), AssetEvent::new(
+                            asset_id,
+                            AssetEventType::ConfigurationPromoted,
+                            Some(session.user_id),
+                            format!("Version {} promoted to Golden by {}", version_id, session.username),
+                        ));
+                    }
                     Ok(())
                 }
                 Err(e) => {
-                    error!("Failed to archive version: {}", e);
-                    Err(format!("Failed to archive version: {}", e))
+                    error!("Failed to promote to Golden: {}", e);
+                    Err(format!("Failed to promote to Golden: {}", e))
                 }
             }
         }
@@ -893,13 +947,13 @@ pub async fn archive_version(
 }
 
 #[tauri::command]
-pub async fn restore_version(
+pub async fn promote_branch_to_silver(
     token: String,
-    version_id: i64,
-    restore_reason: Option<String>,
+    branch_id: i64,
+    promotion_notes: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+) -> Result<i64, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -913,22 +967,22 @@ pub async fn restore_version(
     };
     drop(session_manager_guard);
 
-    // Only Engineers and Administrators can restore versions
+    // Both Engineers and Administrators can promote branches to Silver
     if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
-        warn!("User without sufficient permissions attempted to restore version: {}", session.username);
-        return Err("Only Engineers and Administrators can restore versions".to_string());
+        warn!("User without sufficient permissions attempted to promote branch to Silver: {}", session.username);
+        return Err("Insufficient permissions to promote branch to Silver".to_string());
     }
 
     // Validate inputs
-    let restore_reason = restore_reason.map(|r| InputSanitizer::sanitize_string(&r));
+    let promotion_notes = promotion_notes.map(|n| InputSanitizer::sanitize_string(&n));
     
-    if let Some(ref reason) = restore_reason {
-        if InputSanitizer::is_potentially_malicious(reason) {
-            error!("Potentially malicious input detected in restore_version");
+    if let Some(ref notes) = promotion_notes {
+        if InputSanitizer::is_potentially_malicious(notes) {
+            error!("Potentially malicious input detected in promote_branch_to_silver");
             return Err("Invalid input detected".to_string());
         }
-        if reason.len() > 500 {
-            return Err("Restore reason cannot exceed 500 characters".to_string());
+        if notes.len() > 1000 {
+            return Err("Promotion notes cannot exceed 1000 characters".to_string());
         }
     }
 
@@ -936,19 +990,1943 @@ pub async fn restore_version(
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
+            let branch_repo = SqliteBranchRepository::new(db.get_connection());
             let config_repo = SqliteConfigurationRepository::new(db.get_connection());
-
-            match config_repo.restore_version(version_id, session.user_id, restore_reason) {
+            
+            // Get the branch details
+            let branch = match branch_repo.get_branch_by_id(branch_id) {
+                Ok(Some(b)) => b,
+                Ok(None) => return Err("Branch not found".to_string()),
+                Err(e) => {
+                    error!("Failed to get branch details: {}", e);
+                    return Err(format!("Failed to get branch details: {}", e));
+                }
+            };
+            
+            // Get the latest version of the branch
+            let latest_version = match branch_repo.get_branch_latest_version(branch_id) {
+                Ok(Some(version)) => version,
+                Ok(None) => return Err("Branch has no versions to promote".to_string()),
+                Err(e) => {
+                    error!("Failed to get branch latest version: {}", e);
+                    return Err(format!("Failed to get branch latest version: {}", e));
+                }
+            };
+            
+            // Get the configuration content
+            let mut content = match config_repo.get_configuration_content(latest_version.version_id) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!("Failed to get configuration content: {}", e);
+                    return Err(format!("Failed to get configuration content: {}", e));
+                }
+            };
+            
+            // Create a new configuration version in the main line with Silver status
+            let notes = format!(
+                "Promoted from branch '{}' (version {}). {}",
+                branch.name,
+                latest_version.branch_version_number,
+                promotion_notes.unwrap_or_default()
+            );
+            
+            let config_request = CreateConfigurationRequest {
+                asset_id: branch.asset_id,
+                file_name: latest_version.file_name.clone(),
+                file_content: std::mem::take(&mut *content),
+                author: session.user_id,
+                notes,
+            };
+            
+            // Store the new configuration
+            let new_config = match config_repo.store_configuration(config_request) {
+                Ok(config) => config,
+                Err(e) => {
+                    error!("Failed to create Silver configuration: {}", e);
+                    return Err(format!("Failed to create Silver configuration: {}", e));
+                }
+            };
+            
+            // Update the status to Silver
+            match config_repo.update_configuration_status(
+                new_config.id,
+                ConfigurationStatus::Silver,
+                session.user_id,
+                Some("Promoted from branch".to_string())
+            ) {
                 Ok(_) => {
-                    info!("Version restored by {}: Version ID {}", session.username, version_id);
-                    Ok(())
+                    info!("Branch promoted to Silver by {}: Branch {} -> Config ID {}", 
+                          session.username, branch.name, new_config.id);
+                    Ok(new_config.id)
                 }
                 Err(e) => {
-                    error!("Failed to restore version: {}", e);
-                    Err(format!("Failed to restore version: {}", e))
+                    error!("Failed to set Silver status: {}", e);
+                    Err(format!("Failed to set Silver status: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_golden_version(
+    token: String,
+    asset_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Option<ConfigurationVersionInfo>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_golden_version(asset_id) {
+                Ok(golden_version) => {
+                    info!("Golden version accessed by {}: Asset ID {}", session.username, asset_id);
+                    Ok(golden_version)
+                }
+                Err(e) => {
+                    error!("Failed to get Golden version: {}", e);
+                    Err(format!("Failed to get Golden version: {}", e))
                 }
             }
         }
         None => Err("Database not initialized".to_string()),
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn is_version_mutable(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<bool, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            config_repo.is_version_mutable(version_id)
+                .map_err(|e| {
+                    error!("Failed to check version mutability: {}", e);
+                    format!("Failed to check version mutability: {}", e)
+                })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_promotion_eligibility(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<bool, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_promotion_eligibility(version_id) {
+                Ok(eligibility) => {
+                    info!("Promotion eligibility checked by {}: Version ID {}", session.username, version_id);
+                    Ok(eligibility)
+                }
+                Err(e) => {
+                    error!("Failed to check promotion eligibility: {}", e);
+                    Err(format!("Failed to check promotion eligibility: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_approved_awaiting_promotion(
+    token: String,
+    older_than_days: Option<u32>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<ConfigurationVersionInfo>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let older_than_days = older_than_days.unwrap_or(14);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_approved_awaiting_promotion(older_than_days) {
+                Ok(versions) => {
+                    info!("Approved versions awaiting promotion listed by {}: {} older than {} days",
+                          session.username, versions.len(), older_than_days);
+                    Ok(versions)
+                }
+                Err(e) => {
+                    error!("Failed to get approved versions awaiting promotion: {}", e);
+                    Err(format!("Failed to get approved versions awaiting promotion: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_auto_golden_settings(
+    token: String,
+    asset_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<AutoGoldenSettings, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.get_auto_golden_settings(asset_id)
+                .map_err(|e| format!("Failed to get auto-golden settings: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_auto_golden_settings(
+    token: String,
+    settings: AutoGoldenSettings,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_auto_golden_settings").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.set_auto_golden_settings(&settings, session.user_id) {
+                Ok(()) => {
+                    info!("Auto-golden settings for asset {} updated by {}", settings.asset_id, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to set auto-golden settings: {}", e);
+                    Err(format!("Failed to set auto-golden settings: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_approval_escalation_settings(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<ApprovalEscalationSettings, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.get_approval_escalation_settings()
+                .map_err(|e| format!("Failed to get approval escalation settings: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_approval_escalation_settings(
+    token: String,
+    settings: ApprovalEscalationSettings,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_approval_escalation_settings").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.set_approval_escalation_settings(&settings, session.user_id) {
+                Ok(()) => {
+                    info!("Approval escalation settings updated by {}", session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to set approval escalation settings: {}", e);
+                    Err(format!("Failed to set approval escalation settings: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn create_approval_delegation(
+    token: String,
+    from_user_id: i64,
+    to_user_id: i64,
+    expires_at: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<ApprovalDelegation, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "create_approval_delegation").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.create_approval_delegation(from_user_id, to_user_id, &expires_at) {
+                Ok(delegation) => {
+                    info!("Approval authority of {} delegated to {} until {} by {}",
+                          delegation.from_username, delegation.to_username, delegation.expires_at, session.username);
+                    let audit_repo = SqliteAuditRepository::new(db.get_connection());
+                    let audit_event = AuditEventRequest {
+                        event_type: AuditEventType::ConfigurationApprovalDelegated,
+                        user_id: Some(session.user_id),
+                        username: Some(session.username.clone()),
+                        admin_user_id: Some(delegation.from_user_id),
+                        admin_username: Some(delegation.from_username.clone()),
+                        target_user_id: Some(delegation.to_user_id),
+                        target_username: Some(delegation.to_username.clone()),
+                        description: format!(
+                            "Approval authority of {} delegated to {} until {}",
+                            delegation.from_username, delegation.to_username, delegation.expires_at
+                        ),
+                        metadata: Some(serde_json::json!({ "delegation_id": delegation.id }).to_string()),
+                        ip_address: None,
+                        user_agent: None,
+                    };
+                    if let Err(e) = audit_repo.log_event(&audit_event) {
+                        error!("Failed to log audit event: {}", e);
+                    }
+                    Ok(delegation)
+                }
+                Err(e) => {
+                    error!("Failed to create approval delegation: {}", e);
+                    Err(format!("Failed to create approval delegation: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_approval_escalations(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<ApprovalEscalation>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.get_approval_escalations(version_id)
+                .map_err(|e| format!("Failed to get approval escalations: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_configurations_by_inactive_authors(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<ConfigurationVersionInfo>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_configurations_by_inactive_authors").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_configurations_by_inactive_authors() {
+                Ok(versions) => {
+                    info!("Configurations by inactive authors listed by {}: {} found",
+                          session.username, versions.len());
+                    Ok(versions)
+                }
+                Err(e) => {
+                    error!("Failed to get configurations by inactive authors: {}", e);
+                    Err(format!("Failed to get configurations by inactive authors: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_versions_with_inadequate_notes(
+    token: String,
+    asset_id: Option<i64>,
+    min_length: u32,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<ConfigurationVersionInfo>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            match config_repo.get_versions_with_inadequate_notes(asset_id, min_length as usize) {
+                Ok(versions) => {
+                    info!("Versions with inadequate notes listed by {}: {} found",
+                          session.username, versions.len());
+                    Ok(versions)
+                }
+                Err(e) => {
+                    error!("Failed to get versions with inadequate notes: {}", e);
+                    Err(format!("Failed to get versions with inadequate notes: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_configuration_version(
+    token: String,
+    version_id: i64,
+    export_path: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate export path
+    let export_path = export_path.trim();
+    
+    if export_path.is_empty() {
+        return Err("Export path cannot be empty".to_string());
+    }
+    
+    // Use proper file path validation instead of generic malicious input check
+    if let Err(e) = InputSanitizer::validate_file_path(&export_path) {
+        error!("Invalid export path: {}", e);
+        return Err(format!("Invalid export path: {}", e));
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            
+            let start_time = std::time::Instant::now();
+            
+            match config_repo.export_configuration_version(version_id, &export_path, session.user_id) {
+                Ok(_) => {
+                    let duration = start_time.elapsed();
+                    
+                    // Log performance metrics
+                    if duration.as_secs() >= 2 {
+                        warn!("Export operation took {} seconds, exceeding 2-second requirement", duration.as_secs_f64());
+                    } else {
+                        info!("Export completed in {:.2} seconds", duration.as_secs_f64());
+                    }
+                    
+                    info!("Configuration exported by {}: Version {} to {}", session.username, version_id, export_path);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to export configuration: {}", e);
+                    Err(format!("Failed to export configuration: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_versions_archive(
+    token: String,
+    version_ids: Vec<i64>,
+    dest_path: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<VersionArchiveManifest, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate export path
+    let dest_path = dest_path.trim();
+
+    if dest_path.is_empty() {
+        return Err("Export path cannot be empty".to_string());
+    }
+
+    if let Err(e) = InputSanitizer::validate_file_path(dest_path) {
+        error!("Invalid export path: {}", e);
+        return Err(format!("Invalid export path: {}", e));
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.export_versions_archive(&version_ids, dest_path, session.user_id) {
+                Ok(manifest) => {
+                    info!("Configuration archive exported by {}: {} versions to {}",
+                          session.username, manifest.entries.len(), dest_path);
+                    Ok(manifest)
+                }
+                Err(e) => {
+                    error!("Failed to export configuration archive: {}", e);
+                    Err(format!("Failed to export configuration archive: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_golden_configs(
+    token: String,
+    root_asset_id: Option<i64>,
+    dest_dir: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<GoldenExportManifest, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate export path
+    let dest_dir = dest_dir.trim();
+
+    if dest_dir.is_empty() {
+        return Err("Export path cannot be empty".to_string());
+    }
+
+    if let Err(e) = InputSanitizer::validate_file_path(dest_dir) {
+        error!("Invalid export path: {}", e);
+        return Err(format!("Invalid export path: {}", e));
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.export_golden_configs(root_asset_id, dest_dir, session.user_id) {
+                Ok(manifest) => {
+                    info!("Golden configurations exported by {}: {} versions to {}, {} assets without golden",
+                          session.username, manifest.entries.len(), dest_dir, manifest.assets_without_golden.len());
+                    Ok(manifest)
+                }
+                Err(e) => {
+                    error!("Failed to export golden configurations: {}", e);
+                    Err(format!("Failed to export golden configurations: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_configuration_converted(
+    token: String,
+    version_id: i64,
+    export_path: String,
+    encoding: String,
+    line_ending: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Validate export path
+    let export_path = export_path.trim();
+
+    if export_path.is_empty() {
+        return Err("Export path cannot be empty".to_string());
+    }
+
+    if let Err(e) = InputSanitizer::validate_file_path(&export_path) {
+        error!("Invalid export path: {}", e);
+        return Err(format!("Invalid export path: {}", e));
+    }
+
+    let encoding = encoding.parse::<TextEncoding>().map_err(|e| e.to_string())?;
+    let line_ending = line_ending.parse::<LineEnding>().map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.export_configuration_converted(version_id, &export_path, encoding, line_ending, session.user_id) {
+                Ok(_) => {
+                    info!("Configuration exported with conversion by {}: Version {} to {}", session.username, version_id, export_path);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to export configuration: {}", e);
+                    Err(format!("Failed to export configuration: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_configuration_export_history(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<ConfigurationExportRecord>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_configuration_export_history").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.get_export_history(version_id) {
+                Ok(history) => {
+                    info!("Export history of version {} viewed by {}: {} exports", version_id, session.username, history.len());
+                    Ok(history)
+                }
+                Err(e) => {
+                    error!("Failed to get export history: {}", e);
+                    Err(format!("Failed to get export history: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_version_timeline(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<u8>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // The timeline includes export destinations, so it is gated like the export history
+    authorize_command(&command_policy, &session, "export_version_timeline").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.export_version_timeline(version_id) {
+                Ok(timeline) => {
+                    info!("Timeline of version {} exported by {}", version_id, session.username);
+                    Ok(timeline)
+                }
+                Err(e) => {
+                    error!("Failed to export version timeline: {}", e);
+                    Err(format!("Failed to export version timeline: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn diff_configuration_with_previous(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Option<ConfigDiff>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.diff_with_previous(version_id) {
+                Ok(diff) => {
+                    info!("Configuration version {} diffed against predecessor by {}", version_id, session.username);
+                    Ok(diff)
+                }
+                Err(e) => {
+                    error!("Failed to diff configuration version: {}", e);
+                    Err(format!("Failed to diff configuration version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn diff_configuration_versions(
+    token: String,
+    version_a_id: i64,
+    version_b_id: i64,
+    options: Option<DiffFilterOptions>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<ConfigDiff, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.diff_configuration_versions_filtered(version_a_id, version_b_id, &options.unwrap_or_default()) {
+                Ok(diff) => {
+                    info!("Configuration versions {} and {} diffed by {}", version_a_id, version_b_id, session.username);
+                    Ok(diff)
+                }
+                Err(e) => {
+                    error!("Failed to diff configuration versions: {}", e);
+                    Err(format!("Failed to diff configuration versions: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+/// Default and ceiling for `preview_configuration_content`
+const DEFAULT_PREVIEW_BYTES: usize = 4096;
+const MAX_PREVIEW_BYTES: usize = 64 * 1024;
+
+#[tauri::command]
+pub async fn preview_configuration_content(
+    token: String,
+    version_id: i64,
+    max_bytes: Option<usize>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<ConfigurationPreview, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_PREVIEW_BYTES).clamp(1, MAX_PREVIEW_BYTES);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            let asset_repo = SqliteAssetRepository::new(db.get_connection());
+
+            let version = match config_repo.get_configuration_by_id(version_id) {
+                Ok(Some(version)) => version,
+                Ok(None) => return Err("Configuration version not found".to_string()),
+                Err(e) => {
+                    error!("Failed to load configuration version: {}", e);
+                    return Err(format!("Failed to load configuration version: {}", e));
+                }
+            };
+
+            match asset_repo.check_asset_permission(session.user_id, version.asset_id, "read") {
+                Ok(true) => {}
+                Ok(false) => {
+                    warn!("User {} denied preview of configuration version {}", session.username, version_id);
+                    return Err("Permission denied: cannot read this asset".to_string());
+                }
+                Err(e) => {
+                    error!("Failed to check asset permission: {}", e);
+                    return Err(format!("Failed to check asset permission: {}", e));
+                }
+            }
+
+            match config_repo.preview_configuration_content(version_id, max_bytes) {
+                Ok(preview) => {
+                    info!("Configuration version {} previewed by {}", version_id, session.username);
+                    Ok(preview)
+                }
+                Err(e) => {
+                    error!("Failed to preview configuration content: {}", e);
+                    Err(format!("Failed to preview configuration content: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_file_metadata(
+    file_path: String,
+) -> Result<FileMetadata, String> {
+    // Validate file path
+    if let Err(e) = InputSanitizer::validate_file_path(&file_path) {
+        error!("Invalid file path: {}", e);
+        return Err(format!("Invalid file path: {}", e));
+    }
+
+    match crate::configurations::file_utils::get_file_metadata(&file_path) {
+        Ok(metadata) => {
+            info!("File metadata retrieved: {}", file_path);
+            Ok(metadata)
+        }
+        Err(e) => {
+            error!("Failed to get file metadata: {}", e);
+            Err(format!("Failed to get file metadata: {}", e))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn lint_configuration_content(
+    content: Vec<u8>,
+    content_type: String,
+) -> Result<LintResult, String> {
+    let content_type = content_type.parse::<LintContentType>().map_err(|e| e.to_string())?;
+
+    Ok(lint::lint_configuration_content(&content, &content_type))
+}
+
+#[tauri::command]
+pub async fn archive_version(
+    token: String,
+    version_id: i64,
+    archive_reason: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can archive versions
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to archive version: {}", session.username);
+        return Err("Only Engineers and Administrators can archive versions".to_string());
+    }
+
+    // Validate inputs
+    let archive_reason = archive_reason.map(|r| InputSanitizer::sanitize_string(&r));
+    
+    if let Some(ref reason) = archive_reason {
+        if InputSanitizer::is_potentially_malicious(reason) {
+            error!("Potentially malicious input detected in archive_version");
+            return Err("Invalid input detected".to_string());
+        }
+        if reason.len() > 500 {
+            return Err("Archive reason cannot exceed 500 characters".to_string());
+        }
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.archive_version(version_id, session.user_id, archive_reason) {
+                Ok(_) => {
+                    info!("Version archived by {}: Version ID {}", session.username, version_id);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to archive version: {}", e);
+                    Err(format!("Failed to archive version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn restore_version(
+    token: String,
+    version_id: i64,
+    restore_reason: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can restore versions
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to restore version: {}", session.username);
+        return Err("Only Engineers and Administrators can restore versions".to_string());
+    }
+
+    // Validate inputs
+    let restore_reason = restore_reason.map(|r| InputSanitizer::sanitize_string(&r));
+    
+    if let Some(ref reason) = restore_reason {
+        if InputSanitizer::is_potentially_malicious(reason) {
+            error!("Potentially malicious input detected in restore_version");
+            return Err("Invalid input detected".to_string());
+        }
+        if reason.len() > 500 {
+            return Err("Restore reason cannot exceed 500 characters".to_string());
+        }
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.restore_version(version_id, session.user_id, restore_reason) {
+                Ok(_) => {
+                    info!("Version restored by {}: Version ID {}", session.username, version_id);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to restore version: {}", e);
+                    Err(format!("Failed to restore version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn rollback_configuration(
+    token: String,
+    asset_id: i64,
+    target_version_id: i64,
+    notes: Option<String>,
+    allow_archived: Option<bool>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<ConfigurationVersionInfo, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can roll back configurations
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to roll back configuration: {}", session.username);
+        return Err("Only Engineers and Administrators can roll back configurations".to_string());
+    }
+
+    // Validate inputs
+    let notes = InputSanitizer::sanitize_string(&notes.unwrap_or_default());
+    if InputSanitizer::is_potentially_malicious(&notes) {
+        error!("Potentially malicious input detected in rollback_configuration");
+        return Err("Invalid input detected".to_string());
+    }
+    if notes.len() > 1000 {
+        return Err("Notes cannot exceed 1000 characters".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.rollback_configuration(asset_id, target_version_id, session.user_id, &notes, allow_archived.unwrap_or(false)) {
+                Ok(version) => {
+                    info!("Asset {} rolled back to version ID {} by {} as {}",
+                          asset_id, target_version_id, session.username, version.version_number);
+                    Ok(version)
+                }
+                Err(e) => {
+                    error!("Failed to roll back configuration: {}", e);
+                    Err(format!("Failed to roll back configuration: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn pin_version(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can pin versions
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to pin version: {}", session.username);
+        return Err("Only Engineers and Administrators can pin versions".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.pin_version(version_id) {
+                Ok(_) => {
+                    info!("Version pinned by {}: Version ID {}", session.username, version_id);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to pin version: {}", e);
+                    Err(format!("Failed to pin version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn unpin_version(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can unpin versions
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to unpin version: {}", session.username);
+        return Err("Only Engineers and Administrators can unpin versions".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.unpin_version(version_id) {
+                Ok(_) => {
+                    info!("Version unpinned by {}: Version ID {}", session.username, version_id);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to unpin version: {}", e);
+                    Err(format!("Failed to unpin version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn apply_retention_policy(
+    token: String,
+    asset_id: i64,
+    keep_recent: usize,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<i64>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "apply_retention_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.apply_retention_policy(asset_id, keep_recent, session.user_id) {
+                Ok(archived) => {
+                    info!("Retention policy applied by {}: Asset ID {}, {} versions archived", session.username, asset_id, archived.len());
+                    Ok(archived)
+                }
+                Err(e) => {
+                    error!("Failed to apply retention policy: {}", e);
+                    Err(format!("Failed to apply retention policy: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_configuration_lint_mode(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<LintMode, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.get_lint_mode()
+                .map_err(|e| format!("Failed to get configuration lint mode: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_configuration_lint_mode(
+    token: String,
+    mode: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_configuration_lint_mode").map_err(|e| e.to_string())?;
+
+    let mode = mode.parse::<LintMode>().map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.set_lint_mode(mode) {
+                Ok(()) => {
+                    info!("Configuration lint mode updated by {}", session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to set configuration lint mode: {}", e);
+                    Err(format!("Failed to set configuration lint mode: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_max_upload_bytes(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<u64, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_max_upload_bytes").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.get_max_upload_bytes()
+                .map_err(|e| format!("Failed to get maximum upload size: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_max_upload_bytes(
+    token: String,
+    max_upload_bytes: u64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_max_upload_bytes").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.set_max_upload_bytes(max_upload_bytes) {
+                Ok(()) => {
+                    info!("Maximum upload size set to {} bytes by {}", max_upload_bytes, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to set maximum upload size: {}", e);
+                    Err(format!("Failed to set maximum upload size: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_configuration_signing_payload(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<String, String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "sign_configuration_version").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            // The client signs these bytes with its own key; the private key never reaches the backend
+            config_repo.configuration_signing_payload(version_id)
+                .map(|payload| general_purpose::STANDARD.encode(payload))
+                .map_err(|e| {
+                    error!("Failed to build configuration signing payload: {}", e);
+                    format!("Failed to build configuration signing payload: {}", e)
+                })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn sign_configuration_version(
+    token: String,
+    version_id: i64,
+    signature: String,
+    public_key: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<ConfigurationSignature, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "sign_configuration_version").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.add_configuration_signature(version_id, &signature, &public_key, session.user_id) {
+                Ok(signature) => {
+                    info!("Configuration version {} signed by {}", version_id, session.username);
+                    Ok(signature)
+                }
+                Err(e) => {
+                    error!("Failed to sign configuration version: {}", e);
+                    Err(format!("Failed to sign configuration version: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn register_configuration_signer_key(
+    token: String,
+    user_id: i64,
+    public_key: String,
+    label: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<SignerKey, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "register_configuration_signer_key").map_err(|e| e.to_string())?;
+
+    let label = InputSanitizer::sanitize_string(&label);
+    if InputSanitizer::is_potentially_malicious(&label) {
+        return Err("Signer key label contains potentially malicious content".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.register_signer_key(user_id, &public_key, &label, session.user_id) {
+                Ok(key) => {
+                    info!("Signer key {} registered for user {} by {}", key.id, user_id, session.username);
+                    Ok(key)
+                }
+                Err(e) => {
+                    error!("Failed to register signer key: {}", e);
+                    Err(format!("Failed to register signer key: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn revoke_configuration_signer_key(
+    token: String,
+    key_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "revoke_configuration_signer_key").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.revoke_signer_key(key_id) {
+                Ok(()) => {
+                    info!("Signer key {} revoked by {}", key_id, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to revoke signer key: {}", e);
+                    Err(format!("Failed to revoke signer key: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn list_configuration_signer_keys(
+    token: String,
+    user_id: Option<i64>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<SignerKey>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "list_configuration_signer_keys").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            config_repo.list_signer_keys(user_id).map_err(|e| {
+                error!("Failed to list signer keys: {}", e);
+                format!("Failed to list signer keys: {}", e)
+            })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn verify_configuration_signature(
+    token: String,
+    version_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<SignatureVerification, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            match config_repo.verify_configuration_signature(version_id) {
+                Ok(verification) => {
+                    if verification.signed && !verification.valid {
+                        warn!("Signature verification failed for configuration version {} (checked by {}): {}",
+                              version_id, session.username, verification.reason.as_deref().unwrap_or_default());
+                    }
+                    Ok(verification)
+                }
+                Err(e) => {
+                    error!("Failed to verify configuration signature: {}", e);
+                    Err(format!("Failed to verify configuration signature: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_auto_version_notes_enabled(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<bool, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            config_repo.get_auto_notes_enabled()
+                .map_err(|e| format!("Failed to get version notes policy: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_auto_version_notes_enabled(
+    token: String,
+    enabled: bool,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_auto_version_notes_enabled").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.set_auto_notes_enabled(enabled)
+                .map_err(|e| format!("Failed to update version notes policy: {}", e))?;
+            info!("Automatic version notes set to {} by {}", enabled, session.username);
+            Ok(())
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_golden_signature_required(
+    token: String,
+    required: bool,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_golden_signature_required").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+
+            config_repo.set_golden_signature_required(required)
+                .map_err(|e| format!("Failed to update signing policy: {}", e))?;
+            info!("Golden signature requirement set to {} by {}", required, session.username);
+            Ok(())
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
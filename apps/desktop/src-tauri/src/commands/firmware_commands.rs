@@ -1,23 +1,25 @@
 // Firmware management commands
 
-use crate::auth::SessionManager;
+use crate::auth::{SessionManager, authorize_command, CommandPolicy};
 use crate::database::Database;
 use crate::users::UserRole;
 use crate::validation::{InputSanitizer, RateLimiter};
 use crate::assets::{AssetRepository, SqliteAssetRepository};
 use crate::configurations::{ConfigurationRepository, SqliteConfigurationRepository, ConfigurationVersionInfo, file_utils};
 use crate::audit::{AuditRepository, SqliteAuditRepository, AuditEventRequest, AuditEventType};
-use crate::firmware::{FirmwareRepository, SqliteFirmwareRepository, CreateFirmwareRequest, FirmwareVersionInfo, FirmwareFileStorage, FirmwareStatus, FirmwareStatusHistory, get_firmware_storage_dir};
-use crate::firmware_analysis::{FirmwareAnalysisRepository, SqliteFirmwareAnalysisRepository, FirmwareAnalysisResult, AnalysisQueue, AnalysisJob};
+use crate::audit::subscriptions::{AssetEvent, AssetEventType, emit_asset_event};
+use crate::firmware::{FirmwareRepository, SqliteFirmwareRepository, CreateFirmwareRequest, FirmwareVersionInfo, FirmwareFileStorage, FirmwareStatus, FirmwareStatusHistory, FirmwareConfigImportRequest, FirmwareConfigImport, get_firmware_storage_dir};
+use crate::firmware_analysis::{FirmwareAnalysisRepository, SqliteFirmwareAnalysisRepository, FirmwareAnalysisResult, FirmwareAnalysisDiff, AnalysisQueue, AnalysisJob};
 use crate::recovery::{RecoveryExporter, RecoveryExportRequest, RecoveryManifest};
 use crate::vault::{VaultRepository, SqliteVaultRepository};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use tauri::{AppHandle, State, Manager};
 use tracing::{error, info, warn};
 use serde_json;
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 type RateLimiterState = Mutex<RateLimiter>;
 
 fn get_or_create_analysis_queue(app: &AppHandle) -> Arc<AnalysisQueue> {
@@ -174,6 +176,84 @@ pub async fn unlink_firmware_from_configuration(
     }
 }
 
+#[tauri::command]
+pub async fn import_firmware_and_config(
+    app: AppHandle,
+    token: String,
+    request: FirmwareConfigImportRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<FirmwareConfigImport, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Only Engineers and Administrators can import firmware
+    if session.role != UserRole::Engineer && session.role != UserRole::Administrator {
+        warn!("User without sufficient permissions attempted to import firmware and configuration: {}", session.username);
+        return Err("Only Engineers and Administrators can import firmware".to_string());
+    }
+
+    let firmware_dir = get_firmware_storage_dir(&app)
+        .map_err(|e| format!("Failed to resolve firmware storage: {}", e))?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let firmware_repo = SqliteFirmwareRepository::new(db.get_connection());
+            let audit_repo = SqliteAuditRepository::new(db.get_connection());
+            let asset_id = request.asset_id;
+
+            match firmware_repo.import_firmware_and_config(&firmware_dir, request, session.user_id) {
+                Ok(import) => {
+                    let audit_event = AuditEventRequest {
+                        event_type: AuditEventType::DatabaseOperation,
+                        user_id: Some(session.user_id),
+                        username: Some(session.username.clone()),
+                        admin_user_id: None,
+                        admin_username: None,
+                        target_user_id: None,
+                        target_username: None,
+                        description: format!("Firmware {} and configuration {} imported for asset {}",
+                            import.firmware_id, import.config_version_id, asset_id),
+                        metadata: Some(serde_json::json!({
+                            "asset_id": asset_id,
+                            "firmware_id": import.firmware_id,
+                            "config_id": import.config_version_id,
+                            "linked": import.linked_firmware_id.is_some(),
+                            "imported_by": session.username
+                        }).to_string()),
+                        ip_address: None,
+                        user_agent: None,
+                    };
+
+                    if let Err(e) = audit_repo.log_event(&audit_event) {
+                        error!("Failed to log audit event: {}", e);
+                    }
+
+                    info!("Firmware and configuration imported by {} for asset {}", session.username, asset_id);
+                    Ok(import)
+                }
+                Err(e) => {
+                    error!("Failed to import firmware and configuration: {}", e);
+                    Err(format!("Failed to import firmware and configuration: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn upload_firmware(
     app: AppHandle,
@@ -476,6 +556,13 @@ pub async fn upload_firmware(
                     } else {
                         info!("Audit event logged successfully");
                     }
+
+                    emit_asset_event(&app, audit_db.get_connection(), AssetEvent::new(
+                        asset_id,
+                        AssetEventType::FirmwareUploaded,
+                        Some(session.user_id),
+                        format!("Firmware v{} uploaded by {}", firmware_info.version, session.username),
+                    ));
                 }
             } else {
                 warn!("Could not acquire audit lock for logging");
@@ -683,6 +770,48 @@ pub async fn get_firmware_analysis(
     }
 }
 
+#[tauri::command]
+pub async fn compare_firmware_analysis(
+    token: String,
+    firmware_id_a: i64,
+    firmware_id_b: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<FirmwareAnalysisDiff, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let analysis_repo = SqliteFirmwareAnalysisRepository::new(db.get_connection());
+            
+            match analysis_repo.compare_firmware_analysis(firmware_id_a, firmware_id_b) {
+                Ok(diff) => {
+                    info!("Firmware analyses compared by {}: Firmware ID {} vs {}", session.username, firmware_id_a, firmware_id_b);
+                    Ok(diff)
+                }
+                Err(e) => {
+                    error!("Failed to compare firmware analyses: {}", e);
+                    Err(format!("Failed to compare firmware analyses: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn export_complete_recovery(
     app: AppHandle,
@@ -1039,6 +1168,7 @@ pub async fn promote_firmware_to_golden(
     reason: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
     audit_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
     // Validate session
@@ -1054,11 +1184,7 @@ pub async fn promote_firmware_to_golden(
     };
     drop(session_manager_guard);
 
-    // Only Administrators can promote firmware to Golden
-    if session.role != UserRole::Administrator {
-        warn!("Non-administrator attempted to promote firmware to Golden: {}", session.username);
-        return Err("Only Administrators can promote firmware to Golden status".to_string());
-    }
+    authorize_command(&command_policy, &session, "promote_firmware_to_golden").map_err(|e| e.to_string())?;
 
     // Validate reason
     if reason.trim().is_empty() {
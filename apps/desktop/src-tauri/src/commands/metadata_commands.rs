@@ -1,7 +1,7 @@
 use crate::metadata::{
     SqliteMetadataRepository, MetadataRepository, FieldTemplateRepository, 
     CreateMetadataSchemaRequest, UpdateMetadataSchemaRequest, AssetMetadataSchema, MetadataFieldTemplate,
-    FieldCategory, FieldTypeInfo, TemplateUsageStats, validation::SchemaValidator,
+    FieldCategory, FieldTypeInfo, TemplateUsageStats, validation::{SchemaValidator, MetadataSizePolicy},
     SqliteMetadataSearchRepository, MetadataSearchRepository, SearchQuery, AssetSearchResult,
     SearchSuggestion, FilterPreset, SearchAnalytics, FilterableField,
     api::{
@@ -19,7 +19,8 @@ use crate::metadata::{
         MetadataExportImportApi, MetadataIntegrationApi,
     },
 };
-use crate::{DatabaseState, SessionManagerState};
+use crate::auth::authorize_command;
+use crate::{DatabaseState, SessionManagerState, CommandPolicyState};
 use tauri::State;
 use tracing::{info, warn, error};
 use rusqlite::params;
@@ -1156,6 +1157,64 @@ pub async fn update_asset_metadata_partial(
     Ok(result)
 }
 
+/// Get the size limits applied to asset metadata values
+#[tauri::command]
+pub async fn get_metadata_size_policy(
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>,
+    token: String,
+) -> Result<MetadataSizePolicy, String> {
+    // Verify session
+    let session_manager_guard = session_state.lock().map_err(|_| "Session lock error")?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {},
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(_) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock().map_err(|_| "Database lock error")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let repo = SqliteMetadataRepository::new(db.get_connection());
+    repo.get_metadata_size_policy()
+        .map_err(|e| format!("Failed to get metadata size policy: {}", e))
+}
+
+/// Update the size limits applied to asset metadata values
+#[tauri::command]
+pub async fn set_metadata_size_policy(
+    db_state: State<'_, DatabaseState>,
+    session_state: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+    token: String,
+    policy: MetadataSizePolicy,
+) -> Result<(), String> {
+    // Verify session
+    let session_manager_guard = session_state.lock().map_err(|_| "Session lock error")?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(_) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_metadata_size_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock().map_err(|_| "Database lock error")?;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let repo = SqliteMetadataRepository::new(db.get_connection());
+    repo.set_metadata_size_policy(&policy)
+        .map_err(|e| {
+            error!("Failed to set metadata size policy: {}", e);
+            format!("Failed to set metadata size policy: {}", e)
+        })?;
+
+    info!("Metadata size policy updated by {}", session.username);
+    Ok(())
+}
+
 /// Copy metadata between assets
 #[tauri::command]
 pub async fn copy_metadata_between_assets(
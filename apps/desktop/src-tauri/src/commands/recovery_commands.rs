@@ -2,7 +2,7 @@
 // Extracted from lib.rs.backup
 
 use crate::auth::SessionManager;
-use crate::recovery::{RecoveryExporter, RecoveryImporter, RecoveryManifest, RecoveryImportRequest};
+use crate::recovery::{RecoveryExporter, RecoveryImporter, RecoveryManifest, RecoveryImportRequest, RecoveryImportReport, ConflictStrategy};
 use crate::configurations::{ConfigurationRepository, SqliteConfigurationRepository};
 use crate::firmware::{FirmwareRepository, SqliteFirmwareRepository};
 use crate::vault::{VaultRepository, SqliteVaultRepository};
@@ -142,6 +142,7 @@ pub async fn preview_recovery_bundle(
             });
             
             let manifest = RecoveryManifest {
+                bundle_format_version: crate::recovery::BUNDLE_FORMAT_VERSION,
                 asset_id,
                 export_date: chrono::Utc::now().to_rfc3339(),
                 exported_by: session.username.clone(),
@@ -183,9 +184,10 @@ pub async fn import_recovery_bundle(
     bundle_path: String,
     target_asset_id: i64,
     import_vault: bool,
+    conflict_strategy: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<RecoveryManifest, String> {
+) -> Result<RecoveryImportReport, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session lock".to_string())?;
@@ -199,6 +201,12 @@ pub async fn import_recovery_bundle(
     };
     drop(session_manager_guard);
 
+    // Existing items are skipped unless the caller explicitly chooses otherwise
+    let conflict_strategy = match conflict_strategy {
+        Some(strategy) => strategy.parse::<ConflictStrategy>().map_err(|e| e.to_string())?,
+        None => ConflictStrategy::default(),
+    };
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
@@ -214,6 +222,7 @@ pub async fn import_recovery_bundle(
                 bundle_path,
                 target_asset_id,
                 import_vault,
+                conflict_strategy,
             };
 
             match importer.import_recovery_bundle(
@@ -223,7 +232,7 @@ pub async fn import_recovery_bundle(
                 &session.username,
                 &session.role,
             ) {
-                Ok(manifest) => Ok(manifest),
+                Ok(report) => Ok(report),
                 Err(e) => Err(e.to_string()),
             }
         }
@@ -1,11 +1,11 @@
 use crate::security::{SecurityValidator, SecurityValidationResult, FileIntegrityResult};
-use crate::auth::SessionManager;
+use crate::auth::{SessionManager, authorize_command, CommandPolicy};
 use crate::database::Database;
-use crate::audit::{AuditRepository, SqliteAuditRepository};
-use std::sync::Mutex;
+use crate::audit::{format_audit_export, AuditChainVerification, AuditExportFormat, AuditRepository, SqliteAuditRepository};
+use std::sync::{Mutex, RwLock};
 use std::collections::HashMap;
 use tauri::{command, State};
-use tracing::info;
+use tracing::{info, warn};
 use serde::{Serialize, Deserialize};
 
 /// Security classification levels enum
@@ -102,6 +102,7 @@ pub struct FrontendAuditEvent {
 // Type aliases for Tauri state
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 
 /// Validate asset name against security patterns
 #[command]
@@ -545,6 +546,7 @@ pub async fn get_audit_events(
             code if code.starts_with("SEC_") => "security_validation",
             code if code.starts_with("VAULT_") => "vault_operation",
             code if code.starts_with("META_") => "metadata_operation",
+            code if code.starts_with("CONFIG_") => "permission_change",
             _ => "system_event",
         }.to_string();
         
@@ -587,18 +589,22 @@ pub async fn get_audit_events(
     Ok(filtered_events)
 }
 
-/// Export audit log in specified format
+/// Export audit log events between `from` and `to` as JSON (the default), CSV or NDJSON
 #[command]
 pub async fn export_audit_log(
     token: String,
-    start_date: String,
-    end_date: String,
-    format: String,
+    from: Option<String>,
+    to: Option<String>,
+    format: Option<AuditExportFormat>,
     filters: Option<AuditFilter>,
     session_state: State<'_, SessionManagerState>,
     db_state: State<'_, DatabaseState>,
 ) -> Result<String, String> {
-    info!("Exporting audit log in {} format from {} to {}", format, start_date, end_date);
+    let format = format.unwrap_or_default();
+    // Dates in the filter are honoured when the explicit range is not given
+    let from = from.or_else(|| filters.as_ref().and_then(|f| f.start_date.clone()));
+    let to = to.or_else(|| filters.as_ref().and_then(|f| f.end_date.clone()));
+    info!("Exporting audit log in {:?} format from {:?} to {:?}", format, from, to);
 
     // Validate session
     let session_manager = session_state.lock().unwrap();
@@ -623,52 +629,52 @@ pub async fn export_audit_log(
     let conn = db.get_connection();
     let audit_repo = SqliteAuditRepository::new(conn);
 
-    // Get all events (can be optimized with date filtering in SQL)
-    let events = audit_repo.get_events(None, None)
+    let events = audit_repo.get_events_in_range(from.as_deref(), to.as_deref())
         .map_err(|e| format!("Failed to retrieve audit events: {}", e))?;
 
-    // Filter by date range
-    let filtered_events: Vec<_> = events.into_iter().filter(|event| {
-        // Simple date comparison (can be improved)
-        event.timestamp >= start_date && event.timestamp <= end_date
-    }).collect();
-
-    // Count before formatting
-    let event_count = filtered_events.len();
-    
-    // Format output based on requested format
-    let output = match format.as_str() {
-        "csv" => {
-            let mut csv_output = String::from("ID,Timestamp,Event Type,User ID,Description,Result\n");
-            for event in &filtered_events {
-                csv_output.push_str(&format!(
-                    "{},{},{},{:?},\"{}\",{}\n",
-                    event.id,
-                    event.timestamp,
-                    event.event_code,
-                    event.user_id.unwrap_or(0),
-                    event.description.replace("\"", "\"\""),
-                    "success" // Default result as the audit module doesn't store result directly
-                ));
-            }
-            csv_output
-        },
-        "json" => {
-            serde_json::to_string_pretty(&filtered_events)
-                .map_err(|e| format!("Failed to serialize to JSON: {}", e))?
-        },
-        "pdf" => {
-            // PDF export would require additional dependencies
-            return Err("PDF export not yet implemented".to_string());
-        },
-        _ => return Err(format!("Unsupported export format: {}", format)),
-    };
+    let output = format_audit_export(&events, format)
+        .map_err(|e| format!("Failed to format audit export: {}", e))?;
 
     // TODO: Actually save to file and return file path
-    info!("Exported {} audit events in {} format", event_count, format);
+    info!("Exported {} audit events in {:?} format", events.len(), format);
     Ok(output)
 }
 
+/// Verify the audit log hash chain and report the first entry where it breaks
+#[command]
+pub async fn verify_audit_chain(
+    token: String,
+    session_state: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<AuditChainVerification, String> {
+    // Validate session
+    let session_manager = session_state.lock().unwrap();
+    let session = match session_manager.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err(format!("Session validation failed: {}", e)),
+    };
+    drop(session_manager);
+
+    authorize_command(&command_policy, &session, "verify_audit_chain").map_err(|e| e.to_string())?;
+
+    // Get database connection
+    let db_guard = db_state.lock().unwrap();
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let audit_repo = SqliteAuditRepository::new(db.get_connection());
+    let verification = audit_repo.verify_audit_chain()
+        .map_err(|e| format!("Failed to verify audit chain: {}", e))?;
+
+    match verification.first_broken_event_id {
+        Some(event_id) => warn!("Audit chain verification by {} found a break at event {}", session.username, event_id),
+        None => info!("Audit chain verified intact across {} events by {}", verification.entries_checked, session.username),
+    }
+    Ok(verification)
+}
+
 /// Get security metrics for dashboard
 #[command]
 pub async fn get_security_metrics(
@@ -86,6 +86,75 @@ pub async fn update_user_settings(
     }
 }
 
+#[tauri::command]
+pub async fn get_user_ui_state(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<serde_json::Value, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(_) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let settings_repo = SqliteUserSettingsRepository::new(db.get_connection());
+
+            // Users without stored UI state start from an empty document
+            settings_repo.get_user_ui_state(session.user_id)
+                .map(|state| state.unwrap_or_else(|| serde_json::json!({})))
+                .map_err(|e| format!("Failed to get UI state: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_user_ui_state(
+    token: String,
+    state: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<serde_json::Value, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(_) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let settings_repo = SqliteUserSettingsRepository::new(db.get_connection());
+
+            match settings_repo.set_user_ui_state(session.user_id, &state) {
+                Ok(value) => {
+                    info!("UI state updated by {}", session.username);
+                    Ok(value)
+                }
+                Err(e) => {
+                    error!("Failed to save UI state for {}: {}", session.username, e);
+                    Err(format!("Failed to save UI state: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_retry_presets() -> Result<std::collections::HashMap<String, RetryPreferences>, String> {
     let mut presets = std::collections::HashMap::new();
@@ -1,11 +1,19 @@
+use crate::audit::subscriptions::{AssetEvent, AssetEventType, emit_asset_event_to_user};
+use crate::configurations::{ConfigurationRepository, SqliteConfigurationRepository};
 use crate::database::Database;
-use std::sync::Mutex;
+use crate::vault::{SqliteVaultRepository, VaultRepository};
+use crate::validation::{RateLimiter, RateLimitPolicyRepository, SqliteRateLimitPolicyRepository};
+use crate::auth::{CommandPolicy, CommandPolicyRepository, SqliteCommandPolicyRepository};
+use std::sync::{Mutex, RwLock};
 use std::path::Path;
+use std::time::Duration;
 use tauri::{AppHandle, Manager, State};
-use tracing::info;
+use tracing::{error, info};
 use serde::Serialize;
 
 type DatabaseState = Mutex<Option<Database>>;
+type RateLimiterState = Mutex<RateLimiter>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 
 #[derive(Serialize)]
 pub struct FileInfo {
@@ -22,7 +30,12 @@ pub fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-pub async fn initialize_database(app: AppHandle, db_state: State<'_, DatabaseState>) -> Result<bool, String> {
+pub async fn initialize_database(
+    app: AppHandle,
+    db_state: State<'_, DatabaseState>,
+    rate_limiter: State<'_, RateLimiterState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<bool, String> {
     info!("Initializing database...");
     
     let mut db_guard = db_state.lock()
@@ -40,15 +53,95 @@ pub async fn initialize_database(app: AppHandle, db_state: State<'_, DatabaseSta
     let db_path = app_data_dir.join("ferrocodex.db");
     info!("Database path: {:?}", db_path);
     info!("Database exists before creation: {}", db_path.exists());
+
+    crate::vault::master_key::load_key_encryption_key()
+        .map_err(|e| format!("Failed to load the vault key-encryption key: {}", e))?;
     
     let db = Database::new(db_path)
         .map_err(|e| format!("Failed to create database: {}", e))?;
+
+    let policies = SqliteRateLimitPolicyRepository::new(db.get_connection()).list_rate_limit_policies()
+        .map_err(|e| format!("Failed to load rate limit policies: {}", e))?;
+    rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?
+        .set_policies(&policies);
+
+    let policy = SqliteCommandPolicyRepository::new(db.get_connection()).load_command_policy()
+        .map_err(|e| format!("Failed to load command policy: {}", e))?;
+    *command_policy.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
     
     *db_guard = Some(db);
     info!("Database initialized successfully");
+
+    start_access_log_retention_task(app.clone());
+    start_approval_escalation_task(app);
     Ok(true)
 }
 
+/// Checks hourly whether the access log retention policy is due and prunes when it is.
+/// The policy itself decides the retention window and run interval.
+fn start_access_log_retention_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut check_interval = tokio::time::interval(Duration::from_secs(3600));
+
+        loop {
+            check_interval.tick().await;
+
+            let db_state = app.state::<DatabaseState>();
+            let Ok(db_guard) = db_state.lock() else {
+                error!("Failed to acquire database lock for access log retention");
+                continue;
+            };
+            let Some(db) = db_guard.as_ref() else {
+                continue;
+            };
+
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+            match vault_repo.run_scheduled_access_log_prune() {
+                Ok(Some(result)) => info!("Scheduled access log prune removed {} entries", result.deleted),
+                Ok(None) => {}
+                Err(e) => error!("Scheduled access log prune failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Checks hourly for Approved versions past the escalation threshold and notifies the
+/// escalation target of each one. The policy decides the threshold and the target.
+fn start_approval_escalation_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut check_interval = tokio::time::interval(Duration::from_secs(3600));
+
+        loop {
+            check_interval.tick().await;
+
+            let db_state = app.state::<DatabaseState>();
+            let Ok(db_guard) = db_state.lock() else {
+                error!("Failed to acquire database lock for approval escalation");
+                continue;
+            };
+            let Some(db) = db_guard.as_ref() else {
+                continue;
+            };
+
+            let config_repo = SqliteConfigurationRepository::new(db.get_connection());
+            match config_repo.run_approval_escalations() {
+                Ok(escalations) => {
+                    for escalation in escalations {
+                        emit_asset_event_to_user(&app, escalation.escalated_to, AssetEvent::new(
+                            escalation.asset_id,
+                            AssetEventType::ApprovalEscalated,
+                            None,
+                            format!("Approved version {} is awaiting promotion to Golden", escalation.version_number),
+                        ));
+                    }
+                }
+                Err(e) => error!("Approval escalation check failed: {}", e),
+            }
+        }
+    });
+}
+
 #[tauri::command]
 pub async fn database_health_check(db_state: State<'_, DatabaseState>) -> Result<bool, String> {
     let db_guard = db_state.lock()
@@ -1,14 +1,15 @@
-use crate::auth::{SessionManager};
+use crate::auth::{SessionManager, SessionInfo, authorize_command, CommandPolicy, CommandPolicyRepository, SqliteCommandPolicyRepository, LoginAuditEntry, LoginAuditRepository, LoginAuditSettings, SqliteLoginAuditRepository};
 use crate::users::{UserRepository, SqliteUserRepository, UserRole, UserInfo};
-use crate::audit::{AuditRepository, SqliteAuditRepository, create_user_created_event, create_user_deactivated_event, create_user_reactivated_event};
-use crate::validation::{UsernameValidator, PasswordValidator, InputSanitizer, RateLimiter};
+use crate::audit::{AuditRepository, SqliteAuditRepository, create_user_created_event, create_user_deactivated_event, create_user_reactivated_event, create_session_revoked_event};
+use crate::validation::{UsernameValidator, PasswordValidator, InputSanitizer, RateLimiter, RateLimitPolicy, RateLimitPolicyRepository, SqliteRateLimitPolicyRepository};
 use crate::database::Database;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use tauri::State;
 use tracing::{error, info, warn};
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
 type RateLimiterState = Mutex<RateLimiter>;
 
 #[tauri::command]
@@ -18,6 +19,7 @@ pub async fn create_engineer_user(
     initial_password: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
     rate_limiter: State<'_, RateLimiterState>,
 ) -> Result<UserInfo, String> {
     // Check rate limiting
@@ -60,11 +62,7 @@ pub async fn create_engineer_user(
         }
     };
 
-    // Ensure user is an Administrator
-    if session.role != UserRole::Administrator {
-        warn!("Non-admin user attempted to create engineer account: {}", session.username);
-        return Err("Only administrators can create engineer accounts".to_string());
-    }
+    authorize_command(&command_policy, &session, "create_engineer_user").map_err(|e| e.to_string())?;
 
     drop(session_manager_guard);
 
@@ -106,6 +104,7 @@ pub async fn list_users(
     token: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
 ) -> Result<Vec<UserInfo>, String> {
     // Validate session and get user info
     let session_manager_guard = session_manager.lock()
@@ -119,11 +118,7 @@ pub async fn list_users(
         }
     };
 
-    // Ensure user is an Administrator
-    if session.role != UserRole::Administrator {
-        warn!("Non-admin user attempted to list users: {}", session.username);
-        return Err("Only administrators can list users".to_string());
-    }
+    authorize_command(&command_policy, &session, "list_users").map_err(|e| e.to_string())?;
 
     drop(session_manager_guard);
 
@@ -154,6 +149,7 @@ pub async fn deactivate_user(
     user_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
 ) -> Result<(), String> {
     // Validate session and get user info
     let session_manager_guard = session_manager.lock()
@@ -167,11 +163,7 @@ pub async fn deactivate_user(
         }
     };
 
-    // Ensure user is an Administrator
-    if session.role != UserRole::Administrator {
-        warn!("Non-admin user attempted to deactivate user: {}", session.username);
-        return Err("Only administrators can deactivate users".to_string());
-    }
+    authorize_command(&command_policy, &session, "deactivate_user").map_err(|e| e.to_string())?;
 
     // Prevent self-deactivation
     if session.user_id == user_id {
@@ -226,6 +218,7 @@ pub async fn reactivate_user(
     user_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
 ) -> Result<(), String> {
     // Validate session and get user info
     let session_manager_guard = session_manager.lock()
@@ -239,11 +232,7 @@ pub async fn reactivate_user(
         }
     };
 
-    // Ensure user is an Administrator
-    if session.role != UserRole::Administrator {
-        warn!("Non-admin user attempted to reactivate user: {}", session.username);
-        return Err("Only administrators can reactivate users".to_string());
-    }
+    authorize_command(&command_policy, &session, "reactivate_user").map_err(|e| e.to_string())?;
 
     drop(session_manager_guard);
 
@@ -285,4 +274,336 @@ pub async fn reactivate_user(
         }
         None => Err("Database not initialized".to_string()),
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn list_active_sessions(
+    token: String,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<SessionInfo>, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    authorize_command(&command_policy, &session, "list_active_sessions").map_err(|e| e.to_string())?;
+
+    Ok(session_manager_guard.list_active_sessions())
+}
+
+#[tauri::command]
+pub async fn revoke_session(
+    token: String,
+    session_id: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    authorize_command(&command_policy, &session, "revoke_session").map_err(|e| e.to_string())?;
+
+    // Administrators end their own session through logout
+    if session.session_id == session_id {
+        return Err("Administrators cannot revoke their own session".to_string());
+    }
+
+    let revoked = match session_manager_guard.revoke_session(&session_id) {
+        Ok(Some(revoked)) => revoked,
+        Ok(None) => return Err("Session not found".to_string()),
+        Err(e) => {
+            error!("Failed to revoke session: {}", e);
+            return Err(format!("Failed to revoke session: {}", e));
+        }
+    };
+
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    if let Some(db) = db_guard.as_ref() {
+        let audit_repo = SqliteAuditRepository::new(db.get_connection());
+        let audit_event = create_session_revoked_event(
+            session.user_id,
+            &session.username,
+            revoked.user_id,
+            &revoked.username,
+        );
+        if let Err(e) = audit_repo.log_event(&audit_event) {
+            error!("Failed to log audit event: {}", e);
+        }
+    }
+
+    info!("Session for user {} revoked by admin: {}", revoked.username, session.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_command_policy(
+    token: String,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<CommandPolicy, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_command_policy").map_err(|e| e.to_string())?;
+
+    Ok(command_policy.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone())
+}
+
+#[tauri::command]
+pub async fn set_command_minimum_role(
+    token: String,
+    command: String,
+    role: UserRole,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "set_command_minimum_role").map_err(|e| e.to_string())?;
+
+    let command = InputSanitizer::sanitize_string(&command);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            SqliteCommandPolicyRepository::new(db.get_connection())
+                .set_command_minimum_role(&command, &role)
+                .map_err(|e| e.to_string())?;
+
+            command_policy.write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .set_minimum_role(&command, role.clone())
+                .map_err(|e| e.to_string())?;
+
+            info!("Minimum role for {} set to {} by {}", command, role, session.username);
+            Ok(())
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_rate_limit_policies(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<RateLimitPolicy>, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_rate_limit_policies").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => SqliteRateLimitPolicyRepository::new(db.get_connection())
+            .list_rate_limit_policies()
+            .map_err(|e| format!("Failed to get rate limit policies: {}", e)),
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+/// Create or replace the limit for one command and role; omit the role to cover every caller
+#[tauri::command]
+pub async fn update_rate_limit_policy(
+    token: String,
+    policy: RateLimitPolicy,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+    rate_limiter: State<'_, RateLimiterState>,
+) -> Result<RateLimitPolicy, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "update_rate_limit_policy").map_err(|e| e.to_string())?;
+
+    let policy = RateLimitPolicy {
+        command_name: InputSanitizer::sanitize_string(&policy.command_name),
+        ..policy
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            SqliteRateLimitPolicyRepository::new(db.get_connection())
+                .upsert_rate_limit_policy(&policy)
+                .map_err(|e| format!("Failed to update rate limit policy: {}", e))?;
+
+            rate_limiter.lock()
+                .map_err(|_| "Failed to acquire rate limiter lock".to_string())?
+                .set_policy(&policy);
+
+            info!("Rate limit for {} ({}) set to {} per {}s by {}",
+                  policy.command_name,
+                  policy.user_role.as_ref().map_or_else(|| "all roles".to_string(), UserRole::to_string),
+                  policy.max_requests, policy.window_seconds, session.username);
+            Ok(policy)
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+/// Login history for a user, newest first; defaults to the caller. Only administrators can view
+/// another user's history.
+#[tauri::command]
+pub async fn get_login_history(
+    token: String,
+    user_id: Option<i64>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<LoginAuditEntry>, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let target_user_id = user_id.unwrap_or(session.user_id);
+    if target_user_id != session.user_id && session.role != UserRole::Administrator {
+        return Err("Insufficient permissions to view other users' login history".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => SqliteLoginAuditRepository::new(db.get_connection())
+            .get_login_history(target_user_id)
+            .map_err(|e| format!("Failed to get login history: {}", e)),
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_login_audit_settings(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<LoginAuditSettings, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "get_login_audit_settings").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => SqliteLoginAuditRepository::new(db.get_connection())
+            .get_login_audit_settings()
+            .map_err(|e| format!("Failed to get login audit settings: {}", e)),
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn update_login_audit_settings(
+    token: String,
+    settings: LoginAuditSettings,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<LoginAuditSettings, String> {
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "update_login_audit_settings").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            SqliteLoginAuditRepository::new(db.get_connection())
+                .update_login_audit_settings(&settings)
+                .map_err(|e| format!("Failed to update login audit settings: {}", e))?;
+
+            info!("Login audit {} by {}", if settings.enabled { "enabled" } else { "disabled" }, session.username);
+            Ok(settings)
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
@@ -1,34 +1,42 @@
 // Vault management commands
 // Extracted from lib.rs.backup
 
-use crate::auth::{SessionManager, verify_password};
+use crate::auth::{SessionManager, verify_password, authorize_command, CommandPolicy};
 use crate::users::{UserRepository, SqliteUserRepository, UserRole, UserInfo};
 use crate::audit::{AuditRepository, SqliteAuditRepository, AuditEventRequest, AuditEventType, create_vault_access_granted_event, create_vault_access_revoked_event};
-use crate::validation::{InputSanitizer};
+use crate::validation::{InputSanitizer, RateLimiter};
 use crate::vault::{
     VaultRepository, SqliteVaultRepository, CreateVaultRequest, AddSecretRequest, VaultInfo, 
     IdentityVault, GeneratePasswordRequest, UpdateCredentialPasswordRequest, UpdateVaultSecretRequest, 
-    DeleteVaultSecretRequest, PasswordStrength, PasswordHistory, PasswordGenerator, PasswordStrengthAnalyzer,
+    DeleteVaultSecretRequest, PasswordPolicy, PasswordStrength, PasswordHistory, PasswordGenerator, PasswordStrengthAnalyzer,
     CreateStandaloneCredentialRequest, UpdateStandaloneCredentialRequest, SearchCredentialsRequest, 
     CreateCategoryRequest, StandaloneCredentialInfo, CategoryWithChildren, VaultAccessControlService,
     PermissionType, VaultAccessInfo, GrantVaultAccessRequest, VaultPermission, RevokeVaultAccessRequest,
-    VaultAccessLog, CreatePermissionRequest, PermissionRequest,
+    VaultAccessLog, CreatePermissionRequest, PermissionRequest, ReusedSecretGroup, ReusedSecretLocation, IpConflictGroup,
+    DecryptFailure, DecryptFailureReason, DecryptAuditPolicy, ExportRedactionPolicy, VaultSecretConstraints,
+    AccessLogRetentionPolicy, AccessLogPruneResult, PasswordReusePolicy, VaultSecret, SecretOrder,
+    ComplianceReportFormat, CredentialExportFormat, TotpCode, SecretExportFormat, SecretExportTransform,
+    SecretValueExport, BulkSecretImportResult, TimedCredentialReveal, CategoryDeleteMode, KdbxExportSummary,
+    ExpiringSecret, SecretRevealWithStrength, VaultAccessLogQuery, VaultAccessLogPage, CredentialCsvImportReport,
     rotation::{
         PasswordRotationService, PasswordRotationRequest, RotationScheduler, RotationSchedule,
         RotationBatch, BatchRotationService, PasswordRotationHistory, CreateRotationBatchRequest,
-        UpdateRotationScheduleRequest, RotationAlert, CreateRotationScheduleRequest, BatchRotationRequest
+        UpdateRotationScheduleRequest, RotationAlert, CreateRotationScheduleRequest, BatchRotationRequest,
+        RotationReasonTemplate, CreateRotationReasonTemplateRequest, BatchThrottleSettings,
+        BatchRotationProgress, ComplianceAlertSettings, ComplianceAlert
     }
 };
-use crate::encryption::FileEncryption;
 use crate::database::Database;
 use rusqlite::Connection;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use tauri::{AppHandle, State};
 use tracing::{error, info, warn};
 use serde::{Serialize, Deserialize};
 
 type DatabaseState = Mutex<Option<Database>>;
 type SessionManagerState = Mutex<SessionManager>;
+type CommandPolicyState = RwLock<CommandPolicy>;
+type RateLimiterState = Mutex<RateLimiter>;
 
 #[tauri::command]
 pub async fn create_identity_vault(
@@ -130,6 +138,7 @@ pub async fn add_vault_secret(
                 label: sanitized_label,
                 value: sanitized_value,
                 author_id: session.user_id,
+                expires_at: secret_request.expires_at,
             };
 
             match vault_repo.add_secret(sanitized_request) {
@@ -191,6 +200,131 @@ pub async fn get_vault_by_asset_id(
     }
 }
 
+#[tauri::command]
+pub async fn get_vault_secrets(
+    token: String,
+    vault_id: i64,
+    order_by: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<VaultSecret>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let order_by = match order_by {
+        Some(order_by) => order_by.parse::<SecretOrder>().map_err(|e| e.to_string())?,
+        None => SecretOrder::CreatedAt,
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.get_vault_secrets_ordered(vault_id, order_by) {
+                Ok(secrets) => {
+                    info!("Secrets of vault {} listed by {} ordered by {}", vault_id, session.username, order_by);
+                    Ok(secrets)
+                }
+                Err(e) => {
+                    error!("Failed to get vault secrets for vault {}: {}", vault_id, e);
+                    Err(format!("Failed to get vault secrets: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn reorder_vault_secrets(
+    token: String,
+    vault_id: i64,
+    secret_order: Vec<(i64, Option<i64>)>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.reorder_vault_secrets(vault_id, secret_order) {
+                Ok(()) => {
+                    info!("Secrets of vault {} reordered by {}", vault_id, session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to reorder secrets of vault {}: {}", vault_id, e);
+                    Err(format!("Failed to reorder vault secrets: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn suggest_secret_labels(
+    token: String,
+    prefix: String,
+    limit: Option<usize>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<String>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+            vault_repo.suggest_secret_labels(&prefix, limit.unwrap_or(10))
+                .map_err(|e| {
+                    error!("Failed to suggest secret labels: {}", e);
+                    format!("Failed to suggest secret labels: {}", e)
+                })
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_vault_history(
     token: String,
@@ -294,6 +428,7 @@ pub async fn decrypt_vault_secret(
     vault_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    rate_limiter: State<'_, RateLimiterState>,
 ) -> Result<String, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
@@ -308,50 +443,143 @@ pub async fn decrypt_vault_secret(
     };
     drop(session_manager_guard);
 
+    let rate_limiter_guard = rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?;
+    rate_limiter_guard.check_command_rate_limit("decrypt_vault_secret", Some(&session.role), &session.user_id.to_string())?;
+    drop(rate_limiter_guard);
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
             
-            match vault_repo.get_secret_by_id(secret_id) {
-                Ok(Some(secret)) => {
-                    // Decrypt the secret value
-                    let encryption = FileEncryption::new(&format!("vault_{}_{}", vault_id, session.user_id));
-                    use base64::{Engine as _, engine::general_purpose};
-                    match general_purpose::STANDARD.decode(&secret.encrypted_value) {
-                        Ok(encrypted_bytes) => {
-                            match encryption.decrypt(&encrypted_bytes) {
-                                Ok(decrypted_bytes) => {
-                                    match String::from_utf8(decrypted_bytes) {
-                                        Ok(decrypted_value) => {
-                                            info!("Secret decrypted for user {}: {} ({})", 
-                                                  session.username, secret.label, secret.secret_type.to_string());
-                                            Ok(decrypted_value)
-                                        }
-                                        Err(e) => {
-                                            error!("Failed to convert decrypted bytes to string: {}", e);
-                                            Err("Failed to decrypt secret".to_string())
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    error!("Failed to decrypt secret: {}", e);
-                                    Err("Failed to decrypt secret".to_string())
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to decode base64 encrypted value: {}", e);
-                            Err("Failed to decrypt secret".to_string())
-                        }
-                    }
+            match vault_repo.decrypt_secret_value(secret_id, vault_id, session.user_id) {
+                Ok(decrypted_value) => {
+                    info!("Secret {} decrypted for user {} in vault {}", secret_id, session.username, vault_id);
+                    Ok(decrypted_value.to_string())
                 }
-                Ok(None) => Err("Secret not found".to_string()),
-                Err(e) => {
-                    error!("Failed to get secret {}: {}", secret_id, e);
-                    Err(format!("Failed to get secret: {}", e))
+                Err(e) => Err(decrypt_error_message(e, secret_id, &session.username)),
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+/// Like `decrypt_vault_secret`, but a password also comes back with a strength assessment
+/// recomputed from its value, so the UI can prompt rotation of secrets that have become weak
+#[tauri::command]
+pub async fn decrypt_vault_secret_with_strength(
+    token: String,
+    secret_id: i64,
+    vault_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    rate_limiter: State<'_, RateLimiterState>,
+) -> Result<SecretRevealWithStrength, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Shares the decrypt bucket so the variant cannot be used to double the allowance
+    let rate_limiter_guard = rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?;
+    rate_limiter_guard.check_command_rate_limit("decrypt_vault_secret", Some(&session.role), &session.user_id.to_string())?;
+    drop(rate_limiter_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.reveal_secret_with_strength(secret_id, vault_id, session.user_id) {
+                Ok(reveal) => {
+                    info!("Secret {} decrypted with strength check for user {} in vault {}", secret_id, session.username, vault_id);
+                    Ok(reveal)
+                }
+                Err(e) => Err(decrypt_error_message(e, secret_id, &session.username)),
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+/// Message returned to the caller for a failed decrypt; details stay in the log
+fn decrypt_error_message(e: anyhow::Error, secret_id: i64, username: &str) -> String {
+    match e.downcast_ref::<DecryptFailure>() {
+        Some(failure) if failure.reason == DecryptFailureReason::NotFound => "Secret not found".to_string(),
+        Some(failure) if failure.reason == DecryptFailureReason::Expired => {
+            warn!("Expired secret {} requested by {}: {}", secret_id, username, failure.message);
+            "Secret has expired and must be rotated before use".to_string()
+        }
+        Some(failure) => {
+            warn!("Failed to decrypt secret {} for {} ({}): {}",
+                  secret_id, username, failure.reason, failure.message);
+            "Failed to decrypt secret".to_string()
+        }
+        None => {
+            error!("Failed to decrypt secret {}: {}", secret_id, e);
+            format!("Failed to decrypt secret: {}", e)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn generate_totp_code(
+    token: String,
+    secret_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<TotpCode, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.generate_totp_code(secret_id, session.user_id) {
+                Ok(code) => {
+                    info!("TOTP code generated from secret {} for user {}", secret_id, session.username);
+                    Ok(code)
                 }
+                Err(e) => match e.downcast_ref::<DecryptFailure>() {
+                    Some(failure) if failure.reason == DecryptFailureReason::NotFound => Err("Secret not found".to_string()),
+                    Some(failure) if failure.reason == DecryptFailureReason::Expired => {
+                        Err("Secret has expired and must be rotated before use".to_string())
+                    }
+                    Some(failure) => {
+                        warn!("Failed to decrypt TOTP seed {} for {} ({}): {}",
+                              secret_id, session.username, failure.reason, failure.message);
+                        Err("Failed to decrypt secret".to_string())
+                    }
+                    None => {
+                        error!("Failed to generate TOTP code from secret {}: {}", secret_id, e);
+                        Err(format!("Failed to generate TOTP code: {}", e))
+                    }
+                },
             }
         }
         None => Err("Database not initialized".to_string()),
@@ -364,6 +592,7 @@ pub async fn export_vault(
     vault_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    rate_limiter: State<'_, RateLimiterState>,
 ) -> Result<String, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
@@ -378,6 +607,11 @@ pub async fn export_vault(
     };
     drop(session_manager_guard);
 
+    let rate_limiter_guard = rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?;
+    rate_limiter_guard.check_command_rate_limit("export_vault", Some(&session.role), &session.user_id.to_string())?;
+    drop(rate_limiter_guard);
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
@@ -427,9 +661,10 @@ pub async fn export_vault(
 }
 
 #[tauri::command]
-pub async fn generate_secure_password(
+pub async fn export_vault_metadata(
     token: String,
-    request: GeneratePasswordRequest,
+    vault_id: i64,
+    db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
 ) -> Result<String, String> {
     // Validate session
@@ -445,24 +680,43 @@ pub async fn generate_secure_password(
     };
     drop(session_manager_guard);
 
-    match PasswordGenerator::generate(&request) {
-        Ok(password) => {
-            info!("Password generated by {}", session.username);
-            Ok(password)
-        }
-        Err(e) => {
-            error!("Failed to generate password: {}", e);
-            Err(format!("Failed to generate password: {}", e))
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.export_vault_metadata(vault_id) {
+                Ok(export) => match serde_json::to_string_pretty(&export) {
+                    Ok(json_string) => {
+                        info!("Vault {} metadata exported by {}: {} secrets (labels redacted: {})",
+                              vault_id, session.username, export.secrets.len(), export.labels_redacted);
+                        Ok(json_string)
+                    }
+                    Err(e) => {
+                        error!("Failed to serialize vault metadata: {}", e);
+                        Err("Failed to serialize vault metadata".to_string())
+                    }
+                },
+                Err(e) => {
+                    error!("Failed to export metadata for vault {}: {}", vault_id, e);
+                    Err(format!("Failed to export vault metadata: {}", e))
+                }
+            }
         }
+        None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn validate_password_strength(
+pub async fn export_policy_compliance_report(
     token: String,
-    password: String,
+    vault_id: Option<i64>,
+    format: String,
+    db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<PasswordStrength, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<u8>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -476,19 +730,39 @@ pub async fn validate_password_strength(
     };
     drop(session_manager_guard);
 
-    let strength = PasswordStrengthAnalyzer::analyze(&password);
-    info!("Password strength analyzed by {} - Score: {}", session.username, strength.score);
-    Ok(strength)
+    authorize_command(&command_policy, &session, "export_policy_compliance_report").map_err(|e| e.to_string())?;
+    let format = format.parse::<ComplianceReportFormat>().map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.export_policy_compliance_report(vault_id, format) {
+                Ok(report) => {
+                    info!("Password policy compliance report ({}, vault {:?}) exported by {}",
+                          format, vault_id, session.username);
+                    Ok(report)
+                }
+                Err(e) => {
+                    error!("Failed to export policy compliance report: {}", e);
+                    Err(format!("Failed to export policy compliance report: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
 }
 
 #[tauri::command]
-pub async fn check_password_reuse(
+pub async fn get_policy_drift(
     token: String,
-    password: String,
-    exclude_secret_id: Option<i64>,
+    vault_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<bool, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<VaultSecret>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -502,29 +776,23 @@ pub async fn check_password_reuse(
     };
     drop(session_manager_guard);
 
-    // Hash the password for comparison
-    let password_hash = match PasswordGenerator::hash_password(&password) {
-        Ok(hash) => hash,
-        Err(e) => {
-            error!("Failed to hash password for reuse check: {}", e);
-            return Err("Failed to process password".to_string());
-        }
-    };
+    authorize_command(&command_policy, &session, "get_policy_drift").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-            
-            match vault_repo.check_password_reuse(&password_hash, exclude_secret_id) {
-                Ok(is_reused) => {
-                    info!("Password reuse check by {}: {}", session.username, is_reused);
-                    Ok(is_reused)
+
+            match vault_repo.get_policy_drift(vault_id) {
+                Ok(secrets) => {
+                    info!("Password policy drift for vault {} checked by {}: {} secrets out of policy",
+                          vault_id, session.username, secrets.len());
+                    Ok(secrets)
                 }
                 Err(e) => {
-                    error!("Failed to check password reuse: {}", e);
-                    Err(format!("Failed to check password reuse: {}", e))
+                    error!("Failed to check password policy drift for vault {}: {}", vault_id, e);
+                    Err(format!("Failed to check password policy drift: {}", e))
                 }
             }
         }
@@ -533,12 +801,13 @@ pub async fn check_password_reuse(
 }
 
 #[tauri::command]
-pub async fn get_password_history(
+pub async fn get_effective_password_policy(
     token: String,
-    secret_id: i64,
+    vault_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<PasswordHistory>, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<PasswordPolicy, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -552,34 +821,29 @@ pub async fn get_password_history(
     };
     drop(session_manager_guard);
 
+    authorize_command(&command_policy, &session, "get_effective_password_policy").map_err(|e| e.to_string())?;
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-            
-            match vault_repo.get_password_history(secret_id) {
-                Ok(history) => {
-                    info!("Password history retrieved by {} for secret {}", session.username, secret_id);
-                    Ok(history)
-                }
-                Err(e) => {
-                    error!("Failed to get password history: {}", e);
-                    Err(format!("Failed to get password history: {}", e))
-                }
-            }
+            vault_repo.get_effective_password_policy(vault_id)
+                .map_err(|e| format!("Failed to get password policy: {}", e))
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn update_credential_password(
+pub async fn update_password_policy(
     token: String,
-    request: UpdateCredentialPasswordRequest,
+    vault_id: Option<i64>,
+    policy: PasswordPolicy,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<PasswordPolicy, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -593,52 +857,25 @@ pub async fn update_credential_password(
     };
     drop(session_manager_guard);
 
-    // Validate inputs
-    let sanitized_password = InputSanitizer::sanitize_string(&request.new_password);
-    if InputSanitizer::is_potentially_malicious(&sanitized_password) {
-        error!("Potentially malicious input detected in update_credential_password");
-        return Err("Invalid input detected".to_string());
-    }
-
-    // Analyze password strength
-    let strength = PasswordStrengthAnalyzer::analyze(&request.new_password);
-    
-    // Hash password for storage
-    let password_hash = match PasswordGenerator::hash_password(&request.new_password) {
-        Ok(hash) => hash,
-        Err(e) => {
-            error!("Failed to hash password: {}", e);
-            return Err("Failed to process password".to_string());
-        }
-    };
+    authorize_command(&command_policy, &session, "update_password_policy").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-            
-            // Check for password reuse
-            match vault_repo.check_password_reuse(&password_hash, Some(request.secret_id)) {
-                Ok(is_reused) => {
-                    if is_reused {
-                        return Err("Password has been used before on another credential".to_string());
+
+            match vault_repo.update_password_policy(vault_id, &policy) {
+                Ok(updated) => {
+                    match vault_id {
+                        Some(vault_id) => info!("Password policy for vault {} updated by {}", vault_id, session.username),
+                        None => info!("Default password policy updated by {}", session.username),
                     }
+                    Ok(updated)
                 }
                 Err(e) => {
-                    error!("Failed to check password reuse: {}", e);
-                    return Err("Failed to validate password uniqueness".to_string());
-                }
-            }
-            
-            match vault_repo.update_password(request, &password_hash, strength.score) {
-                Ok(()) => {
-                    info!("Password updated by {} for secret with strength score {}", session.username, strength.score);
-                    Ok(())
-                }
-                Err(e) => {
-                    error!("Failed to update password: {}", e);
-                    Err(format!("Failed to update password: {}", e))
+                    error!("Failed to update password policy: {}", e);
+                    Err(format!("Failed to update password policy: {}", e))
                 }
             }
         }
@@ -647,12 +884,133 @@ pub async fn update_credential_password(
 }
 
 #[tauri::command]
-pub async fn update_vault_secret(
+pub async fn get_export_redaction_policy(
     token: String,
-    request: UpdateVaultSecretRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<ExportRedactionPolicy, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_export_redaction_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_export_redaction_policy()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_export_redaction_policy(
+    token: String,
+    policy: ExportRedactionPolicy,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "update_export_redaction_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.update_export_redaction_policy(&policy)
+        .map_err(|e| e.to_string())?;
+
+    info!("Export redaction policy updated by {}", user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_export_transforms(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<SecretExportTransform>, String> {
+    // Validate session
+    {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+            .ok_or("Invalid session")?;
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_export_transforms()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_export_transform(
+    token: String,
+    transform: SecretExportTransform,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
 ) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "set_export_transform").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.set_export_transform(&transform)
+        .map_err(|e| e.to_string())?;
+
+    info!("Export transform for {} secrets updated by {}", transform.secret_type.to_string(), user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_vault_secret(
+    token: String,
+    secret_id: i64,
+    format: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<SecretValueExport, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -666,37 +1024,25 @@ pub async fn update_vault_secret(
     };
     drop(session_manager_guard);
 
-    // Validate inputs
-    if let Some(ref label) = request.label {
-        let sanitized_label = InputSanitizer::sanitize_string(label);
-        if InputSanitizer::is_potentially_malicious(&sanitized_label) {
-            error!("Potentially malicious input detected in update_vault_secret");
-            return Err("Invalid input detected".to_string());
-        }
-    }
-    
-    if let Some(ref value) = request.value {
-        let sanitized_value = InputSanitizer::sanitize_string(value);
-        if InputSanitizer::is_potentially_malicious(&sanitized_value) {
-            error!("Potentially malicious input detected in update_vault_secret");
-            return Err("Invalid input detected".to_string());
-        }
-    }
+    let format = format.as_deref()
+        .map(str::parse::<SecretExportFormat>)
+        .transpose()
+        .map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-            
-            match vault_repo.update_vault_secret(request) {
-                Ok(()) => {
-                    info!("Vault secret updated by {}", session.username);
-                    Ok(())
+
+            match vault_repo.export_secret_value(secret_id, session.user_id, format) {
+                Ok(export) => {
+                    info!("Secret {} exported by {} as {}", secret_id, session.username, export.format);
+                    Ok(export)
                 }
                 Err(e) => {
-                    error!("Failed to update vault secret: {}", e);
-                    Err(format!("Failed to update secret: {}", e))
+                    error!("Failed to export secret {}: {}", secret_id, e);
+                    Err(format!("Failed to export secret: {}", e))
                 }
             }
         }
@@ -705,12 +1051,19 @@ pub async fn update_vault_secret(
 }
 
 #[tauri::command]
-pub async fn delete_vault_secret(
+pub async fn export_vault_kdbx(
     token: String,
-    request: DeleteVaultSecretRequest,
+    vault_id: i64,
+    master_password: String,
+    output_path: String,
+    include_standalone_credentials: Option<bool>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+    command_policy: State<'_, CommandPolicyState>,
+    rate_limiter: State<'_, RateLimiterState>,
+) -> Result<KdbxExportSummary, String> {
+    let master_password = zeroize::Zeroizing::new(master_password);
+
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session manager lock".to_string())?;
@@ -724,20 +1077,50 @@ pub async fn delete_vault_secret(
     };
     drop(session_manager_guard);
 
+    // Standalone credentials are not covered by any vault permission, so including them
+    // takes the separate credential export policy
+    let include_standalone_credentials = include_standalone_credentials.unwrap_or(false);
+    if include_standalone_credentials {
+        authorize_command(&command_policy, &session, "export_standalone_credentials").map_err(|e| e.to_string())?;
+    }
+
+    // Shares the export bucket so the KDBX variant cannot be used to double the allowance
+    let rate_limiter_guard = rate_limiter.lock()
+        .map_err(|_| "Failed to acquire rate limiter lock".to_string())?;
+    rate_limiter_guard.check_command_rate_limit("export_vault", Some(&session.role), &session.user_id.to_string())?;
+    drop(rate_limiter_guard);
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
+            // Every value leaves the vault in one file, so this needs export permission
+            let user_repo = SqliteUserRepository::new(db.get_connection());
+            let user = user_repo.find_by_id(session.user_id)
+                .map_err(|e| e.to_string())?
+                .ok_or("User not found")?;
+            let access_control = VaultAccessControlService::new(db.get_connection());
+            if !access_control.can_export(&user, vault_id).map_err(|e| e.to_string())? {
+                warn!("User {} denied KDBX export of vault {}", session.username, vault_id);
+                return Err("Export permission required for this vault".to_string());
+            }
+
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-            
-            match vault_repo.delete_vault_secret(request) {
-                Ok(()) => {
-                    info!("Vault secret deleted by {}", session.username);
-                    Ok(())
+            match vault_repo.export_vault_kdbx(
+                vault_id,
+                session.user_id,
+                &master_password,
+                std::path::Path::new(&output_path),
+                include_standalone_credentials,
+            ) {
+                Ok(summary) => {
+                    info!("Vault {} exported to KDBX by {}: {} secrets, {} standalone credentials",
+                        vault_id, session.username, summary.secret_count, summary.standalone_credential_count);
+                    Ok(summary)
                 }
                 Err(e) => {
-                    error!("Failed to delete vault secret: {}", e);
-                    Err(format!("Failed to delete secret: {}", e))
+                    error!("Failed to export vault {} to KDBX: {}", vault_id, e);
+                    Err(format!("Failed to export vault to KDBX: {}", e))
                 }
             }
         }
@@ -745,18 +1128,17 @@ pub async fn delete_vault_secret(
     }
 }
 
-// Standalone credential commands for Story 4.3
-
 #[tauri::command]
-pub async fn create_standalone_credential(
+pub async fn import_secrets_from_dotenv(
     token: String,
-    credential_request: CreateStandaloneCredentialRequest,
+    vault_id: i64,
+    file_path: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<StandaloneCredentialInfo, String> {
+) -> Result<BulkSecretImportResult, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
@@ -765,56 +1147,27 @@ pub async fn create_standalone_credential(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
 
-    // Sanitize input
-    let sanitized_name = InputSanitizer::sanitize_string(&credential_request.name);
-    let sanitized_description = InputSanitizer::sanitize_string(&credential_request.description);
-    let sanitized_value = InputSanitizer::sanitize_string(&credential_request.value);
-    let sanitized_tags = credential_request.tags.as_ref().map(|tags| 
-        tags.iter().map(|tag| InputSanitizer::sanitize_string(tag)).collect()
+    let dotenv_bytes = zeroize::Zeroizing::new(
+        std::fs::read(&file_path).map_err(|e| format!("Failed to read dotenv file: {}", e))?
     );
 
-    // Check for malicious input
-    if InputSanitizer::is_potentially_malicious(&sanitized_name) ||
-       InputSanitizer::is_potentially_malicious(&sanitized_description) ||
-       InputSanitizer::is_potentially_malicious(&sanitized_value) {
-        error!("Potentially malicious input detected in create_standalone_credential");
-        return Err("Invalid input detected. Please avoid using special characters or script-like patterns.".to_string());
-    }
-
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
 
-            let sanitized_request = CreateStandaloneCredentialRequest {
-                name: sanitized_name,
-                description: sanitized_description,
-                credential_type: credential_request.credential_type,
-                category_id: credential_request.category_id,
-                value: sanitized_value,
-                tags: sanitized_tags,
-                created_by: session.user_id,
-            };
-
-            match vault_repo.create_standalone_credential(sanitized_request) {
-                Ok(credential) => {
-                    info!("Standalone credential created by {}: {}", session.username, credential.name);
-                    
-                    // Fetch the full credential info
-                    match vault_repo.get_standalone_credential(credential.id) {
-                        Ok(Some(credential_info)) => Ok(credential_info),
-                        Ok(None) => Err("Failed to retrieve created credential".to_string()),
-                        Err(e) => {
-                            error!("Failed to retrieve credential info: {}", e);
-                            Err(format!("Failed to retrieve credential info: {}", e))
-                        }
-                    }
+            match vault_repo.import_secrets_from_dotenv(vault_id, &dotenv_bytes, session.user_id) {
+                Ok(result) => {
+                    info!("Dotenv import into vault {} by {}: {} imported, {} failed",
+                          vault_id, session.username, result.imported, result.failed);
+                    Ok(result)
                 }
                 Err(e) => {
-                    error!("Failed to create standalone credential: {}", e);
-                    Err(format!("Failed to create standalone credential: {}", e))
+                    error!("Failed to import secrets from dotenv: {}", e);
+                    Err(format!("Failed to import secrets from dotenv: {}", e))
                 }
             }
         }
@@ -823,66 +1176,132 @@ pub async fn create_standalone_credential(
 }
 
 #[tauri::command]
-pub async fn search_credentials(
+pub async fn get_vault_constraints(
     token: String,
-    search_request: SearchCredentialsRequest,
+    vault_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<crate::vault::SearchCredentialsResponse, String> {
+) -> Result<VaultSecretConstraints, String> {
     // Validate session
-    let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let _session = match session_manager_guard.validate_session(&token) {
-        Ok(Some(session)) => session,
-        Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => {
-            error!("Session validation error: {}", e);
-            return Err("Session validation error".to_string());
-        }
-    };
+    {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+            .ok_or("Invalid session")?;
+    }
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    match db_guard.as_ref() {
-        Some(db) => {
-            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
 
-            // Sanitize search query if provided
-            let sanitized_request = SearchCredentialsRequest {
-                query: search_request.query.map(|q| InputSanitizer::sanitize_string(&q)),
-                credential_type: search_request.credential_type,
-                category_id: search_request.category_id,
-                tags: search_request.tags.map(|tags| 
-                    tags.iter().map(|tag| InputSanitizer::sanitize_string(tag)).collect()
-                ),
-                created_after: search_request.created_after,
-                created_before: search_request.created_before,
-                limit: search_request.limit,
-                offset: search_request.offset,
-            };
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_vault_constraints(vault_id)
+        .map_err(|e| e.to_string())
+}
 
-            match vault_repo.search_standalone_credentials(sanitized_request) {
-                Ok(response) => Ok(response),
-                Err(e) => {
-                    error!("Failed to search credentials: {}", e);
-                    Err(format!("Failed to search credentials: {}", e))
-                }
-            }
+#[tauri::command]
+pub async fn set_vault_constraints(
+    token: String,
+    vault_id: i64,
+    constraints: VaultSecretConstraints,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "set_vault_constraints").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.set_vault_constraints(vault_id, &constraints)
+        .map_err(|e| e.to_string())?;
+
+    info!("Secret constraints for vault {} updated by {}", vault_id, user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn generate_secure_password(
+    token: String,
+    request: GeneratePasswordRequest,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<String, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    match PasswordGenerator::generate(&request) {
+        Ok(password) => {
+            info!("Password generated by {}", session.username);
+            Ok(password)
+        }
+        Err(e) => {
+            error!("Failed to generate password: {}", e);
+            Err(format!("Failed to generate password: {}", e))
         }
-        None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn get_credential_categories(
+pub async fn validate_password_strength(
+    token: String,
+    password: String,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<PasswordStrength, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let strength = PasswordStrengthAnalyzer::analyze(&password);
+    info!("Password strength analyzed by {} - Score: {}", session.username, strength.score);
+    Ok(strength)
+}
+
+#[tauri::command]
+pub async fn check_password_reuse(
     token: String,
+    password: String,
+    exclude_secret_id: Option<i64>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<CategoryWithChildren>, String> {
+) -> Result<bool, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let _session = match session_manager_guard.validate_session(&token) {
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
         Err(e) => {
@@ -890,18 +1309,22 @@ pub async fn get_credential_categories(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match vault_repo.get_credential_categories() {
-                Ok(categories) => Ok(categories),
+            
+            match vault_repo.check_password_reuse(&password, exclude_secret_id) {
+                Ok(is_reused) => {
+                    info!("Password reuse check by {}: {}", session.username, is_reused);
+                    Ok(is_reused)
+                }
                 Err(e) => {
-                    error!("Failed to get credential categories: {}", e);
-                    Err(format!("Failed to get credential categories: {}", e))
+                    error!("Failed to check password reuse: {}", e);
+                    Err(format!("Failed to check password reuse: {}", e))
                 }
             }
         }
@@ -910,18 +1333,78 @@ pub async fn get_credential_categories(
 }
 
 #[tauri::command]
-pub async fn manage_credential_categories(
+pub async fn get_password_reuse_policy(
     token: String,
-    action: String,
-    category_id: Option<i64>,
-    category_request: Option<CreateCategoryRequest>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<String, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<PasswordReusePolicy, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_password_reuse_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_password_reuse_policy()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_password_reuse_policy(
+    token: String,
+    policy: PasswordReusePolicy,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "update_password_reuse_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.update_password_reuse_policy(&policy)
+        .map_err(|e| e.to_string())?;
+
+    info!("Password reuse policy updated by {}", user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn find_reused_secret_values(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<ReusedSecretGroup>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let _session = match session_manager_guard.validate_session(&token) {
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
         Err(e) => {
@@ -929,61 +1412,25 @@ pub async fn manage_credential_categories(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "find_reused_secret_values").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match action.as_str() {
-                "create" => {
-                    if let Some(request) = category_request {
-                        let sanitized_request = CreateCategoryRequest {
-                            name: InputSanitizer::sanitize_string(&request.name),
-                            description: request.description.map(|d| InputSanitizer::sanitize_string(&d)),
-                            parent_category_id: request.parent_category_id,
-                            color_code: request.color_code,
-                            icon: request.icon,
-                        };
-
-                        match vault_repo.create_credential_category(sanitized_request) {
-                            Ok(category) => Ok(format!("Created category: {}", category.name)),
-                            Err(e) => Err(format!("Failed to create category: {}", e)),
-                        }
-                    } else {
-                        Err("Category request data required for create action".to_string())
-                    }
-                }
-                "update" => {
-                    if let (Some(cat_id), Some(request)) = (category_id, category_request) {
-                        let sanitized_request = CreateCategoryRequest {
-                            name: InputSanitizer::sanitize_string(&request.name),
-                            description: request.description.map(|d| InputSanitizer::sanitize_string(&d)),
-                            parent_category_id: request.parent_category_id,
-                            color_code: request.color_code,
-                            icon: request.icon,
-                        };
-
-                        match vault_repo.update_credential_category(cat_id, sanitized_request) {
-                            Ok(_) => Ok(format!("Updated category ID: {}", cat_id)),
-                            Err(e) => Err(format!("Failed to update category: {}", e)),
-                        }
-                    } else {
-                        Err("Category ID and request data required for update action".to_string())
-                    }
+            
+            match vault_repo.find_reused_secret_values() {
+                Ok(groups) => {
+                    info!("Password reuse scan by {}: {} shared values found", session.username, groups.len());
+                    Ok(groups)
                 }
-                "delete" => {
-                    if let Some(cat_id) = category_id {
-                        match vault_repo.delete_credential_category(cat_id) {
-                            Ok(_) => Ok(format!("Deleted category ID: {}", cat_id)),
-                            Err(e) => Err(format!("Failed to delete category: {}", e)),
-                        }
-                    } else {
-                        Err("Category ID required for delete action".to_string())
-                    }
+                Err(e) => {
+                    error!("Failed to scan for reused passwords: {}", e);
+                    Err(format!("Failed to scan for reused passwords: {}", e))
                 }
-                _ => Err("Invalid action. Must be 'create', 'update', or 'delete'".to_string()),
             }
         }
         None => Err("Database not initialized".to_string()),
@@ -991,16 +1438,17 @@ pub async fn manage_credential_categories(
 }
 
 #[tauri::command]
-pub async fn get_credential_history(
+pub async fn find_secrets_by_ip(
     token: String,
-    credential_id: i64,
+    ip_address: String,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<crate::vault::StandaloneCredentialHistory>, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<ReusedSecretLocation>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let _session = match session_manager_guard.validate_session(&token) {
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
         Err(e) => {
@@ -1008,21 +1456,24 @@ pub async fn get_credential_history(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "find_secrets_by_ip").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match vault_repo.get_standalone_credential_history(credential_id) {
-                Ok(history) => {
-                    info!("Retrieved history for credential {}", credential_id);
-                    Ok(history)
+            
+            match vault_repo.find_secrets_by_ip(&ip_address) {
+                Ok(secrets) => {
+                    info!("IP address lookup by {}: {} secrets found", session.username, secrets.len());
+                    Ok(secrets)
                 }
                 Err(e) => {
-                    error!("Failed to get credential history: {}", e);
-                    Err(format!("Failed to get credential history: {}", e))
+                    error!("Failed to look up secrets by IP address: {}", e);
+                    Err(format!("Failed to look up secrets by IP address: {}", e))
                 }
             }
         }
@@ -1031,15 +1482,15 @@ pub async fn get_credential_history(
 }
 
 #[tauri::command]
-pub async fn update_standalone_credential(
+pub async fn find_ip_conflicts(
     token: String,
-    update_request: UpdateStandaloneCredentialRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<String, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<IpConflictGroup>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
@@ -1048,30 +1499,24 @@ pub async fn update_standalone_credential(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "find_ip_conflicts").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            let sanitized_request = UpdateStandaloneCredentialRequest {
-                id: update_request.id,
-                name: update_request.name.map(|n| InputSanitizer::sanitize_string(&n)),
-                description: update_request.description.map(|d| InputSanitizer::sanitize_string(&d)),
-                category_id: update_request.category_id,
-                value: update_request.value.map(|v| InputSanitizer::sanitize_string(&v)),
-                author_id: session.user_id,
-            };
-
-            match vault_repo.update_standalone_credential(sanitized_request) {
-                Ok(_) => {
-                    info!("Updated standalone credential {} by {}", update_request.id, session.username);
-                    Ok("Credential updated successfully".to_string())
+            
+            match vault_repo.find_ip_conflicts() {
+                Ok(conflicts) => {
+                    info!("IP conflict scan by {}: {} shared addresses found", session.username, conflicts.len());
+                    Ok(conflicts)
                 }
                 Err(e) => {
-                    error!("Failed to update standalone credential: {}", e);
-                    Err(format!("Failed to update credential: {}", e))
+                    error!("Failed to scan for IP address conflicts: {}", e);
+                    Err(format!("Failed to scan for IP address conflicts: {}", e))
                 }
             }
         }
@@ -1080,15 +1525,15 @@ pub async fn update_standalone_credential(
 }
 
 #[tauri::command]
-pub async fn delete_standalone_credential(
+pub async fn rebuild_ip_index(
     token: String,
-    credential_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<String, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<usize, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
@@ -1097,21 +1542,24 @@ pub async fn delete_standalone_credential(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "rebuild_ip_index").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match vault_repo.delete_standalone_credential(credential_id, session.user_id) {
-                Ok(_) => {
-                    info!("Deleted standalone credential {} by {}", credential_id, session.username);
-                    Ok("Credential deleted successfully".to_string())
+            
+            match vault_repo.rebuild_ip_index() {
+                Ok(indexed) => {
+                    info!("IP address search index rebuilt by {}: {} secrets indexed", session.username, indexed);
+                    Ok(indexed)
                 }
                 Err(e) => {
-                    error!("Failed to delete standalone credential: {}", e);
-                    Err(format!("Failed to delete credential: {}", e))
+                    error!("Failed to rebuild IP address index: {}", e);
+                    Err(format!("Failed to rebuild IP address index: {}", e))
                 }
             }
         }
@@ -1120,16 +1568,16 @@ pub async fn delete_standalone_credential(
 }
 
 #[tauri::command]
-pub async fn get_standalone_credential(
+pub async fn get_password_history(
     token: String,
-    credential_id: i64,
+    secret_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<StandaloneCredentialInfo, String> {
+) -> Result<Vec<PasswordHistory>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let _session = match session_manager_guard.validate_session(&token) {
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
         Err(e) => {
@@ -1137,19 +1585,22 @@ pub async fn get_standalone_credential(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match vault_repo.get_standalone_credential(credential_id) {
-                Ok(Some(credential_info)) => Ok(credential_info),
-                Ok(None) => Err("Credential not found".to_string()),
+            
+            match vault_repo.get_password_history(secret_id) {
+                Ok(history) => {
+                    info!("Password history retrieved by {} for secret {}", session.username, secret_id);
+                    Ok(history)
+                }
                 Err(e) => {
-                    error!("Failed to get standalone credential: {}", e);
-                    Err(format!("Failed to get credential: {}", e))
+                    error!("Failed to get password history: {}", e);
+                    Err(format!("Failed to get password history: {}", e))
                 }
             }
         }
@@ -1158,15 +1609,16 @@ pub async fn get_standalone_credential(
 }
 
 #[tauri::command]
-pub async fn decrypt_standalone_credential(
+pub async fn dedupe_password_history(
     token: String,
-    credential_id: i64,
+    secret_id: i64,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<String, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<usize, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
@@ -1175,35 +1627,24 @@ pub async fn decrypt_standalone_credential(
             return Err("Session validation error".to_string());
         }
     };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "dedupe_password_history").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
             let vault_repo = SqliteVaultRepository::new(db.get_connection());
-
-            match vault_repo.get_standalone_credential(credential_id) {
-                Ok(Some(credential_info)) => {
-                    // Decrypt the credential value
-                    let encryption = FileEncryption::new(&format!("standalone_{}_{}", credential_id, session.user_id));
-                    use base64::{Engine as _, engine::general_purpose};
-                    
-                    let encrypted_bytes = general_purpose::STANDARD.decode(&credential_info.credential.encrypted_data)
-                        .map_err(|e| format!("Failed to decode encrypted data: {}", e))?;
-                    
-                    let decrypted_bytes = encryption.decrypt(&encrypted_bytes)
-                        .map_err(|e| format!("Failed to decrypt credential: {}", e))?;
-                    
-                    let decrypted_value = String::from_utf8(decrypted_bytes)
-                        .map_err(|e| format!("Failed to convert decrypted data to string: {}", e))?;
-                    
-                    info!("Decrypted standalone credential {} for user {}", credential_id, session.username);
-                    Ok(decrypted_value)
+            
+            match vault_repo.dedupe_password_history(secret_id) {
+                Ok(removed) => {
+                    info!("Password history for secret {} deduplicated by {}: {} entries removed", secret_id, session.username, removed);
+                    Ok(removed)
                 }
-                Ok(None) => Err("Credential not found".to_string()),
                 Err(e) => {
-                    error!("Failed to get standalone credential: {}", e);
-                    Err(format!("Failed to get credential: {}", e))
+                    error!("Failed to deduplicate password history: {}", e);
+                    Err(format!("Failed to deduplicate password history: {}", e))
                 }
             }
         }
@@ -1211,18 +1652,16 @@ pub async fn decrypt_standalone_credential(
     }
 }
 
-// Password Rotation Commands for Story 4.6
-
 #[tauri::command]
-pub async fn rotate_password(
+pub async fn update_credential_password(
     token: String,
-    request: PasswordRotationRequest,
+    request: UpdateCredentialPasswordRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
 ) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
@@ -1233,597 +1672,2170 @@ pub async fn rotate_password(
     };
     drop(session_manager_guard);
 
-    // Check permissions - Engineers can rotate passwords
-    if session.role != UserRole::Administrator && session.role != UserRole::Engineer {
-        return Err("Insufficient permissions to rotate passwords".to_string());
+    // Validate inputs
+    let sanitized_password = InputSanitizer::sanitize_string(&request.new_password);
+    if InputSanitizer::is_potentially_malicious(&sanitized_password) {
+        error!("Potentially malicious input detected in update_credential_password");
+        return Err("Invalid input detected".to_string());
     }
 
+    // Analyze password strength
+    let strength = PasswordStrengthAnalyzer::analyze(&request.new_password);
+    
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
-            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
-            
-            let rotation_service = PasswordRotationService::new(conn, vault_repo, audit_repo);
-            
-            // Validate rotation before executing
-            rotation_service.validate_rotation(request.secret_id, &request.new_password)
-                .map_err(|e| e.to_string())?;
-            
-            // Execute rotation with author_id set to session user
-            let mut rotation_request = request;
-            rotation_request.author_id = session.user_id;
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
             
-            rotation_service.rotate_password(rotation_request)
-                .map_err(|e| {
-                    error!("Failed to rotate password: {}", e);
-                    format!("Failed to rotate password: {}", e)
-                })?;
+            // Check for password reuse
+            match vault_repo.check_password_reuse(&request.new_password, Some(request.secret_id)) {
+                Ok(is_reused) => {
+                    if is_reused {
+                        return Err("Password has been used before on another credential".to_string());
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to check password reuse: {}", e);
+                    return Err("Failed to validate password uniqueness".to_string());
+                }
+            }
             
-            info!("Password rotated successfully by user {}", session.username);
-            Ok(())
+            match vault_repo.update_password(request, strength.score) {
+                Ok(()) => {
+                    info!("Password updated by {} for secret with strength score {}", session.username, strength.score);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to update password: {}", e);
+                    Err(format!("Failed to update password: {}", e))
+                }
+            }
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn get_rotation_schedule(
+pub async fn update_vault_secret(
     token: String,
-    vault_id: i64,
+    request: UpdateVaultSecretRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Option<RotationSchedule>, String> {
+) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
+    // Validate inputs
+    if let Some(ref label) = request.label {
+        let sanitized_label = InputSanitizer::sanitize_string(label);
+        if InputSanitizer::is_potentially_malicious(&sanitized_label) {
+            error!("Potentially malicious input detected in update_vault_secret");
+            return Err("Invalid input detected".to_string());
+        }
+    }
+    
+    if let Some(ref value) = request.value {
+        let sanitized_value = InputSanitizer::sanitize_string(value);
+        if InputSanitizer::is_potentially_malicious(&sanitized_value) {
+            error!("Potentially malicious input detected in update_vault_secret");
+            return Err("Invalid input detected".to_string());
+        }
+    }
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let scheduler = RotationScheduler::new(conn);
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
             
-            scheduler.get_active_schedule(vault_id)
-                .map_err(|e| format!("Failed to get rotation schedule: {}", e))
+            match vault_repo.update_vault_secret(request) {
+                Ok(()) => {
+                    info!("Vault secret updated by {}", session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to update vault secret: {}", e);
+                    Err(format!("Failed to update secret: {}", e))
+                }
+            }
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn create_rotation_batch(
+pub async fn get_expiring_secrets(
     token: String,
-    request: CreateRotationBatchRequest,
+    within_days: u32,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<RotationBatch, String> {
+) -> Result<Vec<ExpiringSecret>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let session = match session_manager_guard.validate_session(&token) {
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
-    // Check permissions
-    if session.role != UserRole::Administrator && session.role != UserRole::Engineer {
-        return Err("Insufficient permissions to create rotation batch".to_string());
-    }
-
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
-            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
-            
-            let batch_service = BatchRotationService::new(conn, vault_repo, audit_repo);
-            
-            // Set created_by to session user
-            let mut batch_request = request;
-            batch_request.created_by = session.user_id;
-            
-            batch_service.create_batch(batch_request)
-                .map_err(|e| format!("Failed to create rotation batch: {}", e))
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+            vault_repo.get_expiring_secrets(within_days)
+                .map_err(|e| format!("Failed to get expiring secrets: {}", e))
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn get_rotation_history(
+pub async fn delete_vault_secret(
     token: String,
-    secret_id: i64,
+    request: DeleteVaultSecretRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<PasswordRotationHistory>, String> {
+) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
-            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
-            
-            let rotation_service = PasswordRotationService::new(conn, vault_repo, audit_repo);
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
             
-            rotation_service.get_rotation_history(secret_id)
-                .map_err(|e| format!("Failed to get rotation history: {}", e))
+            match vault_repo.delete_vault_secret(request) {
+                Ok(()) => {
+                    info!("Vault secret deleted by {}", session.username);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to delete vault secret: {}", e);
+                    Err(format!("Failed to delete secret: {}", e))
+                }
+            }
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
-// Vault permission commands for Story 4.5
+// Standalone credential commands for Story 4.3
 
 #[tauri::command]
-pub async fn check_vault_access(
+pub async fn create_standalone_credential(
+    token: String,
+    credential_request: CreateStandaloneCredentialRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<StandaloneCredentialInfo, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    // Sanitize input
+    let sanitized_name = InputSanitizer::sanitize_string(&credential_request.name);
+    let sanitized_description = InputSanitizer::sanitize_string(&credential_request.description);
+    let sanitized_value = InputSanitizer::sanitize_string(&credential_request.value);
+    let sanitized_tags = credential_request.tags.as_ref().map(|tags| 
+        tags.iter().map(|tag| InputSanitizer::sanitize_string(tag)).collect()
+    );
+
+    // Check for malicious input
+    if InputSanitizer::is_potentially_malicious(&sanitized_name) ||
+       InputSanitizer::is_potentially_malicious(&sanitized_description) ||
+       InputSanitizer::is_potentially_malicious(&sanitized_value) {
+        error!("Potentially malicious input detected in create_standalone_credential");
+        return Err("Invalid input detected. Please avoid using special characters or script-like patterns.".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            let sanitized_request = CreateStandaloneCredentialRequest {
+                name: sanitized_name,
+                description: sanitized_description,
+                credential_type: credential_request.credential_type,
+                category_id: credential_request.category_id,
+                value: sanitized_value,
+                tags: sanitized_tags,
+                created_by: session.user_id,
+            };
+
+            match vault_repo.create_standalone_credential(sanitized_request) {
+                Ok(credential) => {
+                    info!("Standalone credential created by {}: {}", session.username, credential.name);
+                    
+                    // Fetch the full credential info
+                    match vault_repo.get_standalone_credential(credential.id) {
+                        Ok(Some(credential_info)) => Ok(credential_info),
+                        Ok(None) => Err("Failed to retrieve created credential".to_string()),
+                        Err(e) => {
+                            error!("Failed to retrieve credential info: {}", e);
+                            Err(format!("Failed to retrieve credential info: {}", e))
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to create standalone credential: {}", e);
+                    Err(format!("Failed to create standalone credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn search_credentials(
+    token: String,
+    search_request: SearchCredentialsRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<crate::vault::SearchCredentialsResponse, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            // Sanitize search query if provided
+            let sanitized_request = SearchCredentialsRequest {
+                query: search_request.query.map(|q| InputSanitizer::sanitize_string(&q)),
+                credential_type: search_request.credential_type,
+                category_id: search_request.category_id,
+                tags: search_request.tags.map(|tags| 
+                    tags.iter().map(|tag| InputSanitizer::sanitize_string(tag)).collect()
+                ),
+                created_after: search_request.created_after,
+                created_before: search_request.created_before,
+                limit: search_request.limit,
+                offset: search_request.offset,
+            };
+
+            match vault_repo.search_standalone_credentials(sanitized_request) {
+                Ok(response) => Ok(response),
+                Err(e) => {
+                    error!("Failed to search credentials: {}", e);
+                    Err(format!("Failed to search credentials: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_credential_categories(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<CategoryWithChildren>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.get_credential_categories() {
+                Ok(categories) => Ok(categories),
+                Err(e) => {
+                    error!("Failed to get credential categories: {}", e);
+                    Err(format!("Failed to get credential categories: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn manage_credential_categories(
+    token: String,
+    action: String,
+    category_id: Option<i64>,
+    category_request: Option<CreateCategoryRequest>,
+    delete_mode: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<String, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match action.as_str() {
+                "create" => {
+                    if let Some(request) = category_request {
+                        let sanitized_request = CreateCategoryRequest {
+                            name: InputSanitizer::sanitize_string(&request.name),
+                            description: request.description.map(|d| InputSanitizer::sanitize_string(&d)),
+                            parent_category_id: request.parent_category_id,
+                            color_code: request.color_code,
+                            icon: request.icon,
+                        };
+
+                        match vault_repo.create_credential_category(sanitized_request) {
+                            Ok(category) => Ok(format!("Created category: {}", category.name)),
+                            Err(e) => Err(format!("Failed to create category: {}", e)),
+                        }
+                    } else {
+                        Err("Category request data required for create action".to_string())
+                    }
+                }
+                "update" => {
+                    if let (Some(cat_id), Some(request)) = (category_id, category_request) {
+                        let sanitized_request = CreateCategoryRequest {
+                            name: InputSanitizer::sanitize_string(&request.name),
+                            description: request.description.map(|d| InputSanitizer::sanitize_string(&d)),
+                            parent_category_id: request.parent_category_id,
+                            color_code: request.color_code,
+                            icon: request.icon,
+                        };
+
+                        match vault_repo.update_credential_category(cat_id, sanitized_request) {
+                            Ok(_) => Ok(format!("Updated category ID: {}", cat_id)),
+                            Err(e) => Err(format!("Failed to update category: {}", e)),
+                        }
+                    } else {
+                        Err("Category ID and request data required for update action".to_string())
+                    }
+                }
+                "delete" => {
+                    if let Some(cat_id) = category_id {
+                        let mode = match delete_mode.as_deref() {
+                            Some(mode) => mode.parse::<CategoryDeleteMode>().map_err(|e| e.to_string())?,
+                            None => CategoryDeleteMode::Reject,
+                        };
+
+                        match vault_repo.delete_credential_category_with_mode(cat_id, mode) {
+                            Ok(_) => Ok(format!("Deleted category ID: {}", cat_id)),
+                            Err(e) => Err(format!("Failed to delete category: {}", e)),
+                        }
+                    } else {
+                        Err("Category ID required for delete action".to_string())
+                    }
+                }
+                _ => Err("Invalid action. Must be 'create', 'update', or 'delete'".to_string()),
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn export_credentials_by_category(
+    token: String,
+    format: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<u8>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "export_credentials_by_category").map_err(|e| e.to_string())?;
+    let format = format.parse::<CredentialExportFormat>().map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.export_credentials_by_category(format) {
+                Ok(export) => {
+                    info!("Standalone credentials exported by category ({}) by {}", format, session.username);
+                    Ok(export)
+                }
+                Err(e) => {
+                    error!("Failed to export credentials by category: {}", e);
+                    Err(format!("Failed to export credentials by category: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn import_standalone_credentials_csv(
+    token: String,
+    file_path: String,
+    default_category_id: Option<i64>,
+    strict: Option<bool>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<CredentialCsvImportReport, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session manager lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let csv_bytes = zeroize::Zeroizing::new(
+        std::fs::read(&file_path).map_err(|e| format!("Failed to read CSV file: {}", e))?
+    );
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.import_standalone_credentials_csv(&csv_bytes, default_category_id, session.user_id, strict.unwrap_or(false)) {
+                Ok(report) => {
+                    info!("Standalone credential CSV import by {}: {} imported, {} skipped, {} errors",
+                          session.username, report.imported, report.skipped, report.errors);
+                    Ok(report)
+                }
+                Err(e) => {
+                    error!("Failed to import standalone credentials from CSV: {}", e);
+                    Err(format!("Failed to import standalone credentials from CSV: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn move_credential_category(
+    token: String,
+    category_id: i64,
+    new_parent_id: Option<i64>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.move_category(category_id, new_parent_id) {
+                Ok(_) => {
+                    info!("Category {} moved by {}", category_id, session.username);
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to move category: {}", e)),
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn detect_category_cycles(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<Vec<i64>>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "detect_category_cycles").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+            vault_repo.detect_category_cycles()
+                .map_err(|e| format!("Failed to detect category cycles: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn break_category_cycle(
+    token: String,
+    category_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "break_category_cycle").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.break_category_cycle(category_id) {
+                Ok(()) => {
+                    info!("Category cycle broken at category {} by {}", category_id, session.username);
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to break category cycle: {}", e)),
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_max_category_depth(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<i64, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_max_category_depth()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_max_category_depth(
+    token: String,
+    max_depth: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "set_max_category_depth").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.set_max_category_depth(max_depth)
+        .map_err(|e| e.to_string())?;
+
+    info!("Maximum category depth set to {} by {}", max_depth, user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_credential_history(
+    token: String,
+    credential_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<crate::vault::StandaloneCredentialHistory>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.get_standalone_credential_history(credential_id) {
+                Ok(history) => {
+                    info!("Retrieved history for credential {}", credential_id);
+                    Ok(history)
+                }
+                Err(e) => {
+                    error!("Failed to get credential history: {}", e);
+                    Err(format!("Failed to get credential history: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn update_standalone_credential(
+    token: String,
+    update_request: UpdateStandaloneCredentialRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<String, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            let sanitized_request = UpdateStandaloneCredentialRequest {
+                id: update_request.id,
+                name: update_request.name.map(|n| InputSanitizer::sanitize_string(&n)),
+                description: update_request.description.map(|d| InputSanitizer::sanitize_string(&d)),
+                category_id: update_request.category_id,
+                value: update_request.value.map(|v| InputSanitizer::sanitize_string(&v)),
+                author_id: session.user_id,
+            };
+
+            match vault_repo.update_standalone_credential(sanitized_request) {
+                Ok(_) => {
+                    info!("Updated standalone credential {} by {}", update_request.id, session.username);
+                    Ok("Credential updated successfully".to_string())
+                }
+                Err(e) => {
+                    error!("Failed to update standalone credential: {}", e);
+                    Err(format!("Failed to update credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_standalone_credential(
+    token: String,
+    credential_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<String, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.delete_standalone_credential(credential_id, session.user_id) {
+                Ok(_) => {
+                    info!("Deleted standalone credential {} by {}", credential_id, session.username);
+                    Ok("Credential deleted successfully".to_string())
+                }
+                Err(e) => {
+                    error!("Failed to delete standalone credential: {}", e);
+                    Err(format!("Failed to delete credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_standalone_credential(
+    token: String,
+    credential_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<StandaloneCredentialInfo, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.get_standalone_credential(credential_id) {
+                Ok(Some(credential_info)) => Ok(credential_info),
+                Ok(None) => Err("Credential not found".to_string()),
+                Err(e) => {
+                    error!("Failed to get standalone credential: {}", e);
+                    Err(format!("Failed to get credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn decrypt_standalone_credential(
+    token: String,
+    credential_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<String, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.get_standalone_credential(credential_id) {
+                Ok(Some(credential_info)) => {
+                    // Decrypt the credential value
+                    let decrypted_value = vault_repo
+                        .decrypt_standalone_credential_value(&credential_info.credential, session.user_id)
+                        .map_err(|e| e.to_string())?;
+                    
+                    info!("Decrypted standalone credential {} for user {}", credential_id, session.username);
+                    Ok(decrypted_value.as_str().to_owned())
+                }
+                Ok(None) => Err("Credential not found".to_string()),
+                Err(e) => {
+                    error!("Failed to get standalone credential: {}", e);
+                    Err(format!("Failed to get credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn reveal_credential_timed(
+    token: String,
+    credential_id: i64,
+    ttl_seconds: u32,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<TimedCredentialReveal, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let vault_repo = SqliteVaultRepository::new(db.get_connection());
+
+            match vault_repo.reveal_credential_timed(credential_id, session.user_id, ttl_seconds) {
+                Ok(reveal) => {
+                    info!("Credential {} revealed to {} until {}", credential_id, session.username, reveal.expires_at);
+                    Ok(reveal)
+                }
+                Err(e) => {
+                    error!("Failed to reveal credential: {}", e);
+                    Err(format!("Failed to reveal credential: {}", e))
+                }
+            }
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+// Password Rotation Commands for Story 4.6
+
+#[tauri::command]
+pub async fn rotate_password(
+    token: String,
+    request: PasswordRotationRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    // Check permissions - Engineers can rotate passwords
+    if session.role != UserRole::Administrator && session.role != UserRole::Engineer {
+        return Err("Insufficient permissions to rotate passwords".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
+            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
+            
+            let rotation_service = PasswordRotationService::new(conn, vault_repo, audit_repo);
+            
+            // Validate rotation before executing
+            rotation_service.validate_rotation(request.secret_id, &request.new_password)
+                .map_err(|e| e.to_string())?;
+            
+            // Execute rotation with author_id set to session user
+            let mut rotation_request = request;
+            rotation_request.author_id = session.user_id;
+            
+            rotation_service.rotate_password(rotation_request)
+                .map_err(|e| {
+                    error!("Failed to rotate password: {}", e);
+                    format!("Failed to rotate password: {}", e)
+                })?;
+            
+            info!("Password rotated successfully by user {}", session.username);
+            Ok(())
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_rotation_schedule(
+    token: String,
+    vault_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Option<RotationSchedule>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let scheduler = RotationScheduler::new(conn);
+            
+            scheduler.get_active_schedule(vault_id)
+                .map_err(|e| format!("Failed to get rotation schedule: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn create_rotation_batch(
+    token: String,
+    request: CreateRotationBatchRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<RotationBatch, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    // Check permissions
+    if session.role != UserRole::Administrator && session.role != UserRole::Engineer {
+        return Err("Insufficient permissions to create rotation batch".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
+            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
+            
+            let batch_service = BatchRotationService::new(conn, vault_repo, audit_repo);
+            
+            // Set created_by to session user
+            let mut batch_request = request;
+            batch_request.created_by = session.user_id;
+            
+            batch_service.create_batch(batch_request)
+                .map_err(|e| format!("Failed to create rotation batch: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_rotation_history(
+    token: String,
+    secret_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<PasswordRotationHistory>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
+            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
+            
+            let rotation_service = PasswordRotationService::new(conn, vault_repo, audit_repo);
+            
+            rotation_service.get_rotation_history(secret_id)
+                .map_err(|e| format!("Failed to get rotation history: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+// Vault permission commands for Story 4.5
+
+#[tauri::command]
+pub async fn check_vault_access(
+    token: String,
+    vault_id: i64,
+    permission_type: PermissionType,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<VaultAccessInfo, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+            .ok_or("Invalid session")?
+    };
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let access_control = VaultAccessControlService::new(db.get_connection());
+    let user_repo = SqliteUserRepository::new(db.get_connection());
+    let full_user = user_repo.find_by_id(user.user_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("User not found")?;
+    
+    access_control.check_vault_access(&full_user, vault_id, permission_type)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn grant_vault_access(
+    token: String,
+    request: GrantVaultAccessRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<VaultPermission, String> {
+    // Validate session and get current user
+    let admin_user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+            .ok_or("Invalid session")?
+    };
+
+    authorize_command(&command_policy, &admin_user, "grant_vault_access").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    // Verify target user exists
+    let user_repo = SqliteUserRepository::new(db.get_connection());
+    let target_user = user_repo.find_by_id(request.user_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Target user not found")?;
+
+    // Grant the permission - Set the correct granted_by value
+    let mut updated_request = request.clone();
+    updated_request.granted_by = admin_user.user_id;
+    
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    let permission = vault_repo.grant_vault_access(updated_request)
+        .map_err(|e| e.to_string())?;
+
+    // Log audit event
+    let audit_repo = SqliteAuditRepository::new(db.get_connection());
+    let audit_event = create_vault_access_granted_event(
+        admin_user.user_id,
+        &admin_user.username,
+        request.user_id,
+        &target_user.username,
+        request.vault_id,
+        &request.permission_type.to_string(),
+    );
+    
+    audit_repo.log_event(&audit_event)
+        .map_err(|e| e.to_string())?;
+
+    Ok(permission)
+}
+
+#[tauri::command]
+pub async fn revoke_vault_access(
+    token: String,
+    request: RevokeVaultAccessRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let admin_user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+            .ok_or("Invalid session")?
+    };
+
+    authorize_command(&command_policy, &admin_user, "revoke_vault_access").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    // Verify target user exists
+    let user_repo = SqliteUserRepository::new(db.get_connection());
+    let target_user = user_repo.find_by_id(request.user_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Target user not found")?;
+
+    // Revoke the permission
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.revoke_vault_access(request.clone())
+        .map_err(|e| e.to_string())?;
+
+    // Log audit event
+    let audit_repo = SqliteAuditRepository::new(db.get_connection());
+    let permission_str = request.permission_type.as_ref().map(|p| p.to_string());
+    let audit_event = create_vault_access_revoked_event(
+        admin_user.user_id,
+        &admin_user.username,
+        request.user_id,
+        &target_user.username,
+        request.vault_id,
+        permission_str.as_deref(),
+    );
+    
+    audit_repo.log_event(&audit_event)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_user_vault_permissions(
+    token: String,
+    user_id: Option<i64>,
+    vault_id: Option<i64>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<VaultPermission>, String> {
+    // Validate session and get current user
+    let current_user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let current_user = current_user.ok_or("Invalid session")?;
+
+    // Determine target user - default to current user if not specified
+    let target_user_id = user_id.unwrap_or(current_user.user_id);
+
+    // Only administrators can view other users' permissions
+    if target_user_id != current_user.user_id && current_user.role != crate::users::UserRole::Administrator {
+        return Err("Insufficient permissions to view other users' vault permissions".to_string());
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    // Get permissions
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_user_vault_permissions(target_user_id, vault_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_vault_permissions(
+    token: String,
+    vault_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<VaultPermission>, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_vault_permissions").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    // Get vault permissions
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_vault_permissions(vault_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_vault_access_log(
+    token: String,
+    vault_id: i64,
+    limit: Option<i32>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<Vec<VaultAccessLog>, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_vault_access_log").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    // Get access log
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_vault_access_log(vault_id, limit)
+        .map_err(|e| e.to_string())
+}
+
+/// Page through a vault's full access log, optionally filtered by time range and result
+#[tauri::command]
+pub async fn get_vault_access_log_page(
+    token: String,
+    vault_id: i64,
+    query: VaultAccessLogQuery,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<VaultAccessLogPage, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_vault_access_log_page").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_vault_access_log_page(vault_id, &query)
+        .map_err(|e| format!("Failed to get vault access log: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_vault_access_log_count(
+    token: String,
+    vault_id: i64,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<i64, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_vault_access_log_count").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_vault_access_log_count(vault_id)
+        .map_err(|e| format!("Failed to count vault access log entries: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_decrypt_audit_policy(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<DecryptAuditPolicy, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_decrypt_audit_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_decrypt_audit_policy()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_decrypt_audit_policy(
+    token: String,
+    policy: DecryptAuditPolicy,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "update_decrypt_audit_policy").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.update_decrypt_audit_policy(&policy)
+        .map_err(|e| e.to_string())?;
+
+    info!("Decrypt audit policy updated by {}", user.username);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn prune_vault_access_log(
+    token: String,
+    older_than_days: u32,
+    archive_path: Option<String>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<AccessLogPruneResult, String> {
+    // Validate session and get current user
+    let user = {
+        let session_manager = session_manager.lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())?;
+        session_manager.validate_session(&token)
+            .map_err(|e| e.to_string())?
+    };
+
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "prune_vault_access_log").map_err(|e| e.to_string())?;
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    let db = db_guard.as_ref()
+        .ok_or("Database not initialized")?;
+
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    let result = vault_repo.prune_vault_access_log(older_than_days, archive_path.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    info!("Vault access log pruned by {}: {} entries older than {} days removed",
+          user.username, result.deleted, older_than_days);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_access_log_retention_policy(
     token: String,
-    vault_id: i64,
-    permission_type: PermissionType,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<VaultAccessInfo, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<AccessLogRetentionPolicy, String> {
     // Validate session and get current user
     let user = {
         let session_manager = session_manager.lock()
             .map_err(|_| "Failed to acquire session lock".to_string())?;
         session_manager.validate_session(&token)
             .map_err(|e| e.to_string())?
-            .ok_or("Invalid session")?
     };
 
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "get_access_log_retention_policy").map_err(|e| e.to_string())?;
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     let db = db_guard.as_ref()
         .ok_or("Database not initialized")?;
 
-    let access_control = VaultAccessControlService::new(db.get_connection());
-    let user_repo = SqliteUserRepository::new(db.get_connection());
-    let full_user = user_repo.find_by_id(user.user_id)
-        .map_err(|e| e.to_string())?
-        .ok_or("User not found")?;
-    
-    access_control.check_vault_access(&full_user, vault_id, permission_type)
+    let vault_repo = SqliteVaultRepository::new(db.get_connection());
+    vault_repo.get_access_log_retention_policy()
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn grant_vault_access(
+pub async fn update_access_log_retention_policy(
     token: String,
-    request: GrantVaultAccessRequest,
+    policy: AccessLogRetentionPolicy,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<VaultPermission, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
     // Validate session and get current user
-    let admin_user = {
+    let user = {
         let session_manager = session_manager.lock()
             .map_err(|_| "Failed to acquire session lock".to_string())?;
         session_manager.validate_session(&token)
             .map_err(|e| e.to_string())?
-            .ok_or("Invalid session")?
     };
 
-    // Verify administrator role
-    if admin_user.role != crate::users::UserRole::Administrator {
-        return Err("Only administrators can grant vault access".to_string());
-    }
+    let user = user.ok_or("Invalid session")?;
+
+    authorize_command(&command_policy, &user, "update_access_log_retention_policy").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     let db = db_guard.as_ref()
         .ok_or("Database not initialized")?;
 
-    // Verify target user exists
-    let user_repo = SqliteUserRepository::new(db.get_connection());
-    let target_user = user_repo.find_by_id(request.user_id)
-        .map_err(|e| e.to_string())?
-        .ok_or("Target user not found")?;
-
-    // Grant the permission - Set the correct granted_by value
-    let mut updated_request = request.clone();
-    updated_request.granted_by = admin_user.user_id;
-    
     let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    let permission = vault_repo.grant_vault_access(updated_request)
-        .map_err(|e| e.to_string())?;
-
-    // Log audit event
-    let audit_repo = SqliteAuditRepository::new(db.get_connection());
-    let audit_event = create_vault_access_granted_event(
-        admin_user.user_id,
-        &admin_user.username,
-        request.user_id,
-        &target_user.username,
-        request.vault_id,
-        &request.permission_type.to_string(),
-    );
-    
-    audit_repo.log_event(&audit_event)
+    vault_repo.update_access_log_retention_policy(&policy)
         .map_err(|e| e.to_string())?;
 
-    Ok(permission)
+    info!("Access log retention policy updated by {}", user.username);
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn revoke_vault_access(
+pub async fn create_permission_request(
     token: String,
-    request: RevokeVaultAccessRequest,
+    request: CreatePermissionRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+) -> Result<PermissionRequest, String> {
     // Validate session and get current user
-    let admin_user = {
+    let user = {
         let session_manager = session_manager.lock()
             .map_err(|_| "Failed to acquire session lock".to_string())?;
         session_manager.validate_session(&token)
             .map_err(|e| e.to_string())?
-            .ok_or("Invalid session")?
     };
 
-    // Verify administrator role
-    if admin_user.role != crate::users::UserRole::Administrator {
-        return Err("Only administrators can revoke vault access".to_string());
-    }
+    let user = user.ok_or("Invalid session")?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     let db = db_guard.as_ref()
         .ok_or("Database not initialized")?;
 
-    // Verify target user exists
-    let user_repo = SqliteUserRepository::new(db.get_connection());
-    let target_user = user_repo.find_by_id(request.user_id)
-        .map_err(|e| e.to_string())?
-        .ok_or("Target user not found")?;
-
-    // Revoke the permission
+    // Create the permission request
     let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    vault_repo.revoke_vault_access(request.clone())
+    let permission_request = vault_repo.create_permission_request(request.clone(), user.user_id)
         .map_err(|e| e.to_string())?;
 
     // Log audit event
     let audit_repo = SqliteAuditRepository::new(db.get_connection());
-    let permission_str = request.permission_type.as_ref().map(|p| p.to_string());
-    let audit_event = create_vault_access_revoked_event(
-        admin_user.user_id,
-        &admin_user.username,
-        request.user_id,
-        &target_user.username,
-        request.vault_id,
-        permission_str.as_deref(),
-    );
+    let audit_event = crate::audit::AuditEventRequest {
+        event_type: crate::audit::AuditEventType::VaultPermissionRequested,
+        user_id: Some(user.user_id),
+        username: Some(user.username.clone()),
+        admin_user_id: None,
+        admin_username: None,
+        target_user_id: None,
+        target_username: None,
+        description: format!("Permission requested for vault {}", request.vault_id),
+        metadata: Some(serde_json::json!({
+            "vault_id": request.vault_id,
+            "permission_type": request.requested_permission.to_string()
+        }).to_string()),
+        ip_address: None,
+        user_agent: None,
+    };
     
     audit_repo.log_event(&audit_event)
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(permission_request)
 }
 
+// Additional rotation commands
+
 #[tauri::command]
-pub async fn get_user_vault_permissions(
+pub async fn get_rotation_alerts(
     token: String,
-    user_id: Option<i64>,
-    vault_id: Option<i64>,
+    days_ahead: i32,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<VaultPermission>, String> {
-    // Validate session and get current user
-    let current_user = {
-        let session_manager = session_manager.lock()
+) -> Result<Vec<RotationAlert>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let scheduler = RotationScheduler::new(conn);
+            
+            scheduler.get_rotation_alerts(days_ahead)
+                .map_err(|e| format!("Failed to get rotation alerts: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_secrets_due_for_rotation(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<RotationAlert>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    match session_manager_guard.validate_session(&token) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(_) => return Err("Session validation error".to_string()),
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let scheduler = RotationScheduler::new(db.get_connection());
+            
+            scheduler.get_secrets_due_for_rotation()
+                .map_err(|e| format!("Failed to get secrets due for rotation: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn execute_batch_rotation(
+    token: String,
+    request: BatchRotationRequest,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<(), String> {
+    // Validate session
+    let session = {
+        let session_manager_guard = session_manager.lock()
             .map_err(|_| "Failed to acquire session lock".to_string())?;
-        session_manager.validate_session(&token)
-            .map_err(|e| e.to_string())?
+        match session_manager_guard.validate_session(&token) {
+            Ok(Some(session)) => session,
+            Ok(None) => return Err("Invalid or expired session".to_string()),
+            Err(e) => {
+                error!("Session validation error: {}", e);
+                return Err("Session validation error".to_string());
+            }
+        }
     };
 
-    let current_user = current_user.ok_or("Invalid session")?;
+    // Check permissions
+    if session.role != crate::users::UserRole::Administrator && session.role != crate::users::UserRole::Engineer {
+        return Err("Insufficient permissions to execute batch rotation".to_string());
+    }
 
-    // Determine target user - default to current user if not specified
-    let target_user_id = user_id.unwrap_or(current_user.user_id);
+    // Set author_id to session user
+    let mut batch_request = request;
+    batch_request.author_id = session.user_id;
+
+    let throttle = {
+        let db_guard = db_state.lock()
+            .map_err(|_| "Failed to acquire database lock".to_string())?;
+        let db = db_guard.as_ref().ok_or_else(|| "Database not initialized".to_string())?;
+        let conn = db.get_connection();
+        let batch_service = BatchRotationService::new(
+            conn,
+            Box::new(SqliteVaultRepository::new(conn)),
+            Box::new(SqliteAuditRepository::new(conn)),
+        );
+        let throttle = batch_service.get_throttle_settings()
+            .map_err(|e| format!("Failed to load rotation throttle settings: {}", e))?;
+        batch_service.start_batch(batch_request.batch_id)
+            .map_err(|e| format!("Failed to execute batch rotation: {}", e))?;
+        throttle
+    };
 
-    // Only administrators can view other users' permissions
-    if target_user_id != current_user.user_id && current_user.role != crate::users::UserRole::Administrator {
-        return Err("Insufficient permissions to view other users' vault permissions".to_string());
+    // Rotate one item per database lock so interactive commands can run in between,
+    // sleeping without the lock held while the batch is throttled
+    let total_items = batch_request.items.len();
+    let mut progress = BatchRotationProgress::default();
+    'chunks: for chunk in batch_request.items.chunks(throttle.batch_size as usize) {
+        for item in chunk {
+            if progress.processed > 0 && !throttle.item_delay().is_zero() {
+                tokio::time::sleep(throttle.item_delay()).await;
+            }
+
+            {
+                let db_guard = db_state.lock()
+                    .map_err(|_| "Failed to acquire database lock".to_string())?;
+                let db = db_guard.as_ref().ok_or_else(|| "Database not initialized".to_string())?;
+                let conn = db.get_connection();
+                let batch_service = BatchRotationService::new(
+                    conn,
+                    Box::new(SqliteVaultRepository::new(conn)),
+                    Box::new(SqliteAuditRepository::new(conn)),
+                );
+                batch_service.rotate_batch_items(
+                    batch_request.batch_id,
+                    std::slice::from_ref(item),
+                    batch_request.author_id,
+                    std::time::Duration::ZERO,
+                    &mut progress,
+                ).map_err(|e| format!("Failed to execute batch rotation: {}", e))?;
+            }
+            if progress.cancelled {
+                break 'chunks;
+            }
+        }
+        info!("Batch rotation {} progress: {} of {} items", batch_request.batch_id, progress.processed, total_items);
+    }
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let batch_service = BatchRotationService::new(
+                conn,
+                Box::new(SqliteVaultRepository::new(conn)),
+                Box::new(SqliteAuditRepository::new(conn)),
+            );
+            batch_service.finish_batch(batch_request.batch_id, total_items, &progress)
+                .map_err(|e| format!("Failed to execute batch rotation: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_rotation_throttle_settings(
+    token: String,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<BatchThrottleSettings, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let batch_service = BatchRotationService::new(
+                conn,
+                Box::new(SqliteVaultRepository::new(conn)),
+                Box::new(SqliteAuditRepository::new(conn)),
+            );
+            batch_service.get_throttle_settings()
+                .map_err(|e| format!("Failed to get rotation throttle settings: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
     }
+}
+
+#[tauri::command]
+pub async fn update_rotation_throttle_settings(
+    token: String,
+    settings: BatchThrottleSettings,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<BatchThrottleSettings, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
+
+    authorize_command(&command_policy, &session, "update_rotation_throttle_settings").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    let db = db_guard.as_ref()
-        .ok_or("Database not initialized")?;
-
-    // Get permissions
-    let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    vault_repo.get_user_vault_permissions(target_user_id, vault_id)
-        .map_err(|e| e.to_string())
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let batch_service = BatchRotationService::new(
+                conn,
+                Box::new(SqliteVaultRepository::new(conn)),
+                Box::new(SqliteAuditRepository::new(conn)),
+            );
+            batch_service.update_throttle_settings(&settings)
+                .map_err(|e| format!("Failed to update rotation throttle settings: {}", e))?;
+            info!(
+                "Rotation throttle set to batch size {} with {}ms delay by {}",
+                settings.batch_size, settings.item_delay_ms, session.username
+            );
+            Ok(settings)
+        }
+        None => Err("Database not initialized".to_string()),
+    }
 }
 
 #[tauri::command]
-pub async fn get_vault_permissions(
+pub async fn create_rotation_schedule(
     token: String,
-    vault_id: i64,
+    request: CreateRotationScheduleRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<VaultPermission>, String> {
-    // Validate session and get current user
-    let user = {
-        let session_manager = session_manager.lock()
-            .map_err(|_| "Failed to acquire session lock".to_string())?;
-        session_manager.validate_session(&token)
-            .map_err(|e| e.to_string())?
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<RotationSchedule, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
     };
+    drop(session_manager_guard);
 
-    let user = user.ok_or("Invalid session")?;
-
-    // Only administrators can view vault permissions
-    if user.role != crate::users::UserRole::Administrator {
-        return Err("Only administrators can view vault permissions".to_string());
-    }
+    authorize_command(&command_policy, &session, "create_rotation_schedule").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    let db = db_guard.as_ref()
-        .ok_or("Database not initialized")?;
-
-    // Get vault permissions
-    let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    vault_repo.get_vault_permissions(vault_id)
-        .map_err(|e| e.to_string())
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let scheduler = RotationScheduler::new(conn);
+            
+            // Set created_by to session user
+            let mut schedule_request = request;
+            schedule_request.created_by = session.user_id;
+            
+            scheduler.create_rotation_schedule(schedule_request)
+                .map_err(|e| format!("Failed to create rotation schedule: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
 }
 
 #[tauri::command]
-pub async fn get_vault_access_log(
+pub async fn get_rotation_compliance_metrics(
     token: String,
-    vault_id: i64,
-    limit: Option<i32>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<VaultAccessLog>, String> {
-    // Validate session and get current user
-    let user = {
-        let session_manager = session_manager.lock()
-            .map_err(|_| "Failed to acquire session lock".to_string())?;
-        session_manager.validate_session(&token)
-            .map_err(|e| e.to_string())?
+) -> Result<std::collections::HashMap<String, serde_json::Value>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => return Err("Session validation error".to_string()),
     };
-
-    let user = user.ok_or("Invalid session")?;
-
-    // Only administrators can view access logs
-    if user.role != crate::users::UserRole::Administrator {
-        return Err("Only administrators can view vault access logs".to_string());
-    }
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    let db = db_guard.as_ref()
-        .ok_or("Database not initialized")?;
-
-    // Get access log
-    let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    vault_repo.get_vault_access_log(vault_id, limit)
-        .map_err(|e| e.to_string())
+    match db_guard.as_ref() {
+        Some(db) => {
+            let conn = db.get_connection();
+            let scheduler = RotationScheduler::new(conn);
+            
+            scheduler.get_rotation_compliance_metrics()
+                .map_err(|e| format!("Failed to get compliance metrics: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
 }
 
 #[tauri::command]
-pub async fn create_permission_request(
+pub async fn get_compliance_alert_settings(
     token: String,
-    request: CreatePermissionRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<PermissionRequest, String> {
-    // Validate session and get current user
-    let user = {
-        let session_manager = session_manager.lock()
-            .map_err(|_| "Failed to acquire session lock".to_string())?;
-        session_manager.validate_session(&token)
-            .map_err(|e| e.to_string())?
+) -> Result<ComplianceAlertSettings, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
-
-    let user = user.ok_or("Invalid session")?;
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
-    let db = db_guard.as_ref()
-        .ok_or("Database not initialized")?;
-
-    // Create the permission request
-    let vault_repo = SqliteVaultRepository::new(db.get_connection());
-    let permission_request = vault_repo.create_permission_request(request.clone(), user.user_id)
-        .map_err(|e| e.to_string())?;
-
-    // Log audit event
-    let audit_repo = SqliteAuditRepository::new(db.get_connection());
-    let audit_event = crate::audit::AuditEventRequest {
-        event_type: crate::audit::AuditEventType::VaultPermissionRequested,
-        user_id: Some(user.user_id),
-        username: Some(user.username.clone()),
-        admin_user_id: None,
-        admin_username: None,
-        target_user_id: None,
-        target_username: None,
-        description: format!("Permission requested for vault {}", request.vault_id),
-        metadata: Some(serde_json::json!({
-            "vault_id": request.vault_id,
-            "permission_type": request.requested_permission.to_string()
-        }).to_string()),
-        ip_address: None,
-        user_agent: None,
-    };
-    
-    audit_repo.log_event(&audit_event)
-        .map_err(|e| e.to_string())?;
-
-    Ok(permission_request)
+    match db_guard.as_ref() {
+        Some(db) => {
+            let scheduler = RotationScheduler::new(db.get_connection());
+            scheduler.get_compliance_alert_settings()
+                .map_err(|e| format!("Failed to get compliance alert settings: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
+    }
 }
 
-// Additional rotation commands
-
 #[tauri::command]
-pub async fn get_rotation_alerts(
+pub async fn update_compliance_alert_settings(
     token: String,
-    days_ahead: i32,
+    settings: ComplianceAlertSettings,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<Vec<RotationAlert>, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<ComplianceAlertSettings, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
+    authorize_command(&command_policy, &session, "update_compliance_alert_settings").map_err(|e| e.to_string())?;
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let scheduler = RotationScheduler::new(conn);
-            
-            scheduler.get_rotation_alerts(days_ahead)
-                .map_err(|e| format!("Failed to get rotation alerts: {}", e))
+            let scheduler = RotationScheduler::new(db.get_connection());
+            scheduler.update_compliance_alert_settings(&settings)
+                .map_err(|e| format!("Failed to update compliance alert settings: {}", e))?;
+            info!(
+                "Rotation compliance alert threshold set to {}% (enabled: {}) by {}",
+                settings.threshold_percentage, settings.is_enabled, session.username
+            );
+            Ok(settings)
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn execute_batch_rotation(
+pub async fn check_rotation_compliance_alert(
     token: String,
-    request: BatchRotationRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<(), String> {
+) -> Result<Option<ComplianceAlert>, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let session = match session_manager_guard.validate_session(&token) {
+    let _session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
-    // Check permissions
-    if session.role != crate::users::UserRole::Administrator && session.role != crate::users::UserRole::Engineer {
-        return Err("Insufficient permissions to execute batch rotation".to_string());
+    let db_guard = db_state.lock()
+        .map_err(|_| "Failed to acquire database lock".to_string())?;
+    match db_guard.as_ref() {
+        Some(db) => {
+            let scheduler = RotationScheduler::new(db.get_connection());
+            scheduler.check_rotation_compliance_alert()
+                .map_err(|e| format!("Failed to check rotation compliance: {}", e))
+        }
+        None => Err("Database not initialized".to_string()),
     }
+}
+
+#[tauri::command]
+pub async fn get_rotation_reason_templates(
+    token: String,
+    include_inactive: Option<bool>,
+    db_state: State<'_, DatabaseState>,
+    session_manager: State<'_, SessionManagerState>,
+) -> Result<Vec<RotationReasonTemplate>, String> {
+    // Validate session
+    let session_manager_guard = session_manager.lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    let _session = match session_manager_guard.validate_session(&token) {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err("Invalid or expired session".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
+    };
+    drop(session_manager_guard);
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let vault_repo = Box::new(SqliteVaultRepository::new(conn));
-            let audit_repo = Box::new(SqliteAuditRepository::new(conn));
-            
-            let batch_service = BatchRotationService::new(conn, vault_repo, audit_repo);
-            
-            // Set author_id to session user
-            let mut batch_request = request;
-            batch_request.author_id = session.user_id;
+            let scheduler = RotationScheduler::new(db.get_connection());
             
-            batch_service.execute_batch_rotation(batch_request)
-                .map_err(|e| format!("Failed to execute batch rotation: {}", e))
+            scheduler.get_rotation_reason_templates(include_inactive.unwrap_or(false))
+                .map_err(|e| format!("Failed to get rotation reason templates: {}", e))
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn create_rotation_schedule(
+pub async fn create_rotation_reason_template(
     token: String,
-    request: CreateRotationScheduleRequest,
+    name: String,
+    description: Option<String>,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<RotationSchedule, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<RotationReasonTemplate, String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
-    // Only administrators can create rotation schedules
-    if session.role != crate::users::UserRole::Administrator {
-        return Err("Only administrators can create rotation schedules".to_string());
-    }
+    authorize_command(&command_policy, &session, "create_rotation_reason_template").map_err(|e| e.to_string())?;
+
+    let name = InputSanitizer::sanitize_string(&name);
+    let description = description.map(|d| InputSanitizer::sanitize_string(&d));
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let scheduler = RotationScheduler::new(conn);
-            
-            // Set created_by to session user
-            let mut schedule_request = request;
-            schedule_request.created_by = session.user_id;
+            let scheduler = RotationScheduler::new(db.get_connection());
             
-            scheduler.create_rotation_schedule(schedule_request)
-                .map_err(|e| format!("Failed to create rotation schedule: {}", e))
+            match scheduler.create_rotation_reason_template(CreateRotationReasonTemplateRequest {
+                name,
+                description,
+                created_by: session.user_id,
+            }) {
+                Ok(template) => {
+                    info!("Rotation reason template '{}' created by {}", template.name, session.username);
+                    Ok(template)
+                }
+                Err(e) => {
+                    error!("Failed to create rotation reason template: {}", e);
+                    Err(format!("Failed to create rotation reason template: {}", e))
+                }
+            }
         }
         None => Err("Database not initialized".to_string()),
     }
 }
 
 #[tauri::command]
-pub async fn get_rotation_compliance_metrics(
+pub async fn set_rotation_reason_template_active(
     token: String,
+    template_id: i64,
+    is_active: bool,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
-) -> Result<std::collections::HashMap<String, serde_json::Value>, String> {
+    command_policy: State<'_, CommandPolicyState>,
+) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
         .map_err(|_| "Failed to acquire session lock".to_string())?;
     let session = match session_manager_guard.validate_session(&token) {
         Ok(Some(session)) => session,
         Ok(None) => return Err("Invalid or expired session".to_string()),
-        Err(e) => return Err("Session validation error".to_string()),
+        Err(e) => {
+            error!("Session validation error: {}", e);
+            return Err("Session validation error".to_string());
+        }
     };
     drop(session_manager_guard);
 
+    authorize_command(&command_policy, &session, "set_rotation_reason_template_active").map_err(|e| e.to_string())?;
+
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
     match db_guard.as_ref() {
         Some(db) => {
-            let conn = db.get_connection();
-            let scheduler = RotationScheduler::new(conn);
+            let scheduler = RotationScheduler::new(db.get_connection());
             
-            scheduler.get_rotation_compliance_metrics()
-                .map_err(|e| format!("Failed to get compliance metrics: {}", e))
+            scheduler.set_rotation_reason_template_active(template_id, is_active)
+                .map_err(|e| format!("Failed to update rotation reason template: {}", e))?;
+            info!("Rotation reason template {} set active={} by {}", template_id, is_active, session.username);
+            Ok(())
         }
         None => Err("Database not initialized".to_string()),
     }
@@ -1869,6 +3881,7 @@ pub async fn update_rotation_policy(
     request: UpdateRotationScheduleRequest,
     db_state: State<'_, DatabaseState>,
     session_manager: State<'_, SessionManagerState>,
+    command_policy: State<'_, CommandPolicyState>,
 ) -> Result<(), String> {
     // Validate session
     let session_manager_guard = session_manager.lock()
@@ -1880,10 +3893,7 @@ pub async fn update_rotation_policy(
     };
     drop(session_manager_guard);
 
-    // Only administrators can update rotation policies
-    if session.role != UserRole::Administrator {
-        return Err("Only administrators can update rotation policies".to_string());
-    }
+    authorize_command(&command_policy, &session, "update_rotation_policy").map_err(|e| e.to_string())?;
 
     let db_guard = db_state.lock()
         .map_err(|_| "Failed to acquire database lock".to_string())?;
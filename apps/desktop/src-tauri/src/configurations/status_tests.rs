@@ -73,6 +73,12 @@ fn test_configuration_version_info_structure() {
         status_changed_by: Some(5),
         status_changed_at: Some("2023-01-01T12:00:00Z".to_string()),
         firmware_version_id: None,
+        pinned: false,
+        content_type: None,
+        notes_auto_generated: false,
+        rolled_back_from: None,
+        source_system: None,
+        source_reference: None,
         created_at: "2023-01-01T12:00:00Z".to_string(),
     };
 
@@ -135,6 +141,12 @@ fn test_version_info_with_different_statuses() {
             status_changed_by: Some(5),
             status_changed_at: Some("2023-01-01T12:00:00Z".to_string()),
             firmware_version_id: None,
+            pinned: false,
+            content_type: None,
+            notes_auto_generated: false,
+            rolled_back_from: None,
+            source_system: None,
+            source_reference: None,
             created_at: "2023-01-01T12:00:00Z".to_string(),
         };
 
@@ -5,12 +5,15 @@ use tracing::info;
 use crate::users::SqliteUserRepository;
 use crate::user_settings::SqliteUserSettingsRepository;
 use crate::audit::{SqliteAuditRepository, AuditRepository};
+use crate::audit::subscriptions::{SqliteAssetSubscriptionRepository, AssetSubscriptionRepository};
 use crate::assets::SqliteAssetRepository;
-use crate::configurations::SqliteConfigurationRepository;
+use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository};
 use crate::branches::SqliteBranchRepository;
 use crate::firmware::SqliteFirmwareRepository;
 use crate::firmware_analysis::{SqliteFirmwareAnalysisRepository, FirmwareAnalysisRepository};
 use crate::vault::{SqliteVaultRepository, VaultRepository};
+use crate::validation::SqliteRateLimitPolicyRepository;
+use crate::auth::{SqliteLoginAuditRepository, SqliteCommandPolicyRepository};
 // Epic 5 imports
 use crate::metadata::{SqliteMetadataRepository, SqliteMetadataSearchRepository};
 use crate::bulk::{SqliteBulkImportRepository, operations::SqliteBulkOperationsRepository};
@@ -79,6 +82,10 @@ impl Database {
         let audit_repo = SqliteAuditRepository::new(&self.conn);
         audit_repo.initialize_schema()?;
 
+        // Initialize asset event subscription schema
+        let subscription_repo = SqliteAssetSubscriptionRepository::new(&self.conn);
+        subscription_repo.initialize_schema()?;
+
         // Initialize asset schema
         let asset_repo = SqliteAssetRepository::new(&self.conn);
         asset_repo.initialize_schema()?;
@@ -121,6 +128,18 @@ impl Database {
         let bulk_ops_repo = SqliteBulkOperationsRepository::new(&self.conn);
         bulk_ops_repo.initialize_schema()?;
 
+        // Initialize per-command rate limit policies
+        let rate_limit_repo = SqliteRateLimitPolicyRepository::new(&self.conn);
+        rate_limit_repo.initialize_schema()?;
+
+        // Initialize login audit history and its on/off setting
+        let login_audit_repo = SqliteLoginAuditRepository::new(&self.conn);
+        login_audit_repo.initialize_schema()?;
+
+        // Initialize the per-command minimum roles
+        let command_policy_repo = SqliteCommandPolicyRepository::new(&self.conn);
+        command_policy_repo.initialize_schema()?;
+
         // Run data migrations
         self.run_data_migrations()?;
 
@@ -162,40 +181,39 @@ impl Database {
         
         // Check if firmware metadata migration has been run
         let migration_key = "firmware_metadata_migration_20250125";
-        if let Ok(Some(_)) = self.get_metadata(migration_key) {
-            info!("Firmware metadata migration already applied");
-            return Ok(());
-        }
-
-        // Fix firmware records with invalid metadata
-        info!("Fixing firmware metadata issues");
+        if let Ok(None) = self.get_metadata(migration_key) {
+            // Fix firmware records with invalid metadata
+            info!("Fixing firmware metadata issues");
         
-        self.conn.execute_batch(r#"
-            -- Update firmware records with NULL or invalid file_size
-            UPDATE firmware_versions 
-            SET file_size = 0 
-            WHERE file_size IS NULL OR file_size < 0;
-
-            -- Update firmware records with NULL or empty file_hash
-            UPDATE firmware_versions 
-            SET file_hash = 'unknown' 
-            WHERE file_hash IS NULL OR file_hash = '';
+            self.conn.execute_batch(r#"
+                -- Update firmware records with NULL or invalid file_size
+                UPDATE firmware_versions 
+                SET file_size = 0 
+                WHERE file_size IS NULL OR file_size < 0;
+
+                -- Update firmware records with NULL or empty file_hash
+                UPDATE firmware_versions 
+                SET file_hash = 'unknown' 
+                WHERE file_hash IS NULL OR file_hash = '';
+
+                -- Update firmware records with invalid created_at timestamps
+                UPDATE firmware_versions 
+                SET created_at = datetime('now') 
+                WHERE created_at IS NULL OR created_at = '' OR datetime(created_at) IS NULL;
+
+                -- Ensure all firmware records have proper status
+                UPDATE firmware_versions 
+                SET status = 'Draft' 
+                WHERE status IS NULL OR status = '' OR status NOT IN ('Draft', 'Approved', 'Golden', 'Archived');
+            "#)?;
 
-            -- Update firmware records with invalid created_at timestamps
-            UPDATE firmware_versions 
-            SET created_at = datetime('now') 
-            WHERE created_at IS NULL OR created_at = '' OR datetime(created_at) IS NULL;
-
-            -- Ensure all firmware records have proper status
-            UPDATE firmware_versions 
-            SET status = 'Draft' 
-            WHERE status IS NULL OR status = '' OR status NOT IN ('Draft', 'Approved', 'Golden', 'Archived');
-        "#)?;
-
-        // Mark migration as complete
-        self.set_metadata(migration_key, "applied")?;
+            // Mark migration as complete
+            self.set_metadata(migration_key, "applied")?;
         
-        info!("Firmware metadata migration completed");
+            info!("Firmware metadata migration completed");
+        } else {
+            info!("Firmware metadata migration already applied");
+        }
         
         // Story 4.6 - Password rotation migration
         let rotation_migration_key = "password_rotation_migration_20250126";
@@ -261,6 +279,321 @@ impl Database {
             info!("Metadata archive columns migration completed");
         }
         
+        // Configuration version pinning migration
+        let pinning_migration_key = "configuration_version_pinning_20261016";
+        if let Ok(None) = self.get_metadata(pinning_migration_key) {
+            info!("Applying configuration version pinning migration");
+            self.add_column_if_missing("configuration_versions", "pinned", "BOOLEAN NOT NULL DEFAULT 0")?;
+            self.set_metadata(pinning_migration_key, "applied")?;
+        }
+        
+        // Rotation reason templates migration
+        let rotation_reason_migration_key = "rotation_reason_templates_20261016";
+        if let Ok(None) = self.get_metadata(rotation_reason_migration_key) {
+            info!("Applying rotation reason templates migration");
+            self.add_column_if_missing("password_rotation_history", "reason_template_id", "INTEGER")?;
+            self.set_metadata(rotation_reason_migration_key, "applied")?;
+        }
+        
+        // Decrypt failure categorisation migration
+        let decrypt_failure_migration_key = "decrypt_failure_audit_20261016";
+        if let Ok(None) = self.get_metadata(decrypt_failure_migration_key) {
+            info!("Applying decrypt failure audit migration");
+            self.add_column_if_missing("vault_access_log", "failure_reason", "TEXT")?;
+            self.set_metadata(decrypt_failure_migration_key, "applied")?;
+        }
+        
+        // Rotation grace period migration
+        let rotation_grace_migration_key = "rotation_grace_period_20261016";
+        if let Ok(None) = self.get_metadata(rotation_grace_migration_key) {
+            info!("Applying rotation grace period migration");
+            self.add_column_if_missing("rotation_schedules", "grace_period_days", "INTEGER NOT NULL DEFAULT 0")?;
+            self.set_metadata(rotation_grace_migration_key, "applied")?;
+        }
+        
+        // IP address search index migration
+        let ip_index_migration_key = "vault_ip_index_20261016";
+        if let Ok(None) = self.get_metadata(ip_index_migration_key) {
+            info!("Applying vault IP address index migration");
+            self.add_column_if_missing("vault_secrets", "ip_index", "TEXT")?;
+            self.conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_vault_secrets_ip_index ON vault_secrets(ip_index)",
+                [],
+            )?;
+            SqliteVaultRepository::new(&self.conn).rebuild_ip_index()?;
+            self.set_metadata(ip_index_migration_key, "applied")?;
+        }
+        
+        // Configuration content encryption binding migration: content sealed under the
+        // author-only key is re-encrypted under a key bound to its asset and version
+        let content_binding_migration_key = "configuration_content_binding_20261016";
+        if let Ok(None) = self.get_metadata(content_binding_migration_key) {
+            info!("Applying configuration content encryption binding migration");
+            SqliteConfigurationRepository::new(&self.conn).rebind_content_encryption()?;
+            self.set_metadata(content_binding_migration_key, "applied")?;
+        }
+        
+        // Configuration content type migration
+        let content_type_migration_key = "configuration_content_type_20261016";
+        if let Ok(None) = self.get_metadata(content_type_migration_key) {
+            info!("Applying configuration content type migration");
+            self.add_column_if_missing("configuration_versions", "content_type", "TEXT")?;
+            SqliteConfigurationRepository::new(&self.conn).backfill_content_types()?;
+            self.set_metadata(content_type_migration_key, "applied")?;
+        }
+        
+        // Auto-generated configuration notes migration
+        let auto_notes_migration_key = "configuration_auto_notes_20261016";
+        if let Ok(None) = self.get_metadata(auto_notes_migration_key) {
+            info!("Applying configuration auto-generated notes migration");
+            self.add_column_if_missing("configuration_versions", "notes_auto_generated", "BOOLEAN NOT NULL DEFAULT 0")?;
+            self.set_metadata(auto_notes_migration_key, "applied")?;
+        }
+        
+        // Pinned vault secret ordering migration
+        let secret_display_order_migration_key = "vault_secret_display_order_20261016";
+        if let Ok(None) = self.get_metadata(secret_display_order_migration_key) {
+            info!("Applying vault secret display order migration");
+            self.add_column_if_missing("vault_secrets", "display_order", "INTEGER")?;
+            self.set_metadata(secret_display_order_migration_key, "applied")?;
+        }
+        
+        // Vault secret expiry date migration
+        let secret_expiry_migration_key = "vault_secret_expiry_20261016";
+        if let Ok(None) = self.get_metadata(secret_expiry_migration_key) {
+            info!("Applying vault secret expiry date migration");
+            self.add_column_if_missing("vault_secrets", "expires_at", "DATETIME")?;
+            self.set_metadata(secret_expiry_migration_key, "applied")?;
+        }
+        
+        // Password policy character class minimums migration
+        let class_minimums_migration_key = "password_policy_class_minimums_20261016";
+        if let Ok(None) = self.get_metadata(class_minimums_migration_key) {
+            info!("Applying password policy character class minimums migration");
+            for column in ["min_uppercase", "min_lowercase", "min_numbers", "min_special"] {
+                self.add_column_if_missing("password_policies", column, "INTEGER")?;
+            }
+            self.set_metadata(class_minimums_migration_key, "applied")?;
+        }
+        
+        // Branch version content pruning migration
+        let branch_pruning_migration_key = "branch_version_pruning_20261016";
+        if let Ok(None) = self.get_metadata(branch_pruning_migration_key) {
+            info!("Applying branch version content pruning migration");
+            self.add_column_if_missing("branch_versions", "content_pruned_at", "DATETIME")?;
+            self.set_metadata(branch_pruning_migration_key, "applied")?;
+        }
+        
+        // Configuration rollback source migration
+        let rollback_source_migration_key = "configuration_rollback_source_20261016";
+        if let Ok(None) = self.get_metadata(rollback_source_migration_key) {
+            info!("Applying configuration rollback source migration");
+            self.add_column_if_missing("configuration_versions", "rolled_back_from", "INTEGER REFERENCES configuration_versions(id) ON DELETE SET NULL")?;
+            self.set_metadata(rollback_source_migration_key, "applied")?;
+        }
+        
+        // TOTP seed secret type migration
+        let totp_seed_migration_key = "vault_totp_seed_type_20261016";
+        if let Ok(None) = self.get_metadata(totp_seed_migration_key) {
+            info!("Applying vault TOTP seed secret type migration");
+            self.rebuild_table("vault_secrets", |sql| {
+                if sql.contains("'totp_seed'") {
+                    sql.to_string()
+                } else {
+                    sql.replacen("'license_file')", "'license_file', 'totp_seed')", 1)
+                }
+            })?;
+            self.set_metadata(totp_seed_migration_key, "applied")?;
+        }
+        
+        // Password history Argon2 migration: existing rows hold caller-supplied hashes
+        // that reuse checks can no longer verify the same way
+        let history_argon2_migration_key = "password_history_argon2_20261016";
+        if let Ok(None) = self.get_metadata(history_argon2_migration_key) {
+            info!("Applying password history Argon2 migration");
+            self.add_column_if_missing("password_history", "is_legacy_hash", "BOOLEAN NOT NULL DEFAULT 0")?;
+            self.conn.execute(
+                "UPDATE password_history SET is_legacy_hash = 1 WHERE password_hash NOT LIKE '$argon2id$%'",
+                [],
+            )?;
+            self.set_metadata(history_argon2_migration_key, "applied")?;
+        }
+        
+        // Audit hash chain migration: existing events are chained in id order from genesis
+        let audit_chain_migration_key = "audit_hash_chain_20261016";
+        if let Ok(None) = self.get_metadata(audit_chain_migration_key) {
+            info!("Applying audit hash chain migration");
+            self.add_column_if_missing("audit_events", "prev_hash", "TEXT")?;
+            self.add_column_if_missing("audit_events", "entry_hash", "TEXT")?;
+            SqliteAuditRepository::new(&self.conn).seal_audit_chain()?;
+            self.set_metadata(audit_chain_migration_key, "applied")?;
+        }
+        
+        // Branch merge lineage migration: branches merged before this point keep no merge edge
+        let branch_merge_migration_key = "branch_merge_lineage_20261016";
+        if let Ok(None) = self.get_metadata(branch_merge_migration_key) {
+            info!("Applying branch merge lineage migration");
+            self.add_column_if_missing("branches", "merged_version_id", "INTEGER REFERENCES configuration_versions(id) ON DELETE SET NULL")?;
+            self.set_metadata(branch_merge_migration_key, "applied")?;
+        }
+        
+        // Cron rotation schedule migration: the interval becomes optional for cron-driven schedules
+        let rotation_cron_migration_key = "rotation_cron_schedules_20261016";
+        if let Ok(None) = self.get_metadata(rotation_cron_migration_key) {
+            info!("Applying cron rotation schedule migration");
+            self.add_column_if_missing("rotation_schedules", "cron_expression", "TEXT")?;
+            self.rebuild_table("rotation_schedules", |sql| {
+                sql.replacen("rotation_interval INTEGER NOT NULL CHECK", "rotation_interval INTEGER CHECK", 1)
+            })?;
+            self.set_metadata(rotation_cron_migration_key, "applied")?;
+        }
+        
+        // Vault permission inheritance migration: existing grants stay scoped to their own vault
+        let permission_inheritance_migration_key = "vault_permission_inheritance_20261016";
+        if let Ok(None) = self.get_metadata(permission_inheritance_migration_key) {
+            info!("Applying vault permission inheritance migration");
+            self.add_column_if_missing("vault_permissions", "inheritable", "BOOLEAN NOT NULL DEFAULT 0")?;
+            self.set_metadata(permission_inheritance_migration_key, "applied")?;
+        }
+        
+        // SSH private key and certificate secret types
+        let key_material_migration_key = "vault_key_material_types_20261016";
+        if let Ok(None) = self.get_metadata(key_material_migration_key) {
+            info!("Applying vault SSH key and certificate secret type migration");
+            self.rebuild_table("vault_secrets", |sql| {
+                if sql.contains("'certificate'") {
+                    sql.to_string()
+                } else {
+                    sql.replacen("'totp_seed')", "'totp_seed', 'ssh_private_key', 'certificate')", 1)
+                }
+            })?;
+            self.add_column_if_missing("vault_secrets", "certificate_subject", "TEXT")?;
+            self.add_column_if_missing("vault_secrets", "certificate_not_after", "DATETIME")?;
+            self.set_metadata(key_material_migration_key, "applied")?;
+        }
+        
+        // Configuration import provenance migration
+        let import_provenance_migration_key = "configuration_import_provenance_20261016";
+        if let Ok(None) = self.get_metadata(import_provenance_migration_key) {
+            info!("Applying configuration import provenance migration");
+            self.add_column_if_missing("configuration_versions", "source_system", "TEXT")?;
+            self.add_column_if_missing("configuration_versions", "source_reference", "TEXT")?;
+            self.set_metadata(import_provenance_migration_key, "applied")?;
+        }
+        
+        // Password policy minimum strength score migration
+        let min_strength_migration_key = "password_policy_min_strength_20261016";
+        if let Ok(None) = self.get_metadata(min_strength_migration_key) {
+            info!("Applying password policy minimum strength score migration");
+            self.add_column_if_missing("password_policies", "min_strength_score", "INTEGER")?;
+            self.set_metadata(min_strength_migration_key, "applied")?;
+        }
+        
+        // Per-vault password policy migration
+        let vault_policy_migration_key = "vault_password_policy_20261016";
+        if let Ok(None) = self.get_metadata(vault_policy_migration_key) {
+            info!("Applying per-vault password policy migration");
+            self.add_column_if_missing("password_policies", "vault_id", "INTEGER REFERENCES vault_entries(id) ON DELETE CASCADE")?;
+            self.conn.execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_password_policies_vault_id ON password_policies(vault_id)",
+                [],
+            )?;
+            self.set_metadata(vault_policy_migration_key, "applied")?;
+        }
+        
+        // Vault key protection migration: the IP index key is now derived from the wrapped
+        // master key, so the index is rebuilt and the separately stored key dropped
+        let key_protection_migration_key = "vault_key_protection_20261016";
+        if let Ok(None) = self.get_metadata(key_protection_migration_key) {
+            info!("Applying vault key protection migration");
+            SqliteVaultRepository::new(&self.conn).rebuild_ip_index()?;
+            self.conn.execute("DROP TABLE IF EXISTS vault_search_index_key", [])?;
+            self.set_metadata(key_protection_migration_key, "applied")?;
+        }
+        
+        // Password history digest migration: existing rows keep no digest and are matched
+        // by verifying their hash until deduplication backfills one
+        let history_digest_migration_key = "password_history_digest_20261016";
+        if let Ok(None) = self.get_metadata(history_digest_migration_key) {
+            info!("Applying password history digest migration");
+            self.add_column_if_missing("password_history", "password_digest", "TEXT")?;
+            self.conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_password_history_digest ON password_history(password_digest)",
+                [],
+            )?;
+            self.set_metadata(history_digest_migration_key, "applied")?;
+        }
+        
+        // Password reuse digest migration: secrets get their value digest the first time a
+        // reuse scan has to decrypt them
+        let reuse_digest_migration_key = "password_reuse_digest_20261016";
+        if let Ok(None) = self.get_metadata(reuse_digest_migration_key) {
+            info!("Applying password reuse digest migration");
+            self.add_column_if_missing("vault_secrets", "password_digest", "TEXT")?;
+            self.conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_vault_secrets_password_digest ON vault_secrets(password_digest)",
+                [],
+            )?;
+            self.set_metadata(reuse_digest_migration_key, "applied")?;
+        }
+        
+        Ok(())
+    }
+
+    /// Add a column to an existing table when upgrading a database created before the column existed
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let column_count: i32 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+            [column],
+            |row| row.get(0),
+        )?;
+        
+        if column_count == 0 {
+            self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+            info!("Added column {} to {} table", column, table);
+        }
+        
+        Ok(())
+    }
+
+    /// Recreate a table from its stored definition as edited by `rewrite`, for changes ALTER TABLE
+    /// cannot make such as widening a CHECK constraint. Rows, indexes and triggers are carried over.
+    fn rebuild_table(&self, table: &str, rewrite: impl Fn(&str) -> String) -> Result<()> {
+        let create_sql: String = self.conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get(0),
+        )?;
+        let rewritten = rewrite(&create_sql);
+        if rewritten == create_sql {
+            return Ok(());
+        }
+
+        let dependents: Vec<String> = self.conn.prepare(
+            "SELECT sql FROM sqlite_master WHERE tbl_name = ?1 AND type IN ('index', 'trigger') AND sql IS NOT NULL"
+        )?.query_map([table], |row| row.get(0))?.collect::<Result<Vec<_>, _>>()?;
+
+        let rebuilt_table = format!("{}_rebuild", table);
+        let rebuilt_sql = rewritten.replacen(table, &rebuilt_table, 1);
+
+        // Dropping the old table while foreign keys are enforced would cascade into child tables
+        self.conn.execute_batch("PRAGMA foreign_keys = OFF")?;
+        let result = (|| -> Result<()> {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute_batch(&rebuilt_sql)?;
+            tx.execute(&format!("INSERT INTO {} SELECT * FROM {}", rebuilt_table, table), [])?;
+            tx.execute(&format!("DROP TABLE {}", table), [])?;
+            tx.execute(&format!("ALTER TABLE {} RENAME TO {}", rebuilt_table, table), [])?;
+            for sql in &dependents {
+                tx.execute_batch(sql)?;
+            }
+            tx.commit()?;
+            Ok(())
+        })();
+        self.conn.execute_batch("PRAGMA foreign_keys = ON")?;
+        result?;
+
+        info!("Rebuilt {} table with updated definition", table);
         Ok(())
     }
 }
@@ -295,4 +628,34 @@ mod tests {
         let empty_value = db.get_metadata("non_existent").unwrap();
         assert_eq!(empty_value, None);
     }
+
+    #[test]
+    fn test_rebuild_table_keeps_rows_and_children() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Database::new(temp_file.path().to_path_buf()).unwrap();
+
+        db.conn.execute_batch(
+            "CREATE TABLE widgets (id INTEGER PRIMARY KEY, kind TEXT NOT NULL CHECK(kind IN ('a', 'b')));
+             CREATE INDEX idx_widgets_kind ON widgets(kind);
+             CREATE TABLE widget_parts (id INTEGER PRIMARY KEY, widget_id INTEGER NOT NULL,
+                 FOREIGN KEY (widget_id) REFERENCES widgets(id) ON DELETE CASCADE);
+             INSERT INTO widgets (id, kind) VALUES (1, 'a');
+             INSERT INTO widget_parts (widget_id) VALUES (1);"
+        ).unwrap();
+        assert!(db.conn.execute("INSERT INTO widgets (kind) VALUES ('c')", []).is_err());
+
+        db.rebuild_table("widgets", |sql| sql.replacen("'b')", "'b', 'c')", 1)).unwrap();
+
+        db.conn.execute("INSERT INTO widgets (kind) VALUES ('c')", []).unwrap();
+        let widgets: i64 = db.conn.query_row("SELECT COUNT(*) FROM widgets", [], |row| row.get(0)).unwrap();
+        let parts: i64 = db.conn.query_row("SELECT COUNT(*) FROM widget_parts", [], |row| row.get(0)).unwrap();
+        let index: i64 = db.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_widgets_kind'", [], |row| row.get(0)
+        ).unwrap();
+        assert_eq!(widgets, 2);
+        assert_eq!(parts, 1);
+        assert_eq!(index, 1);
+        let foreign_keys: i64 = db.conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert_eq!(foreign_keys, 1);
+    }
 }
\ No newline at end of file
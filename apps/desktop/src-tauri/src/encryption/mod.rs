@@ -5,6 +5,7 @@ use aes_gcm::{
     Aes256Gcm, Nonce, Key
 };
 use pbkdf2::{password_hash::{PasswordHasher, SaltString}, Pbkdf2};
+use zeroize::{Zeroize, Zeroizing};
 
 // AES-256-GCM encryption implementation for vault security
 pub struct FileEncryption {
@@ -15,12 +16,29 @@ impl FileEncryption {
     pub fn new(key: &str) -> Self {
         // Generate a proper AES-256 key using PBKDF2 with salt
         let key_bytes = Self::derive_key_from_string(key);
-        let cipher_key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+        Self::from_key_bytes(&key_bytes)
+    }
+
+    fn from_key_bytes(key_bytes: &[u8; 32]) -> Self {
+        let cipher_key = Key::<Aes256Gcm>::from_slice(key_bytes);
         let cipher = Aes256Gcm::new(cipher_key);
         
         Self { cipher }
     }
 
+    /// Key for a single salted value: HMAC-SHA256 over the salt and key context under the master key
+    fn derive_salted(master_key: &[u8], salt: &[u8], context: &str) -> Result<Self> {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(master_key)
+            .map_err(|e| anyhow::anyhow!("Invalid master key: {}", e))?;
+        mac.update(salt);
+        mac.update(context.as_bytes());
+        let key_bytes = Zeroizing::new(<[u8; 32]>::from(mac.finalize().into_bytes()));
+        Ok(Self::from_key_bytes(&key_bytes))
+    }
+
     /// Derive a 256-bit (32 byte) key from string using PBKDF2
     fn derive_key_from_string(key_str: &str) -> [u8; 32] {
         // Use a static salt for consistency within application
@@ -77,6 +95,185 @@ impl FileEncryption {
         
         Ok(plaintext)
     }
+
+    /// Decrypts into a buffer that is zeroed when dropped, for plaintext that must not
+    /// linger in memory such as secret values and configuration content
+    pub fn decrypt_zeroizing(&self, encrypted_data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        self.decrypt(encrypted_data).map(Zeroizing::new)
+    }
+
+    /// Starts a chunked ciphertext on `inner`; see `ChunkedEncryptor`
+    pub fn chunked_writer<W: Write>(&self, mut inner: W) -> Result<ChunkedEncryptor<W>> {
+        let nonce_prefix: [u8; CHUNK_NONCE_PREFIX_LEN] = rand::random();
+        inner.write_all(CHUNKED_FORMAT_TAG)?;
+        inner.write_all(&nonce_prefix)?;
+
+        Ok(ChunkedEncryptor {
+            cipher: self.cipher.clone(),
+            nonce_prefix,
+            counter: 0,
+            buffer: Zeroizing::new(Vec::with_capacity(ENCRYPTION_CHUNK_SIZE)),
+            inner,
+        })
+    }
+
+    /// Opens a ciphertext written by `ChunkedEncryptor`
+    pub fn decrypt_chunked(&self, encrypted_data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        if !is_chunked_ciphertext(encrypted_data) {
+            return Err(anyhow::anyhow!("Invalid encrypted data: not in chunked format"));
+        }
+
+        let header_len = CHUNKED_FORMAT_TAG.len() + CHUNK_NONCE_PREFIX_LEN;
+        let nonce_prefix = &encrypted_data[CHUNKED_FORMAT_TAG.len()..header_len];
+        let mut remaining = &encrypted_data[header_len..];
+        // Sized up front so growing it never leaves a copy of the plaintext behind in freed memory
+        let mut plaintext = Zeroizing::new(Vec::with_capacity(encrypted_data.len()));
+        let mut counter: u32 = 0;
+
+        loop {
+            if remaining.len() < 4 {
+                return Err(anyhow::anyhow!("Invalid encrypted data: truncated chunk"));
+            }
+            let (length, rest) = remaining.split_at(4);
+            let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+            if rest.len() < length {
+                return Err(anyhow::anyhow!("Invalid encrypted data: truncated chunk"));
+            }
+            let (record, rest) = rest.split_at(length);
+
+            // Only the record that ends the data may carry the final flag, so dropped chunks fail to open
+            let last = rest.is_empty();
+            let nonce = chunk_nonce(nonce_prefix, counter, last);
+            let chunk = Zeroizing::new(self.cipher
+                .decrypt(Nonce::from_slice(&nonce), record)
+                .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?);
+            plaintext.extend_from_slice(&chunk);
+
+            if last {
+                return Ok(plaintext);
+            }
+            remaining = rest;
+            counter = counter.checked_add(1)
+                .ok_or_else(|| anyhow::anyhow!("Invalid encrypted data: too many chunks"))?;
+        }
+    }
+
+    /// Opens stored content in either the single-shot or the chunked format
+    pub fn decrypt_stored(&self, encrypted_data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        // A single-shot ciphertext can start with the chunked tag by chance, so fall back
+        if is_chunked_ciphertext(encrypted_data) {
+            if let Ok(plaintext) = self.decrypt_chunked(encrypted_data) {
+                return Ok(plaintext);
+            }
+        }
+        self.decrypt_zeroizing(encrypted_data)
+    }
+}
+
+/// Plaintext bytes sealed per record by `ChunkedEncryptor`
+pub const ENCRYPTION_CHUNK_SIZE: usize = 64 * 1024;
+/// Leading bytes of values written by `ChunkedEncryptor`
+const CHUNKED_FORMAT_TAG: &[u8; 4] = b"FCK1";
+const CHUNK_NONCE_PREFIX_LEN: usize = 7;
+
+/// Nonce of one record: the random prefix, the record counter and a flag marking the final record
+fn chunk_nonce(prefix: &[u8], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..CHUNK_NONCE_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[CHUNK_NONCE_PREFIX_LEN..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// Encrypts a stream in `ENCRYPTION_CHUNK_SIZE` records so only one chunk of plaintext is held
+/// at a time. Output is the format tag and nonce prefix, then each record as a big-endian
+/// length and its AES-256-GCM ciphertext. Call `finish` to seal the final record.
+pub struct ChunkedEncryptor<W: Write> {
+    cipher: Aes256Gcm,
+    nonce_prefix: [u8; CHUNK_NONCE_PREFIX_LEN],
+    counter: u32,
+    buffer: Zeroizing<Vec<u8>>,
+    inner: W,
+}
+
+impl<W: Write> ChunkedEncryptor<W> {
+    fn seal_chunk(&mut self, last: bool) -> std::io::Result<()> {
+        let nonce = chunk_nonce(&self.nonce_prefix, self.counter, last);
+        let ciphertext = self.cipher
+            .encrypt(Nonce::from_slice(&nonce), self.buffer.as_slice())
+            .map_err(|e| std::io::Error::other(format!("Encryption failed: {}", e)))?;
+
+        self.inner.write_all(&(ciphertext.len() as u32).to_be_bytes())?;
+        self.inner.write_all(&ciphertext)?;
+        // Wipe the sealed plaintext rather than leave it in the spare capacity for the next chunk
+        self.buffer.zeroize();
+        self.counter = self.counter.checked_add(1)
+            .ok_or_else(|| std::io::Error::other("Too many chunks to encrypt"))?;
+        Ok(())
+    }
+
+    /// Seals the buffered remainder as the final record and returns the inner writer
+    pub fn finish(mut self) -> Result<W> {
+        self.seal_chunk(true)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ChunkedEncryptor<W> {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        // A full chunk is sealed only once more data arrives, so the final record is never a spare empty one
+        if self.buffer.len() == ENCRYPTION_CHUNK_SIZE {
+            self.seal_chunk(false)?;
+        }
+        let taken = data.len().min(ENCRYPTION_CHUNK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&data[..taken]);
+        Ok(taken)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Whether `encrypted_data` starts like the output of `ChunkedEncryptor`
+pub fn is_chunked_ciphertext(encrypted_data: &[u8]) -> bool {
+    encrypted_data.len() > CHUNKED_FORMAT_TAG.len() + CHUNK_NONCE_PREFIX_LEN && encrypted_data.starts_with(CHUNKED_FORMAT_TAG)
+}
+
+/// Leading bytes of values sealed with `encrypt_with_salt`. Values without it are the
+/// older nonce-and-ciphertext format keyed by a context string alone.
+const SALTED_FORMAT_TAG: &[u8; 4] = b"FCS1";
+const SALT_LEN: usize = 16;
+
+/// Encrypts `data` under a key derived from `master_key`, a fresh random salt and `context`,
+/// which names the owner of the value. The output is the format tag, the salt, the nonce
+/// and the ciphertext, so equal plaintexts never share a key or ciphertext.
+pub fn encrypt_with_salt(master_key: &[u8], context: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let sealed = FileEncryption::derive_salted(master_key, &salt, context)?.encrypt(data)?;
+
+    let mut result = Vec::with_capacity(SALTED_FORMAT_TAG.len() + SALT_LEN + sealed.len());
+    result.extend_from_slice(SALTED_FORMAT_TAG);
+    result.extend_from_slice(&salt);
+    result.extend_from_slice(&sealed);
+    Ok(result)
+}
+
+/// Opens a value produced by `encrypt_with_salt` with the same master key and context
+pub fn decrypt_with_salt(master_key: &[u8], context: &str, encrypted_data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    if !is_salted_ciphertext(encrypted_data) {
+        return Err(anyhow::anyhow!("Invalid encrypted data: not in salted format"));
+    }
+
+    let (salt, sealed) = encrypted_data[SALTED_FORMAT_TAG.len()..].split_at(SALT_LEN);
+    FileEncryption::derive_salted(master_key, salt, context)?.decrypt_zeroizing(sealed)
+}
+
+/// Whether `encrypted_data` looks like the output of `encrypt_with_salt`. A legacy value can
+/// start with the same bytes by chance, so callers still fall back when opening fails.
+pub fn is_salted_ciphertext(encrypted_data: &[u8]) -> bool {
+    encrypted_data.len() > SALTED_FORMAT_TAG.len() + SALT_LEN && encrypted_data.starts_with(SALTED_FORMAT_TAG)
 }
 
 // Key derivation utilities using proper cryptographic practices
@@ -165,6 +362,92 @@ mod tests {
         assert!(encryption.decrypt(&corrupted).is_err());
     }
 
+    #[test]
+    fn test_decrypt_zeroizing_round_trip() {
+        let encryption = FileEncryption::new("test_key");
+        let encrypted = encryption.encrypt(b"plc admin password").unwrap();
+
+        let decrypted: Zeroizing<Vec<u8>> = encryption.decrypt_zeroizing(&encrypted).unwrap();
+        assert_eq!(decrypted.as_slice(), b"plc admin password");
+        assert!(encryption.decrypt_zeroizing(&encrypted[..8]).is_err());
+    }
+
+    #[test]
+    fn test_chunked_encryptor_wipes_sealed_chunks() {
+        let encryption = FileEncryption::new("test_key");
+        let mut writer = encryption.chunked_writer(Vec::new()).unwrap();
+        writer.write_all(&vec![0x5a; ENCRYPTION_CHUNK_SIZE]).unwrap();
+        writer.write_all(b"tail").unwrap();
+
+        // Sealing the first chunk must leave none of its plaintext in the reused buffer
+        assert_eq!(writer.buffer.as_slice(), b"tail");
+        let allocation = unsafe { std::slice::from_raw_parts(writer.buffer.as_ptr(), writer.buffer.capacity()) };
+        assert!(allocation[4..].iter().all(|&byte| byte == 0));
+
+        let ciphertext = writer.finish().unwrap();
+        let decrypted = encryption.decrypt_chunked(&ciphertext).unwrap();
+        assert_eq!(decrypted.len(), ENCRYPTION_CHUNK_SIZE + 4);
+        assert!(decrypted.capacity() <= ciphertext.len());
+    }
+
+    #[test]
+    fn test_salted_encryption_round_trip() {
+        let master_key = [9u8; 32];
+        let encrypted = encrypt_with_salt(&master_key, "vault_1_1", b"plc admin password").unwrap();
+
+        assert!(is_salted_ciphertext(&encrypted));
+        let decrypted = decrypt_with_salt(&master_key, "vault_1_1", &encrypted).unwrap();
+        assert_eq!(decrypted.as_slice(), b"plc admin password");
+
+        // Both the master key and the context are needed to open the value
+        assert!(decrypt_with_salt(&[8u8; 32], "vault_1_1", &encrypted).is_err());
+        assert!(decrypt_with_salt(&master_key, "vault_1_2", &encrypted).is_err());
+
+        // The context string alone no longer yields the key
+        assert!(FileEncryption::new("vault_1_1").decrypt(&encrypted).is_err());
+        assert!(!is_salted_ciphertext(&FileEncryption::new("vault_1_1").encrypt(b"legacy").unwrap()));
+    }
+
+    #[test]
+    fn test_salted_encryption_differs_for_identical_plaintext() {
+        let master_key = [9u8; 32];
+        let first = encrypt_with_salt(&master_key, "vault_1_1", b"same value").unwrap();
+        let second = encrypt_with_salt(&master_key, "vault_1_1", b"same value").unwrap();
+
+        assert_ne!(first, second);
+        assert_ne!(first[4..20], second[4..20]);
+        assert_eq!(decrypt_with_salt(&master_key, "vault_1_1", &first).unwrap().as_slice(), b"same value");
+        assert_eq!(decrypt_with_salt(&master_key, "vault_1_1", &second).unwrap().as_slice(), b"same value");
+    }
+
+    #[test]
+    fn test_chunked_encryption_round_trip() {
+        let encryption = FileEncryption::new("test_key");
+        let data: Vec<u8> = (0..ENCRYPTION_CHUNK_SIZE * 2 + 100).map(|i| (i % 251) as u8).collect();
+
+        for plaintext in [&data[..], &data[..ENCRYPTION_CHUNK_SIZE], &[][..]] {
+            let mut writer = encryption.chunked_writer(Vec::new()).unwrap();
+            writer.write_all(plaintext).unwrap();
+            let sealed = writer.finish().unwrap();
+
+            assert!(is_chunked_ciphertext(&sealed));
+            assert_eq!(encryption.decrypt_chunked(&sealed).unwrap().as_slice(), plaintext);
+            assert_eq!(encryption.decrypt_stored(&sealed).unwrap().as_slice(), plaintext);
+        }
+
+        // Dropping the final record is detected rather than returning a prefix
+        let mut writer = encryption.chunked_writer(Vec::new()).unwrap();
+        writer.write_all(&data).unwrap();
+        let sealed = writer.finish().unwrap();
+        let first_record_end = 11 + 4 + ENCRYPTION_CHUNK_SIZE + 16;
+        assert!(encryption.decrypt_chunked(&sealed[..first_record_end]).is_err());
+        assert!(FileEncryption::new("other_key").decrypt_chunked(&sealed).is_err());
+
+        // Single-shot ciphertexts still open through decrypt_stored
+        let single = encryption.encrypt(b"legacy").unwrap();
+        assert_eq!(encryption.decrypt_stored(&single).unwrap().as_slice(), b"legacy");
+    }
+
     #[test]
     fn test_key_derivation() {
         let key1 = derive_key_from_user_credentials(1, "user1");
@@ -94,6 +94,30 @@ pub struct UpdateFirmwareStatusRequest {
     pub reason: Option<String>,
 }
 
+/// A firmware image and configuration file imported together for one asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmwareConfigImportRequest {
+    pub asset_id: i64,
+    pub firmware_path: String,
+    pub config_path: String,
+    pub firmware_version: String,
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub notes: Option<String>,
+    /// Link the new configuration version to the new firmware
+    pub link_firmware: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmwareConfigImport {
+    pub firmware_id: i64,
+    pub config_version_id: i64,
+    /// Firmware linked to the configuration version, if linking was requested
+    pub linked_firmware_id: Option<i64>,
+}
+
 pub trait FirmwareRepository {
     fn create_firmware(&self, request: CreateFirmwareRequest, author_id: i64, file_path: String, file_hash: String, file_size: i64) -> Result<FirmwareVersion>;
     fn get_firmware_by_asset(&self, asset_id: i64) -> Result<Vec<FirmwareVersionInfo>>;
@@ -106,6 +130,7 @@ pub trait FirmwareRepository {
     fn promote_firmware_to_golden(&self, firmware_id: i64, user_id: i64, reason: String) -> Result<()>;
     fn update_firmware_notes(&self, firmware_id: i64, notes: String) -> Result<()>;
     fn update_firmware_file_path(&self, firmware_id: i64, file_path: String) -> Result<()>;
+    fn replace_firmware(&self, firmware_dir: &std::path::Path, firmware_id: i64, request: CreateFirmwareRequest, author: i64, username: &str, firmware_data: &[u8]) -> Result<FirmwareVersion>;
 }
 
 pub struct SqliteFirmwareRepository<'a> {
@@ -187,34 +212,8 @@ impl<'a> SqliteFirmwareRepository<'a> {
         })
     }
 
-    fn row_to_firmware_info(row: &Row) -> rusqlite::Result<FirmwareVersionInfo> {
-        Ok(FirmwareVersionInfo {
-            id: row.get("id")?,
-            asset_id: row.get("asset_id")?,
-            author_id: row.get("author_id")?,
-            author_username: row.get("author_username")?,
-            vendor: row.get("vendor")?,
-            model: row.get("model")?,
-            version: row.get("version")?,
-            notes: row.get("notes")?,
-            status: match row.get::<_, String>("status")?.as_str() {
-                "Approved" => FirmwareStatus::Approved,
-                "Golden" => FirmwareStatus::Golden,
-                "Archived" => FirmwareStatus::Archived,
-                _ => FirmwareStatus::Draft,
-            },
-            file_path: row.get("file_path")?,
-            file_hash: row.get("file_hash")?,
-            file_size: row.get("file_size")?,
-            created_at: row.get("created_at")?,
-        })
-    }
-}
-
-impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
-    fn create_firmware(&self, request: CreateFirmwareRequest, author_id: i64, file_path: String, file_hash: String, file_size: i64) -> Result<FirmwareVersion> {
-        let tx = self.conn.unchecked_transaction()?;
-        
+    /// Inserts a firmware row on the current connection without opening a transaction
+    fn insert_firmware(&self, request: CreateFirmwareRequest, author_id: i64, file_path: String, file_hash: String, file_size: i64) -> Result<FirmwareVersion> {
         let firmware = FirmwareVersion {
             id: 0, // Will be set by database
             asset_id: request.asset_id,
@@ -232,7 +231,7 @@ impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
             created_at: String::new(), // Will be set by database
         };
 
-        tx.execute(
+        self.conn.execute(
             "INSERT INTO firmware_versions (asset_id, author_id, vendor, model, version, notes, status, file_path, file_hash, file_size)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             rusqlite::params![
@@ -249,8 +248,7 @@ impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
             ],
         )?;
 
-        let id = tx.last_insert_rowid();
-        tx.commit()?;
+        let id = self.conn.last_insert_rowid();
 
         Ok(FirmwareVersion {
             id,
@@ -258,6 +256,127 @@ impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
         })
     }
 
+    /// Stores a firmware image and a configuration file as one unit. The database rows and
+    /// the optional firmware link are written in a single transaction, and the encrypted
+    /// firmware file under `firmware_dir` is removed again if any later step fails.
+    pub fn import_firmware_and_config(&self, firmware_dir: &std::path::Path, request: FirmwareConfigImportRequest, author: i64) -> Result<FirmwareConfigImport> {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository, CreateConfigurationRequest, file_utils};
+
+        let firmware_data = file_utils::read_firmware_file_content(&request.firmware_path)?;
+        let max_upload_bytes = SqliteConfigurationRepository::new(self.conn).get_max_upload_bytes()?;
+        let config_content = file_utils::read_file_content(&request.config_path, max_upload_bytes)?;
+        let config_file_name = std::path::Path::new(&request.config_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        // Firmware files are encrypted with a key bound to the uploader's id and username
+        let username: String = self.conn.query_row(
+            "SELECT username FROM users WHERE id = ?1",
+            [author],
+            |row| row.get(0),
+        ).optional()?
+            .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut stored_file: Option<String> = None;
+
+        let result = (|| -> Result<FirmwareConfigImport> {
+            let mut hasher = Sha256::new();
+            hasher.update(&firmware_data);
+            let firmware = self.insert_firmware(
+                CreateFirmwareRequest {
+                    asset_id: request.asset_id,
+                    vendor: request.vendor.clone(),
+                    model: request.model.clone(),
+                    version: request.firmware_version.clone(),
+                    notes: request.notes.clone(),
+                },
+                author,
+                String::new(),
+                format!("{:x}", hasher.finalize()),
+                firmware_data.len() as i64,
+            )?;
+
+            let (file_path, _, _) = FirmwareFileStorage::store_firmware_file_in(
+                firmware_dir, request.asset_id, firmware.id, &firmware_data, author, &username,
+            )?;
+            stored_file = Some(file_path.clone());
+            self.update_firmware_file_path(firmware.id, file_path)?;
+
+            let config_repo = SqliteConfigurationRepository::new(self.conn);
+            let config = config_repo.store_configuration(CreateConfigurationRequest {
+                asset_id: request.asset_id,
+                file_name: config_file_name,
+                file_content: config_content,
+                author,
+                notes: request.notes.clone().unwrap_or_default(),
+            })?;
+
+            let linked_firmware_id = if request.link_firmware {
+                config_repo.link_firmware_to_configuration(config.id, firmware.id)?;
+                Some(firmware.id)
+            } else {
+                None
+            };
+
+            Ok(FirmwareConfigImport {
+                firmware_id: firmware.id,
+                config_version_id: config.id,
+                linked_firmware_id,
+            })
+        })();
+
+        match result {
+            Ok(import) => {
+                tx.commit()?;
+                tracing::info!("Imported firmware {} and configuration {} together for asset {}",
+                    import.firmware_id, import.config_version_id, request.asset_id);
+                Ok(import)
+            }
+            Err(e) => {
+                if let Some(file_path) = stored_file {
+                    let _ = fs::remove_file(firmware_dir.join(file_path));
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn row_to_firmware_info(row: &Row) -> rusqlite::Result<FirmwareVersionInfo> {
+        Ok(FirmwareVersionInfo {
+            id: row.get("id")?,
+            asset_id: row.get("asset_id")?,
+            author_id: row.get("author_id")?,
+            author_username: row.get("author_username")?,
+            vendor: row.get("vendor")?,
+            model: row.get("model")?,
+            version: row.get("version")?,
+            notes: row.get("notes")?,
+            status: match row.get::<_, String>("status")?.as_str() {
+                "Approved" => FirmwareStatus::Approved,
+                "Golden" => FirmwareStatus::Golden,
+                "Archived" => FirmwareStatus::Archived,
+                _ => FirmwareStatus::Draft,
+            },
+            file_path: row.get("file_path")?,
+            file_hash: row.get("file_hash")?,
+            file_size: row.get("file_size")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+}
+
+impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
+    fn create_firmware(&self, request: CreateFirmwareRequest, author_id: i64, file_path: String, file_hash: String, file_size: i64) -> Result<FirmwareVersion> {
+        let tx = self.conn.unchecked_transaction()?;
+        let firmware = self.insert_firmware(request, author_id, file_path, file_hash, file_size)?;
+        tx.commit()?;
+
+        Ok(firmware)
+    }
+
     fn get_firmware_by_asset(&self, asset_id: i64) -> Result<Vec<FirmwareVersionInfo>> {
         let mut stmt = self.conn.prepare(
             "SELECT f.id, f.asset_id, f.author_id, u.username as author_username, 
@@ -495,9 +614,74 @@ impl<'a> FirmwareRepository for SqliteFirmwareRepository<'a> {
             "UPDATE firmware_versions SET file_path = ?1 WHERE id = ?2",
             rusqlite::params![file_path, firmware_id],
         )?;
-        
+
+        // The new path is already saved, so a failed re-validation is logged rather than returned
+        if let Err(e) = crate::associations::on_associated_file_changed(self.conn, firmware_id, &crate::associations::AssociationType::Firmware) {
+            tracing::warn!("Failed to re-validate associations of firmware {}: {}", firmware_id, e);
+        }
         Ok(())
     }
+
+    /// Swaps firmware `firmware_id` for a new image. The row delete and insert share one
+    /// transaction, the new file is removed again if any step fails, and the replaced
+    /// file is only removed once the swap is committed.
+    fn replace_firmware(&self, firmware_dir: &std::path::Path, firmware_id: i64, request: CreateFirmwareRequest, author: i64, username: &str, firmware_data: &[u8]) -> Result<FirmwareVersion> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut stored_file: Option<String> = None;
+
+        let result = (|| -> Result<(FirmwareVersion, String)> {
+            let old_path: String = tx.query_row(
+                "SELECT file_path FROM firmware_versions WHERE id = ?1",
+                [firmware_id],
+                |row| row.get(0),
+            ).optional()?
+                .ok_or_else(|| anyhow::anyhow!("Firmware not found"))?;
+            tx.execute("DELETE FROM firmware_versions WHERE id = ?1", [firmware_id])?;
+
+            let mut hasher = Sha256::new();
+            hasher.update(firmware_data);
+            let asset_id = request.asset_id;
+            let firmware = self.insert_firmware(
+                request,
+                author,
+                String::new(),
+                format!("{:x}", hasher.finalize()),
+                firmware_data.len() as i64,
+            )?;
+
+            let (file_path, _, _) = FirmwareFileStorage::store_firmware_file_in(
+                firmware_dir, asset_id, firmware.id, firmware_data, author, username,
+            )?;
+            stored_file = Some(file_path.clone());
+            self.update_firmware_file_path(firmware.id, file_path.clone())?;
+
+            Ok((FirmwareVersion { file_path, ..firmware }, old_path))
+        })();
+
+        match result {
+            Ok((firmware, old_path)) => {
+                if let Err(e) = tx.commit() {
+                    if let Some(file_path) = stored_file {
+                        let _ = fs::remove_file(firmware_dir.join(file_path));
+                    }
+                    return Err(e.into());
+                }
+                if !old_path.is_empty() && old_path != firmware.file_path {
+                    if let Err(e) = fs::remove_file(firmware_dir.join(&old_path)) {
+                        tracing::warn!("Failed to remove replaced firmware file {}: {}", old_path, e);
+                    }
+                }
+                tracing::info!("Replaced firmware {} with firmware {}", firmware_id, firmware.id);
+                Ok(firmware)
+            }
+            Err(e) => {
+                if let Some(file_path) = stored_file {
+                    let _ = fs::remove_file(firmware_dir.join(file_path));
+                }
+                Err(e)
+            }
+        }
+    }
 }
 
 pub fn get_firmware_storage_dir(app_handle: &AppHandle) -> Result<PathBuf> {
@@ -523,6 +707,20 @@ impl FirmwareFileStorage {
         file_data: &[u8],
         user_id: i64,
         username: &str,
+    ) -> Result<(String, String, i64)> {
+        // Get firmware storage directory
+        let firmware_dir = get_firmware_storage_dir(app_handle)?;
+        Self::store_firmware_file_in(&firmware_dir, asset_id, firmware_id, file_data, user_id, username)
+    }
+
+    /// Same as `store_firmware_file`, writing below an explicit storage directory
+    pub fn store_firmware_file_in(
+        firmware_dir: &std::path::Path,
+        asset_id: i64,
+        firmware_id: i64,
+        file_data: &[u8],
+        user_id: i64,
+        username: &str,
     ) -> Result<(String, String, i64)> {
         // Validate file size (max 2GB as specified in requirements)
         const MAX_FILE_SIZE: usize = 2 * 1024 * 1024 * 1024; // 2GB
@@ -530,9 +728,6 @@ impl FirmwareFileStorage {
             return Err(anyhow::anyhow!("File size exceeds 2GB limit"));
         }
 
-        // Get firmware storage directory
-        let firmware_dir = get_firmware_storage_dir(app_handle)?;
-        
         // Create asset-specific subdirectory
         let asset_dir = firmware_dir.join(asset_id.to_string());
         fs::create_dir_all(&asset_dir)?;
@@ -607,6 +802,14 @@ impl FirmwareFileStorage {
     ) -> Result<()> {
         // Get firmware storage directory
         let firmware_dir = get_firmware_storage_dir(app_handle)?;
+        Self::delete_firmware_file_in(&firmware_dir, file_path)
+    }
+
+    /// Same as `delete_firmware_file`, removing from an explicit storage directory
+    pub fn delete_firmware_file_in(
+        firmware_dir: &std::path::Path,
+        file_path: &str,
+    ) -> Result<()> {
         let full_path = firmware_dir.join(file_path);
         
         // Delete file if it exists
@@ -1110,4 +1313,90 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Only approved firmware"));
     }
-}
\ No newline at end of file
+
+    fn co_import_request(firmware_path: &std::path::Path, config_path: &std::path::Path, link_firmware: bool) -> FirmwareConfigImportRequest {
+        FirmwareConfigImportRequest {
+            asset_id: 1,
+            firmware_path: firmware_path.to_str().unwrap().to_string(),
+            config_path: config_path.to_str().unwrap().to_string(),
+            firmware_version: "2.1.0".to_string(),
+            vendor: Some("Siemens".to_string()),
+            model: None,
+            notes: Some("Commissioning bundle".to_string()),
+            link_firmware,
+        }
+    }
+
+    #[test]
+    fn test_import_firmware_and_config_links_both() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository};
+        use tempfile::TempDir;
+
+        let conn = setup_test_db();
+        SqliteConfigurationRepository::new(&conn).initialize_schema().unwrap();
+        let repo = SqliteFirmwareRepository::new(&conn);
+
+        let temp_dir = TempDir::new().unwrap();
+        let firmware_dir = temp_dir.path().join("firmware");
+        let firmware_path = temp_dir.path().join("plc.bin");
+        let config_path = temp_dir.path().join("plc.cfg");
+        fs::write(&firmware_path, b"firmware image").unwrap();
+        fs::write(&config_path, b"setpoint=10").unwrap();
+
+        let import = repo.import_firmware_and_config(
+            &firmware_dir, co_import_request(&firmware_path, &config_path, true), 1,
+        ).unwrap();
+        assert_eq!(import.linked_firmware_id, Some(import.firmware_id));
+
+        let firmware = repo.get_firmware_by_id(import.firmware_id).unwrap().unwrap();
+        assert_eq!(firmware.version, "2.1.0");
+        assert_eq!(firmware.file_size, 14);
+        let stored = fs::read(firmware_dir.join(&firmware.file_path)).unwrap();
+        let encryption = FileEncryption::new(&derive_key_from_user_credentials(1, "testuser"));
+        assert_eq!(encryption.decrypt(&stored).unwrap(), b"firmware image");
+
+        let config_repo = SqliteConfigurationRepository::new(&conn);
+        let config = config_repo.get_configuration_by_id(import.config_version_id).unwrap().unwrap();
+        assert_eq!(config.file_name, "plc.cfg");
+        let linked: Option<i64> = conn.query_row(
+            "SELECT firmware_version_id FROM configuration_versions WHERE id = ?1",
+            [import.config_version_id],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(linked, Some(import.firmware_id));
+
+        // Linking is optional
+        let unlinked = repo.import_firmware_and_config(
+            &firmware_dir, co_import_request(&firmware_path, &config_path, false), 1,
+        ).unwrap();
+        assert_eq!(unlinked.linked_firmware_id, None);
+        assert_eq!(repo.get_linked_configuration_count(unlinked.firmware_id).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_firmware_and_config_is_atomic() {
+        use crate::configurations::{SqliteConfigurationRepository, ConfigurationRepository};
+        use tempfile::TempDir;
+
+        let conn = setup_test_db();
+        SqliteConfigurationRepository::new(&conn).initialize_schema().unwrap();
+        let repo = SqliteFirmwareRepository::new(&conn);
+
+        let temp_dir = TempDir::new().unwrap();
+        let firmware_dir = temp_dir.path().join("firmware");
+        let firmware_path = temp_dir.path().join("plc.bin");
+        let config_path = temp_dir.path().join("plc.cfg");
+        fs::write(&firmware_path, b"firmware image").unwrap();
+        // An empty configuration is rejected after the firmware has been stored
+        fs::write(&config_path, b"").unwrap();
+
+        let result = repo.import_firmware_and_config(
+            &firmware_dir, co_import_request(&firmware_path, &config_path, true), 1,
+        );
+        assert!(result.is_err());
+
+        assert!(repo.get_firmware_by_asset(1).unwrap().is_empty());
+        assert_eq!(SqliteConfigurationRepository::new(&conn).get_configuration_count(1).unwrap(), 0);
+        assert!(!firmware_dir.join("1").exists() || fs::read_dir(firmware_dir.join("1")).unwrap().next().is_none());
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::firmware_analysis::models::{FirmwareAnalysisResult, SecurityFinding, SecuritySeverity};
+
+/// Differences between the analyses of two firmware versions. Side `a` is the
+/// baseline (typically the current golden) and side `b` the candidate, so
+/// "added" means present in `b` but not in `a`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirmwareAnalysisDiff {
+    pub firmware_id_a: i64,
+    pub firmware_id_b: i64,
+    pub file_type_a: Option<String>,
+    pub file_type_b: Option<String>,
+    pub file_type_changed: bool,
+    pub file_size_a: Option<i64>,
+    pub file_size_b: Option<i64>,
+    pub file_size_delta: Option<i64>,
+    pub entropy_a: Option<f64>,
+    pub entropy_b: Option<f64>,
+    pub entropy_delta: Option<f64>,
+    pub added_findings: Vec<SecurityFinding>,
+    pub removed_findings: Vec<SecurityFinding>,
+    pub unchanged_finding_count: usize,
+    pub added_versions: Vec<String>,
+    pub removed_versions: Vec<String>,
+    pub added_components: Vec<String>,
+    pub removed_components: Vec<String>,
+}
+
+impl FirmwareAnalysisDiff {
+    pub fn has_changes(&self) -> bool {
+        self.file_type_changed
+            || self.file_size_delta.is_some_and(|delta| delta != 0)
+            || self.entropy_delta.is_some_and(|delta| delta != 0.0)
+            || !self.added_findings.is_empty()
+            || !self.removed_findings.is_empty()
+            || !self.added_versions.is_empty()
+            || !self.removed_versions.is_empty()
+            || !self.added_components.is_empty()
+            || !self.removed_components.is_empty()
+    }
+}
+
+/// Compares two analyses. Findings are matched on type and severity rather than
+/// offset, since the same issue usually moves between builds.
+pub fn compare_analyses(
+    a: &FirmwareAnalysisResult,
+    file_size_a: Option<i64>,
+    b: &FirmwareAnalysisResult,
+    file_size_b: Option<i64>,
+) -> FirmwareAnalysisDiff {
+    let findings_a = a.security_findings.clone().unwrap_or_default();
+    let findings_b = b.security_findings.clone().unwrap_or_default();
+    let (added_findings, removed_findings, unchanged_finding_count) = diff_findings(&findings_a, &findings_b);
+
+    let versions_a: BTreeSet<String> = a.detected_versions.clone().unwrap_or_default().into_iter().collect();
+    let versions_b: BTreeSet<String> = b.detected_versions.clone().unwrap_or_default().into_iter().collect();
+
+    let components_a = extract_components(a.raw_results.as_deref());
+    let components_b = extract_components(b.raw_results.as_deref());
+
+    FirmwareAnalysisDiff {
+        firmware_id_a: a.firmware_version_id,
+        firmware_id_b: b.firmware_version_id,
+        file_type_changed: a.file_type != b.file_type,
+        file_type_a: a.file_type.clone(),
+        file_type_b: b.file_type.clone(),
+        file_size_a,
+        file_size_b,
+        file_size_delta: file_size_a.zip(file_size_b).map(|(size_a, size_b)| size_b - size_a),
+        entropy_a: a.entropy_score,
+        entropy_b: b.entropy_score,
+        entropy_delta: a.entropy_score.zip(b.entropy_score).map(|(entropy_a, entropy_b)| entropy_b - entropy_a),
+        added_findings,
+        removed_findings,
+        unchanged_finding_count,
+        added_versions: versions_b.difference(&versions_a).cloned().collect(),
+        removed_versions: versions_a.difference(&versions_b).cloned().collect(),
+        added_components: components_b.difference(&components_a).cloned().collect(),
+        removed_components: components_a.difference(&components_b).cloned().collect(),
+    }
+}
+
+fn finding_key(finding: &SecurityFinding) -> (String, SecuritySeverity) {
+    (finding.finding_type.clone(), finding.severity.clone())
+}
+
+/// Multiset difference keyed on finding type and severity
+fn diff_findings(a: &[SecurityFinding], b: &[SecurityFinding]) -> (Vec<SecurityFinding>, Vec<SecurityFinding>, usize) {
+    let mut remaining_a: HashMap<(String, SecuritySeverity), usize> = HashMap::new();
+    for finding in a {
+        *remaining_a.entry(finding_key(finding)).or_default() += 1;
+    }
+
+    let mut added = Vec::new();
+    let mut unchanged = 0;
+    for finding in b {
+        match remaining_a.get_mut(&finding_key(finding)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                unchanged += 1;
+            }
+            _ => added.push(finding.clone()),
+        }
+    }
+
+    let mut removed = Vec::new();
+    for finding in a {
+        if let Some(count) = remaining_a.get_mut(&finding_key(finding)) {
+            if *count > 0 {
+                *count -= 1;
+                removed.push(finding.clone());
+            }
+        }
+    }
+
+    (added, removed, unchanged)
+}
+
+/// Component names from the binwalk signatures stored in the raw results
+fn extract_components(raw_results: Option<&str>) -> BTreeSet<String> {
+    raw_results
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|value| value.get("signatures").and_then(|s| s.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|signature| signature.get("description").and_then(|d| d.as_str()))
+        .map(|description| description.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::firmware_analysis::models::AnalysisStatus;
+
+    fn analysis(firmware_id: i64, findings: Vec<(&str, SecuritySeverity, u64)>, versions: &[&str], components: &[&str], entropy: f64) -> FirmwareAnalysisResult {
+        let signatures: Vec<_> = components.iter()
+            .map(|c| serde_json::json!({"offset": 0, "description": c, "size": 0}))
+            .collect();
+
+        FirmwareAnalysisResult {
+            id: firmware_id,
+            firmware_version_id: firmware_id,
+            analysis_status: AnalysisStatus::Completed,
+            file_type: Some("ELF".to_string()),
+            detected_versions: Some(versions.iter().map(|v| v.to_string()).collect()),
+            entropy_score: Some(entropy),
+            security_findings: Some(findings.into_iter().map(|(finding_type, severity, offset)| SecurityFinding {
+                severity,
+                finding_type: finding_type.to_string(),
+                description: format!("Found {} at offset {:#x}", finding_type, offset),
+                offset: Some(offset),
+            }).collect()),
+            raw_results: Some(serde_json::json!({"signatures": signatures}).to_string()),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            created_at: String::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compare_categorizes_findings() {
+        let golden = analysis(1, vec![
+            ("Hardcoded Password", SecuritySeverity::High, 0x10),
+            ("Debug Interface", SecuritySeverity::Medium, 0x20),
+        ], &["1.0.0"], &["gzip compressed data", "squashfs filesystem"], 5.5);
+        let candidate = analysis(2, vec![
+            // Same issue at a different offset still counts as unchanged
+            ("Debug Interface", SecuritySeverity::Medium, 0x80),
+            ("Private Key", SecuritySeverity::Critical, 0x40),
+        ], &["1.1.0"], &["squashfs filesystem", "uImage header"], 7.0);
+
+        let diff = compare_analyses(&golden, Some(1000), &candidate, Some(1500));
+
+        assert_eq!(diff.added_findings.len(), 1);
+        assert_eq!(diff.added_findings[0].finding_type, "Private Key");
+        assert_eq!(diff.removed_findings.len(), 1);
+        assert_eq!(diff.removed_findings[0].finding_type, "Hardcoded Password");
+        assert_eq!(diff.unchanged_finding_count, 1);
+
+        assert_eq!(diff.file_size_delta, Some(500));
+        assert_eq!(diff.entropy_delta, Some(1.5));
+        assert!(!diff.file_type_changed);
+        assert_eq!(diff.added_versions, vec!["1.1.0".to_string()]);
+        assert_eq!(diff.removed_versions, vec!["1.0.0".to_string()]);
+        assert_eq!(diff.added_components, vec!["uImage header".to_string()]);
+        assert_eq!(diff.removed_components, vec!["gzip compressed data".to_string()]);
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn test_compare_matches_repeated_findings_by_count() {
+        let a = analysis(1, vec![("Telnet", SecuritySeverity::Low, 1)], &[], &[], 4.0);
+        let b = analysis(2, vec![("Telnet", SecuritySeverity::Low, 2), ("Telnet", SecuritySeverity::Low, 3)], &[], &[], 4.0);
+
+        let diff = compare_analyses(&a, None, &b, None);
+        assert_eq!(diff.added_findings.len(), 1);
+        assert!(diff.removed_findings.is_empty());
+        assert_eq!(diff.unchanged_finding_count, 1);
+        assert_eq!(diff.file_size_delta, None);
+
+        // A severity change is reported as one finding removed and one added
+        let c = analysis(3, vec![("Telnet", SecuritySeverity::High, 1)], &[], &[], 4.0);
+        let diff = compare_analyses(&a, None, &c, None);
+        assert_eq!(diff.added_findings.len(), 1);
+        assert_eq!(diff.removed_findings.len(), 1);
+
+        let unchanged = compare_analyses(&a, Some(10), &a, Some(10));
+        assert!(!unchanged.has_changes());
+    }
+}
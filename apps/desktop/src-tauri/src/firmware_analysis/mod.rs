@@ -1,9 +1,11 @@
 pub mod analyzer;
+pub mod comparison;
 pub mod models;
 pub mod repository;
 pub mod queue;
 
 pub use analyzer::FirmwareAnalyzer;
-pub use models::{FirmwareAnalysisResult, AnalysisStatus};
+pub use comparison::FirmwareAnalysisDiff;
+pub use models::{FirmwareAnalysisResult, AnalysisStatus, AnalysisStage, AnalysisError};
 pub use repository::{FirmwareAnalysisRepository, SqliteFirmwareAnalysisRepository};
 pub use queue::{AnalysisQueue, AnalysisJob};
\ No newline at end of file
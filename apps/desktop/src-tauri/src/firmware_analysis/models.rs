@@ -15,6 +15,8 @@ pub struct FirmwareAnalysisResult {
     pub completed_at: Option<String>,
     pub error_message: Option<String>,
     pub created_at: String,
+    #[serde(default)]
+    pub errors: Vec<AnalysisError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -49,6 +51,52 @@ impl From<String> for AnalysisStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisStage {
+    Setup,
+    ReadFile,
+    Analyze,
+    SaveResults,
+}
+
+impl std::fmt::Display for AnalysisStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisStage::Setup => write!(f, "setup"),
+            AnalysisStage::ReadFile => write!(f, "read_file"),
+            AnalysisStage::Analyze => write!(f, "analyze"),
+            AnalysisStage::SaveResults => write!(f, "save_results"),
+        }
+    }
+}
+
+impl std::str::FromStr for AnalysisStage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "setup" => Ok(AnalysisStage::Setup),
+            "read_file" => Ok(AnalysisStage::ReadFile),
+            "analyze" => Ok(AnalysisStage::Analyze),
+            "save_results" => Ok(AnalysisStage::SaveResults),
+            _ => Err(anyhow::anyhow!("Invalid analysis stage: {}", s)),
+        }
+    }
+}
+
+/// A single recorded failure from an analysis run, kept so operators can see
+/// where analysis broke down and decide whether a retry is worthwhile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisError {
+    pub id: i64,
+    pub analysis_id: i64,
+    pub stage: AnalysisStage,
+    pub message: String,
+    pub occurred_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SecurityFinding {
@@ -58,7 +106,7 @@ pub struct SecurityFinding {
     pub offset: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum SecuritySeverity {
     Info,
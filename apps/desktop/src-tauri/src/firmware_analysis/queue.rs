@@ -11,7 +11,7 @@ use crate::database::Database;
 use crate::firmware::{FirmwareRepository, SqliteFirmwareRepository, FirmwareFileStorage};
 use crate::firmware_analysis::{
     FirmwareAnalyzer, FirmwareAnalysisRepository, SqliteFirmwareAnalysisRepository,
-    AnalysisStatus
+    AnalysisStatus, AnalysisStage
 };
 use crate::audit::{AuditRepository, SqliteAuditRepository, AuditEventRequest, AuditEventType};
 
@@ -165,12 +165,20 @@ impl AnalysisQueue {
         Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::InProgress, Some(20), Some("Reading firmware file"));
         
         // Read firmware file (outside of db lock)
-        let firmware_data = FirmwareFileStorage::read_firmware_file(
+        let firmware_data = match FirmwareFileStorage::read_firmware_file(
             &app_handle,
             &firmware_path,
             job.user_id,
             &job.username
-        )?;
+        ) {
+            Ok(data) => data,
+            Err(e) => {
+                let error_msg = format!("Failed to read firmware file: {}", e);
+                Self::record_failure(&app_handle, analysis_id, AnalysisStage::ReadFile, &error_msg);
+                Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::Failed, None, Some(&error_msg));
+                return Err(e);
+            }
+        };
         
         Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::InProgress, Some(40), Some("Analyzing firmware"));
         
@@ -183,7 +191,7 @@ impl AnalysisQueue {
                 let security_findings_json = serde_json::to_string(&results.security_findings)?;
                 
                 // Update analysis results in database (reacquire lock)
-                {
+                let save_result = {
                     let db_state = app_handle.state::<Mutex<Option<Database>>>();
                     let db_guard = db_state.lock().unwrap();
                     let db = db_guard.as_ref()
@@ -198,9 +206,14 @@ impl AnalysisQueue {
                         Some(results.entropy_score),
                         Some(security_findings_json),
                         Some(results.raw_binwalk_output.clone()),
-                    )?;
-                    
-                    analysis_repo.update_analysis_status(analysis_id, AnalysisStatus::Completed, None)?;
+                    ).and_then(|_| analysis_repo.update_analysis_status(analysis_id, AnalysisStatus::Completed, None))
+                };
+                
+                if let Err(e) = save_result {
+                    let error_msg = format!("Failed to save analysis results: {}", e);
+                    Self::record_failure(&app_handle, analysis_id, AnalysisStage::SaveResults, &error_msg);
+                    Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::Failed, None, Some(&error_msg));
+                    return Err(e);
                 }
                 
                 Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::Completed, Some(100), Some("Analysis completed"));
@@ -239,15 +252,8 @@ impl AnalysisQueue {
             Err(e) => {
                 let error_msg = format!("Analysis failed: {}", e);
                 
-                // Update status in database (reacquire lock)
-                {
-                    let db_state = app_handle.state::<Mutex<Option<Database>>>();
-                    let db_guard = db_state.lock().unwrap();
-                    if let Some(db) = db_guard.as_ref() {
-                        let analysis_repo = SqliteFirmwareAnalysisRepository::new(db.get_connection());
-                        let _ = analysis_repo.update_analysis_status(analysis_id, AnalysisStatus::Failed, Some(error_msg.clone()));
-                    }
-                }
+                // Update status and record the error in database (reacquire lock)
+                Self::record_failure(&app_handle, analysis_id, AnalysisStage::Analyze, &error_msg);
                 
                 Self::emit_progress_event(&app_handle, &job.firmware_id, AnalysisStatus::Failed, None, Some(&error_msg));
                 
@@ -287,6 +293,24 @@ impl AnalysisQueue {
         Ok(())
     }
     
+    /// Mark the analysis as failed and keep a structured record of which stage broke
+    fn record_failure(
+        app_handle: &AppHandle,
+        analysis_id: i64,
+        stage: AnalysisStage,
+        error_msg: &str,
+    ) {
+        let db_state = app_handle.state::<Mutex<Option<Database>>>();
+        let db_guard = db_state.lock().unwrap();
+        if let Some(db) = db_guard.as_ref() {
+            let analysis_repo = SqliteFirmwareAnalysisRepository::new(db.get_connection());
+            let _ = analysis_repo.update_analysis_status(analysis_id, AnalysisStatus::Failed, Some(error_msg.to_string()));
+            if let Err(e) = analysis_repo.record_analysis_error(analysis_id, stage, error_msg) {
+                error!("Failed to record analysis error: {}", e);
+            }
+        }
+    }
+    
     fn emit_progress_event(
         app_handle: &AppHandle,
         firmware_id: &i64,
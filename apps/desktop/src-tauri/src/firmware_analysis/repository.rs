@@ -1,6 +1,7 @@
 use anyhow::Result;
 use rusqlite::{Connection, Row};
-use crate::firmware_analysis::models::{FirmwareAnalysisResult, AnalysisStatus};
+use crate::firmware_analysis::models::{FirmwareAnalysisResult, AnalysisStatus, AnalysisStage, AnalysisError};
+use crate::firmware_analysis::comparison::{compare_analyses, FirmwareAnalysisDiff};
 
 pub trait FirmwareAnalysisRepository {
     fn initialize_schema(&self) -> Result<()>;
@@ -17,6 +18,9 @@ pub trait FirmwareAnalysisRepository {
     ) -> Result<()>;
     fn get_analysis_by_firmware_id(&self, firmware_version_id: i64) -> Result<Option<FirmwareAnalysisResult>>;
     fn get_analysis_by_id(&self, id: i64) -> Result<Option<FirmwareAnalysisResult>>;
+    fn record_analysis_error(&self, analysis_id: i64, stage: AnalysisStage, message: &str) -> Result<i64>;
+    fn get_analysis_errors(&self, analysis_id: i64) -> Result<Vec<AnalysisError>>;
+    fn compare_firmware_analysis(&self, firmware_id_a: i64, firmware_id_b: i64) -> Result<FirmwareAnalysisDiff>;
 }
 
 pub struct SqliteFirmwareAnalysisRepository<'a> {
@@ -44,8 +48,62 @@ impl<'a> SqliteFirmwareAnalysisRepository<'a> {
             completed_at: row.get("completed_at")?,
             error_message: row.get("error_message")?,
             created_at: row.get("created_at")?,
+            errors: Vec::new(),
         })
     }
+
+    fn row_to_analysis_error(row: &Row) -> rusqlite::Result<AnalysisError> {
+        Ok(AnalysisError {
+            id: row.get("id")?,
+            analysis_id: row.get("analysis_id")?,
+            stage: row.get::<_, String>("stage")?.parse::<AnalysisStage>().map_err(|e| rusqlite::Error::FromSqlConversionFailure(
+                0,
+                rusqlite::types::Type::Text,
+                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())),
+            ))?,
+            message: row.get("message")?,
+            occurred_at: row.get("occurred_at")?,
+        })
+    }
+
+    /// Completed analysis for a firmware, required before it can be compared
+    fn completed_analysis(&self, firmware_version_id: i64) -> Result<FirmwareAnalysisResult> {
+        let analysis = self.get_analysis_by_firmware_id(firmware_version_id)?
+            .ok_or_else(|| anyhow::anyhow!("No analysis found for firmware {}", firmware_version_id))?;
+
+        if analysis.analysis_status != AnalysisStatus::Completed {
+            return Err(anyhow::anyhow!(
+                "Analysis for firmware {} is {} and cannot be compared",
+                firmware_version_id, analysis.analysis_status
+            ));
+        }
+
+        Ok(analysis)
+    }
+
+    fn firmware_file_size(&self, firmware_version_id: i64) -> Result<Option<i64>> {
+        let result = self.conn.query_row(
+            "SELECT file_size FROM firmware_versions WHERE id = ?1",
+            [firmware_version_id],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(file_size) => Ok(Some(file_size)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn with_errors(&self, analysis: Option<FirmwareAnalysisResult>) -> Result<Option<FirmwareAnalysisResult>> {
+        match analysis {
+            Some(mut analysis) => {
+                analysis.errors = self.get_analysis_errors(analysis.id)?;
+                Ok(Some(analysis))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a> FirmwareAnalysisRepository for SqliteFirmwareAnalysisRepository<'a> {
@@ -68,8 +126,18 @@ impl<'a> FirmwareAnalysisRepository for SqliteFirmwareAnalysisRepository<'a> {
                 FOREIGN KEY (firmware_version_id) REFERENCES firmware_versions(id) ON DELETE CASCADE
             );
             
+            CREATE TABLE IF NOT EXISTS firmware_analysis_errors (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                analysis_id INTEGER NOT NULL,
+                stage TEXT NOT NULL CHECK(stage IN ('setup', 'read_file', 'analyze', 'save_results')),
+                message TEXT NOT NULL,
+                occurred_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (analysis_id) REFERENCES firmware_analysis_results(id) ON DELETE CASCADE
+            );
+            
             CREATE INDEX IF NOT EXISTS idx_analysis_firmware_id ON firmware_analysis_results(firmware_version_id);
             CREATE INDEX IF NOT EXISTS idx_analysis_status ON firmware_analysis_results(analysis_status);
+            CREATE INDEX IF NOT EXISTS idx_analysis_errors_analysis_id ON firmware_analysis_errors(analysis_id);
             "#
         )?;
         Ok(())
@@ -177,7 +245,7 @@ impl<'a> FirmwareAnalysisRepository for SqliteFirmwareAnalysisRepository<'a> {
         
         let result = stmt.query_row([firmware_version_id], Self::row_to_analysis_result);
         match result {
-            Ok(analysis) => Ok(Some(analysis)),
+            Ok(analysis) => self.with_errors(Some(analysis)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
@@ -194,11 +262,44 @@ impl<'a> FirmwareAnalysisRepository for SqliteFirmwareAnalysisRepository<'a> {
         
         let result = stmt.query_row([id], Self::row_to_analysis_result);
         match result {
-            Ok(analysis) => Ok(Some(analysis)),
+            Ok(analysis) => self.with_errors(Some(analysis)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
     }
+    
+    fn record_analysis_error(&self, analysis_id: i64, stage: AnalysisStage, message: &str) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO firmware_analysis_errors (analysis_id, stage, message)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![analysis_id, stage.to_string(), message],
+        )?;
+        
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    fn get_analysis_errors(&self, analysis_id: i64) -> Result<Vec<AnalysisError>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, analysis_id, stage, message, occurred_at 
+             FROM firmware_analysis_errors 
+             WHERE analysis_id = ?1 
+             ORDER BY occurred_at DESC, id DESC"
+        )?;
+        
+        let errors = stmt.query_map([analysis_id], Self::row_to_analysis_error)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        
+        Ok(errors)
+    }
+    
+    fn compare_firmware_analysis(&self, firmware_id_a: i64, firmware_id_b: i64) -> Result<FirmwareAnalysisDiff> {
+        let analysis_a = self.completed_analysis(firmware_id_a)?;
+        let analysis_b = self.completed_analysis(firmware_id_b)?;
+        let file_size_a = self.firmware_file_size(firmware_id_a)?;
+        let file_size_b = self.firmware_file_size(firmware_id_b)?;
+
+        Ok(compare_analyses(&analysis_a, file_size_a, &analysis_b, file_size_b))
+    }
 }
 
 #[cfg(test)]
@@ -217,11 +318,12 @@ mod tests {
                 asset_id INTEGER NOT NULL,
                 author_id INTEGER NOT NULL,
                 version TEXT NOT NULL,
+                file_size INTEGER NOT NULL DEFAULT 0,
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP
             );
             
-            INSERT INTO firmware_versions (asset_id, author_id, version) 
-            VALUES (1, 1, '1.0.0');
+            INSERT INTO firmware_versions (asset_id, author_id, version, file_size) 
+            VALUES (1, 1, '1.0.0', 4096);
             "#
         ).unwrap();
         
@@ -306,4 +408,109 @@ mod tests {
         assert!(analysis.is_some());
         assert_eq!(analysis.unwrap().firmware_version_id, 1);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_failed_analysis_records_structured_error() {
+        let conn = setup_test_db();
+        let repo = SqliteFirmwareAnalysisRepository::new(&conn);
+        
+        let id = repo.create_analysis(1).unwrap();
+        repo.update_analysis_status(id, AnalysisStatus::InProgress, None).unwrap();
+        
+        // Simulate the analyzer failing part way through a run
+        repo.update_analysis_status(id, AnalysisStatus::Failed, Some("Analysis failed: truncated image".to_string())).unwrap();
+        repo.record_analysis_error(id, AnalysisStage::Analyze, "Analysis failed: truncated image").unwrap();
+        
+        let analysis = repo.get_analysis_by_firmware_id(1).unwrap().unwrap();
+        assert_eq!(analysis.analysis_status, AnalysisStatus::Failed);
+        assert_eq!(analysis.errors.len(), 1);
+        
+        let error = &analysis.errors[0];
+        assert_eq!(error.analysis_id, id);
+        assert_eq!(error.stage, AnalysisStage::Analyze);
+        assert_eq!(error.message, "Analysis failed: truncated image");
+        assert!(!error.occurred_at.is_empty());
+    }
+    
+    #[test]
+    fn test_analysis_errors_accumulate_across_retries() {
+        let conn = setup_test_db();
+        let repo = SqliteFirmwareAnalysisRepository::new(&conn);
+        
+        let id = repo.create_analysis(1).unwrap();
+        repo.record_analysis_error(id, AnalysisStage::ReadFile, "Firmware file not found").unwrap();
+        repo.record_analysis_error(id, AnalysisStage::Analyze, "Unsupported format").unwrap();
+        
+        // A successful retry keeps the earlier failures for reference
+        repo.update_analysis_status(id, AnalysisStatus::Completed, None).unwrap();
+        
+        let analysis = repo.get_analysis_by_id(id).unwrap().unwrap();
+        assert_eq!(analysis.analysis_status, AnalysisStatus::Completed);
+        assert_eq!(analysis.errors.len(), 2);
+        // Most recent error first
+        assert_eq!(analysis.errors[0].stage, AnalysisStage::Analyze);
+        assert_eq!(analysis.errors[1].stage, AnalysisStage::ReadFile);
+        
+        // No errors recorded for a fresh analysis
+        let other = repo.get_analysis_errors(id + 1).unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn test_analysis_stage_parsing() {
+        for stage in [AnalysisStage::Setup, AnalysisStage::ReadFile, AnalysisStage::Analyze, AnalysisStage::SaveResults] {
+            assert_eq!(stage.to_string().parse::<AnalysisStage>().unwrap(), stage);
+        }
+        assert!("unpack".parse::<AnalysisStage>().is_err());
+    }
+    
+    #[test]
+    fn test_compare_firmware_analysis() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO firmware_versions (asset_id, author_id, version, file_size) VALUES (1, 1, '1.1.0', 6144)",
+            [],
+        ).unwrap();
+        let repo = SqliteFirmwareAnalysisRepository::new(&conn);
+        
+        let golden_id = repo.create_analysis(1).unwrap();
+        repo.update_analysis_results(
+            golden_id,
+            Some("ELF".to_string()),
+            Some(vec!["1.0.0".to_string()]),
+            Some(6.0),
+            Some(r#"[{"severity":"high","findingType":"Hardcoded Password","description":"at 0x10","offset":16}]"#.to_string()),
+            Some(r#"{"signatures":[{"offset":0,"description":"gzip compressed data","size":0}]}"#.to_string()),
+        ).unwrap();
+        repo.update_analysis_status(golden_id, AnalysisStatus::Completed, None).unwrap();
+        
+        let candidate_id = repo.create_analysis(2).unwrap();
+        
+        // Comparing against an unfinished analysis is rejected
+        let error = repo.compare_firmware_analysis(1, 2).unwrap_err().to_string();
+        assert!(error.contains("pending"), "unexpected error: {}", error);
+        assert!(repo.compare_firmware_analysis(1, 3).is_err());
+        
+        repo.update_analysis_results(
+            candidate_id,
+            Some("ELF".to_string()),
+            Some(vec!["1.1.0".to_string()]),
+            Some(7.0),
+            Some(r#"[{"severity":"critical","findingType":"Private Key","description":"at 0x40","offset":64}]"#.to_string()),
+            Some(r#"{"signatures":[{"offset":0,"description":"gzip compressed data","size":0},{"offset":512,"description":"squashfs filesystem","size":0}]}"#.to_string()),
+        ).unwrap();
+        repo.update_analysis_status(candidate_id, AnalysisStatus::Completed, None).unwrap();
+        
+        let diff = repo.compare_firmware_analysis(1, 2).unwrap();
+        assert_eq!(diff.firmware_id_a, 1);
+        assert_eq!(diff.firmware_id_b, 2);
+        assert_eq!(diff.added_findings.len(), 1);
+        assert_eq!(diff.added_findings[0].finding_type, "Private Key");
+        assert_eq!(diff.removed_findings.len(), 1);
+        assert_eq!(diff.removed_findings[0].finding_type, "Hardcoded Password");
+        assert_eq!(diff.file_size_delta, Some(2048));
+        assert_eq!(diff.entropy_delta, Some(1.0));
+        assert_eq!(diff.added_components, vec!["squashfs filesystem".to_string()]);
+        assert!(diff.removed_components.is_empty());
+    }
+}
@@ -40,7 +40,7 @@ impl AuthHandler {
         let user = user_repo.create_user(request)
             .map_err(|e| format!("Failed to create admin account: {}", e))?;
 
-        let session = session_manager.create_session(&user)
+        let session = session_manager.create_session_with_source(&user, "account_setup")
             .map_err(|e| format!("Failed to create session: {}", e))?;
 
         info!("Created admin account for user: {}", username);
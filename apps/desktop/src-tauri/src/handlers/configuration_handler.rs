@@ -200,7 +200,7 @@ impl ConfigurationHandler {
             .ok_or("Branch has no versions to promote".to_string())?;
         
         // Get the configuration content
-        let content = config_repo.get_configuration_content(latest_version.version_id)
+        let mut content = config_repo.get_configuration_content(latest_version.version_id)
             .map_err(|e| format!("Failed to get configuration content: {}", e))?;
         
         // Create a new configuration version in the main line with Silver status
@@ -214,7 +214,7 @@ impl ConfigurationHandler {
         let config_request = CreateConfigurationRequest {
             asset_id: branch.asset_id,
             file_name: latest_version.file_name.clone(),
-            file_content: content,
+            file_content: std::mem::take(&mut *content),
             author: user_id,
             notes,
         };
@@ -265,6 +265,7 @@ impl ConfigurationHandler {
         db: &Database,
         version_id: i64,
         export_path: &str,
+        exported_by: i64,
     ) -> Result<(), String> {
         // Validate export path
         if export_path.trim().is_empty() {
@@ -280,7 +281,7 @@ impl ConfigurationHandler {
         
         let start_time = std::time::Instant::now();
         
-        match config_repo.export_configuration_version(version_id, export_path) {
+        match config_repo.export_configuration_version(version_id, export_path, exported_by) {
             Ok(_) => {
                 let duration = start_time.elapsed();
                 
@@ -24,6 +24,9 @@ impl SystemHandler {
         let db_path = app_data_dir.join("ferrocodex.db");
         info!("Database path: {:?}", db_path);
         info!("Database exists before creation: {}", db_path.exists());
+
+        crate::vault::master_key::load_key_encryption_key()
+            .map_err(|e| format!("Failed to load the vault key-encryption key: {}", e))?;
         
         let db = Database::new(db_path)
             .map_err(|e| format!("Failed to create database: {}", e))?;
@@ -8,7 +8,7 @@ use crate::validation::InputSanitizer;
 use crate::vault::{
     VaultRepository, SqliteVaultRepository, CreateVaultRequest, AddSecretRequest, VaultInfo,
     IdentityVault, GeneratePasswordRequest, UpdateCredentialPasswordRequest, UpdateVaultSecretRequest,
-    DeleteVaultSecretRequest, PasswordStrength, PasswordHistory, PasswordGenerator, PasswordStrengthAnalyzer,
+    DeleteVaultSecretRequest, PasswordStrength, PasswordHistory, PasswordStrengthAnalyzer,
     CreateStandaloneCredentialRequest, UpdateStandaloneCredentialRequest, SearchCredentialsRequest,
     CreateCategoryRequest, StandaloneCredentialInfo, CategoryWithChildren,
     PermissionType, VaultAccessInfo, GrantVaultAccessRequest, VaultPermission, RevokeVaultAccessRequest,
@@ -19,7 +19,6 @@ use crate::vault::{
         UpdateRotationScheduleRequest
     }
 };
-use crate::encryption::FileEncryption;
 use crate::database::Database;
 use rusqlite::Connection;
 use std::sync::{Arc, Mutex};
@@ -119,6 +118,7 @@ impl VaultHandler {
             label: sanitized_label,
             value: sanitized_value,
             author_id: user_id,
+            expires_at: secret_request.expires_at,
         };
 
         match vault_repo.add_secret(sanitized_request) {
@@ -147,35 +147,16 @@ impl VaultHandler {
         match vault_repo.get_secret_by_id(secret_id) {
             Ok(Some(secret)) => {
                 // Decrypt the secret value
-                let encryption = FileEncryption::new(&format!("vault_{}_{}", vault_id, user_id));
-                use base64::{Engine as _, engine::general_purpose};
-                
-                match general_purpose::STANDARD.decode(&secret.encrypted_value) {
-                    Ok(encrypted_bytes) => {
-                        match encryption.decrypt(&encrypted_bytes) {
-                            Ok(decrypted_bytes) => {
-                                match String::from_utf8(decrypted_bytes) {
-                                    Ok(decrypted_value) => {
-                                        info!(
-                                            "Secret decrypted for user {}: {} ({})",
-                                            username, secret.label, secret.secret_type.to_string()
-                                        );
-                                        Ok(decrypted_value)
-                                    }
-                                    Err(e) => {
-                                        error!("Failed to convert decrypted bytes to string: {}", e);
-                                        Err("Failed to decrypt secret".to_string())
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                error!("Failed to decrypt secret: {}", e);
-                                Err("Failed to decrypt secret".to_string())
-                            }
-                        }
+                match vault_repo.decrypt_secret_value(secret_id, vault_id, user_id) {
+                    Ok(decrypted_value) => {
+                        info!(
+                            "Secret decrypted for user {}: {} ({})",
+                            username, secret.label, secret.secret_type.to_string()
+                        );
+                        Ok(decrypted_value.as_str().to_owned())
                     }
                     Err(e) => {
-                        error!("Failed to decode base64 encrypted value: {}", e);
+                        error!("Failed to decrypt secret: {}", e);
                         Err("Failed to decrypt secret".to_string())
                     }
                 }
@@ -251,16 +232,7 @@ impl VaultHandler {
         username: &str,
         vault_repo: &SqliteVaultRepository,
     ) -> Result<bool, String> {
-        // Hash the password for comparison
-        let password_hash = match PasswordGenerator::hash_password(password) {
-            Ok(hash) => hash,
-            Err(e) => {
-                error!("Failed to hash password for reuse check: {}", e);
-                return Err("Failed to process password".to_string());
-            }
-        };
-
-        match vault_repo.check_password_reuse(&password_hash, exclude_secret_id) {
+        match vault_repo.check_password_reuse(password, exclude_secret_id) {
             Ok(is_reused) => {
                 info!("Password reuse check by {}: {}", username, is_reused);
                 Ok(is_reused)
@@ -284,17 +256,8 @@ impl VaultHandler {
         // Analyze password strength
         let strength = PasswordStrengthAnalyzer::analyze(&request.new_password);
         
-        // Hash password for storage
-        let password_hash = match PasswordGenerator::hash_password(&request.new_password) {
-            Ok(hash) => hash,
-            Err(e) => {
-                error!("Failed to hash password: {}", e);
-                return Err("Failed to process password".to_string());
-            }
-        };
-
         // Check for password reuse
-        match vault_repo.check_password_reuse(&password_hash, Some(request.secret_id)) {
+        match vault_repo.check_password_reuse(&request.new_password, Some(request.secret_id)) {
             Ok(is_reused) => {
                 if is_reused {
                     return Err("Password has been used before on another credential".to_string());
@@ -306,7 +269,7 @@ impl VaultHandler {
             }
         }
         
-        match vault_repo.update_password(request, &password_hash, strength.score) {
+        match vault_repo.update_password(request, strength.score) {
             Ok(()) => {
                 info!("Password updated by {} for secret with strength score {}", username, strength.score);
                 Ok(())
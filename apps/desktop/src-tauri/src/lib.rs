@@ -8,7 +8,7 @@ mod auth;
 mod audit;
 mod validation;
 mod assets;
-mod configurations;
+pub mod configurations;
 mod encryption;
 mod branches;
 mod firmware;
@@ -30,9 +30,9 @@ mod handlers;
 
 // Re-export database and state types for use in commands
 use database::Database;
-use auth::{SessionManager, LoginAttemptTracker};
+use auth::{SessionManager, LoginAttemptTracker, CommandPolicy};
 use validation::RateLimiter;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 use tauri::State;
 use tracing::info;
@@ -43,6 +43,7 @@ pub type DatabaseState = Mutex<Option<Database>>;
 pub type SessionManagerState = Mutex<SessionManager>;
 pub type LoginAttemptTrackerState = Mutex<LoginAttemptTracker>;
 pub type RateLimiterState = Mutex<RateLimiter>;
+pub type CommandPolicyState = RwLock<CommandPolicy>;
 
 
 // Main application entry point
@@ -58,6 +59,7 @@ pub fn run() {
         .manage(SessionManagerState::default())
         .manage(LoginAttemptTrackerState::default())
         .manage(RateLimiterState::new(RateLimiter::new(10, Duration::from_secs(60))))
+        .manage(CommandPolicyState::default())
         .invoke_handler(tauri::generate_handler![
             // System commands
             commands::greet,
@@ -77,6 +79,15 @@ pub fn run() {
             commands::list_users,
             commands::deactivate_user,
             commands::reactivate_user,
+            commands::list_active_sessions,
+            commands::revoke_session,
+            commands::get_command_policy,
+            commands::set_command_minimum_role,
+            commands::get_rate_limit_policies,
+            commands::update_rate_limit_policy,
+            commands::get_login_history,
+            commands::get_login_audit_settings,
+            commands::update_login_audit_settings,
             
             // Asset management commands
             commands::create_asset,
@@ -86,10 +97,20 @@ pub fn run() {
             commands::get_dashboard_stats,
             commands::get_asset_details,
             commands::get_asset_hierarchy,
+            commands::export_asset_hierarchy,
+            commands::get_assets_missing_vaults,
+            commands::get_dormant_assets,
+            commands::get_asset_types,
+            commands::register_asset_type,
+            commands::unregister_asset_type,
             commands::get_children_assets,
             commands::move_asset,
             commands::validate_asset_move,
             commands::get_asset_path,
+            commands::search_assets,
+            commands::subscribe_asset_events,
+            commands::unsubscribe_asset_events,
+            commands::get_asset_event_subscriptions,
             
             // Enhanced tree navigation commands (TODO: Re-enable after fixing imports)
             // commands::batch_load_tree_nodes,
@@ -104,24 +125,74 @@ pub fn run() {
             commands::get_configuration_versions,
             commands::update_configuration_status,
             commands::get_configuration_status_history,
+            commands::add_configuration_comment,
+            commands::get_configuration_comments,
+            commands::delete_configuration_comment,
+            commands::add_version_tag,
+            commands::remove_version_tag,
+            commands::get_version_tags,
+            commands::get_versions_by_tag,
             commands::get_available_status_transitions,
             commands::promote_to_golden,
             commands::promote_branch_to_silver,
             commands::get_golden_version,
             commands::get_promotion_eligibility,
+            commands::is_version_mutable,
+            commands::get_approved_awaiting_promotion,
+            commands::get_auto_golden_settings,
+            commands::set_auto_golden_settings,
+            commands::get_approval_escalation_settings,
+            commands::set_approval_escalation_settings,
+            commands::get_approval_escalations,
+            commands::create_approval_delegation,
+            commands::get_configurations_by_inactive_authors,
+            commands::get_versions_with_inadequate_notes,
             commands::export_configuration_version,
+            commands::export_configuration_converted,
+            commands::export_versions_archive,
+            commands::export_golden_configs,
+            commands::get_configuration_export_history,
+            commands::export_version_timeline,
+            commands::diff_configuration_with_previous,
+            commands::diff_configuration_versions,
+            commands::preview_configuration_content,
             commands::get_file_metadata,
+            commands::lint_configuration_content,
             commands::archive_version,
             commands::restore_version,
+            commands::rollback_configuration,
+            commands::pin_version,
+            commands::unpin_version,
+            commands::apply_retention_policy,
+            commands::get_configuration_lint_mode,
+            commands::set_configuration_lint_mode,
+            commands::get_max_upload_bytes,
+            commands::set_max_upload_bytes,
+            commands::get_auto_version_notes_enabled,
+            commands::set_auto_version_notes_enabled,
+            commands::get_configuration_signing_payload,
+            commands::sign_configuration_version,
+            commands::verify_configuration_signature,
+            commands::register_configuration_signer_key,
+            commands::revoke_configuration_signer_key,
+            commands::list_configuration_signer_keys,
+            commands::set_golden_signature_required,
             
             // Branch management commands
             commands::create_branch,
             commands::get_branches,
             commands::get_branch_details,
             commands::import_version_to_branch,
+            commands::import_configuration_to_new_branch,
+            commands::restore_version_to_branch,
             commands::get_branch_versions,
             commands::get_branch_latest_version,
             commands::compare_branch_versions,
+            commands::prune_branch_versions,
+            commands::merge_branch_to_main,
+            commands::get_version_lineage,
+            commands::get_max_active_branches,
+            commands::set_max_active_branches,
             
             // Firmware management commands
             commands::link_firmware_to_configuration,
@@ -129,9 +200,11 @@ pub fn run() {
             commands::get_configurations_by_firmware,
             commands::export_complete_recovery,
             commands::upload_firmware,
+            commands::import_firmware_and_config,
             commands::get_firmware_list,
             commands::delete_firmware,
             commands::get_firmware_analysis,
+            commands::compare_firmware_analysis,
             commands::retry_firmware_analysis,
             commands::update_firmware_status,
             commands::get_firmware_status_history,
@@ -143,28 +216,63 @@ pub fn run() {
             commands::create_identity_vault,
             commands::add_vault_secret,
             commands::get_vault_by_asset_id,
+            commands::get_vault_secrets,
+            commands::reorder_vault_secrets,
+            commands::suggest_secret_labels,
             commands::get_vault_history,
             commands::decrypt_vault_secret,
+            commands::decrypt_vault_secret_with_strength,
+            commands::generate_totp_code,
             commands::export_vault,
+            commands::export_vault_metadata,
+            commands::export_policy_compliance_report,
+            commands::get_policy_drift,
+            commands::get_effective_password_policy,
+            commands::update_password_policy,
+            commands::get_export_redaction_policy,
+            commands::update_export_redaction_policy,
+            commands::get_export_transforms,
+            commands::set_export_transform,
+            commands::export_vault_secret,
+            commands::import_secrets_from_dotenv,
+            commands::export_vault_kdbx,
+            commands::get_vault_constraints,
+            commands::set_vault_constraints,
             commands::import_vault_from_recovery,
             commands::generate_secure_password,
             commands::validate_password_strength,
             commands::check_password_reuse,
+            commands::get_password_reuse_policy,
+            commands::update_password_reuse_policy,
+            commands::find_reused_secret_values,
+            commands::find_secrets_by_ip,
+            commands::find_ip_conflicts,
+            commands::rebuild_ip_index,
             commands::get_password_history,
+            commands::dedupe_password_history,
             commands::update_credential_password,
             commands::update_vault_secret,
+            commands::get_expiring_secrets,
             commands::delete_vault_secret,
             
             // Standalone credential commands
             commands::create_standalone_credential,
             commands::search_credentials,
             commands::get_credential_categories,
+            commands::export_credentials_by_category,
+            commands::import_standalone_credentials_csv,
             commands::manage_credential_categories,
+            commands::move_credential_category,
+            commands::detect_category_cycles,
+            commands::break_category_cycle,
+            commands::get_max_category_depth,
+            commands::set_max_category_depth,
             commands::get_credential_history,
             commands::update_standalone_credential,
             commands::delete_standalone_credential,
             commands::get_standalone_credential,
             commands::decrypt_standalone_credential,
+            commands::reveal_credential_timed,
             
             // Recovery bundle commands
             commands::get_export_options,
@@ -179,6 +287,13 @@ pub fn run() {
             commands::get_user_vault_permissions,
             commands::get_vault_permissions,
             commands::get_vault_access_log,
+            commands::get_vault_access_log_page,
+            commands::get_vault_access_log_count,
+            commands::get_decrypt_audit_policy,
+            commands::update_decrypt_audit_policy,
+            commands::prune_vault_access_log,
+            commands::get_access_log_retention_policy,
+            commands::update_access_log_retention_policy,
             commands::create_permission_request,
             
             // Password rotation commands
@@ -188,14 +303,25 @@ pub fn run() {
             commands::get_rotation_history,
             commands::update_rotation_policy,
             commands::get_rotation_alerts,
+            commands::get_secrets_due_for_rotation,
             commands::execute_batch_rotation,
+            commands::get_rotation_throttle_settings,
+            commands::update_rotation_throttle_settings,
             commands::create_rotation_schedule,
             commands::get_rotation_compliance_metrics,
+            commands::get_compliance_alert_settings,
+            commands::update_compliance_alert_settings,
+            commands::check_rotation_compliance_alert,
+            commands::get_rotation_reason_templates,
+            commands::create_rotation_reason_template,
+            commands::set_rotation_reason_template_active,
             commands::get_batch_rotation_history,
             
             // User settings commands
             commands::get_user_settings,
             commands::update_user_settings,
+            commands::get_user_ui_state,
+            commands::set_user_ui_state,
             commands::get_retry_presets,
             commands::apply_settings_preset,
             commands::get_operation_retry_configs,
@@ -235,6 +361,8 @@ pub fn run() {
             // commands::restore_metadata_schema,
             commands::metadata_commands::get_asset_metadata_full,
             commands::metadata_commands::update_asset_metadata_partial,
+            commands::metadata_commands::get_metadata_size_policy,
+            commands::metadata_commands::set_metadata_size_policy,
             // commands::copy_metadata_between_assets,
             // commands::validate_metadata_batch,
             // commands::test_metadata_schema,
@@ -278,6 +406,7 @@ pub fn run() {
             commands::perform_security_health_check,
             commands::get_audit_events,
             commands::export_audit_log,
+            commands::verify_audit_chain,
             commands::get_security_metrics,
             commands::export_security_report,
             
@@ -304,6 +433,7 @@ pub fn run() {
             
             // Epic 5 - Association management commands (temporarily disabled)
             // commands::create_file_association,
+            // commands::create_file_associations_bulk,
             // commands::get_asset_file_associations,
             // commands::remove_file_association,
             // commands::reorder_file_associations,
@@ -312,6 +442,9 @@ pub fn run() {
             // commands::get_broken_associations,
             // commands::repair_association,
             // commands::validate_file_association,
+            // commands::revalidate_file_associations,
+            // commands::get_association_auto_revalidate,
+            // commands::set_association_auto_revalidate,
             // commands::create_import_session,
             // commands::update_import_session_status,
             // commands::get_import_session,
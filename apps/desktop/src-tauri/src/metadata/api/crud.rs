@@ -321,6 +321,11 @@ impl<'a> MetadataCrudApi<'a> {
             }
         }
 
+        // Enforce size limits before anything is written
+        let size_policy = self.repo.get_metadata_size_policy()
+            .map_err(|e| format!("Failed to load metadata size policy: {}", e))?;
+        size_policy.check(&current_values)?;
+
         // Create or update metadata
         let updated_values_json = serde_json::to_string(&current_values)
             .map_err(|e| format!("Failed to serialize updated values: {}", e))?;
@@ -636,6 +641,7 @@ impl<'a> MetadataCrudApi<'a> {
 mod tests {
     use super::*;
     use crate::database::Database;
+    use crate::metadata::validation::{MetadataSizePolicy, DEFAULT_MAX_METADATA_DEPTH};
     use tempfile::tempdir;
 
     fn setup_test_db() -> (Database, tempfile::TempDir) {
@@ -684,4 +690,94 @@ mod tests {
         assert_eq!(update.field_path, "nested.field");
         assert_eq!(update.new_value, json!("test value"));
     }
+
+    fn setup_metadata_conn() -> (tempfile::TempDir, Connection, i64) {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("metadata.db")).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL,
+                password_hash TEXT NOT NULL,
+                role TEXT NOT NULL
+            );
+            CREATE TABLE assets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                created_by INTEGER NOT NULL
+            );
+            INSERT INTO users (id, username, password_hash, role) VALUES (1, 'engineer', 'hash', 'Engineer');
+            INSERT INTO assets (id, name, created_by) VALUES (1, 'PLC-01', 1);
+            "#,
+        ).unwrap();
+
+        let repo = SqliteMetadataRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+        let schema = repo.create_metadata_schema(
+            crate::metadata::CreateMetadataSchemaRequest {
+                name: "Device".to_string(),
+                description: "Device metadata".to_string(),
+                schema_json: r#"{"type": "object"}"#.to_string(),
+                asset_type_filter: None,
+            },
+            Some(1),
+        ).unwrap();
+
+        (temp_dir, conn, schema.id.unwrap())
+    }
+
+    fn set_update(schema_id: i64, field_path: &str, new_value: Value) -> PartialMetadata {
+        PartialMetadata {
+            schema_id: Some(schema_id),
+            field_updates: vec![FieldUpdate {
+                field_path: field_path.to_string(),
+                new_value,
+                operation: super::UpdateOperation::Set,
+            }],
+            preserve_history: false,
+            change_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_partial_update_accepts_normal_metadata() {
+        let (_temp_dir, conn, schema_id) = setup_metadata_conn();
+        let api = MetadataCrudApi::new(&conn);
+
+        let updates = set_update(schema_id, "location", serde_json::json!({"site": "North", "rack": 4}));
+        let metadata = api.update_asset_metadata_partial(1, updates).unwrap();
+
+        let values = metadata.get_metadata_values().unwrap();
+        assert_eq!(values["location"]["site"], "North");
+    }
+
+    #[test]
+    fn test_partial_update_rejects_oversized_metadata() {
+        let (_temp_dir, conn, schema_id) = setup_metadata_conn();
+        let repo = SqliteMetadataRepository::new(&conn);
+        repo.set_metadata_size_policy(&MetadataSizePolicy { max_serialized_bytes: 256, max_depth: 8 }).unwrap();
+        let api = MetadataCrudApi::new(&conn);
+
+        let updates = set_update(schema_id, "notes", Value::String("x".repeat(1024)));
+        let err = api.update_asset_metadata_partial(1, updates).unwrap_err();
+        assert!(err.contains("exceeding the maximum of 256 bytes"), "{}", err);
+
+        // Nothing is stored when the update is rejected
+        assert!(repo.get_asset_metadata(1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_partial_update_rejects_deeply_nested_metadata() {
+        let (_temp_dir, conn, schema_id) = setup_metadata_conn();
+        let api = MetadataCrudApi::new(&conn);
+
+        let mut nested = serde_json::json!("leaf");
+        for _ in 0..DEFAULT_MAX_METADATA_DEPTH {
+            nested = serde_json::json!({ "child": nested });
+        }
+        let updates = set_update(schema_id, "tree", nested);
+        let err = api.update_asset_metadata_partial(1, updates).unwrap_err();
+        assert!(err.contains("levels deep"), "{}", err);
+    }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use tracing::{info, warn};
 use super::{
     AssetMetadataSchema, MetadataFieldTemplate, AssetMetadata, FieldType, FieldCategory,
     CreateMetadataSchemaRequest, UpdateMetadataSchemaRequest, TemplateUsageStats,
+    validation::MetadataSizePolicy,
 };
 
 /// Repository for managing metadata schemas
@@ -182,6 +183,14 @@ impl<'a> SqliteMetadataRepository<'a> {
                 FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE CASCADE
             );
 
+            -- Single-row size limits applied to stored metadata values
+            CREATE TABLE IF NOT EXISTS metadata_size_policy (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                max_serialized_bytes INTEGER NOT NULL,
+                max_depth INTEGER NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
             -- Indexes for performance
             CREATE INDEX IF NOT EXISTS idx_metadata_schemas_name ON asset_metadata_schemas(name);
             CREATE INDEX IF NOT EXISTS idx_metadata_schemas_asset_type ON asset_metadata_schemas(asset_type_filter);
@@ -968,6 +977,44 @@ impl<'a> SqliteMetadataRepository<'a> {
         info!("Deleted asset metadata for asset: {}", asset_id);
         Ok(())
     }
+
+    /// Get the size limits for metadata values, falling back to the defaults when unset
+    pub fn get_metadata_size_policy(&self) -> Result<MetadataSizePolicy> {
+        let result = self.conn.query_row(
+            "SELECT max_serialized_bytes, max_depth FROM metadata_size_policy WHERE id = 1",
+            [],
+            |row| Ok(MetadataSizePolicy {
+                max_serialized_bytes: row.get(0)?,
+                max_depth: row.get(1)?,
+            }),
+        );
+
+        match result {
+            Ok(policy) => Ok(policy),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(MetadataSizePolicy::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Set the size limits for metadata values
+    pub fn set_metadata_size_policy(&self, policy: &MetadataSizePolicy) -> Result<()> {
+        if policy.max_serialized_bytes < 1 {
+            return Err(anyhow::anyhow!("Maximum metadata size must be at least 1 byte"));
+        }
+        if policy.max_depth < 1 {
+            return Err(anyhow::anyhow!("Maximum metadata depth must be at least 1"));
+        }
+
+        self.conn.execute(
+            "INSERT INTO metadata_size_policy (id, max_serialized_bytes, max_depth) VALUES (1, ?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET max_serialized_bytes = excluded.max_serialized_bytes,
+                max_depth = excluded.max_depth, updated_at = CURRENT_TIMESTAMP",
+            params![policy.max_serialized_bytes, policy.max_depth],
+        )?;
+
+        info!("Metadata size policy set to {} bytes, depth {}", policy.max_serialized_bytes, policy.max_depth);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use jsonschema::{JSONSchema, Draft, ValidationError as JsonSchemaValidationError};
 use serde_json::{Value, json};
 use std::collections::HashMap;
@@ -421,6 +422,59 @@ impl Default for SchemaValidator {
     }
 }
 
+pub const DEFAULT_MAX_METADATA_BYTES: i64 = 64 * 1024;
+pub const DEFAULT_MAX_METADATA_DEPTH: i64 = 16;
+
+/// Bounds on stored metadata values so a single asset cannot bloat its row
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetadataSizePolicy {
+    pub max_serialized_bytes: i64,
+    pub max_depth: i64,
+}
+
+impl Default for MetadataSizePolicy {
+    fn default() -> Self {
+        Self {
+            max_serialized_bytes: DEFAULT_MAX_METADATA_BYTES,
+            max_depth: DEFAULT_MAX_METADATA_DEPTH,
+        }
+    }
+}
+
+impl MetadataSizePolicy {
+    /// Rejects values nested deeper than `max_depth` or larger than `max_serialized_bytes` once serialized
+    pub fn check(&self, values: &Value) -> Result<(), String> {
+        let depth = json_depth(values);
+        if depth as i64 > self.max_depth {
+            return Err(format!(
+                "Metadata is nested {} levels deep, exceeding the maximum of {}",
+                depth, self.max_depth
+            ));
+        }
+
+        let size = serde_json::to_vec(values)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))?
+            .len();
+        if size as i64 > self.max_serialized_bytes {
+            return Err(format!(
+                "Metadata is {} bytes, exceeding the maximum of {} bytes",
+                size, self.max_serialized_bytes
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Nesting depth of a JSON value; scalars are depth 0 and each object or array adds a level
+fn json_depth(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => 1 + map.values().map(json_depth).max().unwrap_or(0),
+        Value::Array(items) => 1 + items.iter().map(json_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
 /// Utility functions for schema validation
 pub fn validate_field_name(name: &str) -> Result<()> {
     if name.trim().is_empty() {
@@ -6,7 +6,7 @@ use tauri::AppHandle;
 use uuid::Uuid;
 use crate::{
     configurations::{ConfigurationRepository, CreateConfigurationRequest},
-    firmware::{FirmwareRepository, FirmwareFileStorage, CreateFirmwareRequest},
+    firmware::{FirmwareRepository, FirmwareFileStorage, CreateFirmwareRequest, get_firmware_storage_dir},
     vault::{VaultRepository, VaultInfo},
     validation::InputSanitizer,
     audit::{self, AuditRepository},
@@ -23,9 +23,20 @@ pub struct RecoveryExportRequest {
     pub include_vault: Option<bool>,
 }
 
+/// Layout version of recovery bundles written by this build. Bump it whenever the
+/// manifest or bundle contents change in a way older readers cannot handle.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Bundles exported before the format version was embedded use the version 1 layout
+fn legacy_bundle_format_version() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecoveryManifest {
+    #[serde(default = "legacy_bundle_format_version")]
+    pub bundle_format_version: u32,
     pub asset_id: i64,
     pub export_date: String,
     pub exported_by: String,
@@ -204,7 +215,7 @@ impl<'a> RecoveryExporter<'a> {
                 anyhow::anyhow!("Invalid configuration export path")
             })?;
 
-        self.config_repo.export_configuration_version(request.config_version_id, config_export_path_str)
+        self.config_repo.export_configuration_version(request.config_version_id, config_export_path_str, user_id)
             .map_err(|e| {
                 cleanup_files(&created_files, export_dir, export_dir_created);
                 anyhow::anyhow!("Failed to export configuration: {}", e)
@@ -305,6 +316,7 @@ impl<'a> RecoveryExporter<'a> {
         // Create manifest
         let export_date = chrono::Utc::now().to_rfc3339();
         let manifest = RecoveryManifest {
+            bundle_format_version: BUNDLE_FORMAT_VERSION,
             asset_id: request.asset_id,
             export_date: export_date.clone(),
             exported_by: username.to_string(),
@@ -418,6 +430,98 @@ pub struct RecoveryImportRequest {
     pub bundle_path: String,
     pub target_asset_id: i64,
     pub import_vault: bool,
+    #[serde(default)]
+    pub conflict_strategy: ConflictStrategy,
+}
+
+/// How an import handles bundle items that already exist on the target asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictStrategy {
+    /// Leave the existing item untouched and do not import the bundle copy
+    #[default]
+    Skip,
+    /// Replace the existing item with the bundle copy
+    Overwrite,
+    /// Import the bundle copy alongside the existing item under a new name
+    Rename,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(ConflictStrategy::Skip),
+            "overwrite" => Ok(ConflictStrategy::Overwrite),
+            "rename" => Ok(ConflictStrategy::Rename),
+            _ => Err(anyhow::anyhow!("Invalid conflict strategy: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportItemKind {
+    Configuration,
+    Firmware,
+    Vault,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportItemAction {
+    Imported,
+    Skipped,
+    Overwritten,
+    Renamed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportItemOutcome {
+    pub kind: ImportItemKind,
+    /// Name of the item in the bundle (file name, firmware version or vault name)
+    pub name: String,
+    pub action: ImportItemAction,
+    pub existing_id: Option<i64>,
+    pub imported_id: Option<i64>,
+    /// Name the item was stored under, when it differs from the bundle name
+    pub imported_name: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryImportReport {
+    pub manifest: RecoveryManifest,
+    pub conflict_strategy: ConflictStrategy,
+    pub items: Vec<ImportItemOutcome>,
+}
+
+/// Picks a name not in `taken` by appending " (n)"
+fn unique_import_name(name: &str, taken: &[String]) -> String {
+    unique_name_with_extension(name, "", taken)
+}
+
+/// Like `unique_import_name`, keeping the file extension last
+fn unique_import_file_name(file_name: &str, taken: &[String]) -> String {
+    match file_name.rfind('.') {
+        Some(index) if index > 0 => unique_name_with_extension(&file_name[..index], &file_name[index..], taken),
+        _ => unique_import_name(file_name, taken),
+    }
+}
+
+fn unique_name_with_extension(stem: &str, extension: &str, taken: &[String]) -> String {
+    let mut counter = 2;
+    loop {
+        let candidate = format!("{} ({}){}", stem, counter, extension);
+        if !taken.iter().any(|t| t == &candidate) {
+            return candidate;
+        }
+        counter += 1;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -461,6 +565,22 @@ impl<'a> RecoveryImporter<'a> {
         }
     }
 
+    /// Rejects bundles written by a newer build, whose layout this build cannot interpret
+    pub fn check_bundle_format_version(bundle_format_version: u32) -> Result<()> {
+        if bundle_format_version == 0 {
+            return Err(anyhow::anyhow!("Recovery bundle has an invalid format version"));
+        }
+
+        if bundle_format_version > BUNDLE_FORMAT_VERSION {
+            return Err(anyhow::anyhow!(
+                "Recovery bundle format version {} is newer than the supported version {}; update Ferrocodex to import this bundle",
+                bundle_format_version, BUNDLE_FORMAT_VERSION
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn validate_bundle_integrity(bundle_path: &str) -> Result<RecoveryManifest> {
         let bundle_dir = Path::new(bundle_path);
         if !bundle_dir.exists() || !bundle_dir.is_dir() {
@@ -488,7 +608,19 @@ impl<'a> RecoveryImporter<'a> {
 
         let manifest_path = manifest_files[0].path();
         let manifest_content = std::fs::read_to_string(&manifest_path)?;
-        let manifest: RecoveryManifest = serde_json::from_str(&manifest_content)?;
+
+        // Check the format version before parsing the rest, since a newer layout may not
+        // deserialize into this build's manifest at all
+        let manifest_value: serde_json::Value = serde_json::from_str(&manifest_content)?;
+        let bundle_format_version = match manifest_value.get("bundleFormatVersion") {
+            None => legacy_bundle_format_version(),
+            Some(value) => value.as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| anyhow::anyhow!("Recovery bundle has an invalid format version"))?,
+        };
+        Self::check_bundle_format_version(bundle_format_version)?;
+
+        let manifest: RecoveryManifest = serde_json::from_value(manifest_value)?;
 
         // Validate configuration file
         let config_path = bundle_dir.join(&manifest.configuration.filename);
@@ -538,7 +670,20 @@ impl<'a> RecoveryImporter<'a> {
         user_id: i64,
         username: &str,
         user_role: &UserRole,
-    ) -> Result<RecoveryManifest> {
+    ) -> Result<RecoveryImportReport> {
+        let firmware_dir = get_firmware_storage_dir(app)?;
+        self.import_recovery_bundle_into(&firmware_dir, request, user_id, username, user_role)
+    }
+
+    /// Same as `import_recovery_bundle`, storing firmware files below an explicit directory
+    pub fn import_recovery_bundle_into(
+        &self,
+        firmware_dir: &Path,
+        request: RecoveryImportRequest,
+        user_id: i64,
+        username: &str,
+        user_role: &UserRole,
+    ) -> Result<RecoveryImportReport> {
         // Validate user permissions
         if *user_role != UserRole::Engineer && *user_role != UserRole::Administrator {
             return Err(anyhow::anyhow!("Only Engineers and Administrators can import recovery packages"));
@@ -555,78 +700,50 @@ impl<'a> RecoveryImporter<'a> {
 
         let bundle_dir = Path::new(&request.bundle_path);
         let start_time = std::time::Instant::now();
+        let mut items = Vec::new();
 
         // Import configuration
         let config_path = bundle_dir.join(&manifest.configuration.filename);
         let config_content = std::fs::read_to_string(&config_path)?;
-        
-        let config_request = CreateConfigurationRequest {
-            asset_id: request.target_asset_id,
-            file_content: config_content.into_bytes(),
-            file_name: manifest.configuration.filename.clone(),
-            author: user_id,
-            notes: format!("Imported from recovery bundle by {} on {}", 
-                username, chrono::Utc::now().to_rfc3339()),
-        };
-
-        let imported_config = self.config_repo.store_configuration(config_request)?;
+        let (config_outcome, imported_config_id) = self.import_configuration_item(
+            &request,
+            &manifest.configuration.filename,
+            config_content.into_bytes(),
+            user_id,
+            username,
+        )?;
+        items.push(config_outcome);
 
         // Import firmware
         let firmware_path = bundle_dir.join(&manifest.firmware.filename);
         let firmware_data = std::fs::read(&firmware_path)?;
-
-        // Store firmware file - need to create a temporary firmware ID
-        // This will be replaced with the actual ID after creation
-        let temp_firmware_id = 0;
-        let (firmware_storage_path, _, _) = FirmwareFileStorage::store_firmware_file(
-            app,
-            request.target_asset_id,
-            temp_firmware_id,
+        let (firmware_outcome, firmware_id) = self.import_firmware_item(
+            firmware_dir,
+            &request,
+            &manifest.firmware,
             &firmware_data,
             user_id,
             username,
         )?;
-
-        let firmware_request = CreateFirmwareRequest {
-            asset_id: request.target_asset_id,
-            version: manifest.firmware.version.clone(),
-            vendor: Some(manifest.firmware.vendor.clone()),
-            model: Some(manifest.firmware.model.clone()),
-            notes: Some(format!("Imported from recovery bundle by {} on {}", 
-                username, chrono::Utc::now().to_rfc3339())),
-        };
-
-        // Calculate firmware file hash
-        let mut hasher = Sha256::new();
-        hasher.update(&firmware_data);
-        let file_hash = format!("{:x}", hasher.finalize());
-
-        let imported_firmware = self.firmware_repo.create_firmware(
-            firmware_request,
-            user_id,
-            firmware_storage_path,
-            file_hash,
-            manifest.firmware.file_size,
-        )?;
+        items.push(firmware_outcome);
 
         // Import vault if requested and available
-        if request.import_vault && manifest.vault.is_some() {
+        if request.import_vault {
             if let Some(ref vault_info) = manifest.vault {
                 let vault_path = bundle_dir.join(&vault_info.filename);
                 let vault_content = std::fs::read_to_string(&vault_path)?;
                 let vault_data: VaultInfo = serde_json::from_str(&vault_content)?;
 
-                // Import vault with all secrets
-                self.vault_repo.import_vault(&vault_data, user_id)?;
+                items.push(self.import_vault_item(&request, vault_data, user_id)?);
             }
         }
 
-        // Link firmware to configuration if they were linked in the original
+        // Link firmware to a newly stored configuration if they were linked in the original;
+        // existing configurations that were kept are left as they are
         if manifest.compatibility_verified {
-            self.config_repo.link_firmware_to_configuration(
-                imported_config.id,
-                imported_firmware.id,
-            )?;
+            if let (Some(config_id), Some(firmware_id)) = (imported_config_id, firmware_id) {
+                self.config_repo.link_firmware_to_configuration(config_id, firmware_id)?;
+            }
         }
 
         // Log audit event
@@ -641,10 +758,12 @@ impl<'a> RecoveryImporter<'a> {
             description: format!("Recovery bundle imported for asset {}", request.target_asset_id),
             metadata: Some(serde_json::json!({
                 "asset_id": request.target_asset_id,
-                "config_version_id": imported_config.id,
-                "firmware_version_id": imported_firmware.id,
+                "config_version_id": imported_config_id,
+                "firmware_version_id": firmware_id,
                 "bundle_path": request.bundle_path,
-                "vault_imported": request.import_vault && manifest.vault.is_some(),
+                "vault_imported": items.iter().any(|item| item.kind == ImportItemKind::Vault && item.imported_id.is_some()),
+                "conflict_strategy": request.conflict_strategy,
+                "items": items,
                 "imported_by": username,
                 "total_ms": start_time.elapsed().as_millis(),
             }).to_string()),
@@ -658,12 +777,240 @@ impl<'a> RecoveryImporter<'a> {
         }
 
         tracing::info!(
-            "Recovery bundle imported by {}: Asset {} from {} (Total: {}ms)",
-            username, request.target_asset_id, request.bundle_path, 
+            "Recovery bundle imported by {}: Asset {} from {} with {:?} strategy (Total: {}ms)",
+            username, request.target_asset_id, request.bundle_path, request.conflict_strategy,
             start_time.elapsed().as_millis()
         );
 
-        Ok(manifest)
+        Ok(RecoveryImportReport {
+            manifest,
+            conflict_strategy: request.conflict_strategy,
+            items,
+        })
+    }
+
+    /// Stores the bundle configuration, returning the outcome and the id of a newly stored version.
+    ///
+    /// An asset normally holds many versions of one file, so a shared file name alone is not a
+    /// conflict. The bundle copy conflicts only with a version of the same file whose content
+    /// hash matches, i.e. when that exact content is already on the asset; different content is
+    /// stored as the next version of the file.
+    fn import_configuration_item(
+        &self,
+        request: &RecoveryImportRequest,
+        file_name: &str,
+        content: Vec<u8>,
+        user_id: i64,
+        username: &str,
+    ) -> Result<(ImportItemOutcome, Option<i64>)> {
+        let content_hash = crate::configurations::file_utils::calculate_file_hash(&content);
+        let existing_versions = self.config_repo.get_configuration_versions(request.target_asset_id)?;
+        let existing = existing_versions.iter()
+            .find(|v| v.file_name == file_name && v.content_hash == content_hash);
+
+        let mut outcome = ImportItemOutcome {
+            kind: ImportItemKind::Configuration,
+            name: file_name.to_string(),
+            action: ImportItemAction::Imported,
+            existing_id: existing.map(|v| v.id),
+            imported_id: None,
+            imported_name: None,
+            message: String::new(),
+        };
+
+        let (stored_name, replaced_id) = match (existing, request.conflict_strategy) {
+            (None, _) => (file_name.to_string(), None),
+            (Some(existing), ConflictStrategy::Skip) => {
+                outcome.action = ImportItemAction::Skipped;
+                outcome.message = format!("Configuration version {} already exists", existing.version_number);
+                return Ok((outcome, None));
+            }
+            (Some(existing), ConflictStrategy::Overwrite) => {
+                if !self.config_repo.is_version_mutable(existing.id)? {
+                    outcome.action = ImportItemAction::Failed;
+                    outcome.message = format!("Configuration version {} is {} and cannot be overwritten",
+                        existing.version_number, existing.status);
+                    return Ok((outcome, None));
+                }
+                outcome.action = ImportItemAction::Overwritten;
+                (file_name.to_string(), Some(existing.id))
+            }
+            (Some(_), ConflictStrategy::Rename) => {
+                let taken: Vec<String> = existing_versions.iter().map(|v| v.file_name.clone()).collect();
+                outcome.action = ImportItemAction::Renamed;
+                let renamed = unique_import_file_name(file_name, &taken);
+                outcome.imported_name = Some(renamed.clone());
+                (renamed, None)
+            }
+        };
+
+        let config_request = CreateConfigurationRequest {
+            asset_id: request.target_asset_id,
+            file_content: content,
+            file_name: stored_name,
+            author: user_id,
+            notes: format!("Imported from recovery bundle by {} on {}", 
+                username, chrono::Utc::now().to_rfc3339()),
+        };
+
+        // The replaced version is deleted in the same transaction as the new one is stored
+        let imported_config = match replaced_id {
+            Some(replaced_id) => self.config_repo.replace_configuration_version(replaced_id, config_request)?,
+            None => self.config_repo.store_configuration(config_request)?,
+        };
+        outcome.imported_id = Some(imported_config.id);
+        outcome.message = format!("Stored as configuration version {}", imported_config.version_number);
+
+        Ok((outcome, Some(imported_config.id)))
+    }
+
+    /// Stores the bundle firmware, returning the outcome and the id of the firmware to link
+    fn import_firmware_item(
+        &self,
+        firmware_dir: &Path,
+        request: &RecoveryImportRequest,
+        firmware_info: &FirmwareExportInfo,
+        firmware_data: &[u8],
+        user_id: i64,
+        username: &str,
+    ) -> Result<(ImportItemOutcome, Option<i64>)> {
+        let existing_firmware = self.firmware_repo.get_firmware_by_asset(request.target_asset_id)?;
+        let existing = existing_firmware.iter().find(|f| f.version == firmware_info.version);
+
+        let mut outcome = ImportItemOutcome {
+            kind: ImportItemKind::Firmware,
+            name: firmware_info.version.clone(),
+            action: ImportItemAction::Imported,
+            existing_id: existing.map(|f| f.id),
+            imported_id: None,
+            imported_name: None,
+            message: String::new(),
+        };
+
+        let (version, replaced_id) = match (existing, request.conflict_strategy) {
+            (None, _) => (firmware_info.version.clone(), None),
+            (Some(existing), ConflictStrategy::Skip) => {
+                outcome.action = ImportItemAction::Skipped;
+                outcome.message = format!("Firmware version {} already exists", existing.version);
+                return Ok((outcome, Some(existing.id)));
+            }
+            (Some(existing), ConflictStrategy::Overwrite) => {
+                outcome.action = ImportItemAction::Overwritten;
+                (firmware_info.version.clone(), Some(existing.id))
+            }
+            (Some(_), ConflictStrategy::Rename) => {
+                let taken: Vec<String> = existing_firmware.iter().map(|f| f.version.clone()).collect();
+                outcome.action = ImportItemAction::Renamed;
+                let renamed = unique_import_name(&firmware_info.version, &taken);
+                outcome.imported_name = Some(renamed.clone());
+                (renamed, None)
+            }
+        };
+
+        let firmware_request = CreateFirmwareRequest {
+            asset_id: request.target_asset_id,
+            version,
+            vendor: Some(firmware_info.vendor.clone()),
+            model: Some(firmware_info.model.clone()),
+            notes: Some(format!("Imported from recovery bundle by {} on {}", 
+                username, chrono::Utc::now().to_rfc3339())),
+        };
+
+        // The old row goes in the same transaction as the new one is stored, and its file
+        // only once that has committed
+        if let Some(replaced_id) = replaced_id {
+            let imported_firmware = self.firmware_repo.replace_firmware(
+                firmware_dir,
+                replaced_id,
+                firmware_request,
+                user_id,
+                username,
+                firmware_data,
+            )?;
+            outcome.imported_id = Some(imported_firmware.id);
+            outcome.message = format!("Stored as firmware {}", imported_firmware.id);
+            return Ok((outcome, Some(imported_firmware.id)));
+        }
+
+        // Calculate firmware file hash
+        let mut hasher = Sha256::new();
+        hasher.update(firmware_data);
+        let file_hash = format!("{:x}", hasher.finalize());
+
+        // Create the record first so the stored file is named after the real firmware id
+        let imported_firmware = self.firmware_repo.create_firmware(
+            firmware_request,
+            user_id,
+            String::new(),
+            file_hash,
+            firmware_info.file_size,
+        )?;
+
+        let (firmware_storage_path, _, _) = FirmwareFileStorage::store_firmware_file_in(
+            firmware_dir,
+            request.target_asset_id,
+            imported_firmware.id,
+            firmware_data,
+            user_id,
+            username,
+        )?;
+        self.firmware_repo.update_firmware_file_path(imported_firmware.id, firmware_storage_path)?;
+
+        outcome.imported_id = Some(imported_firmware.id);
+        outcome.message = format!("Stored as firmware {}", imported_firmware.id);
+
+        Ok((outcome, Some(imported_firmware.id)))
+    }
+
+    fn import_vault_item(
+        &self,
+        request: &RecoveryImportRequest,
+        mut vault_data: VaultInfo,
+        user_id: i64,
+    ) -> Result<ImportItemOutcome> {
+        let existing = self.vault_repo.get_vault_by_asset_id(request.target_asset_id)?
+            .filter(|existing| existing.vault.name == vault_data.vault.name);
+
+        let mut outcome = ImportItemOutcome {
+            kind: ImportItemKind::Vault,
+            name: vault_data.vault.name.clone(),
+            action: ImportItemAction::Imported,
+            existing_id: existing.as_ref().map(|v| v.vault.id),
+            imported_id: None,
+            imported_name: None,
+            message: String::new(),
+        };
+
+        let replaced_id = match (existing, request.conflict_strategy) {
+            (None, _) => None,
+            (Some(existing), ConflictStrategy::Skip) => {
+                outcome.action = ImportItemAction::Skipped;
+                outcome.message = format!("Vault '{}' already exists", existing.vault.name);
+                return Ok(outcome);
+            }
+            (Some(existing), ConflictStrategy::Overwrite) => {
+                outcome.action = ImportItemAction::Overwritten;
+                Some(existing.vault.id)
+            }
+            (Some(existing), ConflictStrategy::Rename) => {
+                let renamed = unique_import_name(&vault_data.vault.name, &[existing.vault.name]);
+                vault_data.vault.name = renamed.clone();
+                outcome.action = ImportItemAction::Renamed;
+                outcome.imported_name = Some(renamed);
+                None
+            }
+        };
+
+        // Import vault with all secrets; an overwritten vault is deleted in the same transaction
+        vault_data.vault.asset_id = request.target_asset_id;
+        let imported_vault = match replaced_id {
+            Some(replaced_id) => self.vault_repo.replace_vault(replaced_id, &vault_data, user_id)?,
+            None => self.vault_repo.import_vault(&vault_data, user_id)?,
+        };
+        outcome.imported_id = Some(imported_vault.id);
+        outcome.message = format!("Imported vault with {} secrets", vault_data.secret_count);
+
+        Ok(outcome)
     }
 }
 
@@ -39,11 +39,23 @@ use tempfile::TempDir;
             unimplemented!()
         }
 
+        fn store_configuration_from_path(&self, _asset_id: i64, _path: &std::path::Path, _author: i64, _notes: &str) -> Result<crate::configurations::ConfigurationVersionInfo> {
+            unimplemented!()
+        }
+
+        fn set_import_provenance(&self, _version_id: i64, _provenance: &crate::configurations::ImportProvenance) -> Result<()> {
+            unimplemented!()
+        }
+
         fn get_configuration_versions(&self, _asset_id: i64) -> Result<Vec<crate::configurations::ConfigurationVersionInfo>> {
             unimplemented!()
         }
         
-        fn get_configuration_content(&self, _version_id: i64) -> Result<Vec<u8>> {
+        fn get_configuration_content(&self, _version_id: i64) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+            unimplemented!()
+        }
+        
+        fn preview_configuration_content(&self, _version_id: i64, _max_bytes: usize) -> Result<crate::configurations::ConfigurationPreview> {
             unimplemented!()
         }
         
@@ -55,6 +67,10 @@ use tempfile::TempDir;
             unimplemented!()
         }
         
+        fn replace_configuration_version(&self, _version_id: i64, _request: crate::configurations::CreateConfigurationRequest) -> Result<ConfigurationVersion> {
+            unimplemented!()
+        }
+        
         fn get_configuration_count(&self, _asset_id: i64) -> Result<i64> {
             unimplemented!()
         }
@@ -79,12 +95,16 @@ use tempfile::TempDir;
             unimplemented!()
         }
 
-        fn export_configuration_version(&self, _version_id: i64, export_path: &str) -> Result<()> {
+        fn export_configuration_version(&self, _version_id: i64, export_path: &str, _exported_by: i64) -> Result<()> {
             // Write a test configuration file
             std::fs::write(export_path, r#"{"test": "configuration", "version": "1.0.0"}"#)?;
             Ok(())
         }
 
+        fn export_configuration_converted(&self, _version_id: i64, _export_path: &str, _encoding: crate::configurations::TextEncoding, _line_ending: crate::configurations::LineEnding, _exported_by: i64) -> Result<()> {
+            unimplemented!()
+        }
+
         fn get_golden_version(&self, _asset_id: i64) -> Result<Option<crate::configurations::ConfigurationVersionInfo>> {
             unimplemented!()
         }
@@ -93,6 +113,66 @@ use tempfile::TempDir;
             unimplemented!()
         }
 
+        fn get_approved_awaiting_promotion(&self, _older_than_days: u32) -> Result<Vec<crate::configurations::ConfigurationVersionInfo>> {
+            unimplemented!()
+        }
+
+        fn get_auto_golden_settings(&self, _asset_id: i64) -> Result<crate::configurations::AutoGoldenSettings> {
+            unimplemented!()
+        }
+
+        fn set_auto_golden_settings(&self, _settings: &crate::configurations::AutoGoldenSettings, _updated_by: i64) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn get_configurations_by_inactive_authors(&self) -> Result<Vec<crate::configurations::ConfigurationVersionInfo>> {
+            unimplemented!()
+        }
+
+        fn get_versions_with_inadequate_notes(&self, _asset_id: Option<i64>, _min_length: usize) -> Result<Vec<crate::configurations::ConfigurationVersionInfo>> {
+            unimplemented!()
+        }
+
+        fn export_versions_archive(&self, _version_ids: &[i64], _dest_path: &str, _exported_by: i64) -> Result<crate::configurations::VersionArchiveManifest> {
+            unimplemented!()
+        }
+
+        fn export_golden_configs(&self, _root_asset_id: Option<i64>, _dest_dir: &str, _exported_by: i64) -> Result<crate::configurations::GoldenExportManifest> {
+            unimplemented!()
+        }
+
+        fn get_export_history(&self, _version_id: i64) -> Result<Vec<crate::configurations::ConfigurationExportRecord>> {
+            unimplemented!()
+        }
+
+        fn export_version_timeline(&self, _version_id: i64) -> Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        fn backfill_content_types(&self) -> Result<usize> {
+            unimplemented!()
+        }
+
+        fn rebind_content_encryption(&self) -> Result<usize> {
+            unimplemented!()
+        }
+
+        fn is_version_mutable(&self, _version_id: i64) -> Result<bool> {
+            unimplemented!()
+        }
+
+        fn update_configuration_content(&self, _version_id: i64, _content: &[u8]) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn get_auto_notes_enabled(&self) -> Result<bool> {
+            unimplemented!()
+        }
+
+        fn set_auto_notes_enabled(&self, _enabled: bool) -> Result<()> {
+            unimplemented!()
+        }
+
         fn link_firmware_to_configuration(&self, _config_version_id: i64, _firmware_version_id: i64) -> Result<()> {
             unimplemented!()
         }
@@ -112,6 +192,138 @@ use tempfile::TempDir;
         fn restore_version(&self, _version_id: i64, _restored_by: i64, _restore_reason: Option<String>) -> Result<()> {
             unimplemented!()
         }
+
+        fn rollback_configuration(&self, _asset_id: i64, _target_version_id: i64, _author: i64, _notes: &str, _allow_archived: bool) -> Result<crate::configurations::ConfigurationVersionInfo> {
+            unimplemented!()
+        }
+
+        fn pin_version(&self, _version_id: i64) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn unpin_version(&self, _version_id: i64) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn apply_retention_policy(&self, _asset_id: i64, _keep_recent: usize, _performed_by: i64) -> Result<Vec<i64>> {
+            unimplemented!()
+        }
+
+        fn diff_with_previous(&self, _version_id: i64) -> Result<Option<crate::configurations::ConfigDiff>> {
+            unimplemented!()
+        }
+
+        fn diff_configuration_versions(&self, _version_a_id: i64, _version_b_id: i64) -> Result<crate::configurations::ConfigDiff> {
+            unimplemented!()
+        }
+
+        fn diff_configuration_versions_filtered(&self, _version_a_id: i64, _version_b_id: i64, _options: &crate::configurations::DiffFilterOptions) -> Result<crate::configurations::ConfigDiff> {
+            unimplemented!()
+        }
+
+        fn get_approval_escalation_settings(&self) -> Result<crate::configurations::ApprovalEscalationSettings> {
+            unimplemented!()
+        }
+
+        fn set_approval_escalation_settings(&self, _settings: &crate::configurations::ApprovalEscalationSettings, _updated_by: i64) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn run_approval_escalations(&self) -> Result<Vec<crate::configurations::ApprovalEscalation>> {
+            unimplemented!()
+        }
+
+        fn get_approval_escalations(&self, _version_id: i64) -> Result<Vec<crate::configurations::ApprovalEscalation>> {
+            unimplemented!()
+        }
+
+        fn create_approval_delegation(&self, _from_user: i64, _to_user: i64, _expires_at: &str) -> Result<crate::configurations::ApprovalDelegation> {
+            unimplemented!()
+        }
+
+        fn get_active_approval_delegations(&self, _to_user: i64) -> Result<Vec<crate::configurations::ApprovalDelegation>> {
+            unimplemented!()
+        }
+
+        fn find_delegation_for_transition(&self, _version_id: i64, _user_id: i64, _new_status: &crate::configurations::ConfigurationStatus) -> Result<Option<crate::configurations::ApprovalDelegation>> {
+            unimplemented!()
+        }
+        
+        fn get_lint_mode(&self) -> Result<crate::configurations::LintMode> {
+            unimplemented!()
+        }
+        
+        fn set_lint_mode(&self, _mode: crate::configurations::LintMode) -> Result<()> {
+            unimplemented!()
+        }
+        
+        fn configuration_signing_payload(&self, _version_id: i64) -> Result<Vec<u8>> {
+            unimplemented!()
+        }
+        
+        fn add_configuration_signature(&self, _version_id: i64, _signature: &str, _public_key: &str, _signed_by: i64) -> Result<crate::configurations::ConfigurationSignature> {
+            unimplemented!()
+        }
+        
+        fn verify_configuration_signature(&self, _version_id: i64) -> Result<crate::configurations::SignatureVerification> {
+            unimplemented!()
+        }
+        
+        fn register_signer_key(&self, _user_id: i64, _public_key: &str, _label: &str, _registered_by: i64) -> Result<crate::configurations::SignerKey> {
+            unimplemented!()
+        }
+        
+        fn revoke_signer_key(&self, _key_id: i64) -> Result<()> {
+            unimplemented!()
+        }
+        
+        fn list_signer_keys(&self, _user_id: Option<i64>) -> Result<Vec<crate::configurations::SignerKey>> {
+            unimplemented!()
+        }
+        
+        fn get_golden_signature_required(&self) -> Result<bool> {
+            unimplemented!()
+        }
+        
+        fn set_golden_signature_required(&self, _required: bool) -> Result<()> {
+            unimplemented!()
+        }
+        
+        fn add_configuration_comment(&self, _version_id: i64, _author: i64, _comment: &str) -> Result<crate::configurations::ConfigurationComment> {
+            unimplemented!()
+        }
+        
+        fn get_configuration_comments(&self, _version_id: i64) -> Result<Vec<crate::configurations::ConfigurationComment>> {
+            unimplemented!()
+        }
+        
+        fn delete_configuration_comment(&self, _comment_id: i64, _user_id: i64, _user_role: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn add_version_tag(&self, _version_id: i64, _tag: &str, _author: i64) -> Result<crate::configurations::ConfigurationVersionTag> {
+            unimplemented!()
+        }
+
+        fn remove_version_tag(&self, _version_id: i64, _tag: &str, _user_id: i64, _user_role: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn get_version_tags(&self, _version_id: i64) -> Result<Vec<crate::configurations::ConfigurationVersionTag>> {
+            unimplemented!()
+        }
+
+        fn get_versions_by_tag(&self, _asset_id: i64, _tag: &str) -> Result<Vec<crate::configurations::ConfigurationVersionInfo>> {
+            unimplemented!()
+        }
+
+        fn get_max_upload_bytes(&self) -> Result<u64> {
+            unimplemented!()
+        }
+
+        fn set_max_upload_bytes(&self, _max_upload_bytes: u64) -> Result<()> {
+            unimplemented!()
+        }
     }
 
     struct MockFirmwareRepository {
@@ -185,6 +397,10 @@ use tempfile::TempDir;
         fn update_firmware_file_path(&self, _firmware_id: i64, _file_path: String) -> Result<()> {
             unimplemented!()
         }
+        
+        fn replace_firmware(&self, _firmware_dir: &std::path::Path, _firmware_id: i64, _request: crate::firmware::CreateFirmwareRequest, _author: i64, _username: &str, _firmware_data: &[u8]) -> Result<FirmwareVersion> {
+            unimplemented!()
+        }
     }
 
     struct MockVaultRepository {
@@ -221,6 +437,10 @@ use tempfile::TempDir;
                     rotation_interval_days: None,
                     next_rotation_due: None,
                     rotation_policy_id: None,
+                    display_order: None,
+                    expires_at: None,
+                    certificate_subject: None,
+                    certificate_not_after: None,
                 },
                 VaultSecret {
                     id: 2,
@@ -238,6 +458,10 @@ use tempfile::TempDir;
                     rotation_interval_days: None,
                     next_rotation_due: None,
                     rotation_policy_id: None,
+                    display_order: None,
+                    expires_at: None,
+                    certificate_subject: None,
+                    certificate_not_after: None,
                 },
             ];
             
@@ -280,6 +504,14 @@ use tempfile::TempDir;
             unimplemented!()
         }
         
+        fn get_vault_secrets_ordered(&self, _vault_id: i64, _order_by: crate::vault::SecretOrder) -> Result<Vec<VaultSecret>> {
+            unimplemented!()
+        }
+
+        fn reorder_vault_secrets(&self, _vault_id: i64, _secret_order: Vec<(i64, Option<i64>)>) -> Result<()> {
+            unimplemented!()
+        }
+
         fn get_vault_secrets(&self, _vault_id: i64) -> Result<Vec<VaultSecret>> {
             unimplemented!()
         }
@@ -308,12 +540,16 @@ use tempfile::TempDir;
             Ok(self.vaults.get(&1).unwrap().vault.clone())
         }
         
+        fn replace_vault(&self, _vault_id: i64, _vault_info: &VaultInfo, _author_id: i64) -> Result<IdentityVault> {
+            unimplemented!()
+        }
+        
         fn initialize_schema(&self) -> Result<()> {
             Ok(())
         }
         
         // Password management methods
-        fn add_password_history(&self, _secret_id: i64, _password_hash: &str) -> Result<()> {
+        fn add_password_history(&self, _secret_id: i64, _password: &str) -> Result<()> {
             unimplemented!()
         }
         
@@ -321,21 +557,57 @@ use tempfile::TempDir;
             unimplemented!()
         }
         
-        fn check_password_reuse(&self, _password_hash: &str, _exclude_secret_id: Option<i64>) -> Result<bool> {
+        fn check_password_reuse(&self, _password: &str, _exclude_secret_id: Option<i64>) -> Result<bool> {
             unimplemented!()
         }
         
-        fn update_password(&self, _request: crate::vault::UpdateCredentialPasswordRequest, _password_hash: &str, _strength_score: i32) -> Result<()> {
+        fn update_password(&self, _request: crate::vault::UpdateCredentialPasswordRequest, _strength_score: i32) -> Result<()> {
             unimplemented!()
         }
         
         fn get_default_password_policy(&self) -> Result<crate::vault::PasswordPolicy> {
             unimplemented!()
         }
-        
+
+        fn get_effective_password_policy(&self, _vault_id: i64) -> Result<crate::vault::PasswordPolicy> {
+            unimplemented!()
+        }
+
+        fn update_password_policy(&self, _vault_id: Option<i64>, _policy: &crate::vault::PasswordPolicy) -> Result<crate::vault::PasswordPolicy> {
+            unimplemented!()
+        }
+
+        fn get_policy_drift(&self, _vault_id: i64) -> Result<Vec<VaultSecret>> {
+            unimplemented!()
+        }
+
         fn cleanup_password_history(&self, _secret_id: i64, _keep_count: usize) -> Result<()> {
             unimplemented!()
         }
+
+        fn dedupe_password_history(&self, _secret_id: i64) -> Result<usize> {
+            unimplemented!()
+        }
+        
+        fn get_password_reuse_policy(&self) -> Result<crate::vault::PasswordReusePolicy> {
+            unimplemented!()
+        }
+
+        fn update_password_reuse_policy(&self, _policy: &crate::vault::PasswordReusePolicy) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn find_reused_secret_values(&self) -> Result<Vec<crate::vault::ReusedSecretGroup>> {
+            unimplemented!()
+        }
+        
+        fn decrypt_secret_value(&self, _secret_id: i64, _vault_id: i64, _user_id: i64) -> Result<zeroize::Zeroizing<String>> {
+            unimplemented!()
+        }
+
+        fn reveal_secret_with_strength(&self, _secret_id: i64, _vault_id: i64, _user_id: i64) -> Result<crate::vault::SecretRevealWithStrength> {
+            unimplemented!()
+        }
         
         // Standalone credential methods
         fn create_standalone_credential(&self, _request: crate::vault::CreateStandaloneCredentialRequest) -> Result<crate::vault::StandaloneCredential> {
@@ -387,6 +659,18 @@ use tempfile::TempDir;
             unimplemented!()
         }
         
+        fn move_category(&self, _category_id: i64, _new_parent_id: Option<i64>) -> Result<()> {
+            unimplemented!()
+        }
+        
+        fn get_max_category_depth(&self) -> Result<i64> {
+            unimplemented!()
+        }
+        
+        fn set_max_category_depth(&self, _max_depth: i64) -> Result<()> {
+            unimplemented!()
+        }
+        
         // Tag management methods
         fn add_credential_tags(&self, _credential_id: i64, _tags: &[String]) -> Result<()> {
             unimplemented!()
@@ -442,6 +726,18 @@ use tempfile::TempDir;
         fn cleanup_old_events(&self, _days_to_keep: u32) -> Result<u64> {
             unimplemented!()
         }
+
+        fn seal_audit_chain(&self) -> Result<usize> {
+            unimplemented!()
+        }
+
+        fn verify_audit_chain(&self) -> Result<audit::AuditChainVerification> {
+            unimplemented!()
+        }
+
+        fn get_events_in_range(&self, _from: Option<&str>, _to: Option<&str>) -> Result<Vec<audit::AuditEvent>> {
+            unimplemented!()
+        }
     }
 
     #[test]
@@ -542,6 +838,7 @@ use tempfile::TempDir;
 
         // Create a mock manifest with vault
         let manifest = RecoveryManifest {
+            bundle_format_version: BUNDLE_FORMAT_VERSION,
             asset_id: 1,
             export_date: "2024-01-01T00:00:00Z".to_string(),
             exported_by: "test_user".to_string(),
@@ -593,4 +890,391 @@ use tempfile::TempDir;
         let different_data = b"different data";
         let checksum3 = RecoveryExporter::calculate_checksum(different_data);
         assert_ne!(checksum1, checksum3);
-    }
\ No newline at end of file
+    }
+
+    fn write_bundle(bundle_path: &std::path::Path, bundle_format_version: u32) -> RecoveryManifest {
+        let config_data = b"setpoint=42";
+        let firmware_data = b"\x7fELF firmware";
+        std::fs::write(bundle_path.join("plc_config.json"), config_data).unwrap();
+        std::fs::write(bundle_path.join("plc_firmware.bin"), firmware_data).unwrap();
+
+        let manifest = RecoveryManifest {
+            bundle_format_version,
+            asset_id: 1,
+            export_date: "2024-01-01T00:00:00Z".to_string(),
+            exported_by: "test_user".to_string(),
+            configuration: ConfigurationExportInfo {
+                version_id: 1,
+                version_number: "v1".to_string(),
+                filename: "plc_config.json".to_string(),
+                checksum: RecoveryExporter::calculate_checksum(config_data),
+                file_size: config_data.len() as i64,
+            },
+            firmware: FirmwareExportInfo {
+                version_id: 1,
+                version: "2.0.0".to_string(),
+                filename: "plc_firmware.bin".to_string(),
+                checksum: RecoveryExporter::calculate_checksum(firmware_data),
+                vendor: "Test Vendor".to_string(),
+                model: "Test Model".to_string(),
+                file_size: firmware_data.len() as i64,
+            },
+            vault: None,
+            compatibility_verified: true,
+        };
+
+        std::fs::write(
+            bundle_path.join("plc_recovery_manifest.json"),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        ).unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_bundle_validation_accepts_current_format_version() {
+        let temp_dir = TempDir::new().unwrap();
+        write_bundle(temp_dir.path(), BUNDLE_FORMAT_VERSION);
+
+        let manifest = RecoveryImporter::validate_bundle_integrity(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(manifest.bundle_format_version, BUNDLE_FORMAT_VERSION);
+        assert_eq!(manifest.configuration.filename, "plc_config.json");
+    }
+
+    #[test]
+    fn test_bundle_validation_accepts_legacy_manifest_without_version() {
+        let temp_dir = TempDir::new().unwrap();
+        write_bundle(temp_dir.path(), BUNDLE_FORMAT_VERSION);
+
+        // Manifests exported before the version was embedded have no such field
+        let manifest_path = temp_dir.path().join("plc_recovery_manifest.json");
+        let mut manifest: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest.as_object_mut().unwrap().remove("bundleFormatVersion");
+        std::fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        let manifest = RecoveryImporter::validate_bundle_integrity(temp_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(manifest.bundle_format_version, 1);
+    }
+
+    #[test]
+    fn test_bundle_validation_rejects_future_format_version() {
+        let temp_dir = TempDir::new().unwrap();
+        write_bundle(temp_dir.path(), BUNDLE_FORMAT_VERSION + 1);
+
+        // A future layout may also drop fields this build expects; the version check must come first
+        let manifest_path = temp_dir.path().join("plc_recovery_manifest.json");
+        let mut manifest: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest.as_object_mut().unwrap().remove("firmware");
+        std::fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        let error = RecoveryImporter::validate_bundle_integrity(temp_dir.path().to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("newer than the supported version"), "unexpected error: {}", error);
+    }
+
+    fn setup_import_db(temp_dir: &TempDir) -> (crate::database::Database, i64) {
+        use crate::assets::{AssetRepository, SqliteAssetRepository, CreateAssetRequest, AssetType};
+        use crate::users::{UserRepository, SqliteUserRepository, CreateUserRequest};
+
+        let db = crate::database::Database::new(temp_dir.path().join("import.db")).unwrap();
+        let user = SqliteUserRepository::new(db.get_connection()).create_user(CreateUserRequest {
+            username: "engineer".to_string(),
+            password: "password123".to_string(),
+            role: UserRole::Engineer,
+        }).unwrap();
+        let asset = SqliteAssetRepository::new(db.get_connection()).create_asset(CreateAssetRequest {
+            name: "PLC-1".to_string(),
+            description: "Recovery target".to_string(),
+            asset_type: AssetType::Device,
+            parent_id: None,
+            created_by: user.id,
+        }).unwrap();
+        assert_eq!(asset.id, 1, "bundles written by write_bundle target asset 1");
+        (db, user.id)
+    }
+
+    fn try_import_bundle(
+        db: &crate::database::Database,
+        temp_dir: &TempDir,
+        bundle_path: &std::path::Path,
+        user_id: i64,
+        import_vault: bool,
+        conflict_strategy: ConflictStrategy,
+    ) -> Result<RecoveryImportReport> {
+        let conn = db.get_connection();
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(conn);
+        let firmware_repo = crate::firmware::SqliteFirmwareRepository::new(conn);
+        let vault_repo = crate::vault::SqliteVaultRepository::new(conn);
+        let audit_repo = crate::audit::SqliteAuditRepository::new(conn);
+        let importer = RecoveryImporter::new(&config_repo, &firmware_repo, &vault_repo, &audit_repo);
+
+        let request = RecoveryImportRequest {
+            bundle_path: bundle_path.to_str().unwrap().to_string(),
+            target_asset_id: 1,
+            import_vault,
+            conflict_strategy,
+        };
+        importer.import_recovery_bundle_into(&temp_dir.path().join("firmware"), request, user_id, "engineer", &UserRole::Engineer)
+    }
+
+    fn import_bundle(
+        db: &crate::database::Database,
+        temp_dir: &TempDir,
+        bundle_path: &std::path::Path,
+        user_id: i64,
+        conflict_strategy: ConflictStrategy,
+    ) -> RecoveryImportReport {
+        try_import_bundle(db, temp_dir, bundle_path, user_id, false, conflict_strategy).unwrap()
+    }
+
+    /// Creates a vault with one secret on asset 1 and adds its export to the bundle
+    fn write_vault_bundle(db: &crate::database::Database, bundle_path: &std::path::Path, user_id: i64) -> IdentityVault {
+        let vault_repo = crate::vault::SqliteVaultRepository::new(db.get_connection());
+        let vault = vault_repo.create_vault(CreateVaultRequest {
+            asset_id: 1,
+            name: "PLC Vault".to_string(),
+            description: "Recovery target vault".to_string(),
+            created_by: user_id,
+        }).unwrap();
+        vault_repo.add_secret(AddSecretRequest {
+            vault_id: vault.id,
+            secret_type: SecretType::VpnKey,
+            label: "Site VPN".to_string(),
+            value: "vpn-key-material".to_string(),
+            author_id: user_id,
+            expires_at: None,
+        }).unwrap();
+
+        let mut manifest = write_bundle(bundle_path, BUNDLE_FORMAT_VERSION);
+        let vault_json = serde_json::to_string_pretty(&vault_repo.get_vault_by_asset_id(1).unwrap().unwrap()).unwrap();
+        std::fs::write(bundle_path.join("plc_vault.json"), &vault_json).unwrap();
+        manifest.vault = Some(VaultExportInfo {
+            vault_id: vault.id,
+            vault_name: vault.name.clone(),
+            filename: "plc_vault.json".to_string(),
+            checksum: RecoveryExporter::calculate_checksum(vault_json.as_bytes()),
+            secret_count: 1,
+            file_size: vault_json.len() as i64,
+            encrypted: true,
+        });
+        std::fs::write(
+            bundle_path.join("plc_recovery_manifest.json"),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        ).unwrap();
+        vault
+    }
+
+    fn item(report: &RecoveryImportReport, kind: ImportItemKind) -> &ImportItemOutcome {
+        report.items.iter().find(|item| item.kind == kind).unwrap()
+    }
+
+    #[test]
+    fn test_import_conflict_strategy_defaults_to_skip() {
+        let request: RecoveryImportRequest = serde_json::from_value(serde_json::json!({
+            "bundlePath": "/tmp/bundle",
+            "targetAssetId": 1,
+            "importVault": false,
+        })).unwrap();
+        assert_eq!(request.conflict_strategy, ConflictStrategy::Skip);
+        assert_eq!("Rename".parse::<ConflictStrategy>().unwrap(), ConflictStrategy::Rename);
+        assert!("merge".parse::<ConflictStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_import_skips_existing_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+
+        let first = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        assert!(first.items.iter().all(|item| item.action == ImportItemAction::Imported));
+
+        let second = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        let config = item(&second, ImportItemKind::Configuration);
+        assert_eq!(config.action, ImportItemAction::Skipped);
+        assert_eq!(config.existing_id, item(&first, ImportItemKind::Configuration).imported_id);
+        assert_eq!(config.imported_id, None);
+        assert_eq!(item(&second, ImportItemKind::Firmware).action, ImportItemAction::Skipped);
+
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+        let firmware_repo = crate::firmware::SqliteFirmwareRepository::new(db.get_connection());
+        assert_eq!(config_repo.get_configuration_versions(1).unwrap().len(), 1);
+        assert_eq!(firmware_repo.get_firmware_by_asset(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_overwrites_existing_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+
+        let first = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        let second = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Overwrite);
+
+        for kind in [ImportItemKind::Configuration, ImportItemKind::Firmware] {
+            let outcome = item(&second, kind);
+            assert_eq!(outcome.action, ImportItemAction::Overwritten);
+            assert_eq!(outcome.existing_id, item(&first, kind).imported_id);
+            assert_ne!(outcome.imported_id, outcome.existing_id);
+        }
+
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+        let firmware_repo = crate::firmware::SqliteFirmwareRepository::new(db.get_connection());
+        let versions = config_repo.get_configuration_versions(1).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(Some(versions[0].id), item(&second, ImportItemKind::Configuration).imported_id);
+        assert_eq!(versions[0].firmware_version_id, item(&second, ImportItemKind::Firmware).imported_id);
+        let firmware = firmware_repo.get_firmware_by_asset(1).unwrap();
+        assert_eq!(firmware.len(), 1);
+        assert!(temp_dir.path().join("firmware").join(&firmware_repo.get_firmware_by_id(firmware[0].id).unwrap().unwrap().file_path).exists());
+    }
+
+    #[test]
+    fn test_import_overwrite_leaves_golden_configuration_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+
+        let first = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        let golden_id = item(&first, ImportItemKind::Configuration).imported_id.unwrap();
+        db.get_connection().execute(
+            "UPDATE configuration_versions SET status = 'Golden' WHERE id = ?1",
+            [golden_id],
+        ).unwrap();
+
+        let second = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Overwrite);
+        let config = item(&second, ImportItemKind::Configuration);
+        assert_eq!(config.action, ImportItemAction::Failed);
+        assert_eq!(config.existing_id, Some(golden_id));
+        assert_eq!(item(&second, ImportItemKind::Firmware).action, ImportItemAction::Overwritten);
+
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+        let versions = config_repo.get_configuration_versions(1).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].id, golden_id);
+    }
+
+    #[test]
+    fn test_import_renames_conflicting_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+
+        import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        let second = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Rename);
+        let third = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Rename);
+
+        let config = item(&second, ImportItemKind::Configuration);
+        assert_eq!(config.action, ImportItemAction::Renamed);
+        assert_eq!(config.imported_name.as_deref(), Some("plc_config (2).json"));
+        assert_eq!(item(&third, ImportItemKind::Configuration).imported_name.as_deref(), Some("plc_config (3).json"));
+
+        let firmware = item(&second, ImportItemKind::Firmware);
+        assert_eq!(firmware.action, ImportItemAction::Renamed);
+        assert_eq!(firmware.imported_name.as_deref(), Some("2.0.0 (2)"));
+
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+        let firmware_repo = crate::firmware::SqliteFirmwareRepository::new(db.get_connection());
+        assert_eq!(config_repo.get_configuration_versions(1).unwrap().len(), 3);
+        assert_eq!(firmware_repo.get_firmware_by_asset(1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_import_conflicts_only_with_matching_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+
+        // A later version of the same file is its history, not a conflict
+        let newer = config_repo.store_configuration(crate::configurations::CreateConfigurationRequest {
+            asset_id: 1,
+            file_name: "plc_config.json".to_string(),
+            file_content: b"setpoint=43".to_vec(),
+            author: user_id,
+            notes: "Tuned on site".to_string(),
+        }).unwrap();
+        let first = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Overwrite);
+        let config = item(&first, ImportItemKind::Configuration);
+        assert_eq!(config.action, ImportItemAction::Imported);
+        assert_eq!(config.existing_id, None);
+
+        // Overwrite replaces the version holding the bundle content and leaves the rest
+        let second = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Overwrite);
+        let config = item(&second, ImportItemKind::Configuration);
+        assert_eq!(config.action, ImportItemAction::Overwritten);
+        assert_eq!(config.existing_id, item(&first, ImportItemKind::Configuration).imported_id);
+
+        let ids: Vec<i64> = config_repo.get_configuration_versions(1).unwrap().iter().map(|v| v.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&newer.id));
+        assert!(ids.contains(&config.imported_id.unwrap()));
+    }
+
+    #[test]
+    fn test_import_overwrite_failure_keeps_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        write_bundle(bundle_dir.path(), BUNDLE_FORMAT_VERSION);
+        let (db, user_id) = setup_import_db(&temp_dir);
+
+        let first = import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, ConflictStrategy::Skip);
+        let original_id = item(&first, ImportItemKind::Configuration).imported_id.unwrap();
+
+        // The bundle file is not valid JSON, so storing the replacement now fails
+        let config_repo = crate::configurations::SqliteConfigurationRepository::new(db.get_connection());
+        config_repo.set_lint_mode(crate::configurations::LintMode::Reject).unwrap();
+        assert!(try_import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, false, ConflictStrategy::Overwrite).is_err());
+
+        let versions = config_repo.get_configuration_versions(1).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].id, original_id);
+        assert!(config_repo.get_configuration_content(original_id).is_ok());
+    }
+
+    #[test]
+    fn test_import_overwrites_existing_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        let (db, user_id) = setup_import_db(&temp_dir);
+        let original = write_vault_bundle(&db, bundle_dir.path(), user_id);
+
+        let report = try_import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, true, ConflictStrategy::Overwrite).unwrap();
+        let vault = item(&report, ImportItemKind::Vault);
+        assert_eq!(vault.action, ImportItemAction::Overwritten);
+        assert_eq!(vault.existing_id, Some(original.id));
+        assert_ne!(vault.imported_id, Some(original.id));
+
+        let vault_repo = crate::vault::SqliteVaultRepository::new(db.get_connection());
+        assert!(vault_repo.get_vault_by_id(original.id).unwrap().is_none());
+        let imported = vault_repo.get_vault_by_asset_id(1).unwrap().unwrap();
+        assert_eq!(Some(imported.vault.id), vault.imported_id);
+        assert_eq!(imported.vault.name, "PLC Vault");
+        assert_eq!(imported.secrets.len(), 1);
+        assert_eq!(imported.secrets[0].label, "Site VPN");
+    }
+
+    #[test]
+    fn test_import_renames_conflicting_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_dir = TempDir::new().unwrap();
+        let (db, user_id) = setup_import_db(&temp_dir);
+        let original = write_vault_bundle(&db, bundle_dir.path(), user_id);
+
+        let report = try_import_bundle(&db, &temp_dir, bundle_dir.path(), user_id, true, ConflictStrategy::Rename).unwrap();
+        let vault = item(&report, ImportItemKind::Vault);
+        assert_eq!(vault.action, ImportItemAction::Renamed);
+        assert_eq!(vault.existing_id, Some(original.id));
+        assert_eq!(vault.imported_name.as_deref(), Some("PLC Vault (2)"));
+
+        let vault_repo = crate::vault::SqliteVaultRepository::new(db.get_connection());
+        assert!(vault_repo.get_vault_by_id(original.id).unwrap().is_some());
+        let renamed = vault_repo.get_vault_by_id(vault.imported_id.unwrap()).unwrap().unwrap();
+        assert_eq!(renamed.name, "PLC Vault (2)");
+        assert_eq!(renamed.asset_id, 1);
+    }
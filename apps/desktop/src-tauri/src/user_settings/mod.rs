@@ -305,6 +305,26 @@ pub trait UserSettingsRepository {
     fn update_settings(&self, settings: &UserSettings) -> Result<()>;
     fn delete_settings(&self, user_id: i64) -> Result<()>;
     fn get_or_create_settings(&self, user_id: i64) -> Result<UserSettings>;
+    fn get_user_ui_state(&self, user_id: i64) -> Result<Option<serde_json::Value>>;
+    fn set_user_ui_state(&self, user_id: i64, json: &str) -> Result<serde_json::Value>;
+}
+
+/// Upper bound for a stored UI state document
+pub const MAX_UI_STATE_SIZE: usize = 256 * 1024;
+
+/// Parse a UI state document, which must be a JSON object within the size limit
+pub fn parse_ui_state(json: &str) -> Result<serde_json::Value> {
+    if json.len() > MAX_UI_STATE_SIZE {
+        return Err(anyhow::anyhow!("UI state exceeds {} bytes", MAX_UI_STATE_SIZE));
+    }
+
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("Invalid UI state JSON: {}", e))?;
+    if !value.is_object() {
+        return Err(anyhow::anyhow!("UI state must be a JSON object"));
+    }
+
+    Ok(value)
 }
 
 /// SQLite implementation of user settings repository
@@ -329,6 +349,14 @@ impl<'a> SqliteUserSettingsRepository<'a> {
             );
 
             CREATE INDEX IF NOT EXISTS idx_user_settings_updated_at ON user_settings(updated_at);
+
+            -- Free-form UI preferences (column widths, last-open asset, theme), kept apart from retry settings
+            CREATE TABLE IF NOT EXISTS user_ui_state (
+                user_id INTEGER PRIMARY KEY,
+                state TEXT NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+            );
             "#,
         )?;
         Ok(())
@@ -440,6 +468,36 @@ impl<'a> UserSettingsRepository for SqliteUserSettingsRepository<'a> {
             Ok(settings)
         }
     }
+
+    fn get_user_ui_state(&self, user_id: i64) -> Result<Option<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT state FROM user_ui_state WHERE user_id = ?1"
+        )?;
+
+        let result = stmt.query_row([user_id], |row| row.get::<_, String>(0));
+
+        match result {
+            Ok(state) => Ok(Some(serde_json::from_str(&state)?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_user_ui_state(&self, user_id: i64, json: &str) -> Result<serde_json::Value> {
+        let value = parse_ui_state(json)?;
+
+        self.conn.execute(
+            "INSERT INTO user_ui_state (user_id, state, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(user_id) DO UPDATE SET state = excluded.state, updated_at = excluded.updated_at",
+            (
+                &user_id,
+                &value.to_string(),
+                &chrono::Utc::now().to_rfc3339(),
+            ),
+        )?;
+
+        Ok(value)
+    }
 }
 
 /// Utility functions for common settings operations
@@ -747,4 +805,42 @@ mod tests {
         assert_eq!(settings.user_id, deserialized.user_id);
         assert_eq!(settings.version, deserialized.version);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_user_ui_state_round_trip() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteUserSettingsRepository::new(&conn);
+
+        assert!(repo.get_user_ui_state(1).unwrap().is_none());
+
+        let prefs = r#"{"theme":"dark","lastAssetId":42,"columnWidths":{"name":240,"version":96}}"#;
+        repo.set_user_ui_state(1, prefs).unwrap();
+        let state = repo.get_user_ui_state(1).unwrap().unwrap();
+        assert_eq!(state, serde_json::from_str::<serde_json::Value>(prefs).unwrap());
+
+        // Replacing the document overwrites it entirely
+        repo.set_user_ui_state(1, r#"{"theme":"light"}"#).unwrap();
+        let state = repo.get_user_ui_state(1).unwrap().unwrap();
+        assert_eq!(state["theme"], "light");
+        assert!(state.get("columnWidths").is_none());
+
+        // UI state does not touch functional settings
+        assert!(repo.get_settings(1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_user_ui_state_rejects_invalid_json() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteUserSettingsRepository::new(&conn);
+
+        repo.set_user_ui_state(1, r#"{"theme":"dark"}"#).unwrap();
+
+        assert!(repo.set_user_ui_state(1, "{theme: dark").is_err());
+        assert!(repo.set_user_ui_state(1, "[1, 2, 3]").is_err());
+        let oversized = format!(r#"{{"blob":"{}"}}"#, "x".repeat(MAX_UI_STATE_SIZE));
+        assert!(repo.set_user_ui_state(1, &oversized).is_err());
+
+        // The previously stored document is left intact
+        assert_eq!(repo.get_user_ui_state(1).unwrap().unwrap()["theme"], "dark");
+    }
+}
@@ -4,6 +4,12 @@ use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use tracing::{warn, error};
 
+use crate::users::UserRole;
+
+pub mod rate_limits;
+
+pub use rate_limits::{RateLimitPolicy, RateLimitPolicyRepository, SqliteRateLimitPolicyRepository};
+
 #[derive(Debug)]
 pub struct ValidationError {
     pub field: String,
@@ -149,6 +155,8 @@ pub struct RateLimiter {
     requests: Mutex<HashMap<String, Vec<Instant>>>,
     max_requests: usize,
     window_duration: Duration,
+    /// Per-command limits keyed by `(command, role)`, where `"*"` covers every role
+    policies: Mutex<HashMap<(String, String), (usize, Duration)>>,
 }
 
 impl RateLimiter {
@@ -157,20 +165,61 @@ impl RateLimiter {
             requests: Mutex::new(HashMap::new()),
             max_requests,
             window_duration,
+            policies: Mutex::new(HashMap::new()),
         }
     }
 
     pub fn check_rate_limit(&self, key: &str) -> Result<(), String> {
+        self.check_bucket(key, self.max_requests, self.window_duration)
+    }
+
+    /// Rate limit `key` within the bucket for `command` and the caller's role. The role's own
+    /// policy wins over the command's all-roles policy, and commands without either share the
+    /// global default. Buckets are separate, so exhausting one command leaves others usable.
+    pub fn check_command_rate_limit(&self, command: &str, role: Option<&UserRole>, key: &str) -> Result<(), String> {
+        let role_key = role.map_or_else(|| "*".to_string(), UserRole::to_string);
+        let (max_requests, window_duration) = self.limits_for(command, &role_key);
+        self.check_bucket(&format!("{}:{}:{}", command, role_key, key), max_requests, window_duration)
+    }
+
+    /// Replace the per-command policies, typically with those loaded from the database
+    pub fn set_policies(&self, policies: &[RateLimitPolicy]) {
+        let mut active = self.policies.lock().unwrap();
+        active.clear();
+        for policy in policies {
+            active.insert(Self::policy_key(policy), (policy.max_requests, Duration::from_secs(policy.window_seconds)));
+        }
+    }
+
+    pub fn set_policy(&self, policy: &RateLimitPolicy) {
+        self.policies.lock().unwrap()
+            .insert(Self::policy_key(policy), (policy.max_requests, Duration::from_secs(policy.window_seconds)));
+    }
+
+    fn policy_key(policy: &RateLimitPolicy) -> (String, String) {
+        let role = policy.user_role.as_ref().map_or_else(|| "*".to_string(), UserRole::to_string);
+        (policy.command_name.clone(), role)
+    }
+
+    fn limits_for(&self, command: &str, role_key: &str) -> (usize, Duration) {
+        let policies = self.policies.lock().unwrap();
+        policies.get(&(command.to_string(), role_key.to_string()))
+            .or_else(|| policies.get(&(command.to_string(), "*".to_string())))
+            .copied()
+            .unwrap_or((self.max_requests, self.window_duration))
+    }
+
+    fn check_bucket(&self, key: &str, max_requests: usize, window_duration: Duration) -> Result<(), String> {
         let mut requests = self.requests.lock().unwrap();
         let now = Instant::now();
         
         // Clean up old requests
-        let cutoff_time = now - self.window_duration;
+        let cutoff_time = now - window_duration;
         
         let user_requests = requests.entry(key.to_string()).or_insert_with(Vec::new);
         user_requests.retain(|&request_time| request_time > cutoff_time);
         
-        if user_requests.len() >= self.max_requests {
+        if user_requests.len() >= max_requests {
             warn!("Rate limit exceeded for key: {}", key);
             return Err("Rate limit exceeded. Please try again later.".to_string());
         }
@@ -180,9 +229,14 @@ impl RateLimiter {
     }
 
     pub fn cleanup_old_entries(&self) {
+        // Keep anything the longest configured window could still count
+        let longest_window = self.policies.lock().unwrap().values()
+            .map(|&(_, window)| window)
+            .fold(self.window_duration, Duration::max);
+
         let mut requests = self.requests.lock().unwrap();
         let now = Instant::now();
-        let cutoff_time = now - self.window_duration;
+        let cutoff_time = now - longest_window;
         
         // Remove entries that are completely expired
         requests.retain(|_key, timestamps| {
@@ -332,6 +386,39 @@ mod tests {
         assert!(limiter.check_rate_limit("user2").is_ok());
     }
 
+    #[test]
+    fn test_command_rate_limit_buckets() {
+        let limiter = RateLimiter::new(5, Duration::from_secs(60));
+        limiter.set_policies(&[
+            RateLimitPolicy::new("export_vault", None, 2, 60),
+            RateLimitPolicy::new("export_vault", Some(UserRole::Administrator), 3, 60),
+        ]);
+
+        let engineer = Some(&UserRole::Engineer);
+        assert!(limiter.check_command_rate_limit("export_vault", engineer, "token").is_ok());
+        assert!(limiter.check_command_rate_limit("export_vault", engineer, "token").is_ok());
+        let error = limiter.check_command_rate_limit("export_vault", engineer, "token").unwrap_err();
+        assert!(error.contains("Rate limit exceeded"));
+
+        // An unrelated command keeps its own bucket under the global default
+        for _ in 0..5 {
+            assert!(limiter.check_command_rate_limit("decrypt_vault_secret", engineer, "token").is_ok());
+        }
+        assert!(limiter.check_command_rate_limit("decrypt_vault_secret", engineer, "token").is_err());
+
+        // The role-specific policy overrides the all-roles one
+        let admin = Some(&UserRole::Administrator);
+        for _ in 0..3 {
+            assert!(limiter.check_command_rate_limit("export_vault", admin, "token").is_ok());
+        }
+        assert!(limiter.check_command_rate_limit("export_vault", admin, "token").is_err());
+
+        limiter.set_policy(&RateLimitPolicy::new("login", None, 1, 60));
+        assert!(limiter.check_command_rate_limit("login", None, "alice").is_ok());
+        assert!(limiter.check_command_rate_limit("login", None, "alice").is_err());
+        assert!(limiter.check_command_rate_limit("login", None, "bob").is_ok());
+    }
+
     #[test]
     fn test_input_sanitizer() {
         assert_eq!(InputSanitizer::sanitize_string("  hello world  "), "hello world");
@@ -0,0 +1,172 @@
+// Per-command rate limit policies, persisted so administrators can tune them without a rebuild
+
+use anyhow::Result;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::users::UserRole;
+
+/// Stored role value for a policy that applies to every role, and to callers with no session
+const ANY_ROLE: &str = "*";
+
+/// Limit for one `(command, role)` bucket. A policy without a role covers every caller of the
+/// command, including unauthenticated ones such as `login`; a role-specific policy overrides it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitPolicy {
+    pub command_name: String,
+    pub user_role: Option<UserRole>,
+    pub max_requests: usize,
+    pub window_seconds: u64,
+}
+
+impl RateLimitPolicy {
+    pub fn new(command_name: &str, user_role: Option<UserRole>, max_requests: usize, window_seconds: u64) -> Self {
+        Self {
+            command_name: command_name.to_string(),
+            user_role,
+            max_requests,
+            window_seconds,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.command_name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Command name cannot be empty"));
+        }
+        if self.max_requests == 0 {
+            return Err(anyhow::anyhow!("Maximum requests must be greater than 0"));
+        }
+        if self.window_seconds == 0 || self.window_seconds > 86_400 {
+            return Err(anyhow::anyhow!("Window must be between 1 second and 24 hours"));
+        }
+        Ok(())
+    }
+}
+
+/// Stricter buckets for credential guessing and bulk secret disclosure
+pub fn default_rate_limit_policies() -> Vec<RateLimitPolicy> {
+    vec![
+        RateLimitPolicy::new("login", None, 5, 300),
+        RateLimitPolicy::new("decrypt_vault_secret", None, 30, 60),
+        RateLimitPolicy::new("export_vault", None, 3, 300),
+    ]
+}
+
+pub trait RateLimitPolicyRepository {
+    fn list_rate_limit_policies(&self) -> Result<Vec<RateLimitPolicy>>;
+    fn upsert_rate_limit_policy(&self, policy: &RateLimitPolicy) -> Result<()>;
+}
+
+pub struct SqliteRateLimitPolicyRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteRateLimitPolicyRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn initialize_schema(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS rate_limit_policies (
+                command_name TEXT NOT NULL,
+                user_role TEXT NOT NULL,
+                max_requests INTEGER NOT NULL CHECK (max_requests > 0),
+                window_seconds INTEGER NOT NULL CHECK (window_seconds > 0),
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (command_name, user_role)
+            );
+            "#,
+        )?;
+
+        // Seed the defaults once; later edits are left alone
+        for policy in default_rate_limit_policies() {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO rate_limit_policies (command_name, user_role, max_requests, window_seconds)
+                 VALUES (?1, ?2, ?3, ?4)",
+                (&policy.command_name, role_to_column(&policy.user_role), policy.max_requests as i64, policy.window_seconds as i64),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn row_to_policy(row: &Row) -> rusqlite::Result<RateLimitPolicy> {
+        let role: String = row.get("user_role")?;
+        let user_role = match role.as_str() {
+            ANY_ROLE => None,
+            role => Some(role.parse::<UserRole>().map_err(|e| rusqlite::Error::FromSqlConversionFailure(
+                0,
+                rusqlite::types::Type::Text,
+                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())),
+            ))?),
+        };
+        let max_requests: i64 = row.get("max_requests")?;
+        let window_seconds: i64 = row.get("window_seconds")?;
+
+        Ok(RateLimitPolicy {
+            command_name: row.get("command_name")?,
+            user_role,
+            max_requests: max_requests as usize,
+            window_seconds: window_seconds as u64,
+        })
+    }
+}
+
+fn role_to_column(role: &Option<UserRole>) -> String {
+    role.as_ref().map_or_else(|| ANY_ROLE.to_string(), UserRole::to_string)
+}
+
+impl<'a> RateLimitPolicyRepository for SqliteRateLimitPolicyRepository<'a> {
+    fn list_rate_limit_policies(&self) -> Result<Vec<RateLimitPolicy>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT command_name, user_role, max_requests, window_seconds FROM rate_limit_policies
+             ORDER BY command_name, user_role"
+        )?;
+        let policies = stmt.query_map([], Self::row_to_policy)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(policies)
+    }
+
+    fn upsert_rate_limit_policy(&self, policy: &RateLimitPolicy) -> Result<()> {
+        policy.validate()?;
+
+        self.conn.execute(
+            "INSERT INTO rate_limit_policies (command_name, user_role, max_requests, window_seconds, updated_at)
+             VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
+             ON CONFLICT (command_name, user_role) DO UPDATE SET
+                max_requests = excluded.max_requests,
+                window_seconds = excluded.window_seconds,
+                updated_at = CURRENT_TIMESTAMP",
+            (&policy.command_name, role_to_column(&policy.user_role), policy.max_requests as i64, policy.window_seconds as i64),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_policies_persist() {
+        let conn = Connection::open_in_memory().unwrap();
+        let repo = SqliteRateLimitPolicyRepository::new(&conn);
+        repo.initialize_schema().unwrap();
+
+        let policies = repo.list_rate_limit_policies().unwrap();
+        assert_eq!(policies.len(), default_rate_limit_policies().len());
+        assert!(policies.contains(&RateLimitPolicy::new("login", None, 5, 300)));
+
+        repo.upsert_rate_limit_policy(&RateLimitPolicy::new("login", None, 3, 600)).unwrap();
+        repo.upsert_rate_limit_policy(&RateLimitPolicy::new("export_vault", Some(UserRole::Administrator), 10, 300)).unwrap();
+        assert!(repo.upsert_rate_limit_policy(&RateLimitPolicy::new("export_vault", None, 0, 300)).is_err());
+
+        // Re-running the schema setup must not restore the seeded limit
+        repo.initialize_schema().unwrap();
+        let policies = repo.list_rate_limit_policies().unwrap();
+        assert!(policies.contains(&RateLimitPolicy::new("login", None, 3, 600)));
+        assert!(policies.contains(&RateLimitPolicy::new("export_vault", Some(UserRole::Administrator), 10, 300)));
+        assert!(policies.contains(&RateLimitPolicy::new("export_vault", None, 3, 300)));
+    }
+}
@@ -30,6 +30,7 @@ impl<'a> VaultAccessControlService<'a> {
                 has_access: true,
                 permissions: vec![],
                 is_administrator: true,
+                inherited: false,
             });
         }
 
@@ -319,6 +320,8 @@ mod tests {
             granted_at: chrono::Utc::now().to_rfc3339(),
             expires_at: None,
             is_active: true,
+            inheritable: false,
+            inherited: false,
         };
         assert!(service.is_permission_valid(&perm1));
         
@@ -333,6 +336,8 @@ mod tests {
             granted_at: chrono::Utc::now().to_rfc3339(),
             expires_at: Some(future_expiry.to_rfc3339()),
             is_active: true,
+            inheritable: false,
+            inherited: false,
         };
         assert!(service.is_permission_valid(&perm2));
         
@@ -347,6 +352,8 @@ mod tests {
             granted_at: chrono::Utc::now().to_rfc3339(),
             expires_at: Some(past_expiry.to_rfc3339()),
             is_active: true,
+            inheritable: false,
+            inherited: false,
         };
         assert!(!service.is_permission_valid(&perm3));
         
@@ -360,6 +367,8 @@ mod tests {
             granted_at: chrono::Utc::now().to_rfc3339(),
             expires_at: None,
             is_active: false,
+            inheritable: false,
+            inherited: false,
         };
         assert!(!service.is_permission_valid(&perm4));
     }
@@ -0,0 +1,136 @@
+// Parsing of `.env` files for importing credentials into a vault
+
+use zeroize::Zeroizing;
+
+/// One `KEY=VALUE` assignment, or the reason its line could not be read
+#[derive(Debug)]
+pub struct DotenvLine {
+    pub line_number: usize,
+    pub entry: Result<(String, Zeroizing<String>), String>,
+}
+
+/// Parse dotenv content line by line. Blank lines and `#` comments are skipped; every
+/// other line yields either an assignment or an error, so one bad line does not hide
+/// the rest of the file.
+pub fn parse_dotenv(content: &str) -> Vec<DotenvLine> {
+    content.trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            Some(DotenvLine {
+                line_number: index + 1,
+                entry: parse_assignment(line),
+            })
+        })
+        .collect()
+}
+
+fn parse_assignment(line: &str) -> Result<(String, Zeroizing<String>), String> {
+    let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+    let (key, raw_value) = line.split_once('=')
+        .ok_or_else(|| "Expected KEY=VALUE".to_string())?;
+
+    let key = key.trim();
+    let mut chars = key.chars();
+    let valid_key = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if !valid_key {
+        return Err(format!("Invalid key '{}'", key));
+    }
+
+    Ok((key.to_string(), parse_value(raw_value.trim_start())?))
+}
+
+fn parse_value(raw: &str) -> Result<Zeroizing<String>, String> {
+    let mut value = Zeroizing::new(String::new());
+
+    let quote = match raw.chars().next() {
+        Some(quote @ ('"' | '\'')) => quote,
+        _ => {
+            // Unquoted values end at a comment that is preceded by whitespace
+            let end = raw.char_indices()
+                .find(|&(i, c)| c == '#' && raw[..i].ends_with(char::is_whitespace))
+                .map_or(raw.len(), |(i, _)| i);
+            value.push_str(raw[..end].trim_end());
+            return Ok(value);
+        }
+    };
+
+    let mut chars = raw[1..].char_indices();
+    let closing = loop {
+        match chars.next() {
+            Some((i, c)) if c == quote => break i + 1,
+            // Escapes are only interpreted inside double quotes, as shells do
+            Some((_, '\\')) if quote == '"' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, c)) => value.push(c),
+                None => break 0,
+            },
+            Some((_, c)) => value.push(c),
+            None => break 0,
+        }
+    };
+    if closing == 0 {
+        return Err("Unterminated quoted value".to_string());
+    }
+
+    let rest = raw[closing + 1..].trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err("Unexpected characters after quoted value".to_string());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(content: &str) -> Vec<(usize, Result<(String, String), String>)> {
+        parse_dotenv(content).into_iter()
+            .map(|line| (line.line_number, line.entry.map(|(key, value)| (key, value.to_string()))))
+            .collect()
+    }
+
+    fn ok(key: &str, value: &str) -> Result<(String, String), String> {
+        Ok((key.to_string(), value.to_string()))
+    }
+
+    #[test]
+    fn test_parse_dotenv_values() {
+        let parsed = entries(concat!(
+            "# Historian connection\n",
+            "\n",
+            "DB_PASSWORD=plain#value\n",
+            "export API_TOKEN = spaced value   # trailing comment\n",
+            "QUOTED=\"with # hash\\nand newline\" # comment\n",
+            "SINGLE='no \\n escapes'\n",
+            "EMPTY=\n",
+        ));
+
+        assert_eq!(parsed, vec![
+            (3, ok("DB_PASSWORD", "plain#value")),
+            (4, ok("API_TOKEN", "spaced value")),
+            (5, ok("QUOTED", "with # hash\nand newline")),
+            (6, ok("SINGLE", "no \\n escapes")),
+            (7, ok("EMPTY", "")),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_dotenv_errors_are_per_line() {
+        let parsed = entries("NO_EQUALS\n1BAD=x\nOPEN=\"never closed\nTRAILING='a' b\nGOOD=yes\n");
+
+        assert_eq!(parsed.len(), 5);
+        assert_eq!(parsed[0], (1, Err("Expected KEY=VALUE".to_string())));
+        assert_eq!(parsed[1], (2, Err("Invalid key '1BAD'".to_string())));
+        assert_eq!(parsed[2], (3, Err("Unterminated quoted value".to_string())));
+        assert_eq!(parsed[3], (4, Err("Unexpected characters after quoted value".to_string())));
+        assert_eq!(parsed[4], (5, ok("GOOD", "yes")));
+    }
+}
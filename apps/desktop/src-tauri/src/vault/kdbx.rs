@@ -0,0 +1,98 @@
+// KeePass KDBX 4 export, for moving secrets to other tools or keeping offline backups
+
+use std::path::Path;
+
+use anyhow::Result;
+use keepass::db::{Entry, Group, Node, Value};
+use keepass::{Database, DatabaseKey};
+use zeroize::Zeroizing;
+
+use super::SecretType;
+
+/// Shortest master password accepted for an exported database
+pub const MIN_KDBX_MASTER_PASSWORD_LENGTH: usize = 12;
+/// Custom entry field recording the original secret type
+pub const SECRET_TYPE_FIELD: &str = "Secret Type";
+
+/// A decrypted value to be written as one KeePass entry
+pub struct KdbxEntry {
+    pub title: String,
+    pub secret_type: SecretType,
+    pub value: Zeroizing<String>,
+}
+
+/// A KeePass group with its entries and subgroups
+pub struct KdbxGroup {
+    pub name: String,
+    pub entries: Vec<KdbxEntry>,
+    pub groups: Vec<KdbxGroup>,
+}
+
+impl KdbxGroup {
+    pub fn new(name: &str) -> Self {
+        KdbxGroup {
+            name: name.to_string(),
+            entries: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Entries in this group and all of its subgroups
+    pub fn entry_count(&self) -> usize {
+        self.entries.len() + self.groups.iter().map(KdbxGroup::entry_count).sum::<usize>()
+    }
+}
+
+/// Entry field that holds a value of the given type. Passwords go to the password field and
+/// TOTP seeds to the field KeePass reads for time-based codes; other types have no standard
+/// field and are kept in the notes.
+pub fn value_field(secret_type: SecretType) -> &'static str {
+    match secret_type {
+        SecretType::Password => "Password",
+        SecretType::TotpSeed => "TimeOtp-Secret-Base32",
+        SecretType::IpAddress | SecretType::VpnKey | SecretType::LicenseFile
+            | SecretType::SshPrivateKey | SecretType::Certificate => "Notes",
+    }
+}
+
+fn to_keepass_entry(entry: &KdbxEntry) -> Entry {
+    let mut keepass_entry = Entry::new();
+    keepass_entry.fields.insert("Title".to_string(), Value::Unprotected(entry.title.clone()));
+    keepass_entry.fields.insert(
+        value_field(entry.secret_type).to_string(),
+        Value::Protected(entry.value.as_bytes().into()),
+    );
+    keepass_entry.fields.insert(SECRET_TYPE_FIELD.to_string(), Value::Unprotected(entry.secret_type.to_string()));
+    keepass_entry
+}
+
+fn to_keepass_group(group: &KdbxGroup) -> Group {
+    let mut keepass_group = Group::new(&group.name);
+    keepass_group.children.extend(group.entries.iter().map(|entry| Node::Entry(to_keepass_entry(entry))));
+    keepass_group.children.extend(group.groups.iter().map(|child| Node::Group(to_keepass_group(child))));
+    keepass_group
+}
+
+/// Write `root` as a KDBX 4 database encrypted with `master_password`
+pub fn write_kdbx(root: &KdbxGroup, master_password: &str, output_path: &Path) -> Result<()> {
+    if master_password.chars().count() < MIN_KDBX_MASTER_PASSWORD_LENGTH {
+        return Err(anyhow::anyhow!(
+            "Master password must be at least {} characters", MIN_KDBX_MASTER_PASSWORD_LENGTH
+        ));
+    }
+
+    let mut database = Database::new(Default::default());
+    database.meta.database_name = Some(root.name.clone());
+    database.root = to_keepass_group(root);
+
+    // Save beside the destination first so a failed write never leaves a truncated database
+    let directory = output_path.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut staged = tempfile::NamedTempFile::new_in(directory)?;
+    database.save(staged.as_file_mut(), DatabaseKey::new().with_password(master_password))
+        .map_err(|e| anyhow::anyhow!("Failed to write KDBX database: {}", e))?;
+    staged.persist(output_path)
+        .map_err(|e| anyhow::anyhow!("Failed to write KDBX database: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// Protection of the vault master key: the database only holds it wrapped under a
+// key-encryption key kept in the OS keystore, so a copy of the database file opens nothing
+
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
+use once_cell::sync::OnceCell;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::encryption::{decrypt_with_salt, encrypt_with_salt};
+
+const KEYSTORE_SERVICE: &str = "ferrocodex";
+const KEYSTORE_ACCOUNT: &str = "vault-key-encryption-key";
+/// Marks a wrapped master key; older databases stored the bare base64 key
+const WRAPPED_PREFIX: &str = "wrapped:";
+const WRAP_CONTEXT: &str = "vault_master_key";
+/// HKDF info for the IP address search index key
+const IP_INDEX_KEY_LABEL: &[u8] = b"ferrocodex vault ip index key v1";
+/// HKDF info for the password history digest key
+const PASSWORD_DIGEST_KEY_LABEL: &[u8] = b"ferrocodex vault password digest key v1";
+#[cfg(test)]
+const TEST_KEY_ENCRYPTION_KEY: [u8; 32] = [0x5a; 32];
+
+static KEY_ENCRYPTION_KEY: OnceCell<Zeroizing<[u8; 32]>> = OnceCell::new();
+
+/// Load the key-encryption key from the OS keystore, creating it there on first run. Must
+/// be called before the database is opened.
+pub fn load_key_encryption_key() -> Result<()> {
+    if KEY_ENCRYPTION_KEY.get().is_some() {
+        return Ok(());
+    }
+
+    let entry = keyring::Entry::new(KEYSTORE_SERVICE, KEYSTORE_ACCOUNT)
+        .map_err(|e| anyhow::anyhow!("Failed to open the OS keystore: {}", e))?;
+    let key = match entry.get_password() {
+        Ok(encoded) => decode_key(&encoded)?,
+        Err(keyring::Error::NoEntry) => {
+            let key = Zeroizing::new(rand::random::<[u8; 32]>());
+            let encoded = Zeroizing::new(general_purpose::STANDARD.encode(key.as_slice()));
+            entry.set_password(&encoded)
+                .map_err(|e| anyhow::anyhow!("Failed to store the vault key-encryption key in the OS keystore: {}", e))?;
+            key
+        }
+        Err(e) => return Err(anyhow::anyhow!("Failed to read the vault key-encryption key from the OS keystore: {}", e)),
+    };
+    install_key_encryption_key(key)
+}
+
+/// Use `key` as the key-encryption key, for hosts that keep it somewhere other than the OS keystore
+pub fn install_key_encryption_key(key: Zeroizing<[u8; 32]>) -> Result<()> {
+    let installed = KEY_ENCRYPTION_KEY.get_or_init(|| key.clone());
+    if installed.as_slice() != key.as_slice() {
+        return Err(anyhow::anyhow!("A different vault key-encryption key is already loaded"));
+    }
+    Ok(())
+}
+
+fn decode_key(encoded: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let bytes = Zeroizing::new(general_purpose::STANDARD.decode(encoded.trim())
+        .map_err(|_| anyhow::anyhow!("The vault key-encryption key in the OS keystore is not valid base64"))?);
+    let mut key = Zeroizing::new([0u8; 32]);
+    if bytes.len() != key.len() {
+        return Err(anyhow::anyhow!("The vault key-encryption key in the OS keystore has the wrong length"));
+    }
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+fn key_encryption_key() -> Result<&'static [u8; 32]> {
+    #[cfg(test)]
+    let key = Some(KEY_ENCRYPTION_KEY.get_or_init(|| Zeroizing::new(TEST_KEY_ENCRYPTION_KEY)));
+    #[cfg(not(test))]
+    let key = KEY_ENCRYPTION_KEY.get();
+    key.map(|key| &**key)
+        .ok_or_else(|| anyhow::anyhow!("The vault key-encryption key has not been loaded"))
+}
+
+/// A new random master key, wrapped for storage
+pub(crate) fn generate_master_key() -> Result<String> {
+    let master_key = Zeroizing::new(rand::random::<[u8; 32]>());
+    wrap_with(key_encryption_key()?, master_key.as_slice())
+}
+
+/// Whether a stored master key is already wrapped
+pub(crate) fn is_wrapped(stored: &str) -> bool {
+    stored.starts_with(WRAPPED_PREFIX)
+}
+
+/// Wrap the bare base64 master key an older database stored
+pub(crate) fn wrap_legacy_master_key(stored: &str) -> Result<String> {
+    let master_key = Zeroizing::new(general_purpose::STANDARD.decode(stored.trim())?);
+    wrap_with(key_encryption_key()?, &master_key)
+}
+
+pub(crate) fn unwrap_master_key(stored: &str) -> Result<Zeroizing<Vec<u8>>> {
+    unwrap_with(key_encryption_key()?, stored)
+}
+
+fn wrap_with(kek: &[u8], master_key: &[u8]) -> Result<String> {
+    let wrapped = encrypt_with_salt(kek, WRAP_CONTEXT, master_key)?;
+    Ok(format!("{}{}", WRAPPED_PREFIX, general_purpose::STANDARD.encode(wrapped)))
+}
+
+fn unwrap_with(kek: &[u8], stored: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let encoded = stored.strip_prefix(WRAPPED_PREFIX)
+        .ok_or_else(|| anyhow::anyhow!("The vault master key is stored unwrapped"))?;
+    let wrapped = general_purpose::STANDARD.decode(encoded)?;
+    decrypt_with_salt(kek, WRAP_CONTEXT, &wrapped)
+        .map_err(|_| anyhow::anyhow!("The vault master key could not be unwrapped; the key-encryption key does not match this database"))
+}
+
+/// Key for the IP address search index. It is derived rather than stored, so it is exactly
+/// as well protected as the master key.
+pub(crate) fn derive_ip_index_key(master_key: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    derive_subkey(master_key, IP_INDEX_KEY_LABEL)
+}
+
+/// Key for the password digests of secrets and their history, derived the same way as the IP index key
+pub(crate) fn derive_password_digest_key(master_key: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    derive_subkey(master_key, PASSWORD_DIGEST_KEY_LABEL)
+}
+
+fn derive_subkey(master_key: &[u8], label: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    hkdf::Hkdf::<Sha256>::new(None, master_key)
+        .expand(label, key.as_mut_slice())
+        .map_err(|e| anyhow::anyhow!("Failed to derive a vault subkey: {}", e))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_master_key_wrapping() {
+        let master_key = [0x11u8; 32];
+        let stored = wrap_with(&TEST_KEY_ENCRYPTION_KEY, &master_key).unwrap();
+        assert!(is_wrapped(&stored));
+        assert!(!stored.contains(&general_purpose::STANDARD.encode(master_key)));
+        assert_eq!(unwrap_with(&TEST_KEY_ENCRYPTION_KEY, &stored).unwrap().as_slice(), &master_key);
+
+        // Neither another key-encryption key nor a bare key opens it
+        assert!(unwrap_with(&[0x22u8; 32], &stored).is_err());
+        assert!(unwrap_with(&TEST_KEY_ENCRYPTION_KEY, &general_purpose::STANDARD.encode(master_key)).is_err());
+
+        let upgraded = wrap_legacy_master_key(&general_purpose::STANDARD.encode(master_key)).unwrap();
+        assert_eq!(unwrap_master_key(&upgraded).unwrap().as_slice(), &master_key);
+    }
+
+    #[test]
+    fn test_ip_index_key_is_derived_from_master_key() {
+        let index_key = derive_ip_index_key(&[0x11u8; 32]).unwrap();
+        assert_eq!(index_key.as_slice(), derive_ip_index_key(&[0x11u8; 32]).unwrap().as_slice());
+        assert_ne!(index_key.as_slice(), &[0x11u8; 32]);
+        assert_ne!(index_key.as_slice(), derive_ip_index_key(&[0x12u8; 32]).unwrap().as_slice());
+        assert_ne!(index_key.as_slice(), derive_password_digest_key(&[0x11u8; 32]).unwrap().as_slice());
+    }
+}
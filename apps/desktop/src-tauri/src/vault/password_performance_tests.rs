@@ -13,6 +13,7 @@ mod performance_tests {
             include_numbers: true,
             include_special: true,
             exclude_ambiguous: true,
+            ..Default::default()
         };
 
         // Test that password generation completes within 1 second
@@ -62,6 +63,7 @@ mod performance_tests {
             include_numbers: true,
             include_special: true,
             exclude_ambiguous: true,
+            ..Default::default()
         };
 
         // Generate 10 passwords and ensure average time is reasonable
@@ -91,7 +93,12 @@ mod performance_tests {
             require_lowercase: true,
             require_numbers: true,
             require_special: true,
+            min_uppercase: None,
+            min_lowercase: None,
+            min_numbers: None,
+            min_special: None,
             max_age_days: None,
+            min_strength_score: None,
             created_at: "2024-01-01".to_string(),
             updated_at: "2024-01-01".to_string(),
         };
@@ -5,12 +5,35 @@ use rand::seq::SliceRandom;
 
 use super::{GeneratePasswordRequest, PasswordStrength, PasswordPolicy};
 
+/// Lowest entropy, as measured by the strength analyzer, a generated password may have
+pub const MIN_GENERATED_PASSWORD_ENTROPY: f64 = 40.0;
+/// Candidates drawn before giving up on reaching the entropy floor
+pub const MAX_GENERATION_ATTEMPTS: usize = 32;
+
 /// Password generation service with secure random generation
 pub struct PasswordGenerator;
 
 impl PasswordGenerator {
-    /// Generate a secure password based on the provided policy
+    /// Generate a secure password based on the provided policy. Every candidate is run
+    /// through the strength analyzer and regenerated if it falls below the entropy floor.
     pub fn generate(request: &GeneratePasswordRequest) -> Result<String> {
+        let mut best_entropy = 0.0f64;
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let password = Self::generate_candidate(request)?;
+            let entropy = PasswordStrengthAnalyzer::analyze(&password).entropy;
+            if entropy >= MIN_GENERATED_PASSWORD_ENTROPY {
+                return Ok(password);
+            }
+            best_entropy = best_entropy.max(entropy);
+        }
+
+        Err(anyhow::anyhow!(
+            "Generated passwords reach only {:.1} bits of entropy, below the minimum of {:.0}; increase the length or enable more character sets",
+            best_entropy, MIN_GENERATED_PASSWORD_ENTROPY
+        ))
+    }
+
+    fn generate_candidate(request: &GeneratePasswordRequest) -> Result<String> {
         let mut charset = String::new();
         
         if request.include_lowercase {
@@ -53,34 +76,44 @@ impl PasswordGenerator {
             return Err(anyhow::anyhow!("Password length must be at least 8 characters"));
         }
         
-        // Generate password ensuring at least one character from each required set
-        let mut password = Vec::new();
-        let mut remaining_length = request.length;
-        
-        // Add required characters first
-        if request.include_lowercase {
-            let lowercase_chars: Vec<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
-            password.push(*lowercase_chars.choose(&mut rng).unwrap());
-            remaining_length -= 1;
-        }
+        // Each enabled set contributes its configured minimum, or one character by default
+        let required_sets = [
+            ("lowercase letters", request.include_lowercase, request.min_lowercase, "abcdefghijklmnopqrstuvwxyz"),
+            ("uppercase letters", request.include_uppercase, request.min_uppercase, "ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            ("numbers", request.include_numbers, request.min_numbers, "0123456789"),
+            ("special characters", request.include_special, request.min_special, "!@#$%^&*()_+-=[]{}|;:,.<>?"),
+        ];
         
-        if request.include_uppercase {
-            let uppercase_chars: Vec<char> = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
-            password.push(*uppercase_chars.choose(&mut rng).unwrap());
-            remaining_length -= 1;
+        let mut required_total = 0;
+        for (name, included, minimum, _) in required_sets {
+            let minimum = minimum.unwrap_or(0);
+            if minimum > 0 && !included {
+                return Err(anyhow::anyhow!("At least {} {} are required but that set is disabled", minimum, name));
+            }
+            if included {
+                required_total += minimum.max(1);
+            }
         }
         
-        if request.include_numbers {
-            let number_chars: Vec<char> = "0123456789".chars().collect();
-            password.push(*number_chars.choose(&mut rng).unwrap());
-            remaining_length -= 1;
+        if required_total > request.length {
+            return Err(anyhow::anyhow!(
+                "Password length {} cannot satisfy the required character minimums ({})",
+                request.length, required_total
+            ));
         }
         
-        if request.include_special {
-            let special_chars: Vec<char> = "!@#$%^&*()_+-=[]{}|;:,.<>?".chars().collect();
-            password.push(*special_chars.choose(&mut rng).unwrap());
-            remaining_length -= 1;
+        // Generate password with the required characters from each set first
+        let mut password = Vec::new();
+        for (_, included, minimum, set) in required_sets {
+            if !included {
+                continue;
+            }
+            let set_chars: Vec<char> = set.chars().collect();
+            for _ in 0..minimum.unwrap_or(0).max(1) {
+                password.push(*set_chars.choose(&mut rng).unwrap());
+            }
         }
+        let remaining_length = request.length - required_total;
         
         // Fill remaining length with random characters from full charset
         for _ in 0..remaining_length {
@@ -225,6 +258,26 @@ impl PasswordStrengthAnalyzer {
             violations.push("Password must contain special characters".to_string());
         }
         
+        let class_minimums = [
+            ("uppercase letters", policy.min_uppercase, password.chars().filter(|c| c.is_ascii_uppercase()).count()),
+            ("lowercase letters", policy.min_lowercase, password.chars().filter(|c| c.is_ascii_lowercase()).count()),
+            ("numbers", policy.min_numbers, password.chars().filter(|c| c.is_ascii_digit()).count()),
+            ("special characters", policy.min_special, password.chars().filter(|c| !c.is_ascii_alphanumeric()).count()),
+        ];
+        for (name, minimum, count) in class_minimums {
+            if let Some(minimum) = minimum.filter(|&m| m > 0) {
+                if (count as i32) < minimum {
+                    violations.push(format!("Password must contain at least {} {}", minimum, name));
+                }
+            }
+        }
+        
+        if let Some(min_score) = policy.min_strength_score {
+            if strength.score < min_score {
+                violations.push(format!("Password strength score must be at least {}", min_score));
+            }
+        }
+        
         (violations.is_empty(), violations)
     }
     
@@ -278,6 +331,7 @@ mod tests {
             include_numbers: true,
             include_special: true,
             exclude_ambiguous: true,
+            ..Default::default()
         };
 
         let password = PasswordGenerator::generate(&request).unwrap();
@@ -312,7 +366,12 @@ mod tests {
             require_lowercase: true,
             require_numbers: true,
             require_special: true,
+            min_uppercase: None,
+            min_lowercase: None,
+            min_numbers: None,
+            min_special: None,
             max_age_days: None,
+            min_strength_score: None,
             created_at: "2024-01-01".to_string(),
             updated_at: "2024-01-01".to_string(),
         };
@@ -328,6 +387,107 @@ mod tests {
         assert!(!violations.is_empty());
     }
 
+    fn policy_with_min_numbers(min_numbers: i32) -> PasswordPolicy {
+        PasswordPolicy {
+            id: 1,
+            min_length: 12,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_numbers: true,
+            require_special: true,
+            min_uppercase: None,
+            min_lowercase: None,
+            min_numbers: Some(min_numbers),
+            min_special: None,
+            max_age_days: None,
+            min_strength_score: None,
+            created_at: "2024-01-01".to_string(),
+            updated_at: "2024-01-01".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_policy_minimum_class_counts() {
+        let policy = policy_with_min_numbers(2);
+
+        let (compliant, violations) = PasswordStrengthAnalyzer::meets_policy("Abcdefgh1!xyz", &policy);
+        assert!(!compliant);
+        assert_eq!(violations, vec!["Password must contain at least 2 numbers".to_string()]);
+
+        let (compliant, violations) = PasswordStrengthAnalyzer::meets_policy("Abcdefgh19!xyz", &policy);
+        assert!(compliant, "unexpected violations: {:?}", violations);
+    }
+
+    #[test]
+    fn test_generation_honors_policy_minimums() {
+        let policy = policy_with_min_numbers(2);
+        let request = GeneratePasswordRequest::for_policy(&policy, 12);
+
+        for _ in 0..200 {
+            let password = PasswordGenerator::generate(&request).unwrap();
+            assert_eq!(password.len(), 12);
+            assert!(password.chars().filter(|c| c.is_ascii_digit()).count() >= 2);
+            let (compliant, violations) = PasswordStrengthAnalyzer::meets_policy(&password, &policy);
+            assert!(compliant, "{} violates policy: {:?}", password, violations);
+        }
+    }
+
+    #[test]
+    fn test_generation_rejects_unsatisfiable_minimums() {
+        let request = GeneratePasswordRequest {
+            length: 8,
+            min_uppercase: Some(3),
+            min_lowercase: Some(3),
+            min_numbers: Some(3),
+            min_special: Some(3),
+            ..Default::default()
+        };
+        let error = PasswordGenerator::generate(&request).unwrap_err();
+        assert!(error.to_string().contains("cannot satisfy"));
+
+        let request = GeneratePasswordRequest {
+            include_numbers: false,
+            min_numbers: Some(2),
+            ..Default::default()
+        };
+        assert!(PasswordGenerator::generate(&request).is_err());
+    }
+
+    #[test]
+    fn test_generated_passwords_meet_entropy_floor() {
+        for (length, include_uppercase, include_numbers, include_special) in [
+            (8, true, false, false),
+            (10, false, true, false),
+            (12, true, true, true),
+            (16, false, false, true),
+            (32, true, true, true),
+        ] {
+            let request = GeneratePasswordRequest {
+                length,
+                include_uppercase,
+                include_numbers,
+                include_special,
+                ..Default::default()
+            };
+            for _ in 0..250 {
+                let password = PasswordGenerator::generate(&request).unwrap();
+                let entropy = PasswordStrengthAnalyzer::analyze(&password).entropy;
+                assert!(entropy >= MIN_GENERATED_PASSWORD_ENTROPY, "{} has {:.1} bits", password, entropy);
+            }
+        }
+
+        // Eight digits can never reach the floor, so generation refuses rather than returning them
+        let request = GeneratePasswordRequest {
+            length: 8,
+            include_uppercase: false,
+            include_lowercase: false,
+            include_special: false,
+            ..Default::default()
+        };
+        let error = PasswordGenerator::generate(&request).unwrap_err();
+        assert!(error.to_string().contains("below the minimum"));
+    }
+
     #[test]
     fn test_password_hashing() {
         let password = "TestPassword123!";
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use chrono::{Utc, Duration};
+use chrono::{DateTime, NaiveDateTime, Utc, Duration};
 use std::collections::HashMap;
 use std::str::FromStr;
 use tracing::{info, debug, warn};
@@ -16,14 +16,20 @@ pub struct PasswordRotationRequest {
     pub rotation_reason: String,
     pub author_id: i64,
     pub batch_id: Option<i64>,
+    #[serde(default)]
+    pub reason_template_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RotationSchedule {
     pub schedule_id: i64,
     pub vault_id: i64,
-    pub rotation_interval: i32, // days
+    pub rotation_interval: Option<i32>, // days
+    /// Six or seven field cron expression (seconds first, UTC); takes precedence over the interval
+    pub cron_expression: Option<String>,
     pub alert_days_before: i32,
+    /// Days past the due date before a secret counts as overdue
+    pub grace_period_days: i32,
     pub is_active: bool,
     pub created_at: String,
     pub created_by: i64,
@@ -87,6 +93,23 @@ pub struct PasswordRotationHistory {
     pub rotated_by: i64,
     pub rotated_at: String,
     pub batch_id: Option<i64>,
+    pub reason_template_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationReasonTemplate {
+    pub template_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRotationReasonTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub created_by: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,16 +118,23 @@ pub struct RotationAlert {
     pub vault_id: i64,
     pub secret_label: String,
     pub asset_name: String,
+    /// Days until the secret becomes overdue, i.e. the due date plus any grace period
     pub days_until_rotation: i32,
     pub next_rotation_due: String,
+    pub grace_period_days: i32,
     pub last_rotated: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRotationScheduleRequest {
     pub vault_id: i64,
-    pub rotation_interval: i32,
+    #[serde(default)]
+    pub rotation_interval: Option<i32>,
+    #[serde(default)]
+    pub cron_expression: Option<String>,
     pub alert_days_before: i32,
+    #[serde(default)]
+    pub grace_period_days: i32,
     pub created_by: i64,
 }
 
@@ -113,6 +143,7 @@ pub struct UpdateRotationScheduleRequest {
     pub schedule_id: i64,
     pub rotation_interval: Option<i32>,
     pub alert_days_before: Option<i32>,
+    pub grace_period_days: Option<i32>,
     pub is_active: Option<bool>,
 }
 
@@ -128,6 +159,8 @@ pub struct BatchRotationItem {
     pub secret_id: i64,
     pub new_password: String,
     pub rotation_reason: String,
+    #[serde(default)]
+    pub reason_template_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,6 +170,89 @@ pub struct BatchRotationRequest {
     pub author_id: i64,
 }
 
+/// Limits on how hard a rotation batch works the CPU
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchThrottleSettings {
+    /// Items rotated before the batch yields; between chunks cancellation is
+    /// checked and the database is released to other commands
+    pub batch_size: u32,
+    /// Pause between consecutive items, in milliseconds
+    pub item_delay_ms: u64,
+}
+
+impl Default for BatchThrottleSettings {
+    fn default() -> Self {
+        Self {
+            batch_size: 25,
+            item_delay_ms: 0,
+        }
+    }
+}
+
+impl BatchThrottleSettings {
+    pub const MAX_BATCH_SIZE: u32 = 1000;
+    pub const MAX_ITEM_DELAY_MS: u64 = 60_000;
+
+    pub fn validate(&self) -> Result<()> {
+        if self.batch_size == 0 || self.batch_size > Self::MAX_BATCH_SIZE {
+            return Err(anyhow::anyhow!("Batch size must be between 1 and {}", Self::MAX_BATCH_SIZE));
+        }
+        if self.item_delay_ms > Self::MAX_ITEM_DELAY_MS {
+            return Err(anyhow::anyhow!("Item delay cannot exceed {} ms", Self::MAX_ITEM_DELAY_MS));
+        }
+        Ok(())
+    }
+
+    pub fn item_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.item_delay_ms)
+    }
+}
+
+/// Target for plant-wide rotation compliance, below which an alert is raised
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceAlertSettings {
+    /// Minimum percentage of password secrets that must not be overdue
+    pub threshold_percentage: f64,
+    pub is_enabled: bool,
+}
+
+impl Default for ComplianceAlertSettings {
+    fn default() -> Self {
+        Self {
+            threshold_percentage: 90.0,
+            is_enabled: true,
+        }
+    }
+}
+
+impl ComplianceAlertSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=100.0).contains(&self.threshold_percentage) {
+            return Err(anyhow::anyhow!("Compliance threshold must be between 0 and 100 percent"));
+        }
+        Ok(())
+    }
+}
+
+/// Raised when rotation compliance drops below the configured threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceAlert {
+    pub compliance_percentage: f64,
+    pub threshold_percentage: f64,
+    pub total_passwords: i64,
+    pub overdue_passwords: i64,
+    pub checked_at: String,
+}
+
+/// Running totals for a batch rotation that may be executed in several chunks
+#[derive(Debug, Clone, Default)]
+pub struct BatchRotationProgress {
+    pub processed: usize,
+    pub successful: usize,
+    pub failed_items: Vec<(i64, String)>,
+    pub cancelled: bool,
+}
+
 // Password Rotation Service
 pub struct PasswordRotationService<'a> {
     conn: &'a Connection,
@@ -169,6 +285,8 @@ impl<'a> PasswordRotationService<'a> {
             return Err(anyhow::anyhow!("Can only rotate password type secrets"));
         }
 
+        let rotation_reason = self.resolve_rotation_reason(request.reason_template_id, &request.rotation_reason)?;
+
         // Hash the new password
         let new_password_hash = bcrypt::hash(&request.new_password, bcrypt::DEFAULT_COST)
             .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
@@ -179,21 +297,27 @@ impl<'a> PasswordRotationService<'a> {
             .unwrap_or_else(|_| old_encrypted_value.clone());
 
         tx.execute(
-            "INSERT INTO password_rotation_history (secret_id, old_password_hash, rotation_reason, rotated_by, batch_id) 
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO password_rotation_history (secret_id, old_password_hash, rotation_reason, rotated_by, batch_id, reason_template_id) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 request.secret_id,
                 old_password_hash,
-                request.rotation_reason,
+                rotation_reason,
                 request.author_id,
-                request.batch_id
+                request.batch_id,
+                request.reason_template_id
             ],
         )?;
 
         // Task 2.4: Update the secret with new password
         let now = Utc::now();
-        let rotation_interval = secret.rotation_interval_days.unwrap_or(90);
-        let next_rotation_due = now + Duration::days(rotation_interval as i64);
+        let cron_due = match RotationScheduler::new(self.conn).get_active_schedule(secret.vault_id)? {
+            Some(schedule) if schedule.cron_expression.is_some() => compute_next_rotation(&schedule, now)?,
+            _ => None,
+        };
+        let next_rotation_due = cron_due.unwrap_or_else(|| {
+            now + Duration::days(secret.rotation_interval_days.unwrap_or(90) as i64)
+        });
 
         // Encrypt the new password (in real implementation, use proper encryption)
         let encrypted_value = new_password_hash.clone(); // Placeholder - should encrypt
@@ -201,6 +325,7 @@ impl<'a> PasswordRotationService<'a> {
         tx.execute(
             "UPDATE vault_secrets 
              SET encrypted_value = ?1, 
+                 password_digest = NULL,
                  last_changed = ?2, 
                  last_rotated = ?2,
                  next_rotation_due = ?3,
@@ -215,7 +340,7 @@ impl<'a> PasswordRotationService<'a> {
         )?;
 
         // Add to password history for reuse prevention
-        self.vault_repo.add_password_history(request.secret_id, &new_password_hash)?;
+        self.vault_repo.add_password_history(request.secret_id, &request.new_password)?;
 
         // Task 2.6: Audit logging
         let audit_event = AuditEventRequest {
@@ -227,11 +352,12 @@ impl<'a> PasswordRotationService<'a> {
             target_user_id: None,
             target_username: None,
             description: format!("Rotated password for secret '{}' in vault {}. Reason: {}", 
-                secret.label, secret.vault_id, request.rotation_reason),
+                secret.label, secret.vault_id, rotation_reason),
             metadata: Some(serde_json::json!({
                 "secret_id": request.secret_id,
                 "vault_id": secret.vault_id,
-                "reason": request.rotation_reason
+                "reason": rotation_reason,
+                "reason_template_id": request.reason_template_id
             }).to_string()),
             ip_address: None,
             user_agent: None,
@@ -242,14 +368,14 @@ impl<'a> PasswordRotationService<'a> {
         // Add version history
         let mut changes = HashMap::new();
         changes.insert("action".to_string(), "password_rotated".to_string());
-        changes.insert("reason".to_string(), request.rotation_reason.clone());
+        changes.insert("reason".to_string(), rotation_reason.clone());
         changes.insert("batch_id".to_string(), request.batch_id.map_or("null".to_string(), |id| id.to_string()));
         
         self.vault_repo.add_version_history(
             secret.vault_id,
             super::ChangeType::SecretUpdated,
             request.author_id,
-            &format!("Password rotated: {}", request.rotation_reason),
+            &format!("Password rotated: {}", rotation_reason),
             changes,
         )?;
 
@@ -259,6 +385,33 @@ impl<'a> PasswordRotationService<'a> {
         Ok(())
     }
 
+    /// Build the stored rotation reason from a reason template and/or free text. When a
+    /// template is used, any free text is kept as additional detail after the template name.
+    fn resolve_rotation_reason(&self, reason_template_id: Option<i64>, free_text: &str) -> Result<String> {
+        let details = free_text.trim();
+
+        match reason_template_id {
+            Some(template_id) => {
+                let name: String = self.conn.query_row(
+                    "SELECT name FROM rotation_reason_templates WHERE template_id = ?1 AND is_active = 1",
+                    [template_id],
+                    |row| row.get(0),
+                ).map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => anyhow::anyhow!("Rotation reason template not found or inactive"),
+                    e => e.into(),
+                })?;
+
+                if details.is_empty() {
+                    Ok(name)
+                } else {
+                    Ok(format!("{}: {}", name, details))
+                }
+            }
+            None if details.is_empty() => Err(anyhow::anyhow!("Rotation reason is required")),
+            None => Ok(details.to_string()),
+        }
+    }
+
     // Task 2.5: Validation and rollback capabilities
     pub fn validate_rotation(&self, secret_id: i64, new_password: &str) -> Result<()> {
         // Check password complexity
@@ -267,10 +420,7 @@ impl<'a> PasswordRotationService<'a> {
         }
 
         // Check password reuse
-        let password_hash = bcrypt::hash(new_password, bcrypt::DEFAULT_COST)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        if self.vault_repo.check_password_reuse(&password_hash, Some(secret_id))? {
+        if self.vault_repo.check_password_reuse(new_password, Some(secret_id))? {
             return Err(anyhow::anyhow!("Password has been used before. Please choose a different password."));
         }
 
@@ -280,7 +430,7 @@ impl<'a> PasswordRotationService<'a> {
     // Get rotation history for a secret
     pub fn get_rotation_history(&self, secret_id: i64) -> Result<Vec<PasswordRotationHistory>> {
         let mut stmt = self.conn.prepare(
-            "SELECT rotation_id, secret_id, old_password_hash, rotation_reason, rotated_by, rotated_at, batch_id
+            "SELECT rotation_id, secret_id, old_password_hash, rotation_reason, rotated_by, rotated_at, batch_id, reason_template_id
              FROM password_rotation_history
              WHERE secret_id = ?1
              ORDER BY rotated_at DESC"
@@ -295,6 +445,7 @@ impl<'a> PasswordRotationService<'a> {
                 rotated_by: row.get("rotated_by")?,
                 rotated_at: row.get("rotated_at")?,
                 batch_id: row.get("batch_id")?,
+                reason_template_id: row.get("reason_template_id")?,
             })
         })?;
 
@@ -311,8 +462,13 @@ impl<'a> PasswordRotationService<'a> {
         info!("Emergency password rotation initiated for secret {}", request.secret_id);
         
         // Skip normal validation for emergency rotation
+        let rotation_reason = if request.rotation_reason.trim().is_empty() {
+            "EMERGENCY".to_string()
+        } else {
+            format!("EMERGENCY: {}", request.rotation_reason)
+        };
         self.rotate_password(PasswordRotationRequest {
-            rotation_reason: format!("EMERGENCY: {}", request.rotation_reason),
+            rotation_reason,
             ..request
         })
     }
@@ -347,9 +503,463 @@ mod tests {
         assert_eq!(BatchStatus::from_str("failed").unwrap(), BatchStatus::Failed);
         assert_eq!(BatchStatus::from_str("cancelled").unwrap(), BatchStatus::Cancelled);
     }
+
+    fn setup_vault_db() -> (NamedTempFile, Connection, i64) {
+        let (temp_file, conn) = setup_test_db();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL,
+                password_hash TEXT NOT NULL,
+                role TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                is_active BOOLEAN DEFAULT 1
+            );
+            
+            CREATE TABLE assets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                created_by INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            
+            INSERT INTO users (id, username, password_hash, role) VALUES (1, 'testuser', 'hash', 'Engineer');
+            INSERT INTO assets (id, name, description, created_by) VALUES (1, 'Test Asset', 'Test Description', 1);
+            "#,
+        ).unwrap();
+
+        let vault_repo = SqliteVaultRepository::new(&conn);
+        vault_repo.initialize_schema().unwrap();
+        SqliteAuditRepository::new(&conn).initialize_schema().unwrap();
+
+        let vault = vault_repo.create_vault(crate::vault::CreateVaultRequest {
+            asset_id: 1,
+            name: "Test Vault".to_string(),
+            description: "Test Description".to_string(),
+            created_by: 1,
+        }).unwrap();
+        let secret = vault_repo.add_secret(crate::vault::AddSecretRequest {
+            vault_id: vault.id,
+            secret_type: crate::vault::SecretType::Password,
+            label: "Admin Password".to_string(),
+            value: "InitialPassword#1".to_string(),
+            author_id: 1,
+            expires_at: None,
+        }).unwrap();
+
+        (temp_file, conn, secret.id)
+    }
+
+    fn rotation_service(conn: &Connection) -> PasswordRotationService<'_> {
+        PasswordRotationService::new(
+            conn,
+            Box::new(SqliteVaultRepository::new(conn)),
+            Box::new(SqliteAuditRepository::new(conn)),
+        )
+    }
+
+    #[test]
+    fn test_rotation_with_reason_template_is_stored_and_counted() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let scheduler = RotationScheduler::new(&conn);
+        let service = rotation_service(&conn);
+
+        let templates = scheduler.get_rotation_reason_templates(false).unwrap();
+        let staff_departure = templates.iter().find(|t| t.name == "Staff departure").unwrap();
+
+        service.rotate_password(PasswordRotationRequest {
+            secret_id,
+            new_password: "RotatedPassword#2".to_string(),
+            rotation_reason: String::new(),
+            author_id: 1,
+            batch_id: None,
+            reason_template_id: Some(staff_departure.template_id),
+        }).unwrap();
+        service.rotate_password(PasswordRotationRequest {
+            secret_id,
+            new_password: "RotatedPassword#3".to_string(),
+            rotation_reason: "Contractor badge returned".to_string(),
+            author_id: 1,
+            batch_id: None,
+            reason_template_id: Some(staff_departure.template_id),
+        }).unwrap();
+        service.rotate_password(PasswordRotationRequest {
+            secret_id,
+            new_password: "RotatedPassword#4".to_string(),
+            rotation_reason: "Testing new HMI".to_string(),
+            author_id: 1,
+            batch_id: None,
+            reason_template_id: None,
+        }).unwrap();
+
+        let history = service.get_rotation_history(secret_id).unwrap();
+        assert_eq!(history.len(), 3);
+        let reasons: Vec<&str> = history.iter().map(|h| h.rotation_reason.as_str()).collect();
+        assert!(reasons.contains(&"Staff departure"));
+        assert!(reasons.contains(&"Staff departure: Contractor badge returned"));
+        assert!(reasons.contains(&"Testing new HMI"));
+        assert_eq!(history.iter().filter(|h| h.reason_template_id == Some(staff_departure.template_id)).count(), 2);
+
+        let metrics = scheduler.get_rotation_compliance_metrics().unwrap();
+        let rotation_reasons = &metrics["rotation_reasons"];
+        assert_eq!(rotation_reasons["Staff departure"], serde_json::json!(2));
+        assert_eq!(rotation_reasons["Other"], serde_json::json!(1));
+    }
+
+    fn batch_service(conn: &Connection) -> BatchRotationService<'_> {
+        BatchRotationService::new(
+            conn,
+            Box::new(SqliteVaultRepository::new(conn)),
+            Box::new(SqliteAuditRepository::new(conn)),
+        )
+    }
+
+    fn add_password_secrets(conn: &Connection, count: usize) -> Vec<i64> {
+        let vault_repo = SqliteVaultRepository::new(conn);
+        let vault_id = vault_repo.get_vault_by_asset_id(1).unwrap().unwrap().vault.id;
+        (0..count).map(|i| {
+            vault_repo.add_secret(crate::vault::AddSecretRequest {
+                vault_id,
+                secret_type: crate::vault::SecretType::Password,
+                label: format!("Operator Password {}", i),
+                value: format!("OperatorInitial#{}", i),
+                author_id: 1,
+                expires_at: None,
+            }).unwrap().id
+        }).collect()
+    }
+
+    fn start_test_batch(service: &BatchRotationService) -> i64 {
+        service.create_batch(CreateRotationBatchRequest {
+            batch_name: "Quarterly rotation".to_string(),
+            created_by: 1,
+            notes: None,
+        }).unwrap().batch_id
+    }
+
+    #[test]
+    fn test_batch_throttle_delay_is_applied() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let service = batch_service(&conn);
+
+        assert_eq!(service.get_throttle_settings().unwrap(), BatchThrottleSettings::default());
+        assert!(service.update_throttle_settings(&BatchThrottleSettings { batch_size: 0, item_delay_ms: 0 }).is_err());
+        assert!(service.update_throttle_settings(&BatchThrottleSettings { batch_size: 10, item_delay_ms: 120_000 }).is_err());
+        let throttle = BatchThrottleSettings { batch_size: 3, item_delay_ms: 60 };
+        service.update_throttle_settings(&throttle).unwrap();
+        assert_eq!(service.get_throttle_settings().unwrap(), throttle);
+
+        // Too-short passwords fail validation immediately, so the elapsed time is the throttle
+        let batch_id = start_test_batch(&service);
+        let items: Vec<BatchRotationItem> = (0..5).map(|_| BatchRotationItem {
+            secret_id,
+            new_password: "short".to_string(),
+            rotation_reason: "Quarterly rotation".to_string(),
+            reason_template_id: None,
+        }).collect();
+
+        let started = std::time::Instant::now();
+        let result = service.execute_batch_rotation(BatchRotationRequest { batch_id, items, author_id: 1 });
+        let elapsed = started.elapsed();
+
+        assert!(result.is_err());
+        // Four gaps between five items, across chunk boundaries too
+        assert!(elapsed >= std::time::Duration::from_millis(4 * 60), "elapsed {:?}", elapsed);
+        assert_eq!(service.get_batch(batch_id).unwrap().unwrap().status, BatchStatus::Failed);
+    }
+
+    #[test]
+    fn test_batch_rotation_stops_promptly_when_cancelled() {
+        let (temp_file, conn, _) = setup_vault_db();
+        conn.busy_timeout(std::time::Duration::from_secs(10)).unwrap();
+        let secret_ids = add_password_secrets(&conn, 4);
+        let service = batch_service(&conn);
+        let throttle = BatchThrottleSettings { batch_size: 2, item_delay_ms: 300 };
+        service.update_throttle_settings(&throttle).unwrap();
+        let batch_id = start_test_batch(&service);
+
+        // Cancel from another connection as soon as the first rotation has committed
+        let db_path = temp_file.path().to_path_buf();
+        let canceller = std::thread::spawn(move || {
+            let conn = Connection::open(db_path).unwrap();
+            conn.busy_timeout(std::time::Duration::from_secs(10)).unwrap();
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+            while std::time::Instant::now() < deadline {
+                let rotated: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM password_rotation_history WHERE batch_id = ?1",
+                    [batch_id],
+                    |row| row.get(0),
+                ).unwrap();
+                if rotated > 0 {
+                    batch_service(&conn).cancel_batch(batch_id).unwrap();
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+        });
+
+        let items = secret_ids.iter().enumerate().map(|(i, &secret_id)| BatchRotationItem {
+            secret_id,
+            new_password: format!("BatchRotated#Password{}", i),
+            rotation_reason: "Security incident".to_string(),
+            reason_template_id: None,
+        }).collect();
+        let result = service.execute_batch_rotation(BatchRotationRequest { batch_id, items, author_id: 1 });
+        canceller.join().unwrap();
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("cancelled after 1 of 4 items"), "{}", error);
+
+        // The rotation that finished before the cancellation is kept
+        let rotated: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM password_rotation_history WHERE batch_id = ?1",
+            [batch_id],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(rotated, 1);
+        let batch = service.get_batch(batch_id).unwrap().unwrap();
+        assert_eq!(batch.status, BatchStatus::Cancelled);
+        assert!(batch.notes.unwrap().contains("Stopped after 1 of 4 items"));
+
+        // A cancelled batch cannot be restarted
+        assert!(service.start_batch(batch_id).is_err());
+    }
+
+    #[test]
+    fn test_rotation_reason_required_and_template_must_be_active() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let scheduler = RotationScheduler::new(&conn);
+        let service = rotation_service(&conn);
+
+        let template = scheduler.create_rotation_reason_template(CreateRotationReasonTemplateRequest {
+            name: "Firmware upgrade".to_string(),
+            description: None,
+            created_by: 1,
+        }).unwrap();
+        assert!(scheduler.create_rotation_reason_template(CreateRotationReasonTemplateRequest {
+            name: "Firmware upgrade".to_string(),
+            description: None,
+            created_by: 1,
+        }).is_err());
+
+        let request = |reason: &str, template_id: Option<i64>| PasswordRotationRequest {
+            secret_id,
+            new_password: "RotatedPassword#2".to_string(),
+            rotation_reason: reason.to_string(),
+            author_id: 1,
+            batch_id: None,
+            reason_template_id: template_id,
+        };
+
+        // Neither a template nor free text
+        assert!(service.rotate_password(request("   ", None)).is_err());
+
+        scheduler.set_rotation_reason_template_active(template.template_id, false).unwrap();
+        assert!(service.rotate_password(request("", Some(template.template_id))).is_err());
+        assert!(!scheduler.get_rotation_reason_templates(false).unwrap()
+            .iter().any(|t| t.template_id == template.template_id));
+        assert!(scheduler.get_rotation_reason_templates(true).unwrap()
+            .iter().any(|t| t.template_id == template.template_id));
+
+        assert!(service.get_rotation_history(secret_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_grace_period_delays_overdue_alerts() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let scheduler = RotationScheduler::new(&conn);
+        let vault_id: i64 = conn.query_row(
+            "SELECT vault_id FROM vault_secrets WHERE id = ?1", [secret_id], |row| row.get(0),
+        ).unwrap();
+        let set_due = |modifier: &str| {
+            conn.execute(
+                "UPDATE vault_secrets SET next_rotation_due = datetime('now', ?1) WHERE id = ?2",
+                params![modifier, secret_id],
+            ).unwrap();
+        };
+
+        // Without a schedule there is no grace period
+        set_due("-3 days");
+        assert_eq!(scheduler.get_secrets_due_for_rotation().unwrap().len(), 1);
+
+        let schedule = scheduler.create_rotation_schedule(CreateRotationScheduleRequest {
+            vault_id,
+            rotation_interval: Some(90),
+            cron_expression: None,
+            alert_days_before: 14,
+            grace_period_days: 5,
+            created_by: 1,
+        }).unwrap();
+        assert_eq!(scheduler.get_active_schedule(vault_id).unwrap().unwrap().grace_period_days, 5);
+
+        // Three days past due is still within the grace period
+        assert!(scheduler.get_secrets_due_for_rotation().unwrap().is_empty());
+        assert!(scheduler.get_rotation_alerts(0).unwrap().is_empty());
+        assert_eq!(scheduler.get_rotation_compliance_metrics().unwrap()["overdue_passwords"], serde_json::json!(0));
+        let upcoming = scheduler.get_rotation_alerts(7).unwrap();
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0].days_until_rotation, 2);
+        assert_eq!(upcoming[0].grace_period_days, 5);
+
+        // Six days past due is beyond it
+        set_due("-6 days");
+        let overdue = scheduler.get_secrets_due_for_rotation().unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].secret_id, secret_id);
+        assert_eq!(overdue[0].days_until_rotation, -1);
+        assert_eq!(scheduler.get_rotation_compliance_metrics().unwrap()["overdue_passwords"], serde_json::json!(1));
+
+        // Widening the grace period through the policy update clears the alert again
+        scheduler.update_rotation_schedule(UpdateRotationScheduleRequest {
+            schedule_id: schedule.schedule_id,
+            rotation_interval: None,
+            alert_days_before: None,
+            grace_period_days: Some(10),
+            is_active: None,
+        }).unwrap();
+        assert!(scheduler.get_secrets_due_for_rotation().unwrap().is_empty());
+
+        assert!(scheduler.update_rotation_schedule(UpdateRotationScheduleRequest {
+            schedule_id: schedule.schedule_id,
+            rotation_interval: None,
+            alert_days_before: None,
+            grace_period_days: Some(-1),
+            is_active: None,
+        }).is_err());
+    }
+
+    #[test]
+    fn test_cron_schedule_sets_next_rotation_due() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let scheduler = RotationScheduler::new(&conn);
+        let vault_id: i64 = conn.query_row(
+            "SELECT vault_id FROM vault_secrets WHERE id = ?1", [secret_id], |row| row.get(0),
+        ).unwrap();
+        let request = |interval: Option<i32>, cron: Option<&str>| CreateRotationScheduleRequest {
+            vault_id,
+            rotation_interval: interval,
+            cron_expression: cron.map(str::to_string),
+            alert_days_before: 7,
+            grace_period_days: 0,
+            created_by: 1,
+        };
+
+        assert!(scheduler.create_rotation_schedule(request(None, None)).is_err());
+        assert!(scheduler.create_rotation_schedule(request(None, Some("every tuesday"))).is_err());
+
+        // 02:30 UTC on the first of every month, overriding the 90 day interval
+        let schedule = scheduler.create_rotation_schedule(request(Some(90), Some("0 30 2 1 * *"))).unwrap();
+        let from = DateTime::parse_from_rfc3339("2026-10-16T09:00:00Z").unwrap().with_timezone(&Utc);
+        let due = compute_next_rotation(&schedule, from).unwrap().unwrap();
+        assert_eq!(due.to_rfc3339(), "2026-11-01T02:30:00+00:00");
+
+        let interval_only = RotationSchedule { cron_expression: None, ..schedule.clone() };
+        assert_eq!(compute_next_rotation(&interval_only, from).unwrap(), Some(from + Duration::days(90)));
+        let neither = RotationSchedule { rotation_interval: None, ..interval_only };
+        assert_eq!(compute_next_rotation(&neither, from).unwrap(), None);
+
+        scheduler.update_rotation_due_dates(vault_id).unwrap();
+        let next_due: String = conn.query_row(
+            "SELECT next_rotation_due FROM vault_secrets WHERE id = ?1", [secret_id], |row| row.get(0),
+        ).unwrap();
+        assert!(next_due.ends_with("-01 02:30:00"), "{}", next_due);
+    }
+
+    #[test]
+    fn test_compliance_alert_follows_threshold() {
+        let (_temp_file, conn, secret_id) = setup_vault_db();
+        let scheduler = RotationScheduler::new(&conn);
+        add_password_secrets(&conn, 3);
+
+        assert_eq!(scheduler.get_compliance_alert_settings().unwrap(), ComplianceAlertSettings::default());
+        assert!(scheduler.update_compliance_alert_settings(&ComplianceAlertSettings {
+            threshold_percentage: 120.0,
+            is_enabled: true,
+        }).is_err());
+
+        // All four passwords current: compliance is 100%
+        assert!(scheduler.check_rotation_compliance_alert().unwrap().is_none());
+
+        // One of four overdue leaves compliance at 75%, above a 70% target
+        conn.execute(
+            "UPDATE vault_secrets SET next_rotation_due = datetime('now', '-10 days') WHERE id = ?1",
+            [secret_id],
+        ).unwrap();
+        scheduler.update_compliance_alert_settings(&ComplianceAlertSettings {
+            threshold_percentage: 70.0,
+            is_enabled: true,
+        }).unwrap();
+        assert!(scheduler.check_rotation_compliance_alert().unwrap().is_none());
+
+        // ...but below an 80% target
+        scheduler.update_compliance_alert_settings(&ComplianceAlertSettings {
+            threshold_percentage: 80.0,
+            is_enabled: true,
+        }).unwrap();
+        let alert = scheduler.check_rotation_compliance_alert().unwrap().unwrap();
+        assert_eq!(alert.compliance_percentage, 75.0);
+        assert_eq!(alert.threshold_percentage, 80.0);
+        assert_eq!(alert.total_passwords, 4);
+        assert_eq!(alert.overdue_passwords, 1);
+
+        // Disabling the alert silences it without changing the metrics
+        scheduler.update_compliance_alert_settings(&ComplianceAlertSettings {
+            threshold_percentage: 80.0,
+            is_enabled: false,
+        }).unwrap();
+        assert!(scheduler.check_rotation_compliance_alert().unwrap().is_none());
+        assert_eq!(scheduler.get_rotation_compliance_metrics().unwrap()["compliance_percentage"], serde_json::json!(75.0));
+    }
 }
 
 // Task 3: Rotation Scheduling System
+
+/// Grace period of the active schedule for the vault of secret `s`, 0 when there is none
+const GRACE_PERIOD_SQL: &str =
+    "COALESCE((SELECT rs.grace_period_days FROM rotation_schedules rs
+               WHERE rs.vault_id = s.vault_id AND rs.is_active = 1
+               ORDER BY rs.created_at DESC LIMIT 1), 0)";
+
+/// Share of password secrets that are not overdue; an empty plant is fully compliant
+fn compliance_percentage(total_passwords: i64, overdue_passwords: i64) -> f64 {
+    if total_passwords > 0 {
+        (total_passwords - overdue_passwords) as f64 / total_passwords as f64 * 100.0
+    } else {
+        100.0
+    }
+}
+
+/// Parse a cron expression in the six or seven field form the `cron` crate accepts
+fn parse_cron_expression(expression: &str) -> Result<cron::Schedule> {
+    cron::Schedule::from_str(expression.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid cron expression '{}': {}", expression, e))
+}
+
+/// Next rotation due after `from` under `schedule`. A cron expression overrides the fixed
+/// interval; a schedule with neither has no automatic due date.
+pub fn compute_next_rotation(schedule: &RotationSchedule, from: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    match (&schedule.cron_expression, schedule.rotation_interval) {
+        (Some(expression), _) => Ok(parse_cron_expression(expression)?.after(&from).next()),
+        (None, Some(interval)) => Ok(Some(from + Duration::days(interval as i64))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Read a stored timestamp, which is RFC 3339 when written by the app and SQLite's
+/// `YYYY-MM-DD HH:MM:SS` when defaulted by the database
+fn parse_stored_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .map(|timestamp| timestamp.and_utc())
+        .map_err(|e| anyhow::anyhow!("Invalid timestamp '{}': {}", value, e))
+}
+
 pub struct RotationScheduler<'a> {
     conn: &'a Connection,
 }
@@ -363,24 +973,45 @@ impl<'a> RotationScheduler<'a> {
     pub fn create_rotation_schedule(&self, request: CreateRotationScheduleRequest) -> Result<RotationSchedule> {
         debug!("Creating rotation schedule for vault {}", request.vault_id);
 
+        let cron_expression = request.cron_expression
+            .map(|expression| expression.trim().to_string())
+            .filter(|expression| !expression.is_empty());
+
+        if let Some(expression) = &cron_expression {
+            parse_cron_expression(expression)?;
+        } else if request.rotation_interval.is_none() {
+            return Err(anyhow::anyhow!("Either a rotation interval or a cron expression is required"));
+        }
+
         // Validate interval
-        if request.rotation_interval <= 0 {
-            return Err(anyhow::anyhow!("Rotation interval must be positive"));
+        if let Some(interval) = request.rotation_interval {
+            if interval <= 0 {
+                return Err(anyhow::anyhow!("Rotation interval must be positive"));
+            }
+            if request.alert_days_before >= interval {
+                return Err(anyhow::anyhow!("Alert days must be between 0 and rotation interval"));
+            }
         }
 
-        if request.alert_days_before < 0 || request.alert_days_before >= request.rotation_interval {
+        if request.alert_days_before < 0 {
             return Err(anyhow::anyhow!("Alert days must be between 0 and rotation interval"));
         }
 
+        if request.grace_period_days < 0 {
+            return Err(anyhow::anyhow!("Grace period must be non-negative"));
+        }
+
         let now = Utc::now().to_rfc3339();
         
         let schedule_id = self.conn.execute(
-            "INSERT INTO rotation_schedules (vault_id, rotation_interval, alert_days_before, is_active, created_by, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+            "INSERT INTO rotation_schedules (vault_id, rotation_interval, cron_expression, alert_days_before, grace_period_days, is_active, created_by, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
             params![
                 request.vault_id,
                 request.rotation_interval,
+                cron_expression,
                 request.alert_days_before,
+                request.grace_period_days,
                 true,
                 request.created_by,
                 now
@@ -393,7 +1024,9 @@ impl<'a> RotationScheduler<'a> {
             schedule_id: self.conn.last_insert_rowid(),
             vault_id: request.vault_id,
             rotation_interval: request.rotation_interval,
+            cron_expression,
             alert_days_before: request.alert_days_before,
+            grace_period_days: request.grace_period_days,
             is_active: true,
             created_at: now.clone(),
             created_by: request.created_by,
@@ -410,6 +1043,13 @@ impl<'a> RotationScheduler<'a> {
             .ok_or_else(|| anyhow::anyhow!("No active rotation schedule found for vault"))?;
 
         let rotation_interval_days = schedule.rotation_interval;
+
+        if schedule.cron_expression.is_some() {
+            return self.update_cron_due_dates(&schedule);
+        }
+        if rotation_interval_days.is_none() {
+            return Err(anyhow::anyhow!("Rotation schedule has neither an interval nor a cron expression"));
+        }
         
         // Update all password secrets in the vault
         self.conn.execute(
@@ -423,22 +1063,51 @@ impl<'a> RotationScheduler<'a> {
         Ok(())
     }
 
+    /// Cron due dates depend on each secret's last rotation, so they are computed here
+    /// rather than in SQL
+    fn update_cron_due_dates(&self, schedule: &RotationSchedule) -> Result<()> {
+        let secrets: Vec<(i64, String)> = self.conn.prepare(
+            "SELECT id, COALESCE(last_rotated, created_at) FROM vault_secrets
+             WHERE vault_id = ?1 AND secret_type = 'password'"
+        )?.query_map([schedule.vault_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (secret_id, rotated_at) in secrets {
+            let next_rotation_due = compute_next_rotation(schedule, parse_stored_timestamp(&rotated_at)?)?
+                .map(|due| due.format("%Y-%m-%d %H:%M:%S").to_string());
+            self.conn.execute(
+                "UPDATE vault_secrets SET next_rotation_due = ?1, rotation_interval_days = ?2 WHERE id = ?3",
+                params![next_rotation_due, schedule.rotation_interval, secret_id],
+            )?;
+        }
+
+        Ok(())
+    }
+
     // Task 3.3: Get rotation alerts
+    /// Secrets that become overdue within `days_ahead` days. A secret is overdue once its
+    /// due date plus the grace period of its vault's active schedule has passed.
     pub fn get_rotation_alerts(&self, days_ahead: i32) -> Result<Vec<RotationAlert>> {
         let cutoff_date = (Utc::now() + Duration::days(days_ahead as i64)).to_rfc3339();
         
-        let mut stmt = self.conn.prepare(
-            "SELECT s.id, s.vault_id, s.label, s.next_rotation_due, s.last_rotated,
-                    v.name as vault_name, a.name as asset_name,
-                    julianday(s.next_rotation_due) - julianday('now') as days_until_rotation
-             FROM vault_secrets s
-             JOIN vault_entries v ON s.vault_id = v.id
-             JOIN assets a ON v.asset_id = a.id
-             WHERE s.secret_type = 'password' 
-               AND s.next_rotation_due IS NOT NULL
-               AND s.next_rotation_due <= ?1
-             ORDER BY s.next_rotation_due ASC"
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, vault_id, label, next_rotation_due, last_rotated, vault_name, asset_name,
+                    grace_period_days,
+                    julianday(next_rotation_due, '+' || grace_period_days || ' days') - julianday('now') as days_until_rotation
+             FROM (
+                 SELECT s.id, s.vault_id, s.label, s.next_rotation_due, s.last_rotated,
+                        v.name as vault_name, a.name as asset_name,
+                        {} as grace_period_days
+                 FROM vault_secrets s
+                 JOIN vault_entries v ON s.vault_id = v.id
+                 JOIN assets a ON v.asset_id = a.id
+                 WHERE s.secret_type = 'password' 
+                   AND s.next_rotation_due IS NOT NULL
+             )
+             WHERE julianday(next_rotation_due, '+' || grace_period_days || ' days') <= julianday(?1)
+             ORDER BY days_until_rotation ASC",
+            GRACE_PERIOD_SQL
+        ))?;
 
         let alert_iter = stmt.query_map([cutoff_date], |row| {
             Ok(RotationAlert {
@@ -448,6 +1117,7 @@ impl<'a> RotationScheduler<'a> {
                 asset_name: row.get("asset_name")?,
                 days_until_rotation: row.get::<_, f64>("days_until_rotation")?.round() as i32,
                 next_rotation_due: row.get("next_rotation_due")?,
+                grace_period_days: row.get("grace_period_days")?,
                 last_rotated: row.get("last_rotated")?,
             })
         })?;
@@ -461,6 +1131,11 @@ impl<'a> RotationScheduler<'a> {
         Ok(alerts)
     }
 
+    /// Secrets already past their due date plus grace period
+    pub fn get_secrets_due_for_rotation(&self) -> Result<Vec<RotationAlert>> {
+        self.get_rotation_alerts(0)
+    }
+
     // Task 3.4: Update rotation schedule
     pub fn update_rotation_schedule(&self, request: UpdateRotationScheduleRequest) -> Result<()> {
         let mut query_parts = Vec::new();
@@ -482,6 +1157,14 @@ impl<'a> RotationScheduler<'a> {
             params.push(Box::new(alert_days));
         }
 
+        if let Some(grace_days) = request.grace_period_days {
+            if grace_days < 0 {
+                return Err(anyhow::anyhow!("Grace period must be non-negative"));
+            }
+            query_parts.push("grace_period_days = ?");
+            params.push(Box::new(grace_days));
+        }
+
         if let Some(is_active) = request.is_active {
             query_parts.push("is_active = ?");
             params.push(Box::new(is_active));
@@ -512,7 +1195,7 @@ impl<'a> RotationScheduler<'a> {
     // Task 3.5: Get active schedule for a vault
     pub fn get_active_schedule(&self, vault_id: i64) -> Result<Option<RotationSchedule>> {
         let mut stmt = self.conn.prepare(
-            "SELECT schedule_id, vault_id, rotation_interval, alert_days_before, is_active, 
+            "SELECT schedule_id, vault_id, rotation_interval, cron_expression, alert_days_before, grace_period_days, is_active, 
                     created_at, created_by, updated_at
              FROM rotation_schedules
              WHERE vault_id = ?1 AND is_active = 1
@@ -525,7 +1208,9 @@ impl<'a> RotationScheduler<'a> {
                 schedule_id: row.get("schedule_id")?,
                 vault_id: row.get("vault_id")?,
                 rotation_interval: row.get("rotation_interval")?,
+                cron_expression: row.get("cron_expression")?,
                 alert_days_before: row.get("alert_days_before")?,
+                grace_period_days: row.get("grace_period_days")?,
                 is_active: row.get("is_active")?,
                 created_at: row.get("created_at")?,
                 created_by: row.get("created_by")?,
@@ -544,23 +1229,8 @@ impl<'a> RotationScheduler<'a> {
     pub fn get_rotation_compliance_metrics(&self) -> Result<HashMap<String, serde_json::Value>> {
         let mut metrics = HashMap::new();
 
-        // Total passwords
-        let total_passwords: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM vault_secrets WHERE secret_type = 'password'",
-            [],
-            |row| row.get(0),
-        )?;
+        let (total_passwords, overdue_passwords) = self.count_overdue_passwords()?;
         metrics.insert("total_passwords".to_string(), serde_json::json!(total_passwords));
-
-        // Overdue passwords
-        let overdue_passwords: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM vault_secrets 
-             WHERE secret_type = 'password' 
-               AND next_rotation_due IS NOT NULL 
-               AND next_rotation_due < datetime('now')",
-            [],
-            |row| row.get(0),
-        )?;
         metrics.insert("overdue_passwords".to_string(), serde_json::json!(overdue_passwords));
 
         // Passwords due within 7 days
@@ -585,16 +1255,170 @@ impl<'a> RotationScheduler<'a> {
         metrics.insert("avg_days_since_rotation".to_string(), serde_json::json!(avg_days_since_rotation.round()));
 
         // Compliance percentage
-        let compliant = total_passwords - overdue_passwords;
-        let compliance_percentage = if total_passwords > 0 {
-            (compliant as f64 / total_passwords as f64 * 100.0).round()
-        } else {
-            100.0
-        };
+        let compliance_percentage = compliance_percentage(total_passwords, overdue_passwords).round();
         metrics.insert("compliance_percentage".to_string(), serde_json::json!(compliance_percentage));
 
+        // Rotations grouped by reason template; free-text reasons are counted together
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(t.name, 'Other') AS reason, COUNT(*) AS rotation_count
+             FROM password_rotation_history h
+             LEFT JOIN rotation_reason_templates t ON h.reason_template_id = t.template_id
+             GROUP BY reason"
+        )?;
+        let mut rotation_reasons = serde_json::Map::new();
+        for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+            let (reason, count) = row?;
+            rotation_reasons.insert(reason, serde_json::json!(count));
+        }
+        metrics.insert("rotation_reasons".to_string(), serde_json::Value::Object(rotation_reasons));
+
         Ok(metrics)
     }
+
+    /// Total and overdue password secrets, allowing each vault's grace period
+    fn count_overdue_passwords(&self) -> Result<(i64, i64)> {
+        let counts = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*),
+                        COALESCE(SUM(s.next_rotation_due IS NOT NULL
+                            AND julianday(s.next_rotation_due, '+' || {} || ' days') < julianday('now')), 0)
+                 FROM vault_secrets s
+                 WHERE s.secret_type = 'password'",
+                GRACE_PERIOD_SQL
+            ),
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(counts)
+    }
+
+    pub fn get_compliance_alert_settings(&self) -> Result<ComplianceAlertSettings> {
+        let result = self.conn.query_row(
+            "SELECT threshold_percentage, is_enabled FROM rotation_compliance_alert_settings WHERE id = 1",
+            [],
+            |row| Ok(ComplianceAlertSettings {
+                threshold_percentage: row.get("threshold_percentage")?,
+                is_enabled: row.get("is_enabled")?,
+            }),
+        );
+
+        match result {
+            Ok(settings) => Ok(settings),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(ComplianceAlertSettings::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn update_compliance_alert_settings(&self, settings: &ComplianceAlertSettings) -> Result<()> {
+        settings.validate()?;
+
+        self.conn.execute(
+            "INSERT INTO rotation_compliance_alert_settings (id, threshold_percentage, is_enabled) VALUES (1, ?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET threshold_percentage = excluded.threshold_percentage,
+                 is_enabled = excluded.is_enabled, updated_at = CURRENT_TIMESTAMP",
+            params![settings.threshold_percentage, settings.is_enabled],
+        )?;
+
+        info!("Rotation compliance alert threshold set to {}% (enabled: {})",
+              settings.threshold_percentage, settings.is_enabled);
+        Ok(())
+    }
+
+    /// Compare current rotation compliance with the configured threshold. Intended for
+    /// scheduled checks; returns an alert only while compliance is below target.
+    pub fn check_rotation_compliance_alert(&self) -> Result<Option<ComplianceAlert>> {
+        let settings = self.get_compliance_alert_settings()?;
+        if !settings.is_enabled {
+            return Ok(None);
+        }
+
+        let (total_passwords, overdue_passwords) = self.count_overdue_passwords()?;
+        let compliance = compliance_percentage(total_passwords, overdue_passwords);
+        if compliance >= settings.threshold_percentage {
+            return Ok(None);
+        }
+
+        warn!("Rotation compliance {:.1}% is below the {}% target ({} of {} passwords overdue)",
+              compliance, settings.threshold_percentage, overdue_passwords, total_passwords);
+        Ok(Some(ComplianceAlert {
+            compliance_percentage: (compliance * 10.0).round() / 10.0,
+            threshold_percentage: settings.threshold_percentage,
+            total_passwords,
+            overdue_passwords,
+            checked_at: Utc::now().to_rfc3339(),
+        }))
+    }
+
+    // Reason templates offered when rotating a password
+    pub fn get_rotation_reason_templates(&self, include_inactive: bool) -> Result<Vec<RotationReasonTemplate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT template_id, name, description, is_active, created_at
+             FROM rotation_reason_templates
+             WHERE is_active = 1 OR ?1
+             ORDER BY template_id"
+        )?;
+
+        let templates = stmt.query_map([include_inactive], |row| {
+            Ok(RotationReasonTemplate {
+                template_id: row.get("template_id")?,
+                name: row.get("name")?,
+                description: row.get("description")?,
+                is_active: row.get("is_active")?,
+                created_at: row.get("created_at")?,
+            })
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(templates)
+    }
+
+    pub fn create_rotation_reason_template(&self, request: CreateRotationReasonTemplateRequest) -> Result<RotationReasonTemplate> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Rotation reason name cannot be empty"));
+        }
+        if name.len() > 100 {
+            return Err(anyhow::anyhow!("Rotation reason name cannot exceed 100 characters"));
+        }
+
+        self.conn.execute(
+            "INSERT INTO rotation_reason_templates (name, description, created_by) VALUES (?1, ?2, ?3)",
+            params![name, request.description, request.created_by],
+        ).map_err(|e| match e {
+            rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::ConstraintViolation => {
+                anyhow::anyhow!("A rotation reason named '{}' already exists", name)
+            }
+            e => e.into(),
+        })?;
+
+        let template_id = self.conn.last_insert_rowid();
+        info!("Created rotation reason template {} by user {}", template_id, request.created_by);
+
+        self.conn.query_row(
+            "SELECT template_id, name, description, is_active, created_at
+             FROM rotation_reason_templates WHERE template_id = ?1",
+            [template_id],
+            |row| Ok(RotationReasonTemplate {
+                template_id: row.get("template_id")?,
+                name: row.get("name")?,
+                description: row.get("description")?,
+                is_active: row.get("is_active")?,
+                created_at: row.get("created_at")?,
+            }),
+        ).map_err(Into::into)
+    }
+
+    pub fn set_rotation_reason_template_active(&self, template_id: i64, is_active: bool) -> Result<()> {
+        let rows_affected = self.conn.execute(
+            "UPDATE rotation_reason_templates SET is_active = ?1 WHERE template_id = ?2",
+            params![is_active, template_id],
+        )?;
+
+        if rows_affected == 0 {
+            return Err(anyhow::anyhow!("Rotation reason template not found"));
+        }
+
+        Ok(())
+    }
 }
 
 // Task 4: Batch Rotation Workflow
@@ -650,84 +1474,170 @@ impl<'a> BatchRotationService<'a> {
     pub fn execute_batch_rotation(&self, request: BatchRotationRequest) -> Result<()> {
         info!("Executing batch rotation {} with {} items", request.batch_id, request.items.len());
 
-        // Begin transaction for atomic operation
-        let tx = self.conn.unchecked_transaction()?;
+        let throttle = self.get_throttle_settings()?;
+        self.start_batch(request.batch_id)?;
 
-        // Update batch status to in_progress
-        tx.execute(
-            "UPDATE rotation_batches SET status = ?1 WHERE batch_id = ?2",
-            params![BatchStatus::InProgress.to_string(), request.batch_id],
+        let mut progress = BatchRotationProgress::default();
+        for (index, chunk) in request.items.chunks(throttle.batch_size as usize).enumerate() {
+            if index > 0 {
+                std::thread::sleep(throttle.item_delay());
+            }
+            self.rotate_batch_items(request.batch_id, chunk, request.author_id, throttle.item_delay(), &mut progress)?;
+            if progress.cancelled {
+                break;
+            }
+        }
+
+        self.finish_batch(request.batch_id, request.items.len(), &progress)
+    }
+
+    /// Mark a batch as running; cancelled or finished batches cannot be started
+    pub fn start_batch(&self, batch_id: i64) -> Result<()> {
+        let rows_affected = self.conn.execute(
+            "UPDATE rotation_batches SET status = ?1 WHERE batch_id = ?2 AND status IN ('pending', 'in_progress')",
+            params![BatchStatus::InProgress.to_string(), batch_id],
         )?;
 
-        let mut successful_count = 0;
-        let mut failed_items = Vec::new();
+        if rows_affected == 0 {
+            return Err(anyhow::anyhow!("Batch not found or no longer pending"));
+        }
+        Ok(())
+    }
+
+    pub fn is_batch_cancelled(&self, batch_id: i64) -> Result<bool> {
+        let status: String = self.conn.query_row(
+            "SELECT status FROM rotation_batches WHERE batch_id = ?1",
+            [batch_id],
+            |row| row.get(0),
+        )?;
+        Ok(status == BatchStatus::Cancelled.to_string())
+    }
+
+    // Task 4.4: Process each item with transactional integrity
+    /// Rotate one chunk of a batch, pausing `item_delay` between items. Each rotation commits
+    /// on its own, so a cancellation seen before an item keeps everything rotated so far.
+    pub fn rotate_batch_items(
+        &self,
+        batch_id: i64,
+        items: &[BatchRotationItem],
+        author_id: i64,
+        item_delay: std::time::Duration,
+        progress: &mut BatchRotationProgress,
+    ) -> Result<()> {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 && !item_delay.is_zero() {
+                std::thread::sleep(item_delay);
+            }
+            if self.is_batch_cancelled(batch_id)? {
+                info!("Batch rotation {} cancelled after {} items", batch_id, progress.processed);
+                progress.cancelled = true;
+                return Ok(());
+            }
 
-        // Task 4.4: Process each item with transactional integrity
-        for item in &request.items {
             // Task 4.6: Pre-flight validation
-            match self.rotation_service.validate_rotation(item.secret_id, &item.new_password) {
-                Ok(_) => {
-                    match self.rotation_service.rotate_password(PasswordRotationRequest {
-                        secret_id: item.secret_id,
-                        new_password: item.new_password.clone(),
-                        rotation_reason: item.rotation_reason.clone(),
-                        author_id: request.author_id,
-                        batch_id: Some(request.batch_id),
-                    }) {
-                        Ok(_) => successful_count += 1,
-                        Err(e) => {
-                            warn!("Failed to rotate password for secret {}: {}", item.secret_id, e);
-                            failed_items.push((item.secret_id, e.to_string()));
-                        }
-                    }
-                }
+            let result = self.rotation_service.validate_rotation(item.secret_id, &item.new_password)
+                .and_then(|_| self.rotation_service.rotate_password(PasswordRotationRequest {
+                    secret_id: item.secret_id,
+                    new_password: item.new_password.clone(),
+                    rotation_reason: item.rotation_reason.clone(),
+                    author_id,
+                    batch_id: Some(batch_id),
+                    reason_template_id: item.reason_template_id,
+                }));
+
+            match result {
+                Ok(_) => progress.successful += 1,
                 Err(e) => {
-                    warn!("Validation failed for secret {}: {}", item.secret_id, e);
-                    failed_items.push((item.secret_id, e.to_string()));
+                    warn!("Failed to rotate password for secret {}: {}", item.secret_id, e);
+                    progress.failed_items.push((item.secret_id, e.to_string()));
                 }
             }
+            progress.processed += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Record the outcome of a batch. A cancelled batch keeps its cancelled status.
+    pub fn finish_batch(&self, batch_id: i64, total_items: usize, progress: &BatchRotationProgress) -> Result<()> {
+        if progress.cancelled {
+            self.conn.execute(
+                "UPDATE rotation_batches SET notes = COALESCE(notes || ' | ' || ?1, ?1) WHERE batch_id = ?2",
+                params![format!("Stopped after {} of {} items", progress.processed, total_items), batch_id],
+            )?;
+            return Err(anyhow::anyhow!(
+                "Batch rotation was cancelled after {} of {} items", progress.processed, total_items
+            ));
         }
 
         // Update batch status based on results
-        let final_status = if failed_items.is_empty() {
+        let final_status = if progress.failed_items.is_empty() {
             BatchStatus::Completed
-        } else if successful_count == 0 {
+        } else if progress.successful == 0 {
             BatchStatus::Failed
         } else {
             BatchStatus::Completed // Partial success
         };
 
         let completed_at = Utc::now().to_rfc3339();
-        let notes = if !failed_items.is_empty() {
-            let failed_summary = failed_items.iter()
+        let notes = if !progress.failed_items.is_empty() {
+            let failed_summary = progress.failed_items.iter()
                 .map(|(id, err)| format!("Secret {}: {}", id, err))
                 .collect::<Vec<_>>()
                 .join("; ");
             format!("Completed with {} successes and {} failures. Failures: {}", 
-                    successful_count, failed_items.len(), failed_summary)
+                    progress.successful, progress.failed_items.len(), failed_summary)
         } else {
-            format!("Successfully rotated {} passwords", successful_count)
+            format!("Successfully rotated {} passwords", progress.successful)
         };
 
-        tx.execute(
+        self.conn.execute(
             "UPDATE rotation_batches 
              SET status = ?1, completed_at = ?2, notes = COALESCE(notes || ' | ' || ?3, ?3)
              WHERE batch_id = ?4",
-            params![final_status.to_string(), completed_at, notes, request.batch_id],
+            params![final_status.to_string(), completed_at, notes, batch_id],
         )?;
 
-        if failed_items.is_empty() {
-            tx.commit()?;
-            info!("Batch rotation {} completed successfully", request.batch_id);
-        } else {
-            // Partial failure - still commit successful rotations
-            tx.commit()?;
+        if !progress.failed_items.is_empty() {
             return Err(anyhow::anyhow!(
                 "Batch rotation partially failed. {} succeeded, {} failed", 
-                successful_count, failed_items.len()
+                progress.successful, progress.failed_items.len()
             ));
         }
 
+        info!("Batch rotation {} completed successfully", batch_id);
+        Ok(())
+    }
+
+    pub fn get_throttle_settings(&self) -> Result<BatchThrottleSettings> {
+        let result = self.conn.query_row(
+            "SELECT batch_size, item_delay_ms FROM rotation_batch_throttle WHERE id = 1",
+            [],
+            |row| Ok(BatchThrottleSettings {
+                batch_size: row.get("batch_size")?,
+                item_delay_ms: row.get::<_, i64>("item_delay_ms")? as u64,
+            }),
+        );
+
+        match result {
+            Ok(settings) => Ok(settings),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(BatchThrottleSettings::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn update_throttle_settings(&self, settings: &BatchThrottleSettings) -> Result<()> {
+        settings.validate()?;
+
+        self.conn.execute(
+            "INSERT INTO rotation_batch_throttle (id, batch_size, item_delay_ms) VALUES (1, ?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET batch_size = excluded.batch_size,
+                 item_delay_ms = excluded.item_delay_ms, updated_at = CURRENT_TIMESTAMP",
+            params![settings.batch_size, settings.item_delay_ms as i64],
+        )?;
+
+        info!("Rotation batch throttle set to {} items per chunk, {} ms between items",
+              settings.batch_size, settings.item_delay_ms);
         Ok(())
     }
 
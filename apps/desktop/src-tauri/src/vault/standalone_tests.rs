@@ -322,7 +322,256 @@ mod standalone_credential_tests {
         assert_ne!(credential.encrypted_data, secret_value);
         assert!(!credential.encrypted_data.contains(secret_value));
 
-        // In a real scenario, decryption would happen via the Tauri command
-        // which uses the same encryption key pattern
+        let decrypted = repo.decrypt_standalone_credential_value(&credential, 1).unwrap();
+        assert_eq!(decrypted.as_str(), secret_value);
+
+        // A legacy value keyed by the credential and user is re-encrypted when first opened
+        use base64::{Engine as _, engine::general_purpose};
+        let legacy = crate::encryption::FileEncryption::new(&format!("standalone_{}_{}", credential.id, 1))
+            .encrypt(b"legacy_value")
+            .unwrap();
+        conn.execute(
+            "UPDATE standalone_credentials SET encrypted_data = ?1 WHERE id = ?2",
+            (general_purpose::STANDARD.encode(&legacy), credential.id),
+        ).unwrap();
+        let legacy_credential = repo.get_standalone_credential(credential.id).unwrap().unwrap().credential;
+        assert_eq!(repo.decrypt_standalone_credential_value(&legacy_credential, 1).unwrap().as_str(), "legacy_value");
+
+        let resealed = repo.get_standalone_credential(credential.id).unwrap().unwrap().credential;
+        assert_ne!(resealed.encrypted_data, legacy_credential.encrypted_data);
+        assert!(crate::encryption::is_salted_ciphertext(&general_purpose::STANDARD.decode(&resealed.encrypted_data).unwrap()));
+        assert_eq!(repo.decrypt_standalone_credential_value(&resealed, 1).unwrap().as_str(), "legacy_value");
+    }
+
+    #[test]
+    fn test_reveal_credential_timed() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteVaultRepository::new(&conn);
+
+        let credential = repo.create_standalone_credential(CreateStandaloneCredentialRequest {
+            name: "HMI Operator".to_string(),
+            description: "".to_string(),
+            credential_type: SecretType::Password,
+            category_id: None,
+            value: "Operator#Pass1".to_string(),
+            tags: None,
+            created_by: 1,
+        }).unwrap();
+        let reveal_count = || repo.get_standalone_credential_history(credential.id).unwrap()
+            .iter()
+            .filter(|entry| entry.change_type == StandaloneChangeType::Accessed)
+            .count();
+
+        assert!(repo.reveal_credential_timed(credential.id, 1, 0).is_err());
+        assert!(repo.reveal_credential_timed(credential.id, 1, MAX_REVEAL_TTL_SECS + 1).is_err());
+        assert!(repo.reveal_credential_timed(9999, 1, 60).is_err());
+
+        let first = repo.reveal_credential_timed(credential.id, 1, 60).unwrap();
+        assert_eq!(first.value, "Operator#Pass1");
+        assert!(!first.reused);
+        let revealed_at = chrono::DateTime::parse_from_rfc3339(&first.revealed_at).unwrap();
+        let expires_at = chrono::DateTime::parse_from_rfc3339(&first.expires_at).unwrap();
+        assert_eq!((expires_at - revealed_at).num_seconds(), 60);
+        assert_eq!(reveal_count(), 1);
+
+        // Within the window the reveal is reused with its original expiry
+        let second = repo.reveal_credential_timed(credential.id, 1, 120).unwrap();
+        assert!(second.reused);
+        assert_eq!(second.value, "Operator#Pass1");
+        assert_eq!(second.expires_at, first.expires_at);
+        assert_eq!(reveal_count(), 1);
+
+        // Once expired, the next reveal opens and logs a new window
+        conn.execute("UPDATE credential_reveals SET expires_at = '2000-01-01T00:00:00Z'", []).unwrap();
+        let third = repo.reveal_credential_timed(credential.id, 1, 30).unwrap();
+        assert!(!third.reused);
+        assert_eq!(reveal_count(), 2);
+        let history = repo.get_standalone_credential_history(credential.id).unwrap();
+        assert_eq!(history[0].notes.as_deref(), Some("Value revealed for 30 seconds"));
+        assert!(history[0].changes_json.as_deref().unwrap().contains(&third.expires_at));
+    }
+
+    #[test]
+    fn test_export_credentials_by_category() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteVaultRepository::new(&conn);
+
+        let site = repo.create_credential_category(CreateCategoryRequest {
+            name: "North Site".to_string(),
+            description: None,
+            parent_category_id: None,
+            color_code: None,
+            icon: None,
+        }).unwrap();
+        let scada = repo.create_credential_category(CreateCategoryRequest {
+            name: "SCADA".to_string(),
+            description: None,
+            parent_category_id: Some(site.id),
+            color_code: None,
+            icon: None,
+        }).unwrap();
+        let create = |name: &str, category_id: Option<i64>, value: &str| {
+            repo.create_standalone_credential(CreateStandaloneCredentialRequest {
+                name: name.to_string(),
+                description: format!("{} credential", name),
+                credential_type: SecretType::Password,
+                category_id,
+                value: value.to_string(),
+                tags: Some(vec!["handoff".to_string()]),
+                created_by: 1,
+            }).unwrap()
+        };
+        let historian = create("Historian Admin", Some(scada.id), "Historian#Secret1");
+        let gate = create("Gate Controller", Some(site.id), "Gate#Secret2");
+        let loose = create("Spare Laptop", None, "Laptop#Secret3");
+
+        let json = repo.export_credentials_by_category(CredentialExportFormat::Json).unwrap();
+        let export: CredentialCategoryExport = serde_json::from_slice(&json).unwrap();
+        let site_node = export.categories.iter().find(|c| c.category_id == site.id).unwrap();
+        assert_eq!(site_node.path, "North Site");
+        assert_eq!(site_node.credentials.iter().map(|c| c.credential_id).collect::<Vec<_>>(), vec![gate.id]);
+        let scada_node = &site_node.children[0];
+        assert_eq!(scada_node.category_id, scada.id);
+        assert_eq!(scada_node.path, "North Site / SCADA");
+        assert_eq!(scada_node.credentials[0].credential_id, historian.id);
+        assert_eq!(scada_node.credentials[0].tags, vec!["handoff".to_string()]);
+        assert_eq!(export.uncategorized.iter().map(|c| c.credential_id).collect::<Vec<_>>(), vec![loose.id]);
+
+        let csv = String::from_utf8(repo.export_credentials_by_category(CredentialExportFormat::Csv).unwrap()).unwrap();
+        assert!(csv.starts_with("category_path,credential_id,name,"));
+        assert!(csv.contains(&format!("North Site / SCADA,{},Historian Admin,password,", historian.id)));
+        assert!(csv.contains(&format!("North Site,{},Gate Controller,password,", gate.id)));
+        assert!(csv.contains(&format!("\n,{},Spare Laptop,password,", loose.id)));
+
+        // Metadata only: neither plaintext nor ciphertext leaves the export
+        for output in [String::from_utf8(json).unwrap(), csv] {
+            assert!(!output.contains("Secret1") && !output.contains("Secret2") && !output.contains("Secret3"));
+            assert!(!output.contains(&historian.encrypted_data));
+        }
+    }
+
+    #[test]
+    fn test_history_never_records_plaintext_values() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteVaultRepository::new(&conn);
+
+        let credential = repo.create_standalone_credential(CreateStandaloneCredentialRequest {
+            name: "Historian Service".to_string(),
+            description: "".to_string(),
+            credential_type: SecretType::Password,
+            category_id: None,
+            value: "Initial#Plaintext1".to_string(),
+            tags: None,
+            created_by: 1,
+        }).unwrap();
+        repo.update_standalone_credential(UpdateStandaloneCredentialRequest {
+            id: credential.id,
+            name: None,
+            description: None,
+            category_id: None,
+            value: Some("Rotated#Plaintext2".to_string()),
+            author_id: 1,
+        }).unwrap();
+        repo.reveal_credential_timed(credential.id, 1, 60).unwrap();
+
+        let history = repo.get_standalone_credential_history(credential.id).unwrap();
+        assert_eq!(history.len(), 3);
+        for entry in &history {
+            let recorded = format!("{} {}", entry.changes_json.as_deref().unwrap_or(""), entry.notes.as_deref().unwrap_or(""));
+            assert!(!recorded.contains("Initial#Plaintext1"), "plaintext in history: {}", recorded);
+            assert!(!recorded.contains("Rotated#Plaintext2"), "plaintext in history: {}", recorded);
+        }
+        let update = history.iter().find(|entry| entry.change_type == StandaloneChangeType::Updated).unwrap();
+        let changes: serde_json::Value = serde_json::from_str(update.changes_json.as_deref().unwrap()).unwrap();
+        assert_eq!(changes, serde_json::json!({ "value_updated": true }));
+
+        // The writer itself refuses fields named like a secret value
+        for field in ["value", "password", "new_value", "secret_text", "encrypted_data"] {
+            let mut changes = std::collections::HashMap::new();
+            changes.insert(field.to_string(), serde_json::json!("Leaked#Plaintext3"));
+            let error = repo.add_standalone_history(credential.id, StandaloneChangeType::Updated, 1, "", changes).unwrap_err();
+            assert!(error.to_string().contains("Refusing to record"), "{} was accepted", field);
+        }
+        assert_eq!(repo.get_standalone_credential_history(credential.id).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_import_standalone_credentials_csv() {
+        let (_temp_file, conn) = setup_test_db();
+        let repo = SqliteVaultRepository::new(&conn);
+
+        repo.create_standalone_credential(CreateStandaloneCredentialRequest {
+            name: "Plant WiFi".to_string(),
+            description: "".to_string(),
+            credential_type: SecretType::Password,
+            category_id: Some(3),
+            value: "Existing#Value1".to_string(),
+            tags: None,
+            created_by: 1,
+        }).unwrap();
+        let credential_count = || conn.query_row("SELECT COUNT(*) FROM standalone_credentials", [], |row| row.get::<_, i64>(0)).unwrap();
+
+        let csv = "name,type,value,category,tags\n\
+                   Historian Admin,password,Hist#2026,Databases,scada; historian\n\
+                   Gate Controller,ip_address,10.0.4.17,,\n\
+                   Missing Value,password,,,\n\
+                   Badge Reader,smart_card,1234,,\n\
+                   Line 3 HMI,certificate,not-a-cert,,\n\
+                   Spare Switch,password,Sw#1,Nowhere,\n\
+                   historian admin,password,Other#2,databases,\n\
+                   plant wifi,password,New#Value2,Network Equipment,\n\
+                   Short Row,password\n";
+
+        // Strict mode rolls back rows imported before the first bad one
+        let error = repo.import_standalone_credentials_csv(csv.as_bytes(), Some(4), 1, true).unwrap_err();
+        assert_eq!(error.to_string(), "Import aborted at line 4: Value is required");
+        assert_eq!(credential_count(), 1);
+
+        let report = repo.import_standalone_credentials_csv(csv.as_bytes(), Some(4), 1, false).unwrap();
+        assert_eq!((report.imported, report.skipped, report.errors), (2, 2, 5));
+        let statuses: Vec<(u64, CredentialImportStatus)> = report.rows.iter().map(|row| (row.line_number, row.status)).collect();
+        assert_eq!(statuses, vec![
+            (2, CredentialImportStatus::Imported),
+            (3, CredentialImportStatus::Imported),
+            (4, CredentialImportStatus::Error),
+            (5, CredentialImportStatus::Error),
+            (6, CredentialImportStatus::Error),
+            (7, CredentialImportStatus::Error),
+            (8, CredentialImportStatus::Skipped),
+            (9, CredentialImportStatus::Skipped),
+            (10, CredentialImportStatus::Error),
+        ]);
+        assert_eq!(report.rows[5].reason.as_deref(), Some("Unknown category 'Nowhere'"));
+        assert_eq!(report.rows[6].reason.as_deref(), Some("Duplicate of line 2"));
+        assert_eq!(report.rows[8].reason.as_deref(), Some("Expected 5 fields, found 2"));
+        assert_eq!(credential_count(), 3);
+
+        let historian = repo.get_standalone_credential(report.rows[0].credential_id.unwrap()).unwrap().unwrap();
+        assert_eq!(historian.credential.name, "Historian Admin");
+        assert_eq!(historian.credential.category_id, Some(2));
+        let mut tags = historian.tags.clone();
+        tags.sort();
+        assert_eq!(tags, vec!["historian".to_string(), "scada".to_string()]);
+        assert_eq!(repo.decrypt_standalone_credential_value(&historian.credential, 1).unwrap().as_str(), "Hist#2026");
+
+        let gate = repo.get_standalone_credential(report.rows[1].credential_id.unwrap()).unwrap().unwrap();
+        assert_eq!(gate.credential.credential_type, SecretType::IpAddress);
+        assert_eq!(gate.credential.category_id, Some(4));
+        assert!(gate.tags.is_empty());
+
+        // Rows are sanitized and screened like a credential created by hand
+        let csv = "name,type,value,tags\n\
+                   Script Row,password,<script>alert(1)</script>,\n\
+                   Tagged Row,password,Tag#Pass1,ops; ../etc\n\
+                   Clean\u{7}Row,password,Clean#Pass1,\n";
+        let report = repo.import_standalone_credentials_csv(csv.as_bytes(), None, 1, false).unwrap();
+        assert_eq!((report.imported, report.errors), (1, 2));
+        assert_eq!(report.rows[0].reason.as_deref(), Some("Rejected as potentially malicious input"));
+        assert_eq!(report.rows[1].reason.as_deref(), Some("Rejected as potentially malicious input"));
+        let clean = repo.get_standalone_credential(report.rows[2].credential_id.unwrap()).unwrap().unwrap();
+        assert_eq!(clean.credential.name, "CleanRow");
+
+        // A file without the required columns is rejected outright
+        assert!(repo.import_standalone_credentials_csv(b"name,value\nRouter,secret\n", None, 1, false).is_err());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,113 @@
+// Bounds the memory a streamed configuration import holds at once. The tracking allocator
+// replaces the global allocator for the whole binary, so this lives in its own test crate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Write;
+
+use ferrocodex_lib::configurations::{ConfigurationRepository, SqliteConfigurationRepository};
+use rusqlite::Connection;
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Counts heap bytes held per thread so a test can bound the peak of one call
+struct TrackingAllocator;
+
+fn record(delta: isize) {
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + delta;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| if now > peak.get() { peak.set(now) });
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator;
+
+/// Runs `f` and returns its result with the most bytes it held allocated at once
+fn peak_allocation<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let start = CURRENT.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    let result = f();
+    let peak = PEAK.with(Cell::get);
+    (result, (peak - start).max(0) as usize)
+}
+
+#[test]
+fn test_streamed_import_allocation_is_bounded() {
+    let database = tempfile::NamedTempFile::new().unwrap();
+    let conn = Connection::open(database.path()).unwrap();
+    conn.execute_batch(
+        r#"
+        CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            username TEXT NOT NULL,
+            password_hash TEXT NOT NULL,
+            role TEXT NOT NULL,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            is_active BOOLEAN DEFAULT 1
+        );
+
+        CREATE TABLE assets (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT,
+            created_by INTEGER NOT NULL,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (created_by) REFERENCES users(id)
+        );
+
+        INSERT INTO users (id, username, password_hash, role) VALUES (1, 'testuser', 'hash', 'Engineer');
+        INSERT INTO assets (id, name, description, created_by) VALUES (1, 'Test Asset', 'Test Description', 1);
+        "#,
+    ).unwrap();
+    let repo = SqliteConfigurationRepository::new(&conn);
+    repo.initialize_schema().unwrap();
+
+    // 12 MiB of loosely repetitive bytes so compression helps without collapsing the data
+    let mut content = Vec::with_capacity(12 * 1024 * 1024);
+    let mut state: u32 = 0x1234_5678;
+    while content.len() < 12 * 1024 * 1024 {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        content.extend_from_slice(&[(state >> 24) as u8, b'P', b'L', b'C', (state >> 16) as u8 % 16]);
+    }
+    let mut dump = tempfile::Builder::new().suffix(".bin").tempfile().unwrap();
+    dump.write_all(&content).unwrap();
+    dump.flush().unwrap();
+    drop(content);
+
+    let (stored, peak) = peak_allocation(|| {
+        repo.store_configuration_from_path(1, dump.path(), 1, "Controller memory dump")
+    });
+    let stored = stored.unwrap();
+    assert!(peak < 2 * 1024 * 1024, "streamed import held {} bytes at once", peak);
+    assert_eq!(stored.file_size, 12 * 1024 * 1024);
+}
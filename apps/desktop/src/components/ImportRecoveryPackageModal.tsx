@@ -35,6 +35,7 @@ const { Text, Title } = Typography;
 const { Option } = Select;
 
 interface RecoveryManifest {
+  bundleFormatVersion?: number;
   assetId: number;
   exportDate: string;
   exportedBy: string;
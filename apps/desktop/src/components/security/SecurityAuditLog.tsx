@@ -119,15 +119,15 @@ const SecurityAuditLog: React.FC<SecurityAuditLogProps> = ({
   };
 
   // Handle export
-  const handleExport = async (format: 'csv' | 'json' | 'pdf') => {
+  const handleExport = async (format: 'csv' | 'json' | 'ndjson') => {
     try {
       const startDate = auditFilters.startDate || dayjs().subtract(30, 'days').toISOString();
       const endDate = auditFilters.endDate || dayjs().toISOString();
       
       await invoke('export_audit_log', {
         token,
-        startDate,
-        endDate,
+        from: startDate,
+        to: endDate,
         format,
         filters: auditFilters
       });
@@ -471,8 +471,8 @@ const SecurityAuditLog: React.FC<SecurityAuditLogProps> = ({
             <Button onClick={() => handleExport('json')}>
               Export as JSON
             </Button>
-            <Button onClick={() => handleExport('pdf')}>
-              Export as PDF
+            <Button onClick={() => handleExport('ndjson')}>
+              Export as NDJSON
             </Button>
           </Space>
         </Space>
@@ -21,6 +21,7 @@ import {
   BulkOperationStatus
 } from '../types/bulkOperations';
 import { HistoryManager } from '../utils/historyManager';
+import useAuthStore from './auth';
 
 interface BulkOperationsState extends SelectionManager {
   // Selection State
@@ -377,9 +378,15 @@ const useBulkOperationsStore = create<BulkOperationsState>()(
       },
 
       startBulkDelete: async (request: BulkDeleteRequest) => {
+        const token = useAuthStore.getState().token;
+        if (!token) {
+          throw new Error('Not authenticated');
+        }
+
         set({ isLoading: true, error: null });
         try {
           const operationId = await invoke<string>('start_bulk_delete', {
+            token,
             assetIds: request.asset_ids,
             options: request.options,
           });
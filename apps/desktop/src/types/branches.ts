@@ -69,6 +69,30 @@ export interface CreateBranchVersionRequest {
   notes: string;
 }
 
+export type LineageEdgeKind = 'parent' | 'branch_from' | 'merge';
+
+export interface LineageNode {
+  version_id: number;
+  version_number: string;
+  status: string;
+  created_at: string;
+  branch_id: number | null;
+  branch_name: string | null;
+  branch_version_number: string | null;
+}
+
+export interface LineageEdge {
+  from_version_id: number;
+  to_version_id: number;
+  kind: LineageEdgeKind;
+}
+
+export interface VersionLineage {
+  asset_id: number;
+  nodes: LineageNode[];
+  edges: LineageEdge[];
+}
+
 // Validation schemas
 export const BranchValidation = {
   name: {
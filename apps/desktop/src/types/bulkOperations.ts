@@ -46,12 +46,15 @@ export interface BulkMoveOptions {
   new_parent_id: number | null;
   validate_hierarchy: boolean;
   skip_conflicts: boolean;
+  /** Items processed at once; omitted runs them one at a time */
+  concurrency?: number;
 }
 
 export interface BulkMoveRequest {
   asset_ids: number[];
   new_parent_id: number | null;
   options: BulkMoveOptions;
+  dry_run?: boolean;
 }
 
 // Bulk Delete Operation
@@ -59,11 +62,14 @@ export interface BulkDeleteOptions {
   force_delete: boolean;
   delete_children: boolean;
   skip_protected: boolean;
+  /** Items processed at once; omitted runs them one at a time */
+  concurrency?: number;
 }
 
 export interface BulkDeleteRequest {
   asset_ids: number[];
   options: BulkDeleteOptions;
+  dry_run?: boolean;
 }
 
 // Bulk Export Operation
@@ -75,6 +81,8 @@ export interface BulkExportOptions {
   include_children: boolean;
   include_configurations: boolean;
   export_path?: string;
+  /** Items processed at once; omitted runs them one at a time */
+  concurrency?: number;
 }
 
 export interface BulkExportRequest {
@@ -134,6 +142,14 @@ export interface ValidationResult {
   conflicts: ValidationConflict[];
 }
 
+// Returned instead of an operation ID when a move or delete is started as a dry run
+export interface BulkPreview {
+  operation_type: BulkOperationType;
+  affected_asset_ids: number[];
+  affected_count: number;
+  validation: ValidationResult;
+}
+
 export interface ValidationWarning {
   asset_id: number;
   asset_name: string;
@@ -7,6 +7,7 @@ export interface RecoveryExportRequest {
 }
 
 export interface RecoveryManifest {
+  bundle_format_version?: number;
   asset_id: number;
   export_date: string;
   exported_by: string;
@@ -100,6 +100,13 @@ export interface PasswordStrength {
   feedback: string[];
 }
 
+export interface SecretRevealWithStrength {
+  secret_id: number;
+  value: string;
+  current_strength?: PasswordStrength;
+  stored_strength_score?: number;
+}
+
 export interface GeneratePasswordRequest {
   length: number;
   include_uppercase: boolean;
@@ -229,6 +236,23 @@ export interface SearchCredentialsResponse {
   page_size: number;
 }
 
+export type CredentialImportStatus = 'Imported' | 'Skipped' | 'Error';
+
+export interface CredentialImportRowResult {
+  line_number: number;
+  name?: string;
+  status: CredentialImportStatus;
+  credential_id?: number;
+  reason?: string;
+}
+
+export interface CredentialCsvImportReport {
+  imported: number;
+  skipped: number;
+  errors: number;
+  rows: CredentialImportRowResult[];
+}
+
 export interface CreateCategoryRequest {
   name: string;
   description?: string;
@@ -268,6 +292,8 @@ export interface VaultPermission {
   granted_at: string;
   expires_at?: string;
   is_active: boolean;
+  inheritable: boolean; // applies to vaults on descendant assets
+  inherited: boolean; // reported through an ancestor asset's vault
 }
 
 export type PermissionType = 'Read' | 'Write' | 'Export' | 'Share';
@@ -287,6 +313,22 @@ export interface VaultAccessLog {
 export type AccessType = 'View' | 'Edit' | 'Export' | 'Share' | 'Denied';
 export type AccessResult = 'Success' | 'Denied' | 'Error';
 
+export interface VaultAccessLogQuery {
+  limit?: number;
+  offset?: number;
+  from?: string;
+  to?: string;
+  result?: AccessResult;
+}
+
+export interface VaultAccessLogPage {
+  entries: VaultAccessLog[];
+  total_count: number;
+  limit: number;
+  offset: number;
+  has_more: boolean;
+}
+
 export interface PermissionRequest {
   request_id: number;
   user_id: number;
@@ -308,6 +350,7 @@ export interface GrantVaultAccessRequest {
   permission_type: PermissionType;
   granted_by: number;
   expires_at?: string;
+  inheritable?: boolean;
 }
 
 export interface RevokeVaultAccessRequest {
@@ -327,6 +370,7 @@ export interface VaultAccessInfo {
   has_access: boolean;
   permissions: VaultPermission[];
   is_administrator: boolean;
+  inherited: boolean;
 }
 
 export interface CreatePermissionRequest {
@@ -374,7 +418,8 @@ export interface PasswordRotationRequest {
 export interface RotationSchedule {
   schedule_id: number;
   vault_id: number;
-  rotation_interval: number; // days
+  rotation_interval?: number; // days
+  cron_expression?: string; // overrides rotation_interval when set
   alert_days_before: number;
   is_active: boolean;
   created_at: string;
@@ -416,7 +461,8 @@ export interface RotationAlert {
 
 export interface CreateRotationScheduleRequest {
   vault_id: number;
-  rotation_interval: number;
+  rotation_interval?: number;
+  cron_expression?: string;
   alert_days_before: number;
   created_by: number;
 }